@@ -0,0 +1,159 @@
+// Checklists in task descriptions.
+// Vikunja's editor stores checklists as `<ul data-type="taskList">` with one
+// `<li data-type="taskItem" data-checked="true|false">` per item; the web UI shows
+// their progress on task cards.
+
+use crate::html::{collapse_whitespace, find_tag_end, parse_html, parse_tag, start_tag, HtmlNode};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChecklistItem {
+    pub text: String,
+    pub checked: bool,
+}
+
+fn is_checklist_item(node: &HtmlNode) -> bool {
+    node.name() == Some("li") && node.attr("data-type") == Some("taskItem")
+}
+
+/// Text of an item without its checkbox and nested lists
+fn item_text(item: &HtmlNode) -> String {
+    fn walk(nodes: &[HtmlNode], out: &mut String) {
+        for node in nodes {
+            match node {
+                HtmlNode::Text(text) => out.push_str(text),
+                HtmlNode::Element { name, children, .. } => match name.as_str() {
+                    "label" | "input" | "ul" | "ol" => {}
+                    "p" | "div" | "br" => {
+                        out.push(' ');
+                        walk(children, out);
+                    }
+                    _ => walk(children, out),
+                },
+            }
+        }
+    }
+    let mut text = String::new();
+    walk(item.children(), &mut text);
+    collapse_whitespace(&text).trim().to_string()
+}
+
+/// All checklist items of a description, nested items included, in document order
+pub fn checklist_items(description: &str) -> Vec<ChecklistItem> {
+    fn collect(nodes: &[HtmlNode], items: &mut Vec<ChecklistItem>) {
+        for node in nodes {
+            if is_checklist_item(node) {
+                items.push(ChecklistItem { text: item_text(node), checked: node.attr("data-checked") == Some("true") });
+            }
+            collect(node.children(), items);
+        }
+    }
+    // Cheap check first; this runs for every row of the task table
+    if !description.contains("taskItem") {
+        return Vec::new();
+    }
+    let mut items = Vec::new();
+    collect(&parse_html(description), &mut items);
+    items
+}
+
+/// `(done, total)` of a description's checklist, or None without a checklist
+pub fn checklist_progress(description: &str) -> Option<(usize, usize)> {
+    let items = checklist_items(description);
+    if items.is_empty() {
+        None
+    } else {
+        Some((items.iter().filter(|i| i.checked).count(), items.len()))
+    }
+}
+
+/// Flip the checked state of item `index` (as numbered by `checklist_items`).
+/// Only the item's own tags are rewritten, the rest of the HTML is kept byte for byte.
+pub fn toggle_checklist_item(description: &str, index: usize) -> Option<String> {
+    let lower = description.to_ascii_lowercase();
+    let mut seen = 0;
+    let mut pos = 0;
+    while let Some(offset) = lower[pos..].find("<li") {
+        let start = pos + offset;
+        pos = start + 3;
+        if !lower[pos..].starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/') {
+            continue;
+        }
+        let end = start + find_tag_end(&description[start..])?;
+        pos = end + 1;
+        let (_, mut attrs) = parse_tag(description[start + 1..end].trim_end_matches('/'));
+        if !attrs.iter().any(|(k, v)| k == "data-type" && v == "taskItem") {
+            continue;
+        }
+        if seen < index {
+            seen += 1;
+            continue;
+        }
+
+        let checked = attrs.iter().any(|(k, v)| k == "data-checked" && v == "true");
+        let new_state = (!checked).to_string();
+        match attrs.iter_mut().find(|(k, _)| k == "data-checked") {
+            Some(attr) => attr.1 = new_state,
+            None => attrs.insert(0, ("data-checked".to_string(), new_state)),
+        }
+        let mut out = String::with_capacity(description.len() + 20);
+        out.push_str(&description[..start]);
+        out.push_str(&start_tag("li", &attrs, false));
+
+        // Keep the item's checkbox in sync, if it has one before its content ends
+        let rest = &description[end + 1..];
+        let rest_lower = &lower[end + 1..];
+        let boundary = [rest_lower.find("<li"), rest_lower.find("</li")].into_iter().flatten().min().unwrap_or(rest.len());
+        match rest_lower[..boundary].find("<input") {
+            Some(input_start) => {
+                let input_end = input_start + find_tag_end(&rest[input_start..])?;
+                let raw = &rest[input_start + 1..input_end];
+                let (_, mut input_attrs) = parse_tag(raw.trim_end_matches('/'));
+                input_attrs.retain(|(k, _)| k != "checked");
+                if !checked {
+                    input_attrs.push(("checked".to_string(), "checked".to_string()));
+                }
+                out.push_str(&rest[..input_start]);
+                out.push_str(&start_tag("input", &input_attrs, raw.ends_with('/')));
+                out.push_str(&rest[input_end + 1..]);
+            }
+            None => out.push_str(rest),
+        }
+        return Some(out);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESCRIPTION: &str = "<p>Definition of done</p><ul data-type=\"taskList\">\
+        <li data-checked=\"true\" data-type=\"taskItem\"><label><input type=\"checkbox\" checked=\"checked\"><span></span></label><div><p>Tests pass</p></div></li>\
+        <li data-checked=\"false\" data-type=\"taskItem\"><label><input type=\"checkbox\"><span></span></label><div><p>Docs <strong>updated</strong></p>\
+        <ul data-type=\"taskList\"><li data-checked=\"false\" data-type=\"taskItem\"><label><input type=\"checkbox\"><span></span></label><div><p>README</p></div></li></ul></div></li></ul>";
+
+    #[test]
+    fn test_items_and_progress() {
+        let items = checklist_items(DESCRIPTION);
+        assert_eq!(items, vec![
+            ChecklistItem { text: "Tests pass".to_string(), checked: true },
+            ChecklistItem { text: "Docs updated".to_string(), checked: false },
+            ChecklistItem { text: "README".to_string(), checked: false },
+        ]);
+        assert_eq!(checklist_progress(DESCRIPTION), Some((1, 3)));
+        assert_eq!(checklist_progress("<p>No list</p>"), None);
+    }
+
+    #[test]
+    fn test_toggle_rewrites_only_the_item() {
+        let toggled = toggle_checklist_item(DESCRIPTION, 2).unwrap();
+        assert_eq!(checklist_progress(&toggled), Some((2, 3)));
+        assert!(toggled.contains("<li data-checked=\"true\" data-type=\"taskItem\"><label><input type=\"checkbox\" checked=\"checked\"><span></span></label><div><p>README"));
+        assert!(toggled.starts_with("<p>Definition of done</p>"));
+
+        let untoggled = toggle_checklist_item(&toggled, 2).unwrap();
+        assert_eq!(checklist_items(&untoggled), checklist_items(DESCRIPTION));
+        assert!(untoggled.contains("<input type=\"checkbox\"><span></span></label><div><p>README"));
+        assert_eq!(toggle_checklist_item(DESCRIPTION, 3), None);
+    }
+}
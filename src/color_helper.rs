@@ -62,7 +62,7 @@ impl ColorHelper {
     fn detect_terminal_theme(&self) -> Option<bool> {
         // Try COLORFGBG first (most reliable when available)
         if let Ok(colorfgbg) = std::env::var("COLORFGBG") {
-            if let Some(bg) = colorfgbg.split(';').last() {
+            if let Some(bg) = colorfgbg.split(';').next_back() {
                 if let Ok(bg_num) = bg.parse::<u8>() {
                     return Some(bg_num < 8); // Dark if background < 8
                 }
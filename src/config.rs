@@ -2,23 +2,217 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 use std::collections::HashMap;
+use crate::dates::DateSettings;
+use crate::debug::debug_log;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuickAction {
     pub key: String,
-    pub action: String, // "project", "priority", "label"
-    pub target: String, // project name, priority 1-5, or label name
+    pub action: String, // one of QUICK_ACTION_KINDS
+    pub target: String, // project name, priority 1-5, label name, date, user name, ...; steps for "macro"
 }
 
+/// Every quick action type. A `macro` runs several of the others in turn.
+pub const QUICK_ACTION_KINDS: &[&str] = &[
+    "project", "priority", "label", "remove_label", "due", "start", "assign", "unassign",
+    "done", "favorite", "percent_done", "bucket", "comment", "macro",
+];
+
 impl QuickAction {
     pub fn get_description(&self) -> String {
+        let target = &self.target;
         match self.action.as_str() {
-            "project" => format!("Move to project: {}", self.target),
-            "priority" => format!("Set priority to: {}", self.target),
-            "label" => format!("Add label: {}", self.target),
+            "project" => format!("Move to project: {}", target),
+            "priority" => format!("Set priority to: {}", target),
+            "label" => format!("Add label: {}", target),
+            "remove_label" => format!("Remove label: {}", target),
+            "due" | "start" => {
+                let which = if self.action == "due" { "due" } else { "start" };
+                if is_clear_date(target) {
+                    format!("Clear {} date", which)
+                } else {
+                    format!("Set {} date: {}", which, target)
+                }
+            }
+            "assign" => format!("Assign: {}", target),
+            "unassign" => format!("Unassign: {}", target),
+            "done" | "favorite" => {
+                let (on, off, toggle) = if self.action == "done" {
+                    ("Mark done", "Mark not done", "Toggle done")
+                } else {
+                    ("Add to favorites", "Remove from favorites", "Toggle favorite")
+                };
+                match parse_switch(target) {
+                    Some(Some(true)) => on.to_string(),
+                    Some(Some(false)) => off.to_string(),
+                    _ => toggle.to_string(),
+                }
+            }
+            "percent_done" => format!("Set progress to: {}%", target.trim().trim_end_matches('%')),
+            "bucket" => format!("Move to bucket: {}", target),
+            "comment" => format!("Add comment: {}", target),
+            "macro" => self.steps().iter().map(|step| step.get_description()).collect::<Vec<_>>().join(", "),
             _ => format!("Unknown action: {} -> {}", self.action, self.target),
         }
     }
+
+    /// What the action does, in order: the action itself, or a macro's steps. A macro's
+    /// target lists them as `action: target` separated by `;`, e.g.
+    /// `project: Done; remove_label: today; comment: Wrapped up`.
+    pub fn steps(&self) -> Vec<QuickAction> {
+        if self.action != "macro" {
+            return vec![self.clone()];
+        }
+        self.target.split(';')
+            .map(str::trim)
+            .filter(|step| !step.is_empty())
+            .map(|step| {
+                let (action, target) = step.split_once(':').unwrap_or((step, ""));
+                QuickAction { key: self.key.clone(), action: action.trim().to_string(), target: target.trim().to_string() }
+            })
+            .collect()
+    }
+
+    /// Check the action type and any target that can be checked without the server
+    pub fn validate(&self) -> Result<(), String> {
+        if self.action == "macro" {
+            let steps = self.steps();
+            if steps.is_empty() {
+                return Err("macro has no steps".to_string());
+            }
+            for step in steps {
+                if step.action == "macro" {
+                    return Err("a macro can't contain another macro".to_string());
+                }
+                step.validate()?;
+            }
+            return Ok(());
+        }
+        let target = self.target.trim();
+        match self.action.as_str() {
+            "project" | "label" | "remove_label" | "assign" | "unassign" | "comment" if target.is_empty() => {
+                Err(format!("{} needs a target", self.action))
+            }
+            "project" | "label" | "remove_label" | "assign" | "unassign" | "comment" => Ok(()),
+            "priority" => match target.parse::<i32>() {
+                Ok(priority) if (1..=5).contains(&priority) => Ok(()),
+                _ => Err(format!("invalid priority '{}': must be 1-5", target)),
+            },
+            "due" | "start" => {
+                let settings = DateSettings::default();
+                let readable = is_clear_date(target)
+                    || crate::templates::resolve_date(&settings, target, settings.today(), chrono::NaiveTime::MIN).is_some();
+                if readable { Ok(()) } else { Err(format!("can't read '{}' as a date", target)) }
+            }
+            "done" | "favorite" => match parse_switch(target) {
+                Some(_) => Ok(()),
+                None => Err(format!("{} takes toggle, true or false, not '{}'", self.action, target)),
+            },
+            "percent_done" => match parse_percent(target) {
+                Some(_) => Ok(()),
+                None => Err(format!("invalid percent done '{}': must be 0-100", target)),
+            },
+            "bucket" => if target.trim().is_empty() {
+                Err("bucket needs a bucket name or id".to_string())
+            } else {
+                Ok(())
+            },
+            _ => Err(format!("unknown action '{}' (expected one of: {})", self.action, QUICK_ACTION_KINDS.join(", "))),
+        }
+    }
+}
+
+/// "clear" or "none" removes a date
+pub fn is_clear_date(target: &str) -> bool {
+    matches!(target.trim().to_lowercase().as_str(), "clear" | "none")
+}
+
+/// Target of `done` and `favorite`: Some(None) toggles, Some(Some(x)) sets x
+pub fn parse_switch(target: &str) -> Option<Option<bool>> {
+    match target.trim().to_lowercase().as_str() {
+        "" | "toggle" => Some(None),
+        "true" | "yes" | "on" => Some(Some(true)),
+        "false" | "no" | "off" => Some(Some(false)),
+        _ => None,
+    }
+}
+
+/// "50" or "50%"
+pub fn parse_percent(target: &str) -> Option<u8> {
+    let percent: u8 = target.trim().trim_end_matches('%').trim().parse().ok()?;
+    (percent <= 100).then_some(percent)
+}
+
+/// A task to create from the template picker, with its subtasks. `title` and
+/// `description` may hold placeholders: `{date}`, `{week}` and `{year}` are filled in,
+/// anything else in braces is asked for when the template is used.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskTemplate {
+    /// Shown in the picker; the title when not set
+    #[serde(default)]
+    pub name: Option<String>,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Project name. Subtasks go to their parent's project when not set.
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub priority: Option<i32>,
+    /// Dates as in quick add ("friday", "in 3 days") or offsets from today ("+2d")
+    #[serde(default)]
+    pub due: Option<String>,
+    #[serde(default)]
+    pub start: Option<String>,
+    #[serde(default)]
+    pub subtasks: Vec<TaskTemplate>,
+    /// Name other tasks of the same template use in `depends_on`
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Tasks (by `id`) this one is blocked by
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Kanban bucket to put the task in, created when the project doesn't have it
+    #[serde(default)]
+    pub bucket: Option<String>,
+}
+
+impl TaskTemplate {
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.title)
+    }
+
+    /// How many tasks using the template creates
+    pub fn task_count(&self) -> usize {
+        1 + self.subtasks.iter().map(TaskTemplate::task_count).sum::<usize>()
+    }
+
+    /// Check what can be checked without the server, subtasks and dependencies included
+    pub fn validate(&self) -> Result<(), String> {
+        self.validate_fields()?;
+        crate::templates::check_dependencies(std::slice::from_ref(self))
+    }
+
+    /// `validate` without the dependencies, which may point anywhere in a project template
+    pub fn validate_fields(&self) -> Result<(), String> {
+        if self.title.trim().is_empty() {
+            return Err("a task has no title".to_string());
+        }
+        if let Some(priority) = self.priority {
+            if !(1..=5).contains(&priority) {
+                return Err(format!("invalid priority {} for '{}': must be 1-5", priority, self.title));
+            }
+        }
+        let settings = DateSettings::default();
+        for date in self.due.iter().chain(self.start.iter()) {
+            if crate::templates::resolve_date(&settings, date, settings.today(), chrono::NaiveTime::MIN).is_none() {
+                return Err(format!("can't read '{}' as a date for '{}'", date, self.title));
+            }
+        }
+        self.subtasks.iter().try_for_each(TaskTemplate::validate_fields)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +228,54 @@ pub struct CriaConfig {
     pub active_layout: Option<String>,
     pub refresh_interval_seconds: Option<u64>,
     pub auto_refresh: Option<bool>,
+    /// How numeric dates like 03/04/2025 are read in quick add: "us" (default) or "uk"
+    #[serde(default)]
+    pub date_dialect: Option<DateDialect>,
+    /// Time of day for due dates given without one, "HH:MM" (default 23:59).
+    /// Due dates at this time are shown as all-day.
+    #[serde(default)]
+    pub default_due_time: Option<String>,
+    /// Time of day for start dates given without one (default 00:00)
+    #[serde(default)]
+    pub default_start_time: Option<String>,
+    /// Time "eod", "eow" and the other end-of phrases fall on (default: the due time)
+    #[serde(default)]
+    pub workday_end: Option<String>,
+    /// IANA time zone like "Europe/Berlin" used to read and show dates; the system zone when unset
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Seconds a deleted task stays in the trash before it is deleted on the server (default 30).
+    /// The trash is always emptied on quit.
+    #[serde(default)]
+    pub trash_grace_seconds: Option<u64>,
+    /// Choices offered when snoozing tasks: offsets like "+1d" or dates like "monday"
+    /// (default: +1d, +1w, monday)
+    #[serde(default)]
+    pub snooze_options: Option<Vec<String>>,
+    /// Task blueprints for the template picker
+    #[serde(default)]
+    pub templates: Option<Vec<TaskTemplate>>,
+}
+
+/// Order of day and month in numeric dates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateDialect {
+    /// Month first: 03/04/2025 is March 4th
+    #[default]
+    Us,
+    /// Day first: 03/04/2025 is April 3rd
+    #[serde(alias = "eu")]
+    Uk,
+}
+
+impl From<DateDialect> for chrono_english::Dialect {
+    fn from(dialect: DateDialect) -> Self {
+        match dialect {
+            DateDialect::Us => chrono_english::Dialect::Us,
+            DateDialect::Uk => chrono_english::Dialect::Uk,
+        }
+    }
 }
 
 impl Default for CriaConfig {
@@ -50,6 +292,14 @@ impl Default for CriaConfig {
             active_layout: None,
             refresh_interval_seconds: Some(300), // Default to 5 minutes
             auto_refresh: Some(true), // Default to enabled
+            date_dialect: None,
+            default_due_time: None,
+            default_start_time: None,
+            workday_end: None,
+            timezone: None,
+            trash_grace_seconds: None,
+            snooze_options: None,
+            templates: None,
         }
     }
 }
@@ -78,7 +328,62 @@ impl CriaConfig {
         };
         
         let contents = fs::read_to_string(&config_path).ok()?;
-        serde_yaml::from_str(&contents).ok()
+        let mut config: Self = serde_yaml::from_str(&contents).ok()?;
+        let warnings = config.validate_quick_actions().into_iter()
+            .chain(config.validate_templates())
+            .chain(config.validate_snooze_options());
+        for warning in warnings {
+            debug_log(&warning);
+            eprintln!("Warning: {}", warning);
+        }
+        Some(config)
+    }
+
+    /// Drop quick actions that can't work, so a bad entry is reported once at load
+    /// rather than every time its key is pressed. Returns what was dropped and why.
+    pub fn validate_quick_actions(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if let Some(ref mut actions) = self.quick_actions {
+            actions.retain(|action| match action.validate() {
+                Ok(()) => true,
+                Err(e) => {
+                    warnings.push(format!("Ignoring quick action '{}': {}", action.key, e));
+                    false
+                }
+            });
+        }
+        warnings
+    }
+
+    /// Drop templates that can't work, like `validate_quick_actions`
+    pub fn validate_templates(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if let Some(ref mut templates) = self.templates {
+            templates.retain(|template| match template.validate() {
+                Ok(()) => true,
+                Err(e) => {
+                    warnings.push(format!("Ignoring template '{}': {}", template.display_name(), e));
+                    false
+                }
+            });
+        }
+        warnings
+    }
+
+    /// Drop snooze choices that aren't dates, like `validate_quick_actions`
+    pub fn validate_snooze_options(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if let Some(ref mut options) = self.snooze_options {
+            let settings = DateSettings::default();
+            options.retain(|option| {
+                let readable = crate::templates::resolve_date(&settings, option, settings.today(), settings.due_time).is_some();
+                if !readable {
+                    warnings.push(format!("Ignoring snooze option '{}': can't read it as a date", option));
+                }
+                readable
+            });
+        }
+        warnings
     }
 
     /// Check if any API key configuration is present
@@ -179,6 +484,56 @@ impl CriaConfig {
         self.refresh_interval_seconds.unwrap_or(300)
     }
 
+    /// Seconds deleted tasks wait in the trash, 30 unless configured
+    pub fn get_trash_grace_seconds(&self) -> u64 {
+        self.trash_grace_seconds.unwrap_or(30)
+    }
+
+    /// Snooze choices, +1d, +1w and monday unless configured
+    pub fn get_snooze_options(&self) -> Vec<String> {
+        match self.snooze_options {
+            Some(ref options) if !options.is_empty() => options.clone(),
+            _ => vec!["+1d".to_string(), "+1w".to_string(), "monday".to_string()],
+        }
+    }
+
+    /// Dialect for numeric dates in quick add, US (month first) unless configured
+    pub fn get_date_dialect(&self) -> DateDialect {
+        self.date_dialect.unwrap_or_default()
+    }
+
+    /// Dialect, default times and time zone for reading and showing dates.
+    /// Values that can't be read are logged and left at their defaults.
+    pub fn get_date_settings(&self) -> DateSettings {
+        let defaults = DateSettings::default();
+        let time = |name: &str, value: &Option<String>| {
+            let value = value.as_deref()?;
+            let time = crate::dates::parse_time_of_day(value);
+            if time.is_none() {
+                debug_log(&format!("Ignoring {}: '{}' is not a time like 17:00 or 5pm", name, value));
+            }
+            time
+        };
+        let due_time = time("default_due_time", &self.default_due_time).unwrap_or(defaults.due_time);
+        let timezone = match self.timezone.as_deref().map(str::trim) {
+            None | Some("") | Some("local") => None,
+            Some(name) => {
+                let zone = name.parse::<chrono_tz::Tz>().ok();
+                if zone.is_none() {
+                    debug_log(&format!("Ignoring timezone: unknown time zone '{}'", name));
+                }
+                zone
+            }
+        };
+        DateSettings {
+            dialect: self.get_date_dialect(),
+            due_time,
+            start_time: time("default_start_time", &self.default_start_time).unwrap_or(defaults.start_time),
+            workday_end: time("workday_end", &self.workday_end).unwrap_or(due_time),
+            timezone,
+        }
+    }
+
     /// Check if auto refresh is enabled, defaults to true
     #[allow(dead_code)]
     pub fn is_auto_refresh_enabled(&self) -> bool {
@@ -287,6 +642,8 @@ pub enum TaskColumn {
     Assignees,
     Created,
     Updated,
+    /// Progress of the description's checklist, e.g. "3/7"
+    Checklist,
 }
 
 impl TaskColumn {
@@ -358,6 +715,7 @@ impl TaskColumn {
             TaskColumn::Assignees => "Assignees",
             TaskColumn::Created => "Created",
             TaskColumn::Updated => "Updated",
+            TaskColumn::Checklist => "Checklist",
         }
     }
 }
@@ -447,6 +805,16 @@ impl ColumnLayout {
                         wrap_text: Some(false),
                         sort: None,
                     },
+                    TableColumn {
+                        name: "Done".to_string(),
+                        column_type: TaskColumn::Checklist,
+                        width_percentage: None,
+                        enabled: true,
+                        min_width: Some(5),
+                        max_width: Some(7),
+                        wrap_text: Some(false),
+                        sort: None,
+                    },
                     TableColumn {
                         name: "Due".to_string(),
                         column_type: TaskColumn::DueDate,
@@ -0,0 +1,220 @@
+// Time zone and time-of-day defaults for dates.
+// Vikunja stores instants in UTC. Everything typed or shown is wall-clock time in the
+// configured zone, which is the system zone unless `timezone` is set in config.yaml.
+
+use crate::config::DateDialect;
+use chrono::{DateTime, Duration, FixedOffset, Local, LocalResult, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateSettings {
+    /// Order of day and month in numeric dates
+    pub dialect: DateDialect,
+    /// Time given to due dates typed without one. Due dates at this time are shown as all-day.
+    pub due_time: NaiveTime,
+    /// Time given to start dates typed without one
+    pub start_time: NaiveTime,
+    /// Time "eod", "eow" and the other end-of phrases fall on
+    pub workday_end: NaiveTime,
+    /// None uses the system time zone
+    pub timezone: Option<Tz>,
+}
+
+impl Default for DateSettings {
+    fn default() -> Self {
+        let end_of_day = NaiveTime::from_hms_opt(23, 59, 0).unwrap();
+        Self {
+            dialect: DateDialect::default(),
+            due_time: end_of_day,
+            start_time: NaiveTime::MIN,
+            workday_end: end_of_day,
+            timezone: None,
+        }
+    }
+}
+
+impl DateSettings {
+    /// `utc` in the configured zone
+    pub fn zoned(&self, utc: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self.timezone {
+            Some(tz) => utc.with_timezone(&tz).fixed_offset(),
+            None => utc.with_timezone(&Local).fixed_offset(),
+        }
+    }
+
+    /// Wall-clock time of `utc` in the configured zone
+    pub fn local(&self, utc: DateTime<Utc>) -> NaiveDateTime {
+        self.zoned(utc).naive_local()
+    }
+
+    pub fn now(&self) -> NaiveDateTime {
+        self.local(Utc::now())
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.now().date()
+    }
+
+    /// The instant a wall-clock time in the configured zone refers to. The earlier one is
+    /// used when clocks go back; a time skipped when they go forward moves past the gap.
+    pub fn resolve(&self, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+        fn resolve_in<Z: TimeZone>(zone: &Z, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+            let instant = match zone.from_local_datetime(&local) {
+                LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => dt,
+                LocalResult::None => zone.from_local_datetime(&(local + Duration::hours(1))).earliest()?,
+            };
+            Some(instant.with_timezone(&Utc))
+        }
+        match self.timezone {
+            Some(tz) => resolve_in(&tz, local),
+            None => resolve_in(&Local, local),
+        }
+    }
+
+    pub fn at(&self, date: NaiveDate, time: NaiveTime) -> Option<DateTime<Utc>> {
+        self.resolve(date.and_time(time))
+    }
+
+    /// Whether `date` falls on `default_time`, i.e. was set without a time of day.
+    /// Seconds are ignored; older versions stored 23:59:59.
+    pub fn is_all_day(&self, date: DateTime<Utc>, default_time: NaiveTime) -> bool {
+        let time = self.local(date).time();
+        time.hour() == default_time.hour() && time.minute() == default_time.minute()
+    }
+
+    /// "2025-03-04" for all-day dates, "2025-03-04 10:30" otherwise
+    pub fn format(&self, date: DateTime<Utc>, default_time: NaiveTime) -> String {
+        let format = if self.is_all_day(date, default_time) { "%Y-%m-%d" } else { "%Y-%m-%d %H:%M" };
+        self.local(date).format(format).to_string()
+    }
+
+    /// Read a date as written by `format`, giving date-only text `default_time`
+    pub fn parse(&self, text: &str, default_time: NaiveTime) -> Option<DateTime<Utc>> {
+        let text = text.trim();
+        if let Ok(local) = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M") {
+            return self.resolve(local);
+        }
+        let date = NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()?;
+        self.at(date, default_time)
+    }
+}
+
+/// A shift like "+1d", "-2w", "+3h", "+1m" (months) or "+1y"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateOffset {
+    pub amount: i32,
+    pub unit: char,
+}
+
+impl DateOffset {
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let sign = match text.chars().next()? {
+            '+' => 1,
+            '-' => -1,
+            _ => return None,
+        };
+        let unit = text.chars().last()?.to_ascii_lowercase();
+        if !matches!(unit, 'h' | 'd' | 'w' | 'm' | 'y') {
+            return None;
+        }
+        let amount: i32 = text[1..text.len() - 1].parse().ok()?;
+        Some(Self { amount: sign * amount, unit })
+    }
+
+    /// Move `date` by the offset. Days and longer keep its wall-clock time in the configured zone.
+    pub fn apply(&self, settings: &DateSettings, date: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if self.unit == 'h' {
+            return date.checked_add_signed(Duration::try_hours(self.amount as i64)?);
+        }
+        let local = settings.local(date);
+        let months = Months::new(self.amount.unsigned_abs().checked_mul(if self.unit == 'y' { 12 } else { 1 })?);
+        let shifted = match self.unit {
+            'd' => local.checked_add_signed(Duration::try_days(self.amount as i64)?)?,
+            'w' => local.checked_add_signed(Duration::try_weeks(self.amount as i64)?)?,
+            _ if self.amount < 0 => local.checked_sub_months(months)?,
+            _ => local.checked_add_months(months)?,
+        };
+        settings.resolve(shifted)
+    }
+}
+
+/// "17:00", "5pm" or "9:30am"
+pub fn parse_time_of_day(text: &str) -> Option<NaiveTime> {
+    let text = text.trim().to_lowercase();
+    let (clock, am_pm) = match text.strip_suffix("am").or_else(|| text.strip_suffix("pm")) {
+        Some(clock) => (clock.trim(), Some(text.ends_with("pm"))),
+        None => (text.as_str(), None),
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        None => (clock.parse::<u32>().ok()?, 0),
+    };
+    let hour = match am_pm {
+        Some(_) if hour == 0 || hour > 12 => return None,
+        Some(true) if hour != 12 => hour + 12,
+        Some(false) if hour == 12 => 0,
+        _ => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn berlin() -> DateSettings {
+        DateSettings { timezone: Some(chrono_tz::Europe::Berlin), ..DateSettings::default() }
+    }
+
+    #[test]
+    fn test_parse_time_of_day() {
+        assert_eq!(parse_time_of_day("17:00"), NaiveTime::from_hms_opt(17, 0, 0));
+        assert_eq!(parse_time_of_day("5pm"), NaiveTime::from_hms_opt(17, 0, 0));
+        assert_eq!(parse_time_of_day("9:30 am"), NaiveTime::from_hms_opt(9, 30, 0));
+        assert_eq!(parse_time_of_day("12am"), NaiveTime::from_hms_opt(0, 0, 0));
+        assert_eq!(parse_time_of_day("13pm"), None);
+        assert_eq!(parse_time_of_day("25:00"), None);
+        assert_eq!(parse_time_of_day("noon"), None);
+    }
+
+    #[test]
+    fn test_date_offset() {
+        let settings = berlin();
+        let date = settings.at(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(), NaiveTime::from_hms_opt(17, 0, 0).unwrap()).unwrap();
+        let shift = |text: &str| DateOffset::parse(text).and_then(|o| o.apply(&settings, date)).map(|d| settings.local(d));
+        let at = |y, m, d, h| NaiveDate::from_ymd_opt(y, m, d).unwrap().and_hms_opt(h, 0, 0).unwrap();
+        assert_eq!(shift("+1d"), Some(at(2025, 2, 1, 17)));
+        assert_eq!(shift("-2w"), Some(at(2025, 1, 17, 17)));
+        assert_eq!(shift("+3h"), Some(at(2025, 1, 31, 20)));
+        assert_eq!(shift("+1m"), Some(at(2025, 2, 28, 17)));
+        assert_eq!(shift("+1y"), Some(at(2026, 1, 31, 17)));
+        // A day later across the switch to summer time still lands on 17:00
+        let march = settings.at(NaiveDate::from_ymd_opt(2025, 3, 29).unwrap(), NaiveTime::from_hms_opt(17, 0, 0).unwrap()).unwrap();
+        assert_eq!(DateOffset::parse("+1d").and_then(|o| o.apply(&settings, march)).map(|d| settings.local(d)), Some(at(2025, 3, 30, 17)));
+        assert_eq!(DateOffset::parse("1d"), None);
+        assert_eq!(DateOffset::parse("+d"), None);
+        assert_eq!(DateOffset::parse("+1x"), None);
+        // Too many years to count in months
+        assert_eq!(shift("+400000000y"), None);
+    }
+
+    #[test]
+    fn test_resolve_in_configured_zone() {
+        let settings = berlin();
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let due = settings.at(date, settings.due_time).unwrap();
+        assert_eq!(due.to_rfc3339(), "2025-01-15T22:59:00+00:00");
+        assert!(settings.is_all_day(due, settings.due_time));
+        assert_eq!(settings.format(due, settings.due_time), "2025-01-15");
+        assert_eq!(settings.parse("2025-01-15", settings.due_time), Some(due));
+
+        let meeting = settings.parse("2025-01-15 10:30", settings.due_time).unwrap();
+        assert_eq!(meeting.to_rfc3339(), "2025-01-15T09:30:00+00:00");
+        assert_eq!(settings.format(meeting, settings.due_time), "2025-01-15 10:30");
+
+        // 02:30 doesn't exist on the morning clocks go forward
+        let gap = settings.parse("2025-03-30 02:30", settings.due_time).unwrap();
+        assert_eq!(settings.local(gap).format("%H:%M").to_string(), "03:30");
+    }
+}
@@ -63,6 +63,14 @@ pub fn first_run_wizard() -> Option<CriaConfig> {
         table_columns: None,
         column_layouts: None,
         active_layout: None,
+        date_dialect: None,
+        default_due_time: None,
+        default_start_time: None,
+        workday_end: None,
+        timezone: None,
+        trash_grace_seconds: None,
+        snooze_options: None,
+        templates: None,
     };
 
     // Save config
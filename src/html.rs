@@ -0,0 +1,319 @@
+// Lenient HTML parsing for task descriptions and comments.
+// Vikunja stores rich text as the HTML produced by its tiptap editor; this turns it into
+// a small DOM that the Markdown converter and the description renderer can walk.
+
+/// A node of a parsed HTML fragment
+#[derive(Debug, Clone, PartialEq)]
+pub enum HtmlNode {
+    Element {
+        name: String,
+        attrs: Vec<(String, String)>,
+        children: Vec<HtmlNode>,
+    },
+    Text(String),
+}
+
+impl HtmlNode {
+    /// Lowercase tag name, or None for text
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            HtmlNode::Element { name, .. } => Some(name),
+            HtmlNode::Text(_) => None,
+        }
+    }
+
+    pub fn attr(&self, key: &str) -> Option<&str> {
+        match self {
+            HtmlNode::Element { attrs, .. } => attrs.iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str()),
+            HtmlNode::Text(_) => None,
+        }
+    }
+
+    pub fn children(&self) -> &[HtmlNode] {
+        match self {
+            HtmlNode::Element { children, .. } => children,
+            HtmlNode::Text(_) => &[],
+        }
+    }
+
+    /// `Some(checked)` for checklist items: Vikunja's `data-type="taskItem"` markup
+    /// and GitHub-style items starting with a checkbox
+    pub fn checklist_state(&self) -> Option<bool> {
+        if self.attr("data-type") == Some("taskItem") {
+            return Some(self.attr("data-checked") == Some("true"));
+        }
+        self.children().iter()
+            .find(|c| !matches!(c, HtmlNode::Text(t) if t.trim().is_empty()))
+            .filter(|c| c.name() == Some("input") && c.attr("type") == Some("checkbox"))
+            .map(|c| c.attr("checked").is_some())
+    }
+
+    /// All text below this node, without any markup
+    pub fn text_content(&self) -> String {
+        match self {
+            HtmlNode::Text(text) => text.clone(),
+            HtmlNode::Element { children, .. } => children.iter().map(|c| c.text_content()).collect(),
+        }
+    }
+}
+
+/// Elements that never have children or a closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track", "wbr",
+];
+
+/// Elements whose content is not markup
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+/// Whether a string contains markup at all. Older tasks and tasks created through
+/// the API often have plain-text descriptions.
+pub fn looks_like_html(input: &str) -> bool {
+    let bytes = input.as_bytes();
+    bytes.windows(2).any(|w| w[0] == b'<' && (w[1].is_ascii_alphabetic() || w[1] == b'/' || w[1] == b'!'))
+}
+
+/// Name, attributes and children of an element still being parsed
+type OpenElement = (String, Vec<(String, String)>, Vec<HtmlNode>);
+
+/// Parse an HTML fragment. Never fails: unknown constructs become text, unclosed
+/// elements are closed at the end of input and stray closing tags are ignored.
+pub fn parse_html(input: &str) -> Vec<HtmlNode> {
+    // Open elements; index 0 is the fragment root
+    let mut stack: Vec<OpenElement> = vec![(String::new(), Vec::new(), Vec::new())];
+    let mut pos = 0;
+    let mut text_start = 0;
+
+    while let Some(offset) = input[pos..].find('<') {
+        let lt = pos + offset;
+        let rest = &input[lt..];
+        let next = rest[1..].chars().next();
+
+        let is_markup = matches!(next, Some(c) if c.is_ascii_alphabetic() || c == '/' || c == '!' || c == '?');
+        if !is_markup {
+            pos = lt + 1;
+            continue;
+        }
+        push_text(&mut stack, &input[text_start..lt]);
+
+        if rest.starts_with("<!--") {
+            pos = rest.find("-->").map(|end| lt + end + 3).unwrap_or(input.len());
+        } else if next == Some('!') || next == Some('?') {
+            pos = rest.find('>').map(|end| lt + end + 1).unwrap_or(input.len());
+        } else if next == Some('/') {
+            let end = rest.find('>').map(|end| lt + end).unwrap_or(input.len());
+            let name = input[lt + 2..end].trim().to_ascii_lowercase();
+            close_element(&mut stack, &name);
+            pos = (end + 1).min(input.len());
+        } else {
+            let end = find_tag_end(rest).map(|end| lt + end).unwrap_or(input.len());
+            let tag = &input[lt + 1..end];
+            let self_closing = tag.ends_with('/');
+            let tag = tag.trim_end_matches('/');
+            let (name, attrs) = parse_tag(tag);
+            pos = (end + 1).min(input.len());
+
+            // Paragraphs and list items close an open sibling implicitly
+            if (name == "p" || name == "li") && stack.last().is_some_and(|(open, _, _)| *open == name) {
+                close_element(&mut stack, &name);
+            }
+
+            if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                let closing = format!("</{}", name);
+                let content_end = input[pos..].to_ascii_lowercase().find(&closing).map(|i| pos + i).unwrap_or(input.len());
+                pos = input[content_end..].find('>').map(|i| content_end + i + 1).unwrap_or(input.len());
+            } else if self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+                stack.last_mut().unwrap().2.push(HtmlNode::Element { name, attrs, children: Vec::new() });
+            } else {
+                stack.push((name, attrs, Vec::new()));
+            }
+        }
+        text_start = pos;
+    }
+    push_text(&mut stack, &input[text_start..]);
+
+    while stack.len() > 1 {
+        pop_element(&mut stack);
+    }
+    stack.pop().map(|(_, _, children)| children).unwrap_or_default()
+}
+
+fn push_text(stack: &mut [OpenElement], raw: &str) {
+    if raw.is_empty() {
+        return;
+    }
+    let children = &mut stack.last_mut().unwrap().2;
+    let text = decode_entities(raw);
+    if let Some(HtmlNode::Text(previous)) = children.last_mut() {
+        previous.push_str(&text);
+    } else {
+        children.push(HtmlNode::Text(text));
+    }
+}
+
+fn pop_element(stack: &mut Vec<OpenElement>) {
+    if let Some((name, attrs, children)) = stack.pop() {
+        stack.last_mut().unwrap().2.push(HtmlNode::Element { name, attrs, children });
+    }
+}
+
+/// Close the innermost open element called `name` and everything opened inside it
+fn close_element(stack: &mut Vec<OpenElement>, name: &str) {
+    if let Some(index) = stack.iter().rposition(|(open, _, _)| open == name) {
+        if index == 0 {
+            return;
+        }
+        while stack.len() > index {
+            pop_element(stack);
+        }
+    }
+}
+
+/// Offset of the `>` ending a start tag, skipping quoted attribute values
+pub(crate) fn find_tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split the inside of a start tag into a lowercase name and attributes
+pub(crate) fn parse_tag(tag: &str) -> (String, Vec<(String, String)>) {
+    let name_end = tag.find(|c: char| c.is_whitespace()).unwrap_or(tag.len());
+    let name = tag[..name_end].to_ascii_lowercase();
+    let mut attrs = Vec::new();
+    let mut rest = tag[name_end..].trim_start();
+
+    while !rest.is_empty() {
+        let key_end = rest.find(|c: char| c.is_whitespace() || c == '=').unwrap_or(rest.len());
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+        let mut value = String::new();
+        if let Some(after_eq) = rest.strip_prefix('=') {
+            let after_eq = after_eq.trim_start();
+            match after_eq.chars().next() {
+                Some(q) if q == '"' || q == '\'' => {
+                    let close = after_eq[1..].find(q).map(|i| i + 1).unwrap_or(after_eq.len());
+                    value = decode_entities(&after_eq[1..close]);
+                    rest = after_eq.get(close + 1..).unwrap_or("");
+                }
+                _ => {
+                    let end = after_eq.find(char::is_whitespace).unwrap_or(after_eq.len());
+                    value = decode_entities(&after_eq[..end]);
+                    rest = &after_eq[end..];
+                }
+            }
+        }
+        if !key.is_empty() {
+            attrs.push((key, value));
+        }
+        rest = rest.trim_start();
+    }
+    (name, attrs)
+}
+
+/// Write a start tag, escaping attribute values
+pub(crate) fn start_tag(name: &str, attrs: &[(String, String)], self_closing: bool) -> String {
+    let mut tag = format!("<{}", name);
+    for (key, value) in attrs {
+        tag.push_str(&format!(" {}=\"{}\"", key, value.replace('&', "&amp;").replace('"', "&quot;")));
+    }
+    tag.push_str(if self_closing { " />" } else { ">" });
+    tag
+}
+
+/// Collapse runs of whitespace into single spaces, as a browser would.
+/// Non-breaking spaces become plain spaces but are never collapsed.
+pub fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() && c != '\u{a0}' {
+            if !last_space {
+                out.push(' ');
+            }
+            last_space = true;
+        } else {
+            out.push(if c == '\u{a0}' { ' ' } else { c });
+            last_space = false;
+        }
+    }
+    out
+}
+
+/// Decode the character references that occur in editor output
+pub fn decode_entities(input: &str) -> String {
+    if !input.contains('&') {
+        return input.to_string();
+    }
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').filter(|&semi| semi <= 10).and_then(|semi| {
+            let entity = &rest[1..semi];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => entity.strip_prefix('#').and_then(|num| {
+                    let code = match num.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => num.parse().ok(),
+                    };
+                    code.and_then(char::from_u32)
+                }),
+            };
+            c.map(|c| (c, semi))
+        });
+        match decoded {
+            Some((c, semi)) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nested_elements_and_entities() {
+        let nodes = parse_html(r#"<p>Fish &amp; chips <a href="https://example.com/?a=1&amp;b=2">menu</a><br>next</p>"#);
+        assert_eq!(nodes.len(), 1);
+        let p = &nodes[0];
+        assert_eq!(p.name(), Some("p"));
+        assert_eq!(p.children()[0], HtmlNode::Text("Fish & chips ".to_string()));
+        assert_eq!(p.children()[1].attr("href"), Some("https://example.com/?a=1&b=2"));
+        assert_eq!(p.children()[2].name(), Some("br"));
+        assert_eq!(p.text_content(), "Fish & chips menunext");
+    }
+
+    #[test]
+    fn test_parse_is_lenient() {
+        let nodes = parse_html("<ul><li>one<li>two</ul></span>a < b");
+        assert_eq!(nodes[0].children().len(), 2);
+        assert_eq!(nodes[1], HtmlNode::Text("a < b".to_string()));
+        assert!(!looks_like_html("a < b"));
+        assert!(looks_like_html("<p>a</p>"));
+    }
+}
@@ -6,8 +6,14 @@ pub mod vikunja;
 pub mod tui;
 pub mod debug;
 pub mod config;
+pub mod dates;
+pub mod templates;
 pub mod terminal_capabilities;
 pub mod url_utils;
+pub mod color_helper;
+pub mod checklist;
+pub mod html;
+pub mod markdown;
 
 // Re-export commonly used items
 pub use vikunja_client::*;
@@ -7,9 +7,15 @@ mod vikunja_client;
 mod vikunja_parser;
 mod debug;
 mod config;
+mod dates;
+mod templates;
 mod first_run;
 mod ui_loop;
 mod url_utils;
+mod color_helper;
+mod checklist;
+mod html;
+mod markdown;
 
 use crate::debug::debug_log;
 use crate::ui_loop::run_ui;
@@ -20,7 +26,7 @@ fn main() {
 
     // Parse command-line arguments
     let matches = Command::new("cria")
-        .about("CRIA - Terminal User Interface for Vikunja task management\n\nQuick add example:\n  cria --quick \"Buy milk *groceries +personal tomorrow !2\"\n")
+        .about("CRIA - Terminal User Interface for Vikunja task management\n\nQuick add example:\n  cria --quick \"Buy milk *groceries +personal tomorrow !2\"\n\nOne task per line from stdin (indented lines become subtasks):\n  cat tasks.txt | cria --quick -\n")
         .version(env!("CARGO_PKG_VERSION"))
         .arg(
             Arg::new("config")
@@ -44,54 +50,91 @@ fn main() {
         .arg(
             Arg::new("quick")
                 .long("quick")
-                .help("Quick add a task: --quick \"new task *label +project due:tomorrow\"")
+                .help("Quick add a task: --quick \"new task *label +project due:tomorrow\", or - to read one task per line from stdin")
                 .value_name("TASK_STRING")
                 .num_args(1)
         )
+        .subcommand(
+            Command::new("project")
+                .about("Work with projects")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("new")
+                        .about("Create a project with its sub-projects, buckets and tasks from a project template")
+                        .arg(
+                            Arg::new("from")
+                                .long("from")
+                                .help("Project template file (YAML)")
+                                .value_name("FILE")
+                                .required(true)
+                        )
+                        .arg(
+                            Arg::new("start")
+                                .long("start")
+                                .help("Date the template's offsets like \"+3d\" count from (default: today)")
+                                .value_name("DATE")
+                        )
+                        .arg(
+                            Arg::new("set")
+                                .long("set")
+                                .help("Fill in a placeholder: --set client=Acme; may be repeated")
+                                .value_name("NAME=VALUE")
+                                .action(clap::ArgAction::Append)
+                        )
+                        .arg(
+                            Arg::new("parent")
+                                .long("parent")
+                                .help("Create the project under this existing project")
+                                .value_name("PROJECT")
+                        )
+                )
+        )
         .get_matches();
 
+    if let Some(("project", project_matches)) = matches.subcommand() {
+        if let Some(("new", new_matches)) = project_matches.subcommand() {
+            let (api_url, api_key, _, config) = load_cli_config(matches.get_flag("dev-env"), matches.get_one::<String>("config"));
+            let result = new_project_from_template(new_matches, api_url, api_key, config);
+            std::process::exit(if result.is_ok() { 0 } else { 1 });
+        }
+    }
+
     // Quick add mode: if --quick is present, run quick-add logic and exit
     if let Some(quick_str) = matches.get_one::<String>("quick") {
-        let use_env = matches.get_flag("dev-env");
-        let config_path = matches.get_one::<String>("config");
-        let (api_url, api_key, default_project, config) = if use_env {
-            (
-                std::env::var("VIKUNJA_API_URL").unwrap_or_else(|_| "http://localhost:3456/api/v1".to_string()),
-                std::env::var("VIKUNJA_API_TOKEN").unwrap_or_else(|_| "demo-token".to_string()),
-                std::env::var("VIKUNJA_DEFAULT_PROJECT").unwrap_or_else(|_| "Inbox".to_string()),
-                None
-            )
-        } else {
-            match crate::config::CriaConfig::load_from_path(config_path.map(|s| s.as_str())) {
-                Some(cfg) => {
-                    if cfg.has_api_key_config() {
-                        match cfg.get_api_key() {
-                            Ok(api_key) => (cfg.api_url.clone(), api_key, cfg.default_project.clone().unwrap_or_else(|| "Inbox".to_string()), Some(cfg)),
-                            Err(e) => {
-                                eprintln!("Error loading API key: {}", e);
-                                std::process::exit(1);
-                            }
-                        }
-                    } else {
-                        eprintln!("Config exists but no API key configured");
-                        std::process::exit(1);
-                    }
-                },
-                None => {
-                    eprintln!("Config file not found");
-                    std::process::exit(1);
-                }
-            }
-        };
+        let (api_url, api_key, default_project, config) = load_cli_config(matches.get_flag("dev-env"), matches.get_one::<String>("config"));
 
         // Run async quick-add logic in a Tokio runtime
         let result = tokio::runtime::Runtime::new().unwrap().block_on(async {
-            let api_client = crate::vikunja_client::VikunjaClient::new(api_url.clone(), api_key.clone());
+            let mut api_client = crate::vikunja_client::VikunjaClient::new(api_url.clone(), api_key.clone());
+            if let Some(ref cfg) = config {
+                api_client.set_date_settings(cfg.get_date_settings());
+            }
             // Find default project ID (async, fallback to 1 if not found)
             let default_project_id = match api_client.find_or_get_project_id(&default_project).await {
                 Ok(Some(id)) => id,
                 _ => 1,
             };
+            if quick_str == "-" {
+                // One task per line; indented lines become subtasks of the line above
+                let mut input = String::new();
+                if let Err(e) = std::io::Read::read_to_string(&mut std::io::stdin(), &mut input) {
+                    eprintln!("Failed to read stdin: {}", e);
+                    return Err(());
+                }
+                let outline = crate::vikunja_parser::parse_outline(&input);
+                return match api_client.create_outline_with_magic(&outline, default_project_id).await {
+                    Ok(tasks) => {
+                        for task in tasks {
+                            println!("Task created: {} (ID: {:?})", task.title, task.id);
+                        }
+                        Ok(())
+                    },
+                    Err(e) => {
+                        eprintln!("Failed to create tasks: {}", e);
+                        Err(())
+                    }
+                };
+            }
             // Actually create the task using the magic parser
             match api_client.create_task_with_magic(quick_str, default_project_id).await {
                 Ok(task) => {
@@ -224,6 +267,102 @@ fn main() {
     }
 }
 
+/// API settings for the non-interactive commands, exiting when there are none
+fn load_cli_config(use_env: bool, config_path: Option<&String>) -> (String, String, String, Option<crate::config::CriaConfig>) {
+    if use_env {
+        (
+            std::env::var("VIKUNJA_API_URL").unwrap_or_else(|_| "http://localhost:3456/api/v1".to_string()),
+            std::env::var("VIKUNJA_API_TOKEN").unwrap_or_else(|_| "demo-token".to_string()),
+            std::env::var("VIKUNJA_DEFAULT_PROJECT").unwrap_or_else(|_| "Inbox".to_string()),
+            None
+        )
+    } else {
+        match crate::config::CriaConfig::load_from_path(config_path.map(|s| s.as_str())) {
+            Some(cfg) => {
+                if cfg.has_api_key_config() {
+                    match cfg.get_api_key() {
+                        Ok(api_key) => (cfg.api_url.clone(), api_key, cfg.default_project.clone().unwrap_or_else(|| "Inbox".to_string()), Some(cfg)),
+                        Err(e) => {
+                            eprintln!("Error loading API key: {}", e);
+                            std::process::exit(1);
+                        }
+                    }
+                } else {
+                    eprintln!("Config exists but no API key configured");
+                    std::process::exit(1);
+                }
+            },
+            None => {
+                eprintln!("Config file not found");
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// `cria project new --from FILE`: fill in the template and create everything it describes
+fn new_project_from_template(
+    matches: &clap::ArgMatches,
+    api_url: String,
+    api_key: String,
+    config: Option<crate::config::CriaConfig>,
+) -> Result<(), ()> {
+    let path = std::path::Path::new(matches.get_one::<String>("from").expect("required"));
+    let template = crate::templates::ProjectTemplate::load(path).map_err(|e| eprintln!("{}", e))?;
+    let settings = config.as_ref().map(|c| c.get_date_settings()).unwrap_or_default();
+    let start = matches.get_one::<String>("start").map(String::as_str).unwrap_or("");
+    let Some(anchor) = crate::templates::resolve_anchor(&settings, start) else {
+        eprintln!("Can't read '{}' as a date", start);
+        return Err(());
+    };
+    let mut values = std::collections::HashMap::new();
+    for pair in matches.get_many::<String>("set").unwrap_or_default() {
+        match pair.split_once('=') {
+            Some((name, value)) => { values.insert(name.trim().to_string(), value.to_string()); }
+            None => {
+                eprintln!("Expected NAME=VALUE, got '{}'", pair);
+                return Err(());
+            }
+        }
+    }
+    let missing: Vec<String> = crate::templates::project_placeholders(&template).into_iter()
+        .filter(|name| !values.contains_key(name))
+        .collect();
+    if !missing.is_empty() {
+        eprintln!("Missing placeholders, give them with --set: {}", missing.join(", "));
+        return Err(());
+    }
+    let (projects, tasks) = crate::templates::plan_project(&template, &values, &settings, anchor);
+
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let mut api_client = crate::vikunja_client::VikunjaClient::new(api_url, api_key);
+        api_client.set_date_settings(settings);
+        let parent_id = match matches.get_one::<String>("parent") {
+            Some(name) => match api_client.find_or_get_project_id(name).await {
+                Ok(Some(id)) => id,
+                _ => {
+                    eprintln!("Project '{}' not found", name);
+                    return Err(());
+                }
+            },
+            None => 0,
+        };
+        match api_client.create_planned_project(&projects, &tasks, parent_id).await {
+            Ok((project_ids, created)) => {
+                for (project, id) in projects.iter().zip(&project_ids) {
+                    println!("Project created: {} (ID: {})", project.title, id);
+                }
+                println!("{} task{} created", created.len(), if created.len() == 1 { "" } else { "s" });
+                Ok(())
+            }
+            Err(e) => {
+                eprintln!("Failed to create project: {}", e);
+                Err(())
+            }
+        }
+    })
+}
+
 #[tokio::main]
 async fn tokio_main(api_url: String, api_key: String, default_project: String, config: Option<crate::config::CriaConfig>) -> Result<(), Box<dyn std::error::Error>> {
     use std::sync::Arc;
@@ -232,11 +371,17 @@ async fn tokio_main(api_url: String, api_key: String, default_project: String, c
     use crate::vikunja_client::VikunjaClient as ApiClient;
     use crate::debug::debug_log;
 
-    let api_client = Arc::new(Mutex::new(ApiClient::new(api_url, api_key)));
+    let mut api_client = ApiClient::new(api_url, api_key);
+    if let Some(ref cfg) = config {
+        api_client.set_date_settings(cfg.get_date_settings());
+    }
+    let api_client = Arc::new(Mutex::new(api_client));
     
     // Clone config for later use before moving it
     let config_clone = config.clone();
-    let app = Arc::new(Mutex::new(App::new_with_config(config.expect("Config required"), default_project.clone())));
+    let mut app = App::new_with_config(config.expect("Config required"), default_project.clone());
+    app.input_history = crate::tui::input_history::InputHistory::load();
+    let app = Arc::new(Mutex::new(app));
     
     // Test API connection
     {
@@ -269,6 +414,11 @@ async fn tokio_main(api_url: String, api_key: String, default_project: String, c
     } else {
         debug_log("No tasks returned from API");
     }
+    // Fetch the current user for the "Assigned to me" view
+    let current_user = client_clone.lock().await.get_current_user().await;
+    if let Err(ref e) = current_user {
+        debug_log(&format!("Failed to fetch current user: {}", e));
+    }
     // Fetch saved filters (views) from backend
     let filters = client_clone.lock().await.get_saved_filters().await.unwrap_or_default();
     debug_log(&format!("Fetched {} saved filters from backend", filters.len()));
@@ -278,6 +428,10 @@ async fn tokio_main(api_url: String, api_key: String, default_project: String, c
         app_guard.project_map = project_map;
         app_guard.project_colors = project_colors;
         app_guard.set_filters(filters);
+        if let Ok(user) = current_user {
+            app_guard.set_current_user(user);
+        }
+        app_guard.remember_task_assignees();
         // Merge all_labels into label_map and label_colors
         for label in all_labels {
             if let Some(id) = label.id {
@@ -0,0 +1,328 @@
+// Conversion of task descriptions between Vikunja's HTML and Markdown.
+// Descriptions are edited as Markdown (in the form editor and in $EDITOR) and stored as
+// the HTML Vikunja's web editor produces, including its checklist markup.
+
+use crate::html::{collapse_whitespace, looks_like_html, parse_html, HtmlNode};
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag, TagEnd};
+
+/// Convert description HTML to Markdown. Plain-text descriptions are returned unchanged.
+pub fn html_to_markdown(input: &str) -> String {
+    if !looks_like_html(input) {
+        return input.to_string();
+    }
+    render_blocks(&parse_html(input)).join("\n\n")
+}
+
+/// Convert Markdown to the HTML Vikunja stores. Single newlines are kept as line breaks
+/// and checklists use the web editor's task list markup.
+pub fn markdown_to_html(input: &str) -> String {
+    if input.trim().is_empty() {
+        return String::new();
+    }
+    let options = Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES;
+    let events: Vec<Event> = Parser::new_ext(input, options)
+        .map(|event| match event {
+            Event::SoftBreak => Event::HardBreak,
+            other => other,
+        })
+        .collect();
+
+    let mut output = String::new();
+    html::push_html(&mut output, with_vikunja_task_lists(events).into_iter());
+    output.trim_end().to_string()
+}
+
+/// Rewrite GFM task lists into `<ul data-type="taskList">` / `<li data-type="taskItem">`
+fn with_vikunja_task_lists(events: Vec<Event>) -> Vec<Event> {
+    let mut out = Vec::with_capacity(events.len());
+    // Whether each open list is a task list
+    let mut lists: Vec<bool> = Vec::new();
+    // For each open item of a task list: whether we opened a <p> for tight content
+    let mut items: Vec<Option<bool>> = Vec::new();
+
+    let mut i = 0;
+    while i < events.len() {
+        match &events[i] {
+            Event::Start(Tag::List(None)) => {
+                if let Some(Some(true)) = items.last() {
+                    out.push(Event::Html(CowStr::Borrowed("</p>")));
+                    *items.last_mut().unwrap() = Some(false);
+                }
+                let is_task_list = matches!(events.get(i + 2), Some(Event::TaskListMarker(_)));
+                lists.push(is_task_list);
+                out.push(if is_task_list {
+                    Event::Html(CowStr::Borrowed("<ul data-type=\"taskList\">"))
+                } else {
+                    events[i].clone()
+                });
+            }
+            Event::Start(Tag::List(Some(_))) => {
+                if let Some(Some(true)) = items.last() {
+                    out.push(Event::Html(CowStr::Borrowed("</p>")));
+                    *items.last_mut().unwrap() = Some(false);
+                }
+                lists.push(false);
+                out.push(events[i].clone());
+            }
+            Event::End(TagEnd::List(_)) => {
+                if lists.pop() == Some(true) {
+                    out.push(Event::Html(CowStr::Borrowed("</ul>")));
+                } else {
+                    out.push(events[i].clone());
+                }
+            }
+            Event::Start(Tag::Item) if lists.last() == Some(&true) => {
+                let checked = matches!(events.get(i + 1), Some(Event::TaskListMarker(true)));
+                if matches!(events.get(i + 1), Some(Event::TaskListMarker(_))) {
+                    i += 1;
+                }
+                let tight = !matches!(events.get(i + 1), Some(Event::Start(Tag::Paragraph)));
+                out.push(Event::Html(CowStr::from(format!(
+                    "<li data-checked=\"{}\" data-type=\"taskItem\"><label><input type=\"checkbox\"{}><span></span></label><div>{}",
+                    checked,
+                    if checked { " checked=\"checked\"" } else { "" },
+                    if tight { "<p>" } else { "" },
+                ))));
+                items.push(Some(tight));
+            }
+            Event::Start(Tag::Item) => {
+                items.push(None);
+                out.push(events[i].clone());
+            }
+            Event::End(TagEnd::Item) => match items.pop() {
+                Some(Some(p_open)) => {
+                    let close = if p_open { "</p></div></li>" } else { "</div></li>" };
+                    out.push(Event::Html(CowStr::Borrowed(close)));
+                }
+                _ => out.push(events[i].clone()),
+            },
+            _ => out.push(events[i].clone()),
+        }
+        i += 1;
+    }
+    out
+}
+
+fn is_block(node: &HtmlNode) -> bool {
+    matches!(
+        node.name(),
+        Some("p" | "div" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "ul" | "ol" | "pre" | "blockquote" | "hr" | "table")
+    )
+}
+
+/// Render a sequence of nodes as Markdown blocks; runs of inline nodes become paragraphs
+fn render_blocks(nodes: &[HtmlNode]) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut inline = String::new();
+    let flush = |inline: &mut String, blocks: &mut Vec<String>| {
+        let paragraph = inline.lines().map(str::trim).collect::<Vec<_>>().join("\n");
+        let paragraph = paragraph.trim();
+        if !paragraph.is_empty() {
+            blocks.push(paragraph.to_string());
+        }
+        inline.clear();
+    };
+
+    for node in nodes {
+        if is_block(node) {
+            flush(&mut inline, &mut blocks);
+            let block = render_block(node);
+            if !block.trim().is_empty() {
+                blocks.push(block);
+            }
+        } else {
+            inline.push_str(&render_inline(node));
+        }
+    }
+    flush(&mut inline, &mut blocks);
+    blocks
+}
+
+fn render_block(node: &HtmlNode) -> String {
+    match node.name().unwrap_or_default() {
+        "p" | "div" => render_blocks(node.children()).join("\n\n"),
+        name @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6") => {
+            let level = name[1..].parse::<usize>().unwrap_or(1);
+            format!("{} {}", "#".repeat(level), render_inline_children(node).trim())
+        }
+        "ul" | "ol" => render_list(node),
+        "pre" => {
+            let language = node.children().iter()
+                .find(|c| c.name() == Some("code"))
+                .and_then(|code| code.attr("class"))
+                .and_then(|class| class.split_whitespace().find_map(|c| c.strip_prefix("language-")))
+                .unwrap_or_default();
+            format!("```{}\n{}\n```", language, node.text_content().trim_end_matches('\n'))
+        }
+        "blockquote" => render_blocks(node.children())
+            .join("\n\n")
+            .lines()
+            .map(|line| if line.is_empty() { ">".to_string() } else { format!("> {}", line) })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "hr" => "---".to_string(),
+        "table" => render_table(node),
+        _ => render_inline(node),
+    }
+}
+
+fn render_list(list: &HtmlNode) -> String {
+    let ordered = list.name() == Some("ol");
+    let start = list.attr("start").and_then(|s| s.parse::<usize>().ok()).unwrap_or(1);
+    let mut lines = Vec::new();
+
+    for (index, item) in list.children().iter().filter(|c| c.name() == Some("li")).enumerate() {
+        let checked = item.checklist_state();
+        let marker = match (ordered, checked) {
+            (true, _) => format!("{}. ", start + index),
+            (false, Some(true)) => "- [x] ".to_string(),
+            (false, Some(false)) => "- [ ] ".to_string(),
+            (false, None) => "- ".to_string(),
+        };
+        // The checkbox itself is markup, not content
+        let content: Vec<HtmlNode> = item.children().iter()
+            .filter(|c| !matches!(c.name(), Some("label" | "input")))
+            .cloned()
+            .collect();
+        let body = render_blocks(&content).join("\n");
+        let indent = " ".repeat(if checked.is_some() { 2 } else { marker.len() });
+        let mut body_lines = body.lines();
+        lines.push(format!("{}{}", marker, body_lines.next().unwrap_or_default()));
+        for line in body_lines {
+            lines.push(if line.is_empty() { String::new() } else { format!("{}{}", indent, line) });
+        }
+    }
+    lines.join("\n")
+}
+
+fn render_table(table: &HtmlNode) -> String {
+    fn rows(node: &HtmlNode, out: &mut Vec<Vec<String>>) {
+        for child in node.children() {
+            match child.name() {
+                Some("tr") => out.push(child.children().iter()
+                    .filter(|c| matches!(c.name(), Some("td" | "th")))
+                    .map(|c| render_inline_children(c).trim().replace('|', "\\|").replace('\n', " "))
+                    .collect()),
+                Some(_) => rows(child, out),
+                None => {}
+            }
+        }
+    }
+    let mut table_rows = Vec::new();
+    rows(table, &mut table_rows);
+    let Some(columns) = table_rows.iter().map(Vec::len).max().filter(|&n| n > 0) else {
+        return String::new();
+    };
+    let mut lines = Vec::new();
+    for (index, row) in table_rows.iter().enumerate() {
+        let mut cells = row.clone();
+        cells.resize(columns, String::new());
+        lines.push(format!("| {} |", cells.join(" | ")));
+        if index == 0 {
+            lines.push(format!("|{}", " --- |".repeat(columns)));
+        }
+    }
+    lines.join("\n")
+}
+
+fn render_inline_children(node: &HtmlNode) -> String {
+    node.children().iter().map(render_inline).collect()
+}
+
+fn render_inline(node: &HtmlNode) -> String {
+    let text = match node {
+        HtmlNode::Text(text) => return escape_markdown(&collapse_whitespace(text)),
+        HtmlNode::Element { .. } => render_inline_children(node),
+    };
+    let wrap = |marker: &str| {
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            text.clone()
+        } else {
+            // Keep surrounding spaces outside the markers so the emphasis still parses
+            let leading = &text[..text.len() - text.trim_start().len()];
+            let trailing = &text[text.trim_end().len()..];
+            format!("{}{}{}{}{}", leading, marker, trimmed, marker, trailing)
+        }
+    };
+    match node.name().unwrap_or_default() {
+        "br" => "\n".to_string(),
+        "strong" | "b" => wrap("**"),
+        "em" | "i" => wrap("*"),
+        "s" | "del" | "strike" => wrap("~~"),
+        "code" => format!("`{}`", node.text_content()),
+        "a" => match node.attr("href") {
+            Some(href) if node.text_content().trim() == href || text.trim().is_empty() => format!("<{}>", href),
+            Some(href) => format!("[{}]({})", text.trim(), href),
+            None => text,
+        },
+        "img" => format!("![{}]({})", node.attr("alt").unwrap_or_default(), node.attr("src").unwrap_or_default()),
+        "label" | "input" => String::new(),
+        _ => text,
+    }
+}
+
+/// Escape characters that would otherwise turn text into Markdown syntax.
+/// Underscores inside words (snake_case) are left alone since they never emphasize.
+fn escape_markdown(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let escape = match c {
+            '\\' | '*' | '`' | '[' | ']' | '<' => true,
+            '_' => {
+                let before = i.checked_sub(1).and_then(|j| chars.get(j)).is_some_and(|c| c.is_alphanumeric());
+                let after = chars.get(i + 1).is_some_and(|c| c.is_alphanumeric());
+                !(before && after)
+            }
+            _ => false,
+        };
+        if escape {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_is_unchanged() {
+        assert_eq!(html_to_markdown("Buy milk_2 * 3"), "Buy milk_2 * 3");
+        assert_eq!(markdown_to_html(""), "");
+    }
+
+    #[test]
+    fn test_html_to_markdown() {
+        let html = "<h2>Plan</h2><p>Call <strong>Sam</strong> about <a href=\"https://example.com\">the venue</a><br>then book</p>\
+                    <ul><li><p>chairs</p><ul><li><p>folding</p></li></ul></li><li><p>tables</p></li></ul>";
+        assert_eq!(
+            html_to_markdown(html),
+            "## Plan\n\nCall **Sam** about [the venue](https://example.com)\nthen book\n\n- chairs\n  - folding\n- tables"
+        );
+    }
+
+    #[test]
+    fn test_checklists_round_trip() {
+        let html = "<ul data-type=\"taskList\"><li data-checked=\"false\" data-type=\"taskItem\"><label><input type=\"checkbox\"><span></span></label><div><p>pack</p></div></li>\
+                    <li data-checked=\"true\" data-type=\"taskItem\"><label><input type=\"checkbox\" checked=\"checked\"><span></span></label><div><p>book train</p></div></li></ul>";
+        let markdown = html_to_markdown(html);
+        assert_eq!(markdown, "- [ ] pack\n- [x] book train");
+
+        let back = markdown_to_html(&markdown);
+        assert!(back.contains("<ul data-type=\"taskList\">"));
+        assert!(back.contains("<li data-checked=\"true\" data-type=\"taskItem\">"));
+        assert_eq!(html_to_markdown(&back), markdown);
+    }
+
+    #[test]
+    fn test_markdown_round_trip() {
+        let markdown = "Notes for *Monday*\nsecond line\n\n1. first\n2. second\n\n> quoted `code`\n\n```rust\nlet x = 1;\n```";
+        let html = markdown_to_html(markdown);
+        assert!(html.starts_with("<p>Notes for <em>Monday</em><br />"));
+        assert_eq!(html_to_markdown(&html), markdown);
+    }
+}
@@ -0,0 +1,413 @@
+// Task and project templates, turned into the tasks and projects they describe.
+// Task templates live in config.yaml; project templates are YAML files of their own.
+// A template is filled in first (placeholders replaced, dates resolved against an anchor
+// date) and flattened into `PlannedTask`s and `PlannedProject`s, which the client
+// creates in one go.
+
+use crate::config::TaskTemplate;
+use crate::dates::{DateOffset, DateSettings};
+use crate::debug::debug_log;
+use crate::vikunja_parser::QuickAddParser;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Placeholders filled in without asking
+const BUILT_IN: &[&str] = &["date", "week", "year"];
+
+/// A project to create with its buckets, tasks and sub-projects
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectTemplate {
+    /// Shown in the picker; the title when not set
+    #[serde(default)]
+    pub name: Option<String>,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub hex_color: Option<String>,
+    /// Kanban buckets, in order, besides the ones Vikunja creates
+    #[serde(default)]
+    pub buckets: Vec<String>,
+    #[serde(default)]
+    pub tasks: Vec<TaskTemplate>,
+    #[serde(default)]
+    pub projects: Vec<ProjectTemplate>,
+}
+
+impl ProjectTemplate {
+    /// Read and check a project template file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path).map_err(|e| format!("Can't read {}: {}", path.display(), e))?;
+        let template: Self = serde_yaml::from_str(&contents).map_err(|e| format!("Can't parse {}: {}", path.display(), e))?;
+        template.validate().map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(template)
+    }
+
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.title)
+    }
+
+    /// How many tasks using the template creates, in all its projects
+    pub fn task_count(&self) -> usize {
+        self.tasks.iter().map(TaskTemplate::task_count).sum::<usize>()
+            + self.projects.iter().map(ProjectTemplate::task_count).sum::<usize>()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        fn fields(project: &ProjectTemplate) -> Result<(), String> {
+            if project.title.trim().is_empty() {
+                return Err("a project has no title".to_string());
+            }
+            project.tasks.iter().try_for_each(TaskTemplate::validate_fields)?;
+            project.projects.iter().try_for_each(fields)
+        }
+        fields(self)?;
+        // Dependencies may cross projects
+        let mut tasks = Vec::new();
+        let mut stack = vec![self];
+        while let Some(project) = stack.pop() {
+            tasks.extend(project.tasks.iter().cloned());
+            stack.extend(project.projects.iter());
+        }
+        check_dependencies(&tasks)
+    }
+}
+
+/// Every `depends_on` names an `id` given once
+pub fn check_dependencies(tasks: &[TaskTemplate]) -> Result<(), String> {
+    fn walk<'a>(task: &'a TaskTemplate, all: &mut Vec<&'a TaskTemplate>) {
+        all.push(task);
+        task.subtasks.iter().for_each(|subtask| walk(subtask, all));
+    }
+    let mut all = Vec::new();
+    tasks.iter().for_each(|task| walk(task, &mut all));
+    let mut ids = HashSet::new();
+    for id in all.iter().filter_map(|t| t.id.as_deref()) {
+        if !ids.insert(id) {
+            return Err(format!("task id '{}' is used twice", id));
+        }
+    }
+    for task in &all {
+        if let Some(missing) = task.depends_on.iter().find(|d| !ids.contains(d.as_str())) {
+            return Err(format!("'{}' depends on '{}', but no task has that id", task.title, missing));
+        }
+    }
+    Ok(())
+}
+
+/// A task or project template, as listed in the template picker
+#[derive(Debug, Clone)]
+pub enum TemplateEntry {
+    Task(TaskTemplate),
+    Project(ProjectTemplate),
+}
+
+impl TemplateEntry {
+    pub fn display_name(&self) -> &str {
+        match self {
+            TemplateEntry::Task(template) => template.display_name(),
+            TemplateEntry::Project(template) => template.display_name(),
+        }
+    }
+
+    pub fn task_count(&self) -> usize {
+        match self {
+            TemplateEntry::Task(template) => template.task_count(),
+            TemplateEntry::Project(template) => template.task_count(),
+        }
+    }
+
+    /// The placeholders to ask for, in the order they first appear
+    pub fn prompted_placeholders(&self) -> Vec<String> {
+        match self {
+            TemplateEntry::Task(template) => prompted_placeholders(template),
+            TemplateEntry::Project(template) => project_placeholders(template),
+        }
+    }
+}
+
+/// Where project templates are looked for: `project-templates/` next to config.yaml
+pub fn project_templates_dir() -> Option<PathBuf> {
+    match std::env::var("XDG_CONFIG_HOME") {
+        Ok(val) => Some(PathBuf::from(val).join("cria/project-templates")),
+        Err(_) => dirs::home_dir().map(|home| home.join(".config/cria/project-templates")),
+    }
+}
+
+/// Every readable project template in `dir`, by file name. Broken ones are logged and left out.
+pub fn load_project_templates(dir: &Path) -> Vec<ProjectTemplate> {
+    let Ok(entries) = std::fs::read_dir(dir) else { return Vec::new() };
+    let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| matches!(p.extension().and_then(|e| e.to_str()), Some("yaml") | Some("yml")))
+        .collect();
+    paths.sort();
+    paths.iter().filter_map(|path| match ProjectTemplate::load(path) {
+        Ok(template) => Some(template),
+        Err(e) => {
+            debug_log(&format!("Ignoring project template: {}", e));
+            None
+        }
+    }).collect()
+}
+
+/// A task of a filled-in template, in creation order
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedTask {
+    /// Index of the task this one is a subtask of
+    pub parent: Option<usize>,
+    /// Index of the planned project the task goes in
+    pub project_index: Option<usize>,
+    pub title: String,
+    pub description: Option<String>,
+    pub project: Option<String>,
+    pub labels: Vec<String>,
+    pub priority: Option<i32>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub id: Option<String>,
+    pub depends_on: Vec<String>,
+    pub bucket: Option<String>,
+}
+
+/// A project of a filled-in project template, parents before their sub-projects
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedProject {
+    pub parent: Option<usize>,
+    pub title: String,
+    pub description: Option<String>,
+    pub hex_color: Option<String>,
+    pub buckets: Vec<String>,
+}
+
+fn placeholder_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"\{([A-Za-z_][A-Za-z0-9_ -]*)\}").unwrap())
+}
+
+fn collect_placeholders<'a>(texts: impl Iterator<Item = &'a String>, names: &mut Vec<String>) {
+    for text in texts {
+        for cap in placeholder_regex().captures_iter(text) {
+            let name = cap[1].trim().to_string();
+            if !BUILT_IN.contains(&name.as_str()) && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+}
+
+fn task_placeholders(template: &TaskTemplate, names: &mut Vec<String>) {
+    collect_placeholders(std::iter::once(&template.title).chain(template.description.iter()), names);
+    for subtask in &template.subtasks {
+        task_placeholders(subtask, names);
+    }
+}
+
+/// The placeholders a template asks for, in the order they first appear
+pub fn prompted_placeholders(template: &TaskTemplate) -> Vec<String> {
+    let mut names = Vec::new();
+    task_placeholders(template, &mut names);
+    names
+}
+
+/// The placeholders a project template asks for, in the order they first appear
+pub fn project_placeholders(template: &ProjectTemplate) -> Vec<String> {
+    fn collect(project: &ProjectTemplate, names: &mut Vec<String>) {
+        collect_placeholders(std::iter::once(&project.title).chain(project.description.iter()), names);
+        for task in &project.tasks {
+            task_placeholders(task, names);
+        }
+        for subproject in &project.projects {
+            collect(subproject, names);
+        }
+    }
+    let mut names = Vec::new();
+    collect(template, &mut names);
+    names
+}
+
+/// Replace placeholders: `{date}` is the anchor date as 2025-03-04, `{week}` its ISO week
+/// number, `{year}` its year, the rest come from `values`. Unknown ones are left as they are.
+pub fn fill_placeholders(text: &str, values: &HashMap<String, String>, anchor: NaiveDate) -> String {
+    placeholder_regex().replace_all(text, |cap: &regex::Captures| {
+        let name = cap[1].trim();
+        match name {
+            "date" => anchor.format("%Y-%m-%d").to_string(),
+            "week" => anchor.iso_week().week().to_string(),
+            "year" => anchor.year().to_string(),
+            _ => values.get(name).cloned().unwrap_or_else(|| cap[0].to_string()),
+        }
+    }).into_owned()
+}
+
+/// A template date: an offset from the anchor date like "+2d", or anything quick add reads
+pub fn resolve_date(settings: &DateSettings, text: &str, anchor: NaiveDate, default_time: NaiveTime) -> Option<DateTime<Utc>> {
+    match DateOffset::parse(text) {
+        Some(offset) => offset.apply(settings, settings.at(anchor, default_time)?),
+        None => QuickAddParser::with_settings(*settings).parse_date(text, default_time),
+    }
+}
+
+/// The day an anchor date typed in means; empty is today
+pub fn resolve_anchor(settings: &DateSettings, text: &str) -> Option<NaiveDate> {
+    if text.trim().is_empty() {
+        return Some(settings.today());
+    }
+    resolve_date(settings, text, settings.today(), NaiveTime::MIN).map(|date| settings.local(date).date())
+}
+
+fn plan_task(template: &TaskTemplate, parent: Option<usize>, project_index: Option<usize>, values: &HashMap<String, String>, settings: &DateSettings, anchor: NaiveDate, planned: &mut Vec<PlannedTask>) {
+    let index = planned.len();
+    planned.push(PlannedTask {
+        parent,
+        project_index,
+        title: fill_placeholders(&template.title, values, anchor),
+        description: template.description.as_ref().map(|d| fill_placeholders(d, values, anchor)),
+        project: template.project.clone(),
+        labels: template.labels.clone(),
+        priority: template.priority,
+        due_date: template.due.as_deref().and_then(|d| resolve_date(settings, d, anchor, settings.due_time)),
+        start_date: template.start.as_deref().and_then(|d| resolve_date(settings, d, anchor, settings.start_time)),
+        id: template.id.clone(),
+        depends_on: template.depends_on.clone(),
+        bucket: template.bucket.clone(),
+    });
+    for subtask in &template.subtasks {
+        plan_task(subtask, Some(index), project_index, values, settings, anchor, planned);
+    }
+}
+
+/// The tasks a template creates, parents before their subtasks, with offsets counted from today
+pub fn plan_template(template: &TaskTemplate, values: &HashMap<String, String>, settings: &DateSettings) -> Vec<PlannedTask> {
+    let mut planned = Vec::new();
+    plan_task(template, None, None, values, settings, settings.today(), &mut planned);
+    planned
+}
+
+/// The projects and tasks a project template creates, with offsets counted from `anchor`
+pub fn plan_project(template: &ProjectTemplate, values: &HashMap<String, String>, settings: &DateSettings, anchor: NaiveDate) -> (Vec<PlannedProject>, Vec<PlannedTask>) {
+    fn plan(project: &ProjectTemplate, parent: Option<usize>, values: &HashMap<String, String>, settings: &DateSettings, anchor: NaiveDate, projects: &mut Vec<PlannedProject>, tasks: &mut Vec<PlannedTask>) {
+        let index = projects.len();
+        projects.push(PlannedProject {
+            parent,
+            title: fill_placeholders(&project.title, values, anchor),
+            description: project.description.as_ref().map(|d| fill_placeholders(d, values, anchor)),
+            hex_color: project.hex_color.clone(),
+            buckets: project.buckets.clone(),
+        });
+        for task in &project.tasks {
+            plan_task(task, None, Some(index), values, settings, anchor, tasks);
+        }
+        for subproject in &project.projects {
+            plan(subproject, Some(index), values, settings, anchor, projects, tasks);
+        }
+    }
+    let (mut projects, mut tasks) = (Vec::new(), Vec::new());
+    plan(template, None, values, settings, anchor, &mut projects, &mut tasks);
+    (projects, tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release_template() -> TaskTemplate {
+        serde_yaml::from_str(r#"
+name: Weekly release
+title: "Release {version} (week {week})"
+description: "Ship {version} on {date}"
+labels: [release]
+priority: 4
+due: "+4d"
+subtasks:
+  - title: "Freeze main for {version}"
+    due: "+2d"
+  - title: "Write changelog"
+    subtasks:
+      - title: "Ask {reviewer} to proofread"
+"#).unwrap()
+    }
+
+    fn onboarding_template() -> ProjectTemplate {
+        serde_yaml::from_str(r#"
+title: "{client} onboarding"
+buckets: [Waiting]
+tasks:
+  - title: "Kickoff call with {client}"
+    id: kickoff
+    due: "+0d"
+  - title: "Collect access"
+    due: "+3d"
+    depends_on: [kickoff]
+    bucket: Waiting
+projects:
+  - title: "Design"
+    tasks:
+      - title: "Moodboard"
+        due: "+1w"
+        depends_on: [kickoff]
+"#).unwrap()
+    }
+
+    #[test]
+    fn test_prompted_placeholders() {
+        assert_eq!(prompted_placeholders(&release_template()), vec!["version".to_string(), "reviewer".to_string()]);
+        assert_eq!(project_placeholders(&onboarding_template()), vec!["client".to_string()]);
+    }
+
+    #[test]
+    fn test_plan_template() {
+        let settings = DateSettings::default();
+        let today = settings.today();
+        let values = HashMap::from([("version".to_string(), "1.5".to_string())]);
+        let planned = plan_template(&release_template(), &values, &settings);
+
+        let parents: Vec<Option<usize>> = planned.iter().map(|t| t.parent).collect();
+        assert_eq!(parents, vec![None, Some(0), Some(0), Some(2)]);
+        assert_eq!(planned[0].title, format!("Release 1.5 (week {})", today.iso_week().week()));
+        assert_eq!(planned[0].description, Some(format!("Ship 1.5 on {}", today.format("%Y-%m-%d"))));
+        assert_eq!(planned[0].labels, vec!["release".to_string()]);
+        assert_eq!(planned[0].due_date.map(|d| settings.local(d).date()), Some(today + chrono::Duration::days(4)));
+        assert_eq!(planned[1].due_date.map(|d| settings.local(d).date()), Some(today + chrono::Duration::days(2)));
+        // Nothing given for {reviewer}, so it stays visible
+        assert_eq!(planned[3].title, "Ask {reviewer} to proofread");
+    }
+
+    #[test]
+    fn test_plan_project_from_anchor() {
+        let settings = DateSettings::default();
+        let anchor = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let template = onboarding_template();
+        assert!(template.validate().is_ok());
+        assert_eq!(template.task_count(), 3);
+
+        let values = HashMap::from([("client".to_string(), "Acme".to_string())]);
+        let (projects, tasks) = plan_project(&template, &values, &settings, anchor);
+        assert_eq!(projects.iter().map(|p| (p.parent, p.title.as_str())).collect::<Vec<_>>(), vec![(None, "Acme onboarding"), (Some(0), "Design")]);
+        assert_eq!(tasks.iter().map(|t| t.project_index).collect::<Vec<_>>(), vec![Some(0), Some(0), Some(1)]);
+        let due: Vec<Option<NaiveDate>> = tasks.iter().map(|t| t.due_date.map(|d| settings.local(d).date())).collect();
+        assert_eq!(due, vec![Some(anchor), NaiveDate::from_ymd_opt(2025, 3, 6), NaiveDate::from_ymd_opt(2025, 3, 10)]);
+        assert_eq!(tasks[1].bucket.as_deref(), Some("Waiting"));
+        assert_eq!(tasks[2].depends_on, vec!["kickoff".to_string()]);
+    }
+
+    #[test]
+    fn test_broken_dependencies_are_rejected() {
+        let mut template = onboarding_template();
+        template.projects[0].tasks[0].depends_on = vec!["launch".to_string()];
+        assert_eq!(template.validate(), Err("'Moodboard' depends on 'launch', but no task has that id".to_string()));
+        let mut template = onboarding_template();
+        template.projects[0].tasks[0].id = Some("kickoff".to_string());
+        assert_eq!(template.validate(), Err("task id 'kickoff' is used twice".to_string()));
+    }
+
+    #[test]
+    fn test_example_project_template_is_valid() {
+        let template: ProjectTemplate = serde_yaml::from_str(include_str!("../project-template.example.yaml")).unwrap();
+        assert_eq!(template.validate(), Ok(()));
+        assert_eq!(project_placeholders(&template), vec!["client".to_string()]);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::tui::app::state::App;
+use crate::tui::line_editor::{EditOutcome, LineEditor};
+use crossterm::event::KeyEvent;
+use crate::tui::app::picker_context::PickerContext;
+use crate::tui::utils::contains_ignore_case;
+use crate::vikunja::models::User;
+use std::time::{Duration, Instant};
+
+/// How long typing has to pause before a user search goes to the server
+pub const USER_SEARCH_DELAY: Duration = Duration::from_millis(300);
+
+impl App {
+    /// Remember users so their display names can be shown for assignee ids
+    pub fn remember_users<I: IntoIterator<Item = User>>(&mut self, users: I) {
+        for user in users {
+            self.known_users.insert(user.id, user);
+        }
+    }
+
+    /// Collect every assignee from the loaded tasks into `known_users`
+    pub fn remember_task_assignees(&mut self) {
+        let users: Vec<User> = self.all_tasks.iter()
+            .filter_map(|t| t.assignees.as_ref())
+            .flatten()
+            .cloned()
+            .collect();
+        self.remember_users(users);
+    }
+
+    pub fn set_current_user(&mut self, user: User) {
+        self.remember_users(std::iter::once(user.clone()));
+        self.current_user = Some(user);
+    }
+
+    /// Display name for a user id, falling back to the raw id for unknown users
+    pub fn user_display_name(&self, user_id: i64) -> String {
+        self.known_users.get(&user_id)
+            .or_else(|| self.all_tasks.iter()
+                .filter_map(|t| t.assignees.as_ref())
+                .flatten()
+                .find(|u| u.id == user_id))
+            .map(|u| u.display_name().to_string())
+            .unwrap_or_else(|| format!("user #{}", user_id))
+    }
+
+    /// Open the assignee picker from the form editor, preserving form state and context
+    pub fn open_assignee_picker_from_form(&mut self) {
+        self.show_form_edit_modal = false;
+        self.show_assignee_picker = true;
+        self.assignee_picker_input.clear();
+        self.selected_assignee_picker_index = 0;
+        if let Some(ref form) = self.form_edit_state {
+            self.selected_assignee_ids = form.assignee_ids.clone();
+        }
+        self.remember_task_assignees();
+        self.update_filtered_assignees();
+        self.picker_context = PickerContext::FormEditAssignee;
+    }
+
+    pub fn hide_assignee_picker(&mut self) {
+        self.show_assignee_picker = false;
+        self.assignee_picker_input.clear();
+        if self.picker_context == PickerContext::FormEditAssignee {
+            // Return to the form editor without applying changes
+            self.show_form_edit_modal = true;
+            self.picker_context = PickerContext::None;
+        }
+    }
+
+    pub fn add_char_to_assignee_picker(&mut self, c: char) {
+        self.assignee_picker_editor().insert_char(c);
+        self.update_filtered_assignees();
+        self.selected_assignee_picker_index = 0;
+    }
+
+    pub fn delete_char_from_assignee_picker(&mut self) {
+        if self.assignee_picker_editor().backspace() == EditOutcome::Changed {
+            self.update_filtered_assignees();
+            self.selected_assignee_picker_index = 0;
+        }
+    }
+
+    /// Apply an editing key to the search text, refiltering when it changes.
+    /// Returns whether the text changed.
+    pub fn edit_assignee_picker_input(&mut self, key: &KeyEvent) -> bool {
+        let changed = self.assignee_picker_editor().handle_key(key) == EditOutcome::Changed;
+        if changed {
+            self.update_filtered_assignees();
+            self.selected_assignee_picker_index = 0;
+        }
+        changed
+    }
+
+    fn assignee_picker_editor(&mut self) -> LineEditor<'_> {
+        LineEditor::new(&mut self.assignee_picker_input, &mut self.picker_cursor_position, &mut self.kill_ring)
+    }
+
+    pub fn move_assignee_picker_up(&mut self) {
+        if !self.filtered_assignees.is_empty() {
+            self.selected_assignee_picker_index = (self.selected_assignee_picker_index + self.filtered_assignees.len() - 1) % self.filtered_assignees.len();
+        }
+    }
+
+    pub fn move_assignee_picker_down(&mut self) {
+        if !self.filtered_assignees.is_empty() {
+            self.selected_assignee_picker_index = (self.selected_assignee_picker_index + 1) % self.filtered_assignees.len();
+        }
+    }
+
+    pub fn toggle_assignee_picker(&mut self) {
+        if let Some(user) = self.filtered_assignees.get(self.selected_assignee_picker_index) {
+            let user_id = user.id;
+            if self.selected_assignee_ids.contains(&user_id) {
+                self.selected_assignee_ids.retain(|&id| id != user_id);
+            } else {
+                self.selected_assignee_ids.push(user_id);
+            }
+        }
+    }
+
+    /// Confirm the selection and write it back to the form editor
+    pub fn select_assignee_picker(&mut self) {
+        self.show_assignee_picker = false;
+        self.assignee_picker_input.clear();
+        if self.picker_context == PickerContext::FormEditAssignee {
+            if let Some(ref mut form) = self.form_edit_state {
+                form.assignee_ids = self.selected_assignee_ids.clone();
+            }
+            self.show_form_edit_modal = true;
+            self.picker_context = PickerContext::None;
+        }
+    }
+
+    /// Merge users returned by the server search into the picker list
+    pub fn set_assignee_search_results(&mut self, users: Vec<User>) {
+        self.remember_users(users);
+        self.update_filtered_assignees();
+    }
+
+    /// Search the server for `query` once typing pauses; known users are filtered
+    /// locally meanwhile. Each query is only sent once.
+    pub fn queue_user_search(&mut self, query: &str) {
+        let query = query.trim().to_lowercase();
+        self.pending_user_search = if query.is_empty() || self.searched_user_queries.contains(&query) {
+            None
+        } else {
+            Some((query, Instant::now()))
+        };
+    }
+
+    /// The queued search, once typing has paused for `USER_SEARCH_DELAY`
+    pub fn take_due_user_search(&mut self) -> Option<String> {
+        match self.pending_user_search {
+            Some((_, queued_at)) if queued_at.elapsed() >= USER_SEARCH_DELAY => {
+                let (query, _) = self.pending_user_search.take()?;
+                self.searched_user_queries.insert(query.clone());
+                Some(query)
+            }
+            _ => None,
+        }
+    }
+
+    /// Merge users found by a server search into whatever list is showing users
+    pub fn apply_user_search_results(&mut self, users: Vec<User>) {
+        if self.show_assignee_picker {
+            self.set_assignee_search_results(users);
+            return;
+        }
+        self.remember_users(users);
+        // The quick add and edit lines suggest users after `@`
+        let input = if self.show_quick_add_modal {
+            Some((self.quick_add_input.clone(), self.quick_add_cursor_position))
+        } else if self.show_edit_modal {
+            Some((self.edit_input.clone(), self.edit_cursor_position))
+        } else {
+            None
+        };
+        if let Some((input, cursor)) = input {
+            self.update_suggestions(&input, cursor);
+        }
+    }
+
+    /// Filter known users by the picker input; selected users and the current user come first
+    pub fn update_filtered_assignees(&mut self) {
+        let query = &self.assignee_picker_input;
+        let current_id = self.current_user.as_ref().map(|u| u.id);
+        let mut users: Vec<User> = self.known_users.values()
+            .filter(|u| contains_ignore_case(&u.username, query) || contains_ignore_case(u.display_name(), query))
+            .cloned()
+            .collect();
+        users.sort_by(|a, b| {
+            let a_key = (!self.selected_assignee_ids.contains(&a.id), Some(a.id) != current_id, a.display_name().to_lowercase());
+            let b_key = (!self.selected_assignee_ids.contains(&b.id), Some(b.id) != current_id, b.display_name().to_lowercase());
+            a_key.cmp(&b_key)
+        });
+        self.filtered_assignees = users;
+    }
+}
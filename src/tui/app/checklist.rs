@@ -0,0 +1,38 @@
+use crate::checklist::checklist_items;
+use crate::tui::app::state::App;
+use crate::tui::modals::ChecklistModal;
+
+impl App {
+    pub fn show_checklist_modal(&mut self) {
+        let Some(task) = self.get_selected_task() else {
+            self.show_toast("No task selected".to_string());
+            return;
+        };
+        // The detailed copy may be newer than the list entry
+        let task = self.get_detailed_task(task.id).unwrap_or(task);
+        let items = checklist_items(task.description.as_deref().unwrap_or_default());
+        if items.is_empty() {
+            self.show_toast("No checklist in this task's description".to_string());
+            return;
+        }
+        let modal = ChecklistModal::new(task.id, task.title.clone(), items);
+        self.close_all_modals();
+        self.show_checklist_modal = true;
+        self.checklist_modal = Some(modal);
+    }
+
+    pub fn hide_checklist_modal(&mut self) {
+        self.show_checklist_modal = false;
+        self.checklist_modal = None;
+    }
+
+    /// Store a description saved on the server in every loaded copy of the task
+    pub fn set_task_description(&mut self, task_id: i64, description: &str) {
+        let tasks = self.all_tasks.iter_mut()
+            .chain(self.tasks.iter_mut())
+            .chain(self.detailed_task_cache.values_mut());
+        for task in tasks.filter(|t| t.id == task_id) {
+            task.description = Some(description.to_string());
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use crate::tui::app::form_edit_state::FormEditState;
+use crate::tui::app::state::App;
+
+/// A task field that can be merged when someone else edited the task concurrently
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictField {
+    Title,
+    Description,
+    DueDate,
+    StartDate,
+    Priority,
+    Project,
+    Labels,
+    Assignees,
+    Favorite,
+    EndDate,
+    Repeat,
+    Reminders,
+    PercentDone,
+    Color,
+    Bucket,
+}
+
+impl ConflictField {
+    pub const ALL: [ConflictField; 15] = [
+        ConflictField::Title,
+        ConflictField::Description,
+        ConflictField::DueDate,
+        ConflictField::StartDate,
+        ConflictField::Priority,
+        ConflictField::Project,
+        ConflictField::Labels,
+        ConflictField::Assignees,
+        ConflictField::Favorite,
+        ConflictField::EndDate,
+        ConflictField::Repeat,
+        ConflictField::Reminders,
+        ConflictField::PercentDone,
+        ConflictField::Color,
+        ConflictField::Bucket,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ConflictField::Title => "Title",
+            ConflictField::Description => "Description",
+            ConflictField::DueDate => "Due Date",
+            ConflictField::StartDate => "Start Date",
+            ConflictField::Priority => "Priority",
+            ConflictField::Project => "Project",
+            ConflictField::Labels => "Labels",
+            ConflictField::Assignees => "Assignees",
+            ConflictField::Favorite => "Favorite",
+            ConflictField::EndDate => "End Date",
+            ConflictField::Repeat => "Repeat",
+            ConflictField::Reminders => "Reminders",
+            ConflictField::PercentDone => "Progress",
+            ConflictField::Color => "Color",
+            ConflictField::Bucket => "Bucket",
+        }
+    }
+
+    /// Index of the form editor field showing this one
+    pub fn form_field(self) -> usize {
+        match self {
+            ConflictField::Title => 0,
+            ConflictField::Description => 1,
+            ConflictField::DueDate => 2,
+            ConflictField::StartDate => 3,
+            ConflictField::Priority => 4,
+            ConflictField::Project => 5,
+            ConflictField::Labels => 6,
+            ConflictField::Assignees => 7,
+            ConflictField::Favorite => 8,
+            ConflictField::EndDate => 9,
+            ConflictField::Repeat => 10,
+            ConflictField::Reminders => 11,
+            ConflictField::PercentDone => 12,
+            ConflictField::Color => 13,
+            ConflictField::Bucket => 14,
+        }
+    }
+
+    /// Comparable value of this field; label and assignee order does not matter
+    pub(crate) fn key(self, state: &FormEditState) -> String {
+        let sorted = |ids: &[i64]| {
+            let mut ids = ids.to_vec();
+            ids.sort_unstable();
+            format!("{:?}", ids)
+        };
+        match self {
+            ConflictField::Title => state.title.trim().to_string(),
+            ConflictField::Description => state.description.trim().to_string(),
+            ConflictField::DueDate => state.due_date.clone().unwrap_or_default(),
+            ConflictField::StartDate => state.start_date.clone().unwrap_or_default(),
+            ConflictField::Priority => format!("{:?}", state.priority),
+            ConflictField::Project => state.project_id.to_string(),
+            ConflictField::Labels => sorted(&state.label_ids),
+            ConflictField::Assignees => sorted(&state.assignee_ids),
+            ConflictField::Favorite => state.is_favorite.to_string(),
+            ConflictField::EndDate => state.end_date.clone().unwrap_or_default(),
+            ConflictField::Repeat => state.repeat.trim().to_lowercase(),
+            ConflictField::Reminders => state.reminders.trim().to_string(),
+            ConflictField::PercentDone => state.percent_done.to_string(),
+            ConflictField::Color => state.hex_color.trim().trim_start_matches('#').to_lowercase(),
+            ConflictField::Bucket => state.bucket.trim().to_string(),
+        }
+    }
+
+    /// Copy this field's value from one form state into another
+    pub fn copy(self, from: &FormEditState, to: &mut FormEditState) {
+        match self {
+            ConflictField::Title => to.title = from.title.clone(),
+            ConflictField::Description => to.description = from.description.clone(),
+            ConflictField::DueDate => to.due_date = from.due_date.clone(),
+            ConflictField::StartDate => to.start_date = from.start_date.clone(),
+            ConflictField::Priority => to.priority = from.priority,
+            ConflictField::Project => to.project_id = from.project_id,
+            ConflictField::Labels => to.label_ids = from.label_ids.clone(),
+            ConflictField::Assignees => to.assignee_ids = from.assignee_ids.clone(),
+            ConflictField::Favorite => to.is_favorite = from.is_favorite,
+            ConflictField::EndDate => to.end_date = from.end_date.clone(),
+            ConflictField::Repeat => to.repeat = from.repeat.clone(),
+            ConflictField::Reminders => to.reminders = from.reminders.clone(),
+            ConflictField::PercentDone => to.percent_done = from.percent_done,
+            ConflictField::Color => to.hex_color = from.hex_color.clone(),
+            ConflictField::Bucket => to.bucket = from.bucket.clone(),
+        }
+    }
+}
+
+/// Which version of a conflicting field to keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeChoice {
+    Mine,
+    Theirs,
+    Original,
+}
+
+impl MergeChoice {
+    pub fn next(self) -> Self {
+        match self {
+            MergeChoice::Mine => MergeChoice::Theirs,
+            MergeChoice::Theirs => MergeChoice::Original,
+            MergeChoice::Original => MergeChoice::Mine,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldConflict {
+    pub field: ConflictField,
+    pub choice: MergeChoice,
+}
+
+/// Result of merging my edits with someone else's
+#[derive(Debug, Clone)]
+pub struct MergeOutcome {
+    /// My edits with their non-conflicting changes applied
+    pub merged: FormEditState,
+    /// Fields both sides changed differently; `merged` holds my value for these
+    pub conflicts: Vec<FieldConflict>,
+}
+
+/// Three-way merge of form states: fields only one side changed are taken from that side,
+/// fields both sides changed to different values are reported as conflicts.
+pub fn merge_form_states(base: &FormEditState, theirs: &FormEditState, mine: &FormEditState) -> MergeOutcome {
+    let mut merged = mine.clone();
+    let mut conflicts = Vec::new();
+    for field in ConflictField::ALL {
+        let (b, t, m) = (field.key(base), field.key(theirs), field.key(mine));
+        if m == b {
+            field.copy(theirs, &mut merged);
+        } else if t != b && t != m {
+            conflicts.push(FieldConflict { field, choice: MergeChoice::Mine });
+        }
+    }
+    // The merged state is now based on the server's version
+    merged.updated = theirs.updated.clone();
+    let mut new_base = theirs.clone();
+    new_base.base = None;
+    merged.base = Some(Box::new(new_base));
+    MergeOutcome { merged, conflicts }
+}
+
+/// Apply the user's per-field choices to an auto-merged state
+pub fn resolve_conflicts(
+    merged: &FormEditState,
+    base: &FormEditState,
+    theirs: &FormEditState,
+    conflicts: &[FieldConflict],
+) -> FormEditState {
+    let mut resolved = merged.clone();
+    for conflict in conflicts {
+        match conflict.choice {
+            MergeChoice::Mine => {}
+            MergeChoice::Theirs => conflict.field.copy(theirs, &mut resolved),
+            MergeChoice::Original => conflict.field.copy(base, &mut resolved),
+        }
+    }
+    resolved
+}
+
+impl App {
+    /// Human readable value of a form field, for the conflict modal
+    pub fn describe_form_field(&self, field: ConflictField, state: &FormEditState) -> String {
+        let or_none = |s: Option<&String>| s.filter(|s| !s.is_empty()).cloned().unwrap_or_else(|| "(none)".to_string());
+        match field {
+            ConflictField::Title => state.title.clone(),
+            ConflictField::Description => or_none(Some(&state.description)),
+            ConflictField::DueDate => or_none(state.due_date.as_ref()),
+            ConflictField::StartDate => or_none(state.start_date.as_ref()),
+            ConflictField::Priority => state.priority.map(|p| p.to_string()).unwrap_or_else(|| "(none)".to_string()),
+            ConflictField::Project => self.project_map.get(&state.project_id).cloned()
+                .unwrap_or_else(|| format!("Project {}", state.project_id)),
+            ConflictField::Labels => {
+                let names: Vec<String> = state.label_ids.iter()
+                    .map(|id| self.label_map.get(id).cloned().unwrap_or_else(|| format!("label #{}", id)))
+                    .collect();
+                if names.is_empty() { "(none)".to_string() } else { names.join(", ") }
+            }
+            ConflictField::Assignees => {
+                let names: Vec<String> = state.assignee_ids.iter().map(|id| self.user_display_name(*id)).collect();
+                if names.is_empty() { "(none)".to_string() } else { names.join(", ") }
+            }
+            ConflictField::Favorite => if state.is_favorite { "yes".to_string() } else { "no".to_string() },
+            ConflictField::EndDate => or_none(state.end_date.as_ref()),
+            ConflictField::Repeat => or_none(Some(&state.repeat)),
+            ConflictField::Reminders => or_none(Some(&state.reminders)),
+            ConflictField::PercentDone => format!("{}%", state.percent_done),
+            ConflictField::Color => or_none(Some(&state.hex_color)),
+            ConflictField::Bucket => or_none(Some(&state.bucket)),
+        }
+    }
+
+    /// Build the form state an edit-line submission would produce, starting from `base`.
+    /// Mirrors `update_task_with_magic`: labels are replaced, assignees only when given.
+    pub fn form_state_from_edit_input(&self, base: &FormEditState, input: &str) -> FormEditState {
+        let settings = &self.date_settings;
+        let parsed = crate::vikunja_parser::QuickAddParser::with_settings(*settings).parse(input);
+        let mut state = base.clone();
+        state.title = parsed.title;
+        if let Some(priority) = parsed.priority {
+            state.priority = Some(priority as i32);
+        }
+        if let Some(due) = parsed.due_date {
+            state.due_date = Some(settings.format(due, settings.due_time));
+        }
+        if let Some(start) = parsed.start_date {
+            state.start_date = Some(settings.format(start, settings.start_time));
+        }
+        if let Some(ref project) = parsed.project {
+            if let Some((id, _)) = self.project_map.iter().find(|(_, name)| name.eq_ignore_ascii_case(project)) {
+                state.project_id = *id;
+            }
+        }
+        state.label_ids = parsed.labels.iter()
+            .filter_map(|name| self.label_map.iter().find(|(_, title)| title.eq_ignore_ascii_case(name)).map(|(id, _)| *id))
+            .collect();
+        if !parsed.assignees.is_empty() {
+            state.assignee_ids = parsed.assignees.iter()
+                .filter_map(|username| self.known_users.values().find(|u| u.username.eq_ignore_ascii_case(username)).map(|u| u.id))
+                .collect();
+        }
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vikunja::models::Task;
+
+    fn base_state() -> FormEditState {
+        FormEditState::new(&Task {
+            id: 1,
+            title: "Sprint review".to_string(),
+            description: Some("Agenda".to_string()),
+            priority: Some(2),
+            project_id: 3,
+            updated: Some("2025-06-01T10:00:00Z".to_string()),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_non_overlapping_changes_merge_cleanly() {
+        let base = base_state();
+        let mut theirs = base.clone();
+        theirs.description = "Agenda and notes".to_string();
+        theirs.updated = Some("2025-06-01T11:00:00Z".to_string());
+        let mut mine = base.clone();
+        mine.title = "Sprint review (moved)".to_string();
+
+        let outcome = merge_form_states(&base, &theirs, &mine);
+        assert!(outcome.conflicts.is_empty());
+        assert_eq!(outcome.merged.title, "Sprint review (moved)");
+        assert_eq!(outcome.merged.description, "Agenda and notes");
+        assert_eq!(outcome.merged.updated, theirs.updated);
+    }
+
+    #[test]
+    fn test_same_field_changed_is_a_conflict() {
+        let base = base_state();
+        let mut theirs = base.clone();
+        theirs.description = "Their description".to_string();
+        let mut mine = base.clone();
+        mine.description = "My description".to_string();
+        mine.label_ids = vec![5, 4];
+        theirs.label_ids = vec![4, 5];
+
+        let outcome = merge_form_states(&base, &theirs, &mine);
+        assert_eq!(outcome.conflicts, vec![FieldConflict { field: ConflictField::Description, choice: MergeChoice::Mine }]);
+
+        let mut conflicts = outcome.conflicts.clone();
+        conflicts[0].choice = MergeChoice::Theirs;
+        let resolved = resolve_conflicts(&outcome.merged, &base, &theirs, &conflicts);
+        assert_eq!(resolved.description, "Their description");
+    }
+}
@@ -1,4 +1,6 @@
 use crate::tui::app::state::App;
+use crate::tui::line_editor::{EditOutcome, LineEditor};
+use crossterm::event::KeyEvent;
 use crate::tui::utils::contains_ignore_case;
 
 impl App {
@@ -16,18 +18,29 @@ impl App {
     }
     #[allow(dead_code)]
     pub fn add_char_to_filter_picker(&mut self, c: char) {
-        self.filter_picker_input.insert(self.selected_filter_picker_index, c);
-        self.selected_filter_picker_index += 1;
+        self.filter_picker_editor().insert_char(c);
         self.update_filtered_filters();
+        self.selected_filter_picker_index = 0;
     }
     #[allow(dead_code)]
     pub fn delete_char_from_filter_picker(&mut self) {
-        if self.selected_filter_picker_index > 0 {
-            self.selected_filter_picker_index -= 1;
-            self.filter_picker_input.remove(self.selected_filter_picker_index);
+        if self.filter_picker_editor().backspace() == EditOutcome::Changed {
             self.update_filtered_filters();
+            self.selected_filter_picker_index = 0;
         }
     }
+
+    /// Apply an editing key to the search text, refiltering when it changes
+    pub fn edit_filter_picker_input(&mut self, key: &KeyEvent) {
+        if self.filter_picker_editor().handle_key(key) == EditOutcome::Changed {
+            self.update_filtered_filters();
+            self.selected_filter_picker_index = 0;
+        }
+    }
+
+    fn filter_picker_editor(&mut self) -> LineEditor<'_> {
+        LineEditor::new(&mut self.filter_picker_input, &mut self.picker_cursor_position, &mut self.kill_ring)
+    }
     #[allow(dead_code)]
     pub fn move_filter_picker_up(&mut self) {
         if !self.filtered_filters.is_empty() {
@@ -70,6 +83,7 @@ impl App {
     #[allow(dead_code)]
     pub fn apply_filter_tasks(&mut self, tasks: Vec<crate::vikunja::models::Task>) {
         self.tasks = tasks;
+        self.tasks.retain(|task| !self.trash.iter().any(|t| t.task.id == task.id));
         // Apply hierarchical sorting to maintain parent-child relationships
         self.apply_hierarchical_sort();
     }
@@ -89,12 +103,27 @@ impl App {
         }
         "No filter".to_string()
     }
-    pub fn apply_task_filter(&mut self) {
-        self.tasks = self.all_tasks.iter().filter(|task| match self.task_filter {
+    /// Whether a task passes the current Active/All/Completed/assignment filter.
+    /// Tasks in the trash never do.
+    pub fn task_matches_filter(&self, task: &crate::vikunja::models::Task) -> bool {
+        if self.is_trashed(task.id) {
+            return false;
+        }
+        match self.task_filter {
             crate::tui::app::task_filter::TaskFilter::ActiveOnly => !task.done,
             crate::tui::app::task_filter::TaskFilter::All => true,
             crate::tui::app::task_filter::TaskFilter::CompletedOnly => task.done,
-        }).cloned().collect();
+            crate::tui::app::task_filter::TaskFilter::AssignedToMe => {
+                let me = self.current_user.as_ref().map(|u| u.id);
+                !task.done && task.assignees.as_ref().is_some_and(|a| a.iter().any(|u| Some(u.id) == me))
+            }
+            crate::tui::app::task_filter::TaskFilter::Unassigned => {
+                !task.done && task.assignees.as_ref().is_none_or(|a| a.is_empty())
+            }
+        }
+    }
+    pub fn apply_task_filter(&mut self) {
+        self.tasks = self.all_tasks.iter().filter(|task| self.task_matches_filter(task)).cloned().collect();
         
         // Apply hierarchical sorting to maintain parent-child relationships
         self.apply_hierarchical_sort();
@@ -116,14 +145,21 @@ impl App {
                 crate::tui::app::task_filter::TaskFilter::ActiveOnly => "Active Tasks Only".to_string(),
                 crate::tui::app::task_filter::TaskFilter::All => "All Tasks".to_string(),
                 crate::tui::app::task_filter::TaskFilter::CompletedOnly => "Completed Tasks Only".to_string(),
+                crate::tui::app::task_filter::TaskFilter::AssignedToMe => "Assigned to Me".to_string(),
+                crate::tui::app::task_filter::TaskFilter::Unassigned => "Unassigned".to_string(),
             }
         }
     }
     pub fn cycle_task_filter(&mut self) {
+        // The assignment views are only offered once we know who the current user is
+        let has_user = self.current_user.is_some();
         self.task_filter = match self.task_filter {
             crate::tui::app::task_filter::TaskFilter::ActiveOnly => crate::tui::app::task_filter::TaskFilter::All,
             crate::tui::app::task_filter::TaskFilter::All => crate::tui::app::task_filter::TaskFilter::CompletedOnly,
+            crate::tui::app::task_filter::TaskFilter::CompletedOnly if has_user => crate::tui::app::task_filter::TaskFilter::AssignedToMe,
             crate::tui::app::task_filter::TaskFilter::CompletedOnly => crate::tui::app::task_filter::TaskFilter::ActiveOnly,
+            crate::tui::app::task_filter::TaskFilter::AssignedToMe => crate::tui::app::task_filter::TaskFilter::Unassigned,
+            crate::tui::app::task_filter::TaskFilter::Unassigned => crate::tui::app::task_filter::TaskFilter::ActiveOnly,
         };
         
         // If we're currently viewing a specific project, apply project filter (which includes task filter)
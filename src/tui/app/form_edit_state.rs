@@ -69,13 +69,15 @@ mod tests {
     fn test_form_edit_state_field_navigation_and_editing() {
         let task = mock_task();
         let mut form = FormEditState::new(&task);
-        // There are 10 fields (see get_field_count)
+        // There are 16 fields (see get_field_count)
         let _field_names = [
-            "title", "description", "due_date", "start_date", "priority", "", "", "", "", "comment"
+            "title", "description", "due_date", "start_date", "priority", "", "", "", "",
+            "end_date", "repeat", "reminders", "percent_done", "hex_color", "bucket", "comment"
         ];
         // Tab through all fields and set a value for each editable one
         let test_values = [
-            "New Title", "New Description", "2025-12-31", "2025-11-01", "5", "", "", "", "", "A comment"
+            "New Title", "New Description", "2025-12-31", "2025-11-01", "5", "", "", "", "",
+            "2026-01-15", "every 2 weeks", "1d before due", "30", "e8445a", "Doing", "A comment"
         ];
         for i in 0..FormEditState::get_field_count() {
             form.field_index = i;
@@ -92,9 +94,63 @@ mod tests {
         assert_eq!(form.due_date, Some("2025-12-31".to_string()));
         assert_eq!(form.start_date, Some("2025-11-01".to_string()));
         assert_eq!(form.priority, Some(5));
+        assert_eq!(form.end_date, Some("2026-01-15".to_string()));
+        assert_eq!(form.percent_done, 30);
+        assert_eq!(form.bucket, "Doing");
         assert_eq!(form.comment, "A comment");
     }
 
+    #[test]
+    fn test_repeat_round_trip() {
+        assert_eq!(parse_repeat(""), Ok((0, 0)));
+        assert_eq!(parse_repeat("every 2 weeks"), Ok((2 * 604800, 0)));
+        assert_eq!(parse_repeat("Daily from completion"), Ok((86400, 2)));
+        assert_eq!(parse_repeat("monthly"), Ok((0, 1)));
+        assert!(parse_repeat("every 2 months").is_err());
+        assert!(parse_repeat("sometimes").is_err());
+        // Intervals that aren't whole minutes keep their seconds
+        assert_eq!(format_repeat(86400 + 30, 0), "every 86430 seconds");
+        for (after, mode) in [(0, 0), (86400, 0), (3 * 3600, 2), (0, 1), (2 * 604800, 0), (90, 0), (1, 2)] {
+            assert_eq!(parse_repeat(&format_repeat(after, mode)), Ok((after, mode)));
+        }
+    }
+
+    #[test]
+    fn test_reminders_round_trip() {
+        let settings = DateSettings::default();
+        let reminders = parse_reminders(&settings, "1d before due, at start, 2025-07-15 09:30").unwrap();
+        assert_eq!(reminders[0], Reminder { reminder: None, relative_to: Some("due_date".to_string()), relative_period: Some(-86400) });
+        assert_eq!(reminders[1].relative_period, Some(0));
+        assert_eq!(reminders[2].reminder, settings.at(chrono::NaiveDate::from_ymd_opt(2025, 7, 15).unwrap(), chrono::NaiveTime::from_hms_opt(9, 30, 0).unwrap()).map(|d| d.to_rfc3339()));
+        assert_eq!(format_reminders(&settings, &reminders), "1d before due, at start, 2025-07-15 09:30");
+        assert!(parse_reminders(&settings, "1x before due").is_err());
+    }
+
+    #[test]
+    fn test_only_changed_fields_are_sent() {
+        let settings = DateSettings::default();
+        let mut form = FormEditState::new(&Task { percent_done: Some(20), hex_color: Some("#E8445A".to_string()), ..mock_task() });
+        assert!(form.changed_fields().is_empty());
+        assert!(form.changes(&settings).is_empty());
+
+        form.adjust_percent_done(10);
+        form.hex_color = "e8445a".to_string();
+        form.next_palette_color();
+        form.repeat = "weekly".to_string();
+        assert!(form.is_field_changed(12));
+        let changes = form.changes(&settings);
+        assert_eq!(changes.percent_done, Some(30));
+        assert_eq!(changes.hex_color.as_deref(), Some(COLOR_PALETTE[1]));
+        assert_eq!(changes.repeat, Some((604800, 0)));
+        assert_eq!(changes.title, None);
+        assert_eq!(changes.due_date, None);
+
+        form.hex_color = "nope".to_string();
+        form.end_date = Some("2020-01-01".to_string());
+        form.start_date = Some("2021-01-01".to_string());
+        assert_eq!(form.errors(&settings).len(), 2);
+    }
+
     #[test]
     fn test_form_edit_state_priority_parsing() {
         let mut form = FormEditState::new(&mock_task());
@@ -116,7 +172,22 @@ mod tests {
         assert_eq!(form.start_date, None);
     }
 }
-use crate::vikunja::models::Task;
+use crate::tui::app::edit_conflict::ConflictField;
+use crate::tui::date_picker::DatePicker;
+use crate::tui::modals::normalize_hex_color;
+use crate::dates::DateSettings;
+use crate::vikunja::models::{Reminder, Task};
+use crate::vikunja_client::tasks::TaskFormChanges;
+use crate::vikunja_parser::QuickAddParser;
+use chrono::DateTime;
+
+/// Colors the color field cycles through with Space
+pub const COLOR_PALETTE: &[&str] = &[
+    "e8445a", "ff9f1c", "ffcc00", "4caf50", "1973ff", "7f23ff", "ff4136", "00bcd4", "8d6e63", "9e9e9e",
+];
+
+/// Seconds in the units repeats and reminders are written in
+const UNITS: &[(char, &str, i64)] = &[('w', "week", 604800), ('d', "day", 86400), ('h', "hour", 3600), ('m', "minute", 60), ('s', "second", 1)];
 
 #[derive(Clone, Debug)]
 pub struct FormEditState {
@@ -130,9 +201,26 @@ pub struct FormEditState {
     pub label_ids: Vec<i64>,
     pub assignee_ids: Vec<i64>,
     pub is_favorite: bool,
+    pub end_date: Option<String>,
+    /// "every 2 weeks", "monthly", "daily from completion", or empty
+    pub repeat: String,
+    /// Comma-separated: dates, or times relative to a date like "1d before due"
+    pub reminders: String,
+    /// 0-100, in steps of 10 from the keyboard
+    pub percent_done: u8,
+    /// Six hex digits without '#', or empty for no color
+    pub hex_color: String,
+    /// Kanban bucket by title or id
+    pub bucket: String,
     pub task_id: i64,
     pub comment: String,
     pub cursor_position: usize,
+    /// Server `updated` timestamp of the task when the form was opened
+    pub updated: Option<String>,
+    /// Field values as loaded, used to detect and merge concurrent edits
+    pub base: Option<Box<FormEditState>>,
+    /// Calendar popup open over the due or start date field
+    pub date_picker: Option<DatePicker>,
     // Removed unused fields: show_project_picker, show_label_picker
 }
 
@@ -147,25 +235,169 @@ impl FormEditState {
         self.label_ids = label_ids;
     }
     pub fn new(task: &Task) -> Self {
-        Self {
+        Self::with_settings(task, &DateSettings::default())
+    }
+
+    /// Dates are shown in the configured time zone, with a time unless they are all-day
+    pub fn with_settings(task: &Task, settings: &DateSettings) -> Self {
+        let mut state = Self {
             field_index: 0,
             title: task.title.clone(),
-            description: task.description.clone().unwrap_or_default(),
-            due_date: task.due_date.map(|d| d.format("%Y-%m-%d").to_string()),
-            start_date: task.start_date.map(|d| d.format("%Y-%m-%d").to_string()),
+            // Edited as Markdown, converted back to HTML on save
+            description: crate::markdown::html_to_markdown(task.description.as_deref().unwrap_or_default()),
+            due_date: task.due_date.map(|d| settings.format(d, settings.due_time)),
+            start_date: task.start_date.map(|d| settings.format(d, settings.start_time)),
             priority: task.priority,
             project_id: task.project_id,
             label_ids: task.labels.as_ref().map(|labels| labels.iter().map(|l| l.id).collect()).unwrap_or_default(),
             assignee_ids: task.assignees.as_ref().map(|assignees| assignees.iter().map(|a| a.id).collect()).unwrap_or_default(),
             is_favorite: task.is_favorite,
+            end_date: task.end_date.as_deref()
+                .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+                .filter(|d| chrono::Datelike::year(d) > 1900)
+                .map(|d| settings.format(d.to_utc(), settings.due_time)),
+            repeat: format_repeat(task.repeat_after.unwrap_or(0), task.repeat_mode.unwrap_or(0)),
+            reminders: format_reminders(settings, task.reminders.as_deref().unwrap_or_default()),
+            percent_done: task.percent_done.unwrap_or(0),
+            hex_color: task.hex_color.as_deref().unwrap_or_default().trim_start_matches('#').to_string(),
+            bucket: task.bucket_id.filter(|id| *id > 0)
+                .map(|id| task.buckets.iter().flatten()
+                    .find(|b| b.id == id)
+                    .and_then(|b| b.title.clone())
+                    .unwrap_or_else(|| id.to_string()))
+                .unwrap_or_default(),
             task_id: task.id,
             comment: String::new(),
             cursor_position: 0,
+            updated: task.updated.clone(),
+            base: None,
+            date_picker: None,
             // Removed unused fields: show_project_picker, show_label_picker
-        }
+        };
+        state.base = Some(Box::new(state.clone()));
+        state
     }
     pub fn get_field_count() -> usize {
-        10
+        16
+    }
+
+    /// Fields typed as text, edited with the line editor
+    pub fn is_text_field(index: usize) -> bool {
+        matches!(index, 0..=4 | 9..=11 | 13..=15)
+    }
+
+    /// Date fields, which open the calendar with Ctrl+T
+    pub fn is_date_field(index: usize) -> bool {
+        matches!(index, 2 | 3 | 9)
+    }
+
+    pub fn field_label(index: usize) -> &'static str {
+        match index {
+            0 => "Title",
+            1 => "Description",
+            2 => "Due Date",
+            3 => "Start Date",
+            4 => "Priority",
+            5 => "Project",
+            6 => "Labels",
+            7 => "Assignees",
+            8 => "Favorite",
+            9 => "End Date",
+            10 => "Repeat",
+            11 => "Reminders",
+            12 => "Progress",
+            13 => "Color",
+            14 => "Bucket",
+            15 => "Add Comment",
+            _ => "",
+        }
+    }
+
+    /// Fields that differ from the task as loaded
+    pub fn changed_fields(&self) -> Vec<ConflictField> {
+        let Some(ref base) = self.base else { return Vec::new() };
+        ConflictField::ALL.into_iter().filter(|field| field.key(self) != field.key(base)).collect()
+    }
+
+    pub fn is_field_changed(&self, index: usize) -> bool {
+        self.changed_fields().iter().any(|field| field.form_field() == index)
+    }
+
+    /// What is wrong with a field's value, if anything
+    pub fn field_error(&self, index: usize, settings: &DateSettings) -> Option<String> {
+        let bad_date = |date: &Option<String>, default_time| {
+            date.as_deref().is_some_and(|d| !d.trim().is_empty() && settings.parse(d, default_time).is_none())
+        };
+        match index {
+            0 if self.title.trim().is_empty() => Some("Title is required.".to_string()),
+            2 if bad_date(&self.due_date, settings.due_time) => Some("Due date must be in YYYY-MM-DD or YYYY-MM-DD HH:MM format.".to_string()),
+            3 if bad_date(&self.start_date, settings.start_time) => Some("Start date must be in YYYY-MM-DD or YYYY-MM-DD HH:MM format.".to_string()),
+            4 if self.priority.is_some_and(|p| !(0..=5).contains(&p)) => Some("Priority must be between 0 and 5.".to_string()),
+            9 if bad_date(&self.end_date, settings.due_time) => Some("End date must be in YYYY-MM-DD or YYYY-MM-DD HH:MM format.".to_string()),
+            9 => {
+                let start = self.start_date.as_deref().and_then(|d| settings.parse(d, settings.start_time));
+                let end = self.end_date.as_deref().and_then(|d| settings.parse(d, settings.due_time));
+                match (start, end) {
+                    (Some(start), Some(end)) if end < start => Some("End date is before the start date.".to_string()),
+                    _ => None,
+                }
+            }
+            10 => parse_repeat(&self.repeat).err(),
+            11 => parse_reminders(settings, &self.reminders).err(),
+            13 if !self.hex_color.trim().is_empty() && normalize_hex_color(&self.hex_color).is_none() => {
+                Some("Color must be six hex digits, like e8445a.".to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// Every field's error, in field order
+    pub fn errors(&self, settings: &DateSettings) -> Vec<String> {
+        (0..Self::get_field_count()).filter_map(|index| self.field_error(index, settings)).collect()
+    }
+
+    /// The changed fields, ready to send; only meaningful once `errors` is empty
+    pub fn changes(&self, settings: &DateSettings) -> TaskFormChanges {
+        let non_empty = |date: &Option<String>| date.clone().filter(|d| !d.trim().is_empty());
+        let mut changes = TaskFormChanges::default();
+        for field in self.changed_fields() {
+            match field {
+                ConflictField::Title => changes.title = Some(self.title.trim().to_string()),
+                ConflictField::Description => changes.description = Some(self.description.clone()),
+                ConflictField::DueDate => changes.due_date = Some(non_empty(&self.due_date)),
+                ConflictField::StartDate => changes.start_date = Some(non_empty(&self.start_date)),
+                ConflictField::EndDate => changes.end_date = Some(non_empty(&self.end_date)),
+                ConflictField::Priority => changes.priority = Some(self.priority),
+                ConflictField::Project => changes.project_id = Some(self.project_id),
+                ConflictField::Labels => changes.label_ids = Some(self.label_ids.clone()),
+                ConflictField::Assignees => changes.assignee_ids = Some(self.assignee_ids.clone()),
+                ConflictField::Favorite => changes.is_favorite = Some(self.is_favorite),
+                ConflictField::PercentDone => changes.percent_done = Some(self.percent_done),
+                ConflictField::Color => changes.hex_color = Some(normalize_hex_color(&self.hex_color).unwrap_or_default()),
+                ConflictField::Repeat => changes.repeat = parse_repeat(&self.repeat).ok(),
+                ConflictField::Reminders => changes.reminders = parse_reminders(settings, &self.reminders).ok(),
+                ConflictField::Bucket => changes.bucket = Some(self.bucket.trim().to_string()).filter(|b| !b.is_empty()),
+            }
+        }
+        if !self.comment.trim().is_empty() {
+            changes.comment = Some(self.comment.clone());
+        }
+        changes
+    }
+
+    /// Move the progress slider by `step` percent
+    pub fn adjust_percent_done(&mut self, step: i32) {
+        self.percent_done = (self.percent_done as i32 + step).clamp(0, 100) as u8;
+    }
+
+    /// Switch the color to the palette's next one
+    pub fn next_palette_color(&mut self) {
+        let current = normalize_hex_color(&self.hex_color);
+        let next = match COLOR_PALETTE.iter().position(|c| Some(c.to_string()) == current) {
+            Some(i) => COLOR_PALETTE[(i + 1) % COLOR_PALETTE.len()],
+            None => COLOR_PALETTE[0],
+        };
+        self.hex_color = next.to_string();
     }
     pub fn get_current_field_text(&self) -> String {
         match self.field_index {
@@ -174,7 +406,13 @@ impl FormEditState {
             2 => self.due_date.clone().unwrap_or_default(),
             3 => self.start_date.clone().unwrap_or_default(),
             4 => self.priority.map(|p| p.to_string()).unwrap_or_default(),
-            9 => self.comment.clone(),
+            9 => self.end_date.clone().unwrap_or_default(),
+            10 => self.repeat.clone(),
+            11 => self.reminders.clone(),
+            12 => self.percent_done.to_string(),
+            13 => self.hex_color.clone(),
+            14 => self.bucket.clone(),
+            15 => self.comment.clone(),
             _ => String::new(),
         }
     }
@@ -197,9 +435,148 @@ impl FormEditState {
                 self.priority = if text.is_empty() { None } else { text.parse().ok() };
             }
             9 => {
+                self.end_date = if text.is_empty() { None } else { Some(text) };
+            }
+            10 => {
+                self.repeat = text;
+            }
+            11 => {
+                self.reminders = text;
+            }
+            12 => {
+                if let Ok(percent) = text.trim().trim_end_matches('%').parse::<u8>() {
+                    self.percent_done = percent.min(100);
+                }
+            }
+            13 => {
+                self.hex_color = text;
+            }
+            14 => {
+                self.bucket = text;
+            }
+            15 => {
                 self.comment = text;
             }
             _ => {}
         }
     }
 }
+
+/// Read a repeat like "every 2 weeks", "daily", "monthly" or "every 3 days from
+/// completion" as Vikunja's `repeat_after` seconds and `repeat_mode`. Empty is no repeat.
+pub fn parse_repeat(text: &str) -> Result<(i64, i64), String> {
+    let invalid = || format!("Can't read repeat '{}': try \"every 2 weeks\", \"daily\" or \"monthly\", optionally \"from completion\".", text.trim());
+    let text = text.trim().to_lowercase();
+    if text.is_empty() {
+        return Ok((0, 0));
+    }
+    // Mode 2 repeats from when the task was done instead of from its due date
+    let (text, mode) = match text.strip_suffix("from completion") {
+        Some(rest) => (rest.trim(), 2),
+        None => (text.as_str(), 0),
+    };
+    let every = match text {
+        "hourly" => "every hour",
+        "daily" => "every day",
+        "weekly" => "every week",
+        "monthly" => "every month",
+        other => other,
+    };
+    let rest = every.strip_prefix("every").unwrap_or(every).trim();
+    let (amount, unit) = match rest.split_once(' ') {
+        Some((amount, unit)) => (amount.parse::<i64>().map_err(|_| invalid())?, unit.trim()),
+        None => (1, rest),
+    };
+    let unit = unit.trim_end_matches('s');
+    if unit == "month" {
+        // Vikunja repeats by calendar month only one month at a time
+        return if amount == 1 && mode == 0 { Ok((0, 1)) } else { Err("Months repeat only as \"monthly\".".to_string()) };
+    }
+    let seconds = UNITS.iter().find(|(_, name, _)| *name == unit).map(|(_, _, seconds)| *seconds).ok_or_else(invalid)?;
+    if amount <= 0 {
+        return Err(invalid());
+    }
+    Ok((amount * seconds, mode))
+}
+
+/// The text `parse_repeat` reads back as this repeat
+pub fn format_repeat(repeat_after: i64, repeat_mode: i64) -> String {
+    if repeat_mode == 1 {
+        return "monthly".to_string();
+    }
+    if repeat_after <= 0 {
+        return String::new();
+    }
+    // The largest unit that shows it exactly; seconds always do
+    let (name, seconds) = UNITS.iter()
+        .find(|(_, _, seconds)| repeat_after % seconds == 0)
+        .map_or(("second", 1), |(_, name, seconds)| (*name, *seconds));
+    let every = if repeat_after == seconds {
+        format!("every {}", name)
+    } else {
+        format!("every {} {}s", repeat_after / seconds, name)
+    };
+    if repeat_mode == 2 { format!("{} from completion", every) } else { every }
+}
+
+/// Read comma-separated reminders: dates ("2025-07-15 09:00", "friday 9am") or times
+/// relative to one of the task's dates ("1d before due", "30m after start", "at end")
+pub fn parse_reminders(settings: &DateSettings, text: &str) -> Result<Vec<Reminder>, String> {
+    let parser = QuickAddParser::with_settings(*settings);
+    text.split(',').map(str::trim).filter(|entry| !entry.is_empty()).map(|entry| {
+        let words: Vec<&str> = entry.split_whitespace().collect();
+        let relative_to = |word: &str| match word {
+            "due" => Some("due_date"),
+            "start" => Some("start_date"),
+            "end" => Some("end_date"),
+            _ => None,
+        };
+        let relative = match words.as_slice() {
+            ["at", date] => relative_to(date).map(|to| (to, 0)),
+            [amount, direction @ ("before" | "after"), date] => relative_to(date).zip(parse_period(amount))
+                .map(|(to, period)| (to, if *direction == "before" { -period } else { period })),
+            _ => None,
+        };
+        if let Some((relative_to, period)) = relative {
+            return Ok(Reminder { reminder: None, relative_to: Some(relative_to.to_string()), relative_period: Some(period) });
+        }
+        settings.parse(entry, settings.due_time)
+            .or_else(|| parser.parse_date(entry, settings.due_time))
+            .map(|date| Reminder { reminder: Some(date.to_rfc3339()), relative_to: None, relative_period: None })
+            .ok_or_else(|| format!("Can't read reminder '{}': use a date or e.g. \"1d before due\".", entry))
+    }).collect()
+}
+
+/// A length of time like "30m", "2h", "1d" or "1w", in seconds
+fn parse_period(text: &str) -> Option<i64> {
+    let unit = text.chars().last()?;
+    let amount: i64 = text[..text.len() - unit.len_utf8()].parse().ok()?;
+    UNITS.iter().find(|(short, _, _)| *short == unit).map(|(_, _, seconds)| amount * seconds)
+}
+
+/// The text `parse_reminders` reads back as these reminders
+pub fn format_reminders(settings: &DateSettings, reminders: &[Reminder]) -> String {
+    reminders.iter().filter_map(|reminder| {
+        if let Some(date) = reminder.reminder.as_deref().and_then(|d| DateTime::parse_from_rfc3339(d).ok()) {
+            if chrono::Datelike::year(&date) > 1900 {
+                return Some(settings.format(date.to_utc(), settings.due_time));
+            }
+        }
+        let to = match reminder.relative_to.as_deref()? {
+            "due_date" => "due",
+            "start_date" => "start",
+            "end_date" => "end",
+            _ => return None,
+        };
+        let period = reminder.relative_period.unwrap_or(0);
+        if period == 0 {
+            return Some(format!("at {}", to));
+        }
+        let (short, seconds) = UNITS.iter()
+            .find(|(_, _, seconds)| period % seconds == 0)
+            .map(|(short, _, seconds)| (*short, *seconds))
+            .unwrap_or(('s', 1));
+        let direction = if period < 0 { "before" } else { "after" };
+        Some(format!("{}{} {} {}", period.abs() / seconds, short, direction, to))
+    }).collect::<Vec<_>>().join(", ")
+}
@@ -0,0 +1,87 @@
+use crate::tui::app::state::App;
+use crate::tui::input_history::{HistoryKind, Recall};
+
+impl App {
+    /// Close quick add, keeping what was typed as a draft for next time
+    pub fn cancel_quick_add_modal(&mut self) {
+        self.input_history.set_draft(HistoryKind::QuickAdd, 0, &self.quick_add_input);
+        self.hide_quick_add_modal();
+    }
+
+    /// Close the edit line. Unsaved changes are kept as a draft for the task.
+    pub fn cancel_edit_modal(&mut self) {
+        if let Some(task_id) = self.editing_task_id {
+            let unchanged = self.all_tasks.iter()
+                .find(|t| t.id == task_id)
+                .is_some_and(|task| self.task_to_magic_syntax(task) == self.edit_input);
+            let draft = if unchanged { "" } else { self.edit_input.as_str() };
+            self.input_history.set_draft(HistoryKind::Edit, task_id, draft);
+        }
+        self.hide_edit_modal();
+    }
+
+    /// Put a draft left by a cancelled quick add back into the input
+    pub(crate) fn restore_quick_add_draft(&mut self) {
+        if let Some(draft) = self.input_history.draft(HistoryKind::QuickAdd, 0) {
+            self.quick_add_input = draft.to_string();
+            self.quick_add_cursor_position = self.quick_add_input.len();
+            self.show_toast("Restored unsent draft".to_string());
+        }
+    }
+
+    /// Put a draft left by a cancelled edit of the task back into the edit line
+    pub(crate) fn restore_edit_draft(&mut self, task_id: i64) {
+        match self.input_history.draft(HistoryKind::Edit, task_id) {
+            Some(draft) if draft != self.edit_input => {
+                self.edit_input = draft.to_string();
+                self.edit_cursor_position = self.edit_input.len();
+                self.show_toast("Restored unsaved edit".to_string());
+            }
+            Some(_) => self.input_history.clear_draft(HistoryKind::Edit, task_id),
+            None => {}
+        }
+    }
+
+    /// Record a submitted quick add line
+    pub fn remember_quick_add(&mut self, input: &str) {
+        self.record_suggestion_usage(input, None);
+        self.input_history.push(HistoryKind::QuickAdd, input);
+        self.input_history.clear_draft(HistoryKind::QuickAdd, 0);
+    }
+
+    /// Record a submitted edit line
+    pub fn remember_edit(&mut self, task_id: i64, input: &str) {
+        let unchanged = self.all_tasks.iter()
+            .find(|t| t.id == task_id)
+            .map(|task| self.task_to_magic_syntax(task));
+        self.record_suggestion_usage(input, unchanged.as_deref());
+        self.input_history.push(HistoryKind::Edit, input);
+        self.input_history.clear_draft(HistoryKind::Edit, task_id);
+    }
+
+    /// Step through earlier quick add lines that start with what was typed
+    pub fn recall_quick_add_history(&mut self, direction: Recall) -> bool {
+        let entries = self.input_history.entries(HistoryKind::QuickAdd);
+        let Some(text) = self.quick_add_history.recall(entries, &self.quick_add_input, direction) else {
+            return false;
+        };
+        self.quick_add_cursor_position = text.len();
+        self.quick_add_input = text;
+        // Recalled lines often end in a label or project; don't pop up suggestions for it,
+        // so Up/Down keep stepping through the history
+        self.clear_suggestions();
+        true
+    }
+
+    /// Step through earlier edit lines that start with what was typed
+    pub fn recall_edit_history(&mut self, direction: Recall) -> bool {
+        let entries = self.input_history.entries(HistoryKind::Edit);
+        let Some(text) = self.edit_history.recall(entries, &self.edit_input, direction) else {
+            return false;
+        };
+        self.edit_cursor_position = text.len();
+        self.edit_input = text;
+        self.clear_suggestions();
+        true
+    }
+}
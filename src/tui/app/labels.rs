@@ -1,6 +1,11 @@
 use crate::tui::app::state::App;
+use crate::tui::line_editor::{EditOutcome, LineEditor};
+use crossterm::event::KeyEvent;
 use crate::tui::app::picker_context::PickerContext;
 use crate::tui::utils::contains_ignore_case;
+use crate::tui::modals::{LabelManagerModal, LabelEntry};
+use crate::vikunja::models::{Label, Task};
+use std::collections::HashMap;
 
 impl App {
     pub fn show_label_picker(&mut self) {
@@ -19,19 +24,30 @@ impl App {
     
     #[allow(dead_code)]
     pub fn add_char_to_label_picker(&mut self, c: char) {
-        self.label_picker_input.push(c);
+        self.label_picker_editor().insert_char(c);
         self.update_filtered_labels();
         self.selected_label_picker_index = 0; // Reset selection to first item
     }
     
     #[allow(dead_code)]
     pub fn delete_char_from_label_picker(&mut self) {
-        if !self.label_picker_input.is_empty() {
-            self.label_picker_input.pop();
+        if self.label_picker_editor().backspace() == EditOutcome::Changed {
             self.update_filtered_labels();
             self.selected_label_picker_index = 0; // Reset selection to first item
         }
     }
+
+    /// Apply an editing key to the search text, refiltering when it changes
+    pub fn edit_label_picker_input(&mut self, key: &KeyEvent) {
+        if self.label_picker_editor().handle_key(key) == EditOutcome::Changed {
+            self.update_filtered_labels();
+            self.selected_label_picker_index = 0; // Reset selection to first item
+        }
+    }
+
+    fn label_picker_editor(&mut self) -> LineEditor<'_> {
+        LineEditor::new(&mut self.label_picker_input, &mut self.picker_cursor_position, &mut self.kill_ring)
+    }
     
     #[allow(dead_code)]
     pub fn move_label_picker_up(&mut self) {
@@ -88,3 +104,134 @@ impl App {
             .collect::<Vec<_>>();
     }
 }
+
+impl App {
+    /// Number of tasks using each label, counted over all loaded tasks
+    pub fn label_usage_counts(&self) -> HashMap<i64, usize> {
+        let mut counts = HashMap::new();
+        for task in &self.all_tasks {
+            if let Some(ref labels) = task.labels {
+                for label in labels {
+                    *counts.entry(label.id).or_insert(0) += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    fn label_manager_entries(&self) -> Vec<LabelEntry> {
+        let counts = self.label_usage_counts();
+        self.label_map.iter()
+            .map(|(id, title)| LabelEntry {
+                id: *id,
+                title: title.clone(),
+                hex_color: self.label_colors.get(id).cloned(),
+                usage: counts.get(id).copied().unwrap_or(0),
+            })
+            .collect()
+    }
+
+    pub fn show_label_manager_modal(&mut self) {
+        self.close_all_modals();
+        self.show_label_manager_modal = true;
+        self.label_manager_modal = Some(LabelManagerModal::new(self.label_manager_entries()));
+    }
+
+    pub fn hide_label_manager_modal(&mut self) {
+        self.show_label_manager_modal = false;
+        self.label_manager_modal = None;
+    }
+
+    /// Rebuild the label manager list after labels changed
+    fn refresh_label_manager(&mut self) {
+        let entries = self.label_manager_entries();
+        if let Some(ref mut modal) = self.label_manager_modal {
+            modal.set_labels(entries);
+        }
+    }
+
+    /// Apply `f` to the label list of every loaded copy of every task
+    fn update_task_labels<F: FnMut(&mut Vec<Label>)>(&mut self, mut f: F) {
+        let tasks = self.all_tasks.iter_mut()
+            .chain(self.tasks.iter_mut())
+            .chain(self.detailed_task_cache.values_mut());
+        for task in tasks {
+            if let Some(ref mut labels) = task.labels {
+                f(labels);
+            }
+        }
+    }
+
+    pub fn apply_label_rename(&mut self, label_id: i64, title: &str) {
+        self.label_map.insert(label_id, title.to_string());
+        self.update_task_labels(|labels| {
+            for label in labels.iter_mut().filter(|l| l.id == label_id) {
+                label.title = title.to_string();
+            }
+        });
+        self.refresh_label_manager();
+    }
+
+    pub fn apply_label_color(&mut self, label_id: i64, hex_color: &str) {
+        self.label_colors.insert(label_id, hex_color.to_string());
+        self.update_task_labels(|labels| {
+            for label in labels.iter_mut().filter(|l| l.id == label_id) {
+                label.hex_color = Some(hex_color.to_string());
+            }
+        });
+        self.refresh_label_manager();
+    }
+
+    pub fn apply_label_deletion(&mut self, label_id: i64) {
+        self.label_map.remove(&label_id);
+        self.label_colors.remove(&label_id);
+        self.selected_label_ids.retain(|id| *id != label_id);
+        self.update_task_labels(|labels| labels.retain(|l| l.id != label_id));
+        self.refresh_label_manager();
+    }
+
+    /// Tasks that carry `source_id`, each paired with whether it already has `target_id`.
+    /// `server_tasks` are the ones the server reports with the label; loaded tasks it
+    /// missed are added so nothing the user can see keeps the deleted label.
+    pub fn label_merge_plan(&self, server_tasks: &[Task], source_id: i64, target_id: i64) -> Vec<(u64, bool)> {
+        let mut seen = std::collections::HashSet::new();
+        server_tasks.iter().chain(self.all_tasks.iter())
+            .filter(|task| seen.insert(task.id))
+            .filter_map(|task| {
+                let labels = task.labels.as_ref()?;
+                if labels.iter().any(|l| l.id == source_id) {
+                    Some((task.id as u64, labels.iter().any(|l| l.id == target_id)))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Move loaded tasks from `source_id` to `target_id`, except `failed_task_ids`, which
+    /// the server didn't move. The source label is only dropped when every task moved.
+    pub fn apply_label_merge(&mut self, source_id: i64, target_id: i64, failed_task_ids: &[u64]) {
+        let target_title = self.label_map.get(&target_id).cloned().unwrap_or_default();
+        let target_color = self.label_colors.get(&target_id).cloned();
+        if failed_task_ids.is_empty() {
+            self.label_map.remove(&source_id);
+            self.label_colors.remove(&source_id);
+        }
+        let tasks = self.all_tasks.iter_mut()
+            .chain(self.tasks.iter_mut())
+            .chain(self.detailed_task_cache.values_mut())
+            .filter(|task| !failed_task_ids.contains(&(task.id as u64)));
+        for labels in tasks.filter_map(|task| task.labels.as_mut()) {
+            if labels.iter().any(|l| l.id == target_id) {
+                labels.retain(|l| l.id != source_id);
+            } else {
+                for label in labels.iter_mut().filter(|l| l.id == source_id) {
+                    label.id = target_id;
+                    label.title = target_title.clone();
+                    label.hex_color = target_color.clone();
+                }
+            }
+        }
+        self.refresh_label_manager();
+    }
+}
@@ -1,12 +1,24 @@
 pub mod state;
 pub mod form_edit_state;
+pub mod edit_conflict;
 pub mod sort_order;
 pub mod picker_context;
 pub mod task_filter;
 pub mod undoable_action;
 pub mod pending_action;
 pub mod suggestion_mode;
+pub mod suggestions;
 pub mod tasks;
+pub mod undo;
+pub mod undo_history;
+pub mod trash;
+pub mod templates;
+pub mod snooze;
 pub mod projects;
 pub mod labels;
+pub mod assignees;
+pub mod quick_actions;
 pub mod filters;
+pub mod checklist;
+pub mod paste_preview;
+pub mod input_history;
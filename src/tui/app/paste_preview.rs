@@ -0,0 +1,17 @@
+use crate::tui::app::state::App;
+use crate::tui::modals::PastePreviewModal;
+use crate::vikunja_parser::OutlineEntry;
+
+impl App {
+    pub fn show_paste_preview_modal(&mut self, outline: Vec<OutlineEntry>) {
+        let modal = PastePreviewModal::new(outline, self.date_settings);
+        self.close_all_modals();
+        self.show_paste_preview_modal = true;
+        self.paste_preview_modal = Some(modal);
+    }
+
+    pub fn hide_paste_preview_modal(&mut self) {
+        self.show_paste_preview_modal = false;
+        self.paste_preview_modal = None;
+    }
+}
@@ -3,4 +3,5 @@ pub enum PickerContext {
     None,
     FormEditProject,
     FormEditLabel,
+    FormEditAssignee,
 }
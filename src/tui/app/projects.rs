@@ -1,4 +1,6 @@
 use crate::tui::app::state::App;
+use crate::tui::line_editor::{EditOutcome, LineEditor};
+use crossterm::event::KeyEvent;
 use crate::tui::app::picker_context::PickerContext;
 use crate::tui::utils::contains_ignore_case;
 
@@ -17,18 +19,29 @@ impl App {
     }
     #[allow(dead_code)]
     pub fn add_char_to_project_picker(&mut self, c: char) {
-        self.project_picker_input.push(c);
+        self.project_picker_editor().insert_char(c);
         self.update_filtered_projects();
         self.selected_project_picker_index = 0; // Reset selection to first item
     }
     #[allow(dead_code)]
     pub fn delete_char_from_project_picker(&mut self) {
-        if !self.project_picker_input.is_empty() {
-            self.project_picker_input.pop();
+        if self.project_picker_editor().backspace() == EditOutcome::Changed {
             self.update_filtered_projects();
             self.selected_project_picker_index = 0; // Reset selection to first item
         }
     }
+
+    /// Apply an editing key to the search text, refiltering when it changes
+    pub fn edit_project_picker_input(&mut self, key: &KeyEvent) {
+        if self.project_picker_editor().handle_key(key) == EditOutcome::Changed {
+            self.update_filtered_projects();
+            self.selected_project_picker_index = 0; // Reset selection to first item
+        }
+    }
+
+    fn project_picker_editor(&mut self) -> LineEditor<'_> {
+        LineEditor::new(&mut self.project_picker_input, &mut self.picker_cursor_position, &mut self.kill_ring)
+    }
     #[allow(dead_code)]
     pub fn move_project_picker_up(&mut self) {
         if !self.filtered_projects.is_empty() {
@@ -91,11 +104,7 @@ impl App {
                 .collect();
             
             // Apply task filter to the project-filtered tasks
-            self.tasks = project_tasks.into_iter().filter(|task| match self.task_filter {
-                crate::tui::app::task_filter::TaskFilter::ActiveOnly => !task.done,
-                crate::tui::app::task_filter::TaskFilter::All => true,
-                crate::tui::app::task_filter::TaskFilter::CompletedOnly => task.done,
-            }).collect();
+            self.tasks = project_tasks.into_iter().filter(|task| self.task_matches_filter(task)).collect();
             
             // Apply layout-specific sort if no manual sort is active
             if self.current_sort.is_none() {
@@ -111,4 +120,25 @@ impl App {
         }
         "No project".to_string()
     }
+
+    /// Open the sharing modal for the current project, or the selected task's project
+    pub fn show_project_sharing_modal(&mut self) {
+        let project_id = self.current_project_id
+            .or_else(|| self.get_selected_task().map(|t| t.project_id));
+        match project_id {
+            Some(project_id) => {
+                let project_name = self.project_map.get(&project_id).cloned()
+                    .unwrap_or_else(|| format!("Project {}", project_id));
+                self.close_all_modals();
+                self.show_project_sharing_modal = true;
+                self.project_sharing_modal = Some(crate::tui::modals::ProjectSharingModal::new(project_id, project_name));
+            }
+            None => self.show_toast("Select a project first".to_string()),
+        }
+    }
+
+    pub fn hide_project_sharing_modal(&mut self) {
+        self.show_project_sharing_modal = false;
+        self.project_sharing_modal = None;
+    }
 }
@@ -0,0 +1,139 @@
+use crate::config::{is_clear_date, parse_percent, parse_switch, QuickAction};
+use crate::dates::DateOffset;
+use crate::tui::app::state::App;
+use crate::vikunja::models::{Label, Task, User};
+use crate::vikunja_parser::QuickAddParser;
+use chrono::{DateTime, NaiveTime, Utc};
+
+impl App {
+    /// Apply a quick action to the selected task, in `tasks` and `all_tasks`. A macro's
+    /// steps apply together: if one fails, none do. Comments and bucket moves are left
+    /// to the caller, since they go through their own server endpoints.
+    pub fn apply_quick_action(&mut self, action: &QuickAction) -> Result<(), String> {
+        if self.tasks.is_empty() {
+            return Err("No tasks available".to_string());
+        }
+        let mut task = self.tasks.get(self.selected_task_index).ok_or("No selected task")?.clone();
+        for step in action.steps() {
+            self.apply_quick_action_step(&mut task, &step)?;
+        }
+        if let Some(all_task) = self.all_tasks.iter_mut().find(|t| t.id == task.id) {
+            *all_task = task.clone();
+        }
+        self.tasks[self.selected_task_index] = task;
+        Ok(())
+    }
+
+    fn apply_quick_action_step(&self, task: &mut Task, step: &QuickAction) -> Result<(), String> {
+        let target = step.target.trim();
+        match step.action.as_str() {
+            "project" => {
+                let project_id = self.project_map.iter().find_map(|(id, name)| {
+                    if name == target { Some(*id) } else { None }
+                });
+                task.project_id = project_id.ok_or_else(|| format!("Project '{}' not found", target))?;
+            }
+            "priority" => {
+                let priority = target.parse::<i32>()
+                    .map_err(|_| format!("Invalid priority '{}': not a number", target))?;
+                if !(1..=5).contains(&priority) {
+                    return Err(format!("Invalid priority '{}': must be 1-5", target));
+                }
+                task.priority = Some(priority);
+            }
+            "label" => {
+                let label_id = self.label_id(target).ok_or_else(|| format!("Label '{}' not found", target))?;
+                let labels = task.labels.get_or_insert_with(Vec::new);
+                if !labels.iter().any(|l| l.id == label_id) {
+                    labels.push(Label {
+                        id: label_id,
+                        title: target.to_string(),
+                        hex_color: self.label_colors.get(&label_id).cloned(),
+                        description: None,
+                        created: None,
+                        updated: None,
+                        created_by: None,
+                    });
+                }
+            }
+            "remove_label" => {
+                let label_id = self.label_id(target).ok_or_else(|| format!("Label '{}' not found", target))?;
+                if let Some(ref mut labels) = task.labels {
+                    labels.retain(|l| l.id != label_id);
+                }
+            }
+            "due" => task.due_date = self.quick_action_date(task.due_date, target, self.date_settings.due_time)?,
+            "start" => task.start_date = self.quick_action_date(task.start_date, target, self.date_settings.start_time)?,
+            "assign" => {
+                let user = self.user_by_name(target).ok_or_else(|| format!("User '{}' not found", target))?;
+                let assignees = task.assignees.get_or_insert_with(Vec::new);
+                if !assignees.iter().any(|a| a.id == user.id) {
+                    assignees.push(user.clone());
+                }
+            }
+            "unassign" => {
+                let user_id = self.user_by_name(target).map(|u| u.id)
+                    .or_else(|| task.assignees.iter().flatten().find(|a| a.username.eq_ignore_ascii_case(target)).map(|a| a.id))
+                    .ok_or_else(|| format!("User '{}' not found", target))?;
+                if let Some(ref mut assignees) = task.assignees {
+                    assignees.retain(|a| a.id != user_id);
+                }
+            }
+            "done" => {
+                let switch = parse_switch(target).ok_or_else(|| format!("Invalid done value '{}'", target))?;
+                task.done = switch.unwrap_or(!task.done);
+            }
+            "favorite" => {
+                let switch = parse_switch(target).ok_or_else(|| format!("Invalid favorite value '{}'", target))?;
+                task.is_favorite = switch.unwrap_or(!task.is_favorite);
+            }
+            "percent_done" => {
+                let percent = parse_percent(target).ok_or_else(|| format!("Invalid percent done '{}': must be 0-100", target))?;
+                task.percent_done = Some(percent);
+            }
+            "bucket" if target.is_empty() => return Err("Bucket needs a bucket name or id".to_string()),
+            "bucket" | "comment" => {}
+            _ => return Err(format!("Unknown quick action: {}", step.action)),
+        }
+        Ok(())
+    }
+
+    /// Replace a task's copy in `tasks` and `all_tasks`, e.g. to put it back after a
+    /// failed server write
+    pub fn replace_loaded_task(&mut self, task: Task) {
+        if let Some(all_task) = self.all_tasks.iter_mut().find(|t| t.id == task.id) {
+            *all_task = task.clone();
+        }
+        if let Some(loaded) = self.tasks.iter_mut().find(|t| t.id == task.id) {
+            *loaded = task;
+        }
+    }
+
+    fn label_id(&self, name: &str) -> Option<i64> {
+        self.label_map.iter().find_map(|(id, label)| if label == name { Some(*id) } else { None })
+    }
+
+    /// A known user by username, or "me" for the signed-in user
+    fn user_by_name(&self, name: &str) -> Option<&User> {
+        if name.eq_ignore_ascii_case("me") {
+            return self.current_user.as_ref();
+        }
+        self.known_users.values().find(|u| u.username.eq_ignore_ascii_case(name))
+    }
+
+    /// New value for a date: "clear" removes it, an offset like "+1d" moves it (or
+    /// today, when unset), anything else is read like a quick add date
+    fn quick_action_date(&self, current: Option<DateTime<Utc>>, target: &str, default_time: NaiveTime) -> Result<Option<DateTime<Utc>>, String> {
+        if is_clear_date(target) {
+            return Ok(None);
+        }
+        let date = match DateOffset::parse(target) {
+            Some(offset) => {
+                current.or_else(|| self.date_settings.at(self.date_settings.today(), default_time))
+                    .and_then(|date| offset.apply(&self.date_settings, date))
+            }
+            None => QuickAddParser::with_settings(self.date_settings).parse_date(target, default_time),
+        };
+        date.map(Some).ok_or_else(|| format!("Can't read '{}' as a date", target))
+    }
+}
@@ -0,0 +1,134 @@
+use crate::dates::{DateOffset, DateSettings};
+use crate::tui::app::state::App;
+use crate::tui::app::undo::write_task_changes;
+use crate::tui::modals::{RescheduleModal, SnoozeModal};
+use crate::vikunja::models::Task;
+use crate::vikunja_client::VikunjaClient;
+use crate::vikunja_parser::QuickAddParser;
+use chrono::{DateTime, Utc};
+
+/// How far a snooze moves a task dated `anchor`: an offset like "+1w" as it is, or a
+/// day like "monday" as the whole days from the anchor's day to it
+pub fn snooze_offset(settings: &DateSettings, anchor: DateTime<Utc>, target: &str) -> Option<DateOffset> {
+    if let Some(offset) = DateOffset::parse(target) {
+        return Some(offset);
+    }
+    let day = settings.local(QuickAddParser::with_settings(*settings).parse_date(target, settings.due_time)?).date();
+    let days = day.signed_duration_since(settings.local(anchor).date()).num_days();
+    Some(DateOffset { amount: i32::try_from(days).ok()?, unit: 'd' })
+}
+
+/// The task with its due and start dates moved together, each keeping its time of
+/// day. The move is measured from the due date, or the start date when there is none;
+/// an undated task gets a due date.
+pub fn snoozed_task(settings: &DateSettings, task: &Task, target: &str) -> Result<Task, String> {
+    let unreadable = || format!("Can't read '{}' as a date", target);
+    let mut task = task.clone();
+    match task.due_date.or(task.start_date) {
+        Some(anchor) => {
+            let offset = snooze_offset(settings, anchor, target).ok_or_else(unreadable)?;
+            if let Some(due) = task.due_date {
+                task.due_date = Some(offset.apply(settings, due).ok_or_else(unreadable)?);
+            }
+            if let Some(start) = task.start_date {
+                task.start_date = Some(offset.apply(settings, start).ok_or_else(unreadable)?);
+            }
+        }
+        None => {
+            let due = crate::templates::resolve_date(settings, target, settings.today(), settings.due_time);
+            task.due_date = Some(due.ok_or_else(unreadable)?);
+        }
+    }
+    Ok(task)
+}
+
+impl App {
+    /// Mark or unmark the selected task for bulk commands and move on to the next one
+    pub fn toggle_mark_selected_task(&mut self) {
+        let Some(task_id) = self.get_selected_task().map(|t| t.id) else { return };
+        if !self.marked_task_ids.remove(&task_id) {
+            self.marked_task_ids.insert(task_id);
+        }
+        self.next_task();
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked_task_ids.clear();
+    }
+
+    /// What bulk commands act on: the marked tasks in list order, or else the selected task
+    pub fn marked_or_selected_tasks(&self) -> Vec<Task> {
+        let marked: Vec<Task> = self.tasks.iter().filter(|t| self.marked_task_ids.contains(&t.id)).cloned().collect();
+        if marked.is_empty() {
+            self.get_selected_task().cloned().into_iter().collect()
+        } else {
+            marked
+        }
+    }
+
+    /// Open tasks due before today, with what rescheduling them to today makes of them
+    pub fn overdue_reschedule_plan(&self) -> Vec<(Task, Task)> {
+        let today = self.date_settings.today();
+        self.all_tasks.iter()
+            .filter(|t| !t.done && t.due_date.is_some_and(|due| self.date_settings.local(due).date() < today))
+            .filter_map(|t| Some((t.clone(), snoozed_task(&self.date_settings, t, "today").ok()?)))
+            .collect()
+    }
+
+    /// Send new dates to the server, one task at a time, each undoable on its own.
+    /// Returns how many were saved and the first error.
+    pub async fn reschedule_tasks_async(&mut self, client: &VikunjaClient, changes: &[(Task, Task)]) -> (usize, Option<String>) {
+        let mut saved = 0;
+        let mut first_error = None;
+        for (before, after) in changes {
+            match write_task_changes(client, before, after).await {
+                Ok(()) => {
+                    for task in self.tasks.iter_mut().chain(self.all_tasks.iter_mut()).filter(|t| t.id == after.id) {
+                        task.due_date = after.due_date;
+                        task.start_date = after.start_date;
+                    }
+                    self.add_task_edit_to_undo_stack(after.id, before.clone());
+                    saved += 1;
+                }
+                Err(e) => {
+                    self.add_debug_message(format!("Failed to reschedule task {}: {}", after.id, e));
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        (saved, first_error)
+    }
+
+    pub fn show_snooze_modal(&mut self) {
+        let tasks = self.marked_or_selected_tasks();
+        if tasks.is_empty() {
+            self.show_toast("No task to snooze".to_string());
+            return;
+        }
+        let options = self.config.get_snooze_options();
+        self.close_all_modals();
+        self.show_snooze_modal = true;
+        self.snooze_modal = Some(SnoozeModal::new(&tasks, options, &self.date_settings));
+    }
+
+    pub fn hide_snooze_modal(&mut self) {
+        self.show_snooze_modal = false;
+        self.snooze_modal = None;
+    }
+
+    pub fn show_reschedule_modal(&mut self) {
+        let plan = self.overdue_reschedule_plan();
+        if plan.is_empty() {
+            self.show_toast("Nothing is overdue".to_string());
+            return;
+        }
+        self.close_all_modals();
+        self.show_reschedule_modal = true;
+        self.reschedule_modal = Some(RescheduleModal::new(plan));
+    }
+
+    pub fn hide_reschedule_modal(&mut self) {
+        self.show_reschedule_modal = false;
+        self.reschedule_modal = None;
+    }
+}
@@ -1,15 +1,20 @@
-use crate::vikunja::models::Task;
+use crate::vikunja::models::{Task, User};
 use crate::tui::utils::{normalize_string, fuzzy_match_score};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use chrono::{DateTime, Local, Datelike};
 use crate::config::CriaConfig;
+use crate::dates::DateSettings;
 use crate::tui::app::form_edit_state::FormEditState;
 use crate::tui::app::sort_order::SortOrder;
 use crate::tui::app::picker_context::PickerContext;
 use crate::tui::app::task_filter::TaskFilter;
-use crate::tui::app::undoable_action::UndoableAction;
+use crate::tui::app::undoable_action::{AttachmentCopy, UndoEntryInfo, UndoableAction};
 use crate::tui::app::pending_action::PendingAction;
+use crate::tui::app::trash::TrashedTask;
 use crate::tui::app::suggestion_mode::SuggestionMode;
+use crate::tui::line_editor::{EditOutcome, KillRing, LineEditor};
+use crate::tui::input_history::{HistoryBrowser, InputHistory};
+use crossterm::event::KeyEvent;
 
 mod confirm_quit_ext;
 
@@ -21,6 +26,8 @@ pub enum SubtaskOperation {
 
 pub struct App {
     pub config: CriaConfig,
+    /// Time zone and default times from the config, used to read and show dates
+    pub date_settings: DateSettings,
     pub running: bool,
     pub tasks: Vec<Task>,
     pub all_tasks: Vec<Task>, // Store all tasks for local filtering
@@ -40,9 +47,19 @@ pub struct App {
     pub edit_input: String,
     pub edit_cursor_position: usize,
     pub editing_task_id: Option<i64>,
+    /// Killed text shared by the app's text inputs
+    pub kill_ring: KillRing,
+    /// Cursor in the search field of whichever picker is open
+    pub picker_cursor_position: usize,
+    /// Submitted quick add, edit and comment lines, and unsent drafts
+    pub input_history: InputHistory,
+    pub quick_add_history: HistoryBrowser,
+    pub edit_history: HistoryBrowser,
     // Form Edit Modal state
     pub show_form_edit_modal: bool,
     pub form_edit_state: Option<FormEditState>,
+    /// Set by the form editor; the UI loop suspends the TUI and opens $EDITOR on the description
+    pub external_editor_requested: bool,
     // Debug pane state
     pub show_debug_pane: bool,
     pub debug_messages: Vec<(DateTime<Local>, String)>,
@@ -50,6 +67,17 @@ pub struct App {
     pub undo_stack: Vec<UndoableAction>,
     pub redo_stack: Vec<UndoableAction>,
     pub max_undo_history: usize,
+    /// Time, task and server failures of the undo and redo entries, lined up with the top of each stack
+    pub undo_info: Vec<UndoEntryInfo>,
+    pub redo_info: Vec<UndoEntryInfo>,
+    /// Attachment contents of deleted tasks in the undo history, by task ID
+    pub deleted_attachments: HashMap<i64, Vec<AttachmentCopy>>,
+    /// Parts of a task recreated by undo that didn't come back, reported with the undo
+    pub recreate_failures: Vec<String>,
+    /// Deleted tasks waiting out the grace period before they are deleted on the server
+    pub trash: Vec<TrashedTask>,
+    /// Tasks marked with `v`; bulk commands like snooze act on these instead of the selected task
+    pub marked_task_ids: HashSet<i64>,
     // Confirmation dialog state
     pub show_confirmation_dialog: bool,
     pub confirmation_message: String,
@@ -68,6 +96,18 @@ pub struct App {
     pub filtered_labels: Vec<(i64, String)>, // (label_id, title)
     pub selected_label_picker_index: usize,
     pub selected_label_ids: Vec<i64>, // Currently selected labels
+    // Assignee picker modal state
+    pub show_assignee_picker: bool,
+    pub assignee_picker_input: String,
+    pub filtered_assignees: Vec<User>,
+    pub selected_assignee_picker_index: usize,
+    pub selected_assignee_ids: Vec<i64>,
+    // Users: the API token's owner and every user seen in tasks or searches
+    pub current_user: Option<User>,
+    pub known_users: HashMap<i64, User>,
+    /// Server user search waiting for typing to pause, and the queries already sent
+    pub pending_user_search: Option<(String, std::time::Instant)>,
+    pub searched_user_queries: HashSet<String>,
     // Filter picker modal state
     pub show_filter_picker: bool,
     pub filter_picker_input: String,
@@ -89,6 +129,9 @@ pub struct App {
     pub selected_suggestion: usize,
     pub suggestion_mode: Option<SuggestionMode>,
     pub suggestion_prefix: String,
+    /// Byte offset in the input where an accepted suggestion is written
+    pub suggestion_start: usize,
+    pub completion_cycle: Option<crate::tui::suggestions::CompletionCycle>,
     // Default project
     pub default_project_name: String,
     // Modal states
@@ -130,6 +173,31 @@ pub struct App {
     // Comments modal state
     pub show_comments_modal: bool,
     pub comments_modal: Option<crate::tui::modals::CommentsModal>,
+    // Label manager modal state
+    pub show_label_manager_modal: bool,
+    pub label_manager_modal: Option<crate::tui::modals::LabelManagerModal>,
+    pub show_project_sharing_modal: bool,
+    pub show_edit_conflict_modal: bool,
+    pub edit_conflict_modal: Option<crate::tui::modals::EditConflictModal>,
+    /// Task fields as they were when the edit line was opened, for conflict detection
+    pub edit_base_state: Option<FormEditState>,
+    pub project_sharing_modal: Option<crate::tui::modals::ProjectSharingModal>,
+    // Description checklist modal state
+    pub show_checklist_modal: bool,
+    pub checklist_modal: Option<crate::tui::modals::ChecklistModal>,
+    pub show_undo_history_modal: bool,
+    pub undo_history_modal: Option<crate::tui::modals::UndoHistoryModal>,
+    pub show_trash_modal: bool,
+    pub trash_modal: Option<crate::tui::modals::TrashModal>,
+    pub show_template_picker_modal: bool,
+    pub template_picker_modal: Option<crate::tui::modals::TemplatePickerModal>,
+    pub show_snooze_modal: bool,
+    pub snooze_modal: Option<crate::tui::modals::SnoozeModal>,
+    pub show_reschedule_modal: bool,
+    pub reschedule_modal: Option<crate::tui::modals::RescheduleModal>,
+    // Multi-line quick add paste preview state
+    pub show_paste_preview_modal: bool,
+    pub paste_preview_modal: Option<crate::tui::modals::PastePreviewModal>,
     
     // Layout system
     pub current_layout_name: String,
@@ -185,8 +253,10 @@ impl App {
     // ...existing code...
     pub fn new_with_config(config: CriaConfig, default_project_name: String) -> Self {
         let current_layout_name = config.get_active_layout_name();
+        let date_settings = config.get_date_settings();
         Self {
             config,
+            date_settings,
             running: true, 
             tasks: Vec::new(),
             all_tasks: Vec::new(),
@@ -203,14 +273,26 @@ impl App {
             show_edit_modal: false,
             edit_input: String::new(),
             edit_cursor_position: 0,
+            kill_ring: KillRing::default(),
+            picker_cursor_position: 0,
+            input_history: InputHistory::default(),
+            quick_add_history: HistoryBrowser::default(),
+            edit_history: HistoryBrowser::default(),
             editing_task_id: None,
             show_form_edit_modal: false,
             form_edit_state: None,
+            external_editor_requested: false,
             show_debug_pane: false,
             debug_messages: Vec::new(),
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
             max_undo_history: 50,
+            undo_info: Vec::new(),
+            redo_info: Vec::new(),
+            deleted_attachments: HashMap::new(),
+            recreate_failures: Vec::new(),
+            trash: Vec::new(),
+            marked_task_ids: HashSet::new(),
             show_confirmation_dialog: false,
             confirmation_message: String::new(),
             pending_action: None,
@@ -225,6 +307,15 @@ impl App {
             filtered_labels: Vec::new(),
             selected_label_picker_index: 0,
             selected_label_ids: Vec::new(),
+            show_assignee_picker: false,
+            assignee_picker_input: String::new(),
+            filtered_assignees: Vec::new(),
+            selected_assignee_picker_index: 0,
+            selected_assignee_ids: Vec::new(),
+            current_user: None,
+            known_users: HashMap::new(),
+            pending_user_search: None,
+            searched_user_queries: HashSet::new(),
             show_filter_picker: false,
             filter_picker_input: String::new(),
             filtered_filters: Vec::new(),
@@ -242,6 +333,8 @@ impl App {
             selected_suggestion: 0,
             suggestion_mode: None,
             suggestion_prefix: String::new(),
+            suggestion_start: 0,
+            completion_cycle: None,
             default_project_name,
             show_help_modal: false,
             show_advanced_help_modal: false,
@@ -285,6 +378,27 @@ impl App {
             url_modal: None,
             show_comments_modal: false,
             comments_modal: None,
+            show_label_manager_modal: false,
+            label_manager_modal: None,
+            show_project_sharing_modal: false,
+            show_edit_conflict_modal: false,
+            edit_conflict_modal: None,
+            edit_base_state: None,
+            project_sharing_modal: None,
+            show_checklist_modal: false,
+            checklist_modal: None,
+            show_undo_history_modal: false,
+            undo_history_modal: None,
+            show_trash_modal: false,
+            trash_modal: None,
+            show_template_picker_modal: false,
+            template_picker_modal: None,
+            show_snooze_modal: false,
+            snooze_modal: None,
+            show_reschedule_modal: false,
+            reschedule_modal: None,
+            show_paste_preview_modal: false,
+            paste_preview_modal: None,
             current_layout_name,
             layout_notification: None,
             layout_notification_start: None,
@@ -354,12 +468,15 @@ impl App {
         self.show_quick_add_modal = true; 
         self.quick_add_input.clear(); 
         self.quick_add_cursor_position = 0; 
+        self.quick_add_history.reset();
+        self.restore_quick_add_draft();
     }
     pub fn hide_quick_add_modal(&mut self) { self.show_quick_add_modal = false; self.quick_add_input.clear(); self.quick_add_cursor_position = 0; }
-    pub fn add_char_to_quick_add(&mut self, c: char) { self.quick_add_input.insert(self.quick_add_cursor_position, c); self.quick_add_cursor_position += 1; }
-    pub fn delete_char_from_quick_add(&mut self) { if self.quick_add_cursor_position > 0 { self.quick_add_cursor_position -= 1; self.quick_add_input.remove(self.quick_add_cursor_position); } }
-    pub fn move_cursor_left(&mut self) { if self.quick_add_cursor_position > 0 { self.quick_add_cursor_position -= 1; } }
-    pub fn move_cursor_right(&mut self) { if self.quick_add_cursor_position < self.quick_add_input.len() { self.quick_add_cursor_position += 1; } }
+    pub fn quick_add_editor(&mut self) -> LineEditor<'_> { LineEditor::new(&mut self.quick_add_input, &mut self.quick_add_cursor_position, &mut self.kill_ring) }
+    pub fn add_char_to_quick_add(&mut self, c: char) { self.quick_add_editor().insert_char(c); }
+    pub fn delete_char_from_quick_add(&mut self) { self.quick_add_editor().backspace(); }
+    pub fn move_cursor_left(&mut self) { self.quick_add_editor().move_left(); }
+    pub fn move_cursor_right(&mut self) { self.quick_add_editor().move_right(); }
     pub fn get_quick_add_input(&self) -> &str { &self.quick_add_input }
     pub fn clear_quick_add_input(&mut self) { self.quick_add_input.clear(); self.quick_add_cursor_position = 0; }
     pub fn toggle_debug_pane(&mut self) { self.show_debug_pane = !self.show_debug_pane; }
@@ -389,13 +506,16 @@ impl App {
             self.editing_task_id = Some(task_id); 
             self.edit_input = magic_syntax; 
             self.edit_cursor_position = self.edit_input.len(); 
+            self.edit_base_state = self.get_selected_task().map(|task| FormEditState::with_settings(task, &self.date_settings));
+            self.edit_history.reset();
+            self.restore_edit_draft(task_id);
         } 
     }
-    pub fn hide_edit_modal(&mut self) { self.show_edit_modal = false; self.edit_input.clear(); self.edit_cursor_position = 0; self.editing_task_id = None; }
+    pub fn hide_edit_modal(&mut self) { self.show_edit_modal = false; self.edit_input.clear(); self.edit_cursor_position = 0; self.editing_task_id = None; self.edit_base_state = None; }
     
     pub fn show_form_edit_modal(&mut self) {
         if let Some(task) = self.get_selected_task() {
-            let form_state = FormEditState::new(task);
+            let form_state = FormEditState::with_settings(task, &self.date_settings);
             self.close_all_modals();
             self.show_form_edit_modal = true;
             self.form_edit_state = Some(form_state);
@@ -405,15 +525,30 @@ impl App {
     pub fn hide_form_edit_modal(&mut self) {
         self.show_form_edit_modal = false;
         self.form_edit_state = None;
+        self.external_editor_requested = false;
+    }
+
+    /// Show the merge modal, keeping the editor state so Esc can return to it
+    pub fn show_edit_conflict_modal(&mut self, modal: crate::tui::modals::EditConflictModal) {
+        self.show_form_edit_modal = false;
+        self.show_edit_modal = false;
+        self.show_edit_conflict_modal = true;
+        self.edit_conflict_modal = Some(modal);
+    }
+
+    pub fn hide_edit_conflict_modal(&mut self) {
+        self.show_edit_conflict_modal = false;
+        self.edit_conflict_modal = None;
     }
     
-    pub fn add_char_to_edit(&mut self, c: char) { self.edit_input.insert(self.edit_cursor_position, c); self.edit_cursor_position += 1; }
-    pub fn delete_char_from_edit(&mut self) { if self.edit_cursor_position > 0 { self.edit_cursor_position -= 1; self.edit_input.remove(self.edit_cursor_position); } }
-    pub fn move_edit_cursor_left(&mut self) { if self.edit_cursor_position > 0 { self.edit_cursor_position -= 1; } }
-    pub fn move_edit_cursor_right(&mut self) { if self.edit_cursor_position < self.edit_input.len() { self.edit_cursor_position += 1; } }
+    pub fn edit_editor(&mut self) -> LineEditor<'_> { LineEditor::new(&mut self.edit_input, &mut self.edit_cursor_position, &mut self.kill_ring) }
+    pub fn add_char_to_edit(&mut self, c: char) { self.edit_editor().insert_char(c); }
+    pub fn delete_char_from_edit(&mut self) { self.edit_editor().backspace(); }
+    pub fn move_edit_cursor_left(&mut self) { self.edit_editor().move_left(); }
+    pub fn move_edit_cursor_right(&mut self) { self.edit_editor().move_right(); }
     pub fn get_edit_input(&self) -> &str { &self.edit_input }
     pub fn clear_edit_input(&mut self) { self.edit_input.clear(); self.edit_cursor_position = 0; }
-    fn task_to_magic_syntax(&self, task: &crate::vikunja::models::Task) -> String {
+    pub(crate) fn task_to_magic_syntax(&self, task: &crate::vikunja::models::Task) -> String {
         let mut result = task.title.clone();
         
         if task.is_favorite {
@@ -446,7 +581,10 @@ impl App {
         
         if let Some(due_date) = &task.due_date {
             if due_date.year() > 1900 {
-                let formatted_date = due_date.format("%Y-%m-%d").to_string();
+                // Timed due dates keep their time: 2025-03-04T10:30
+                let settings = &self.date_settings;
+                let format = if settings.is_all_day(*due_date, settings.due_time) { "%Y-%m-%d" } else { "%Y-%m-%dT%H:%M" };
+                let formatted_date = settings.local(*due_date).format(format).to_string();
                 result.push_str(&format!(" {}", formatted_date));
             }
         }
@@ -454,116 +592,6 @@ impl App {
         result
     }
     // --- Task manipulation logic moved to tasks.rs ---
-    pub fn update_suggestions(&mut self, input: &str, cursor: usize) {
-        // Find the last * or + before the cursor
-        let before_cursor = &input[..cursor];
-        
-        // Helper function to check if we're still in a suggestion context
-        // We stop suggestions when we encounter certain delimiters or control characters
-        fn is_suggestion_char(c: char) -> bool {
-            !matches!(c, '\n' | '\r' | '\t' | '#' | '@' | '!' | '&' | '|' | '(' | ')' | '{' | '}' | '"' | '\'')
-        }
-        
-        if let Some(pos) = before_cursor.rfind('*') {
-            let after = &before_cursor[pos+1..];
-            // Special handling for square brackets - if we're inside [], continue until ]
-            let suggestion_text = if after.starts_with('[') {
-                &after[1..] // Skip the opening bracket
-            } else {
-                after
-            };
-            
-            // Allow spaces and more characters in label suggestions, but stop at certain delimiters
-            if suggestion_text.chars().all(is_suggestion_char) {
-                self.suggestion_mode = Some(SuggestionMode::Label);
-                self.suggestion_prefix = suggestion_text.to_string();
-                let prefix = suggestion_text.trim();
-                let labels: Vec<_> = self.label_map.values().cloned().collect();
-                
-                // Use fuzzy matching with scoring for better results
-                let mut scored_labels: Vec<(String, f32)> = labels.into_iter()
-                    .map(|label| {
-                        let score = fuzzy_match_score(&label, prefix);
-                        (label, score)
-                    })
-                    .filter(|(_, score)| *score > 0.0)
-                    .collect();
-                
-                // Sort by score (highest first), then alphabetically
-                scored_labels.sort_by(|a, b| {
-                    b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
-                        .then_with(|| a.0.cmp(&b.0))
-                });
-                
-                let filtered: Vec<String> = scored_labels.into_iter()
-                    .map(|(label, _)| label)
-                    .collect();
-                
-                if filtered != self.suggestions {
-                    self.selected_suggestion = 0;
-                } else if self.selected_suggestion >= filtered.len() {
-                    self.selected_suggestion = 0;
-                }
-                self.suggestions = filtered;
-                return;
-            }
-        }
-        
-        if let Some(pos) = before_cursor.rfind('+') {
-            let after = &before_cursor[pos+1..];
-            // Special handling for square brackets - if we're inside [], continue until ]
-            let suggestion_text = if after.starts_with('[') {
-                &after[1..] // Skip the opening bracket
-            } else {
-                after
-            };
-            
-            // Allow spaces and more characters in project suggestions, but stop at certain delimiters
-            if suggestion_text.chars().all(is_suggestion_char) {
-                self.suggestion_mode = Some(SuggestionMode::Project);
-                self.suggestion_prefix = suggestion_text.to_string();
-                let prefix_lower = suggestion_text.to_lowercase();
-                let prefix = prefix_lower.trim();
-                let projects: Vec<_> = self.project_map.iter()
-                    // Filter out system projects (ID <= 0)
-                    .filter(|(id, _)| **id > 0)
-                    .map(|(_, name)| name.clone())
-                    .collect();
-                
-                // Use fuzzy matching with scoring for better results
-                let mut scored_projects: Vec<(String, f32)> = projects.into_iter()
-                    .map(|project| {
-                        let score = fuzzy_match_score(&project, prefix);
-                        (project, score)
-                    })
-                    .filter(|(_, score)| *score > 0.0)
-                    .collect();
-                
-                // Sort by score (highest first), then alphabetically
-                scored_projects.sort_by(|a, b| {
-                    b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
-                        .then_with(|| a.0.cmp(&b.0))
-                });
-                
-                let filtered: Vec<String> = scored_projects.into_iter()
-                    .map(|(project, _)| project)
-                    .collect();
-                
-                if filtered != self.suggestions {
-                    self.selected_suggestion = 0;
-                } else if self.selected_suggestion >= filtered.len() {
-                    self.selected_suggestion = 0;
-                }
-                self.suggestions = filtered;
-                return;
-            }
-        }
-        
-        self.suggestion_mode = None;
-        self.suggestions.clear();
-        self.selected_suggestion = 0;
-        self.suggestion_prefix.clear();
-    }
     pub fn jump_to_top(&mut self) {
         self.selected_task_index = 0;
     }
@@ -704,10 +732,21 @@ impl App {
         if let Some(task) = self.get_selected_task() {
             let comments = task.comments.clone().unwrap_or_default();
             let task_id = task.id;
+            // Number links the same way as the URL modal does
+            let detailed = self.get_detailed_task(task_id).unwrap_or(task);
+            let links = crate::url_utils::extract_urls_from_task(detailed).into_iter().map(|u| u.url).collect();
+            let draft = self.input_history.draft(crate::tui::input_history::HistoryKind::Comment, task_id).unwrap_or_default().to_string();
+            self.remember_task_assignees();
+            let users = self.known_users.values().map(|u| u.username.clone()).collect();
+            let current_user_id = self.current_user.as_ref().map(|u| u.id);
             self.close_all_modals();
             self.show_comments_modal = true;
             self.comments_modal = Some(
                 crate::tui::modals::CommentsModal::new(comments, task_id)
+                    .with_links(links)
+                    .with_input(draft)
+                    .with_current_user(current_user_id)
+                    .with_users(users, self.input_history.usage().clone())
             );
         }
     }
@@ -767,6 +806,7 @@ impl App {
         self.show_form_edit_modal = false;
         self.show_project_picker = false;
         self.show_filter_picker = false;
+        self.show_assignee_picker = false;
         self.show_confirmation_dialog = false;
         self.show_attachment_modal = false;
         self.show_file_picker_modal = false;
@@ -777,6 +817,26 @@ impl App {
         // Comments modal state
         self.show_comments_modal = false;
         self.comments_modal = None;
+        self.show_label_manager_modal = false;
+        self.label_manager_modal = None;
+        self.show_project_sharing_modal = false;
+        self.show_edit_conflict_modal = false;
+        self.edit_conflict_modal = None;
+        self.project_sharing_modal = None;
+        self.show_checklist_modal = false;
+        self.checklist_modal = None;
+        self.show_undo_history_modal = false;
+        self.undo_history_modal = None;
+        self.show_trash_modal = false;
+        self.trash_modal = None;
+        self.show_template_picker_modal = false;
+        self.template_picker_modal = None;
+        self.show_snooze_modal = false;
+        self.snooze_modal = None;
+        self.show_reschedule_modal = false;
+        self.reschedule_modal = None;
+        self.show_paste_preview_modal = false;
+        self.paste_preview_modal = None;
         // Reset modal state
         self.quick_add_input.clear();
         self.quick_add_cursor_position = 0;
@@ -964,6 +1024,21 @@ impl App {
                             },
                         }
                     }
+                    TaskColumn::Checklist => {
+                        // Sort by share of items done; tasks without a checklist always last
+                        let progress = |t: &Task| t.description.as_deref()
+                            .and_then(crate::checklist::checklist_progress)
+                            .map(|(done, total)| done as f64 / total as f64);
+                        match (progress(a), progress(b)) {
+                            (None, None) => std::cmp::Ordering::Equal,
+                            (None, Some(_)) => std::cmp::Ordering::Greater,
+                            (Some(_), None) => std::cmp::Ordering::Less,
+                            (Some(a_done), Some(b_done)) => match sort_config.direction {
+                                SortDirection::Asc => a_done.total_cmp(&b_done),
+                                SortDirection::Desc => b_done.total_cmp(&a_done),
+                            },
+                        }
+                    }
                     TaskColumn::Status => {
                         let cmp = a.done.cmp(&b.done);
                         match sort_config.direction {
@@ -1059,91 +1134,6 @@ impl App {
         }
     }
 
-    pub fn apply_quick_action(&mut self, action: &crate::config::QuickAction) -> Result<(), String> {
-        if self.tasks.is_empty() {
-            return Err("No tasks available".to_string());
-        }
-        let task = self.tasks.get_mut(self.selected_task_index).ok_or("No selected task")?;
-        let task_id = task.id; // Get the task ID before we modify it
-        
-        let result = match action.action.as_str() {
-            "project" => {
-                // Find project by name
-                let project_id = self.project_map.iter().find_map(|(id, name)| {
-                    if name == &action.target { Some(*id) } else { None }
-                });
-                if let Some(pid) = project_id {
-                    task.project_id = pid;
-                    Ok(())
-                } else {
-                    Err(format!("Project '{}' not found", action.target))
-                }
-            },
-            "priority" => {
-                if let Ok(priority) = action.target.parse::<i32>() {
-                    if (1..=5).contains(&priority) {
-                        task.priority = Some(priority);
-                        Ok(())
-                    } else {
-                        Err(format!("Invalid priority '{}': must be 1-5", action.target))
-                    }
-                } else {
-                    Err(format!("Invalid priority '{}': not a number", action.target))
-                }
-            },
-            "label" => {
-                // Find label by name
-                let label_id = self.label_map.iter().find_map(|(id, name)| {
-                    if name == &action.target { Some(*id) } else { None }
-                });
-                if let Some(lid) = label_id {
-                    if let Some(ref mut labels) = task.labels {
-                        if !labels.iter().any(|l| l.id == lid) {
-                            labels.push(crate::vikunja::models::Label {
-                                id: lid,
-                                title: action.target.clone(),
-                                hex_color: self.label_colors.get(&lid).cloned(),
-                                description: None,
-                                created: None,
-                                updated: None,
-                                created_by: None,
-                            });
-                        }
-                    } else {
-                        task.labels = Some(vec![crate::vikunja::models::Label {
-                            id: lid,
-                            title: action.target.clone(),
-                            hex_color: self.label_colors.get(&lid).cloned(),
-                            description: None,
-                            created: None,
-                            updated: None,
-                            created_by: None,
-                        }]);
-                    }
-                    Ok(())
-                } else {
-                    Err(format!("Label '{}' not found", action.target))
-                }
-            },
-            _ => Err(format!("Unknown quick action: {}", action.action)),
-        };
-        
-        // If the quick action was successful, also update the corresponding task in all_tasks
-        if result.is_ok() {
-            if let Some(all_task) = self.all_tasks.iter_mut().find(|t| t.id == task_id) {
-                // Copy the updated fields from the filtered task to all_tasks
-                match action.action.as_str() {
-                    "project" => all_task.project_id = task.project_id,
-                    "priority" => all_task.priority = task.priority,
-                    "label" => all_task.labels = task.labels.clone(),
-                    _ => {}
-                }
-            }
-        }
-        
-        result
-    }
-
     pub fn cycle_filter_backward(&mut self) {
         if self.filters.is_empty() { return; }
         let idx = match self.current_filter_id {
@@ -1219,44 +1209,51 @@ impl App {
         self.add_subtask_cursor_position = 0;
     }
 
+    pub fn add_subtask_editor(&mut self) -> LineEditor<'_> {
+        LineEditor::new(&mut self.add_subtask_input, &mut self.add_subtask_cursor_position, &mut self.kill_ring)
+    }
+
     pub fn add_char_to_add_subtask(&mut self, c: char) {
-        self.add_subtask_input.insert(self.add_subtask_cursor_position, c);
-        self.add_subtask_cursor_position += 1;
+        self.add_subtask_editor().insert_char(c);
     }
 
     pub fn delete_char_from_add_subtask(&mut self) {
-        if self.add_subtask_cursor_position > 0 {
-            self.add_subtask_cursor_position -= 1;
-            self.add_subtask_input.remove(self.add_subtask_cursor_position);
-        }
+        self.add_subtask_editor().backspace();
     }
 
     pub fn move_add_subtask_cursor_left(&mut self) {
-        if self.add_subtask_cursor_position > 0 {
-            self.add_subtask_cursor_position -= 1;
-        }
+        self.add_subtask_editor().move_left();
     }
 
     pub fn move_add_subtask_cursor_right(&mut self) {
-        if self.add_subtask_cursor_position < self.add_subtask_input.len() {
-            self.add_subtask_cursor_position += 1;
-        }
+        self.add_subtask_editor().move_right();
     }
 
     pub fn get_add_subtask_input(&self) -> &str {
         &self.add_subtask_input
     }
 
+    fn subtask_picker_editor(&mut self) -> LineEditor<'_> {
+        LineEditor::new(&mut self.subtask_picker_input, &mut self.picker_cursor_position, &mut self.kill_ring)
+    }
+
     pub fn add_char_to_subtask_input(&mut self, c: char) {
-        self.subtask_picker_input.push(c);
+        self.subtask_picker_editor().insert_char(c);
         self.update_filtered_subtask_tasks();
     }
 
     pub fn delete_char_from_subtask_input(&mut self) {
-        self.subtask_picker_input.pop();
+        self.subtask_picker_editor().backspace();
         self.update_filtered_subtask_tasks();
     }
 
+    /// Apply an editing key to the search text, refiltering when it changes
+    pub fn edit_subtask_picker_input(&mut self, key: &KeyEvent) {
+        if self.subtask_picker_editor().handle_key(key) == EditOutcome::Changed {
+            self.update_filtered_subtask_tasks();
+        }
+    }
+
     pub fn next_subtask_task(&mut self) {
         if !self.filtered_subtask_tasks.is_empty() && self.selected_subtask_picker_index < self.filtered_subtask_tasks.len() - 1 {
             self.selected_subtask_picker_index += 1;
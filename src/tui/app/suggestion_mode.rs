@@ -1,5 +1,11 @@
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SuggestionMode {
     Label,
     Project,
+    /// `@user`
+    Assignee,
+    /// A date after `due` or `start`
+    Date,
+    /// An interval after `every`
+    Repeat,
 }
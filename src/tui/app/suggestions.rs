@@ -0,0 +1,134 @@
+use crate::tui::app::state::App;
+use crate::tui::app::suggestion_mode::SuggestionMode;
+use crate::tui::suggestions::{complete, date_phrases, rank, suggestion_context, CompletionCycle, DATE_SUGGESTIONS, REPEAT_SUGGESTIONS};
+use crate::vikunja_parser::{ParsedTask, QuickAddParser};
+use chrono::Utc;
+
+impl App {
+    /// Suggest labels, projects, users, dates or intervals for what is typed before the cursor
+    pub fn update_suggestions(&mut self, input: &str, cursor: usize) {
+        let Some(context) = suggestion_context(&input[..cursor]) else {
+            self.clear_suggestions();
+            return;
+        };
+        if context.mode == SuggestionMode::Assignee && self.suggestion_mode != Some(SuggestionMode::Assignee) {
+            self.remember_task_assignees();
+        }
+        let mut candidates: Vec<String> = match context.mode {
+            SuggestionMode::Label => self.label_map.values().cloned().collect(),
+            // System projects (ID <= 0) can't take tasks
+            SuggestionMode::Project => self.project_map.iter()
+                .filter(|(id, _)| **id > 0)
+                .map(|(_, name)| name.clone())
+                .collect(),
+            SuggestionMode::Assignee => self.known_users.values().map(|u| u.username.clone()).collect(),
+            SuggestionMode::Date => DATE_SUGGESTIONS.iter().map(|s| s.to_string()).collect(),
+            SuggestionMode::Repeat => REPEAT_SUGGESTIONS.iter().map(|s| s.to_string()).collect(),
+        };
+        if matches!(context.mode, SuggestionMode::Label | SuggestionMode::Project | SuggestionMode::Assignee) {
+            candidates.sort();
+            candidates.dedup();
+        }
+        let ranked = rank(candidates, &context, self.input_history.usage(), Utc::now().timestamp());
+        if ranked.is_empty() {
+            self.clear_suggestions();
+            return;
+        }
+        if ranked != self.suggestions || self.selected_suggestion >= ranked.len() {
+            self.selected_suggestion = 0;
+        }
+        self.suggestion_mode = Some(context.mode);
+        self.suggestion_prefix = context.prefix;
+        self.suggestion_start = context.start;
+        self.suggestions = ranked;
+    }
+
+    pub(crate) fn clear_suggestions(&mut self) {
+        self.suggestion_mode = None;
+        self.suggestions.clear();
+        self.selected_suggestion = 0;
+        self.suggestion_prefix.clear();
+    }
+
+    /// Whether Enter should complete the selected suggestion rather than submit. It
+    /// submits when what was typed already names a label, project or user, and for dates
+    /// and intervals until something has been typed.
+    pub fn should_autocomplete_on_enter(&self) -> bool {
+        let Some(mode) = self.suggestion_mode else {
+            return false;
+        };
+        if self.suggestions.is_empty() {
+            return false;
+        }
+        let prefix = self.suggestion_prefix.trim().to_lowercase();
+        let is_exact_match = match mode {
+            SuggestionMode::Label => self.label_map.values().any(|label| label.to_lowercase() == prefix),
+            SuggestionMode::Project => self.project_map.values().any(|project| project.to_lowercase() == prefix),
+            SuggestionMode::Assignee => self.known_users.values().any(|user| user.username.to_lowercase() == prefix),
+            SuggestionMode::Date | SuggestionMode::Repeat => {
+                prefix.is_empty() || self.suggestions.iter().any(|s| s.to_lowercase() == prefix)
+            }
+        };
+        !is_exact_match && self.suggestions[0].to_lowercase() != prefix
+    }
+
+    /// Write the selected suggestion into `input`, returning the new input and cursor.
+    /// A following Tab can cycle through the other suggestions.
+    pub fn accept_suggestion(&mut self, input: &str, cursor: usize) -> Option<(String, usize)> {
+        let mode = self.suggestion_mode?;
+        let value = self.suggestions.get(self.selected_suggestion)?;
+        let (completed, new_cursor) = complete(input, self.suggestion_start, cursor, mode, value);
+        self.completion_cycle = Some(CompletionCycle {
+            mode,
+            start: self.suggestion_start,
+            candidates: self.suggestions.clone(),
+            index: self.selected_suggestion,
+            input: completed.clone(),
+            cursor: new_cursor,
+        });
+        Some((completed, new_cursor))
+    }
+
+    /// Tab: accept the selected suggestion, or right after a completion replace it with
+    /// the next suggestion
+    pub fn tab_complete(&mut self, input: &str, cursor: usize) -> Option<(String, usize)> {
+        if let Some(cycle) = self.completion_cycle.as_mut().filter(|c| c.input == input && c.cursor == cursor) {
+            cycle.index = (cycle.index + 1) % cycle.candidates.len();
+            let (completed, new_cursor) = complete(input, cycle.start, cursor, cycle.mode, &cycle.candidates[cycle.index]);
+            cycle.input = completed.clone();
+            cycle.cursor = new_cursor;
+            return Some((completed, new_cursor));
+        }
+        self.accept_suggestion(input, cursor)
+    }
+
+    /// Count the labels, projects, users, dates and intervals in a submitted line, so
+    /// they are suggested first next time. Anything also in `unchanged` (the line an
+    /// edit started from) isn't counted.
+    pub fn record_suggestion_usage(&mut self, input: &str, unchanged: Option<&str>) {
+        let parser = QuickAddParser::with_settings(self.date_settings);
+        let before = unchanged.map(|text| suggestion_uses(&parser.parse(text), text)).unwrap_or_default();
+        let uses: Vec<(SuggestionMode, String)> = suggestion_uses(&parser.parse(input), input)
+            .into_iter()
+            .filter(|used| !before.contains(used))
+            .collect();
+        self.input_history.record_usage(&uses, Utc::now().timestamp());
+    }
+}
+
+/// Every value a line uses that could have been suggested
+fn suggestion_uses(parsed: &ParsedTask, text: &str) -> Vec<(SuggestionMode, String)> {
+    let mut uses: Vec<(SuggestionMode, String)> = Vec::new();
+    uses.extend(parsed.labels.iter().map(|l| (SuggestionMode::Label, l.clone())));
+    uses.extend(parsed.project.iter().map(|p| (SuggestionMode::Project, p.clone())));
+    uses.extend(parsed.assignees.iter().map(|a| (SuggestionMode::Assignee, a.clone())));
+    if let Some(ref repeat) = parsed.repeat_interval {
+        let interval = match repeat.amount {
+            1 => repeat.interval_type.clone(),
+            amount => format!("{} {}", amount, repeat.interval_type),
+        };
+        uses.push((SuggestionMode::Repeat, interval.to_lowercase()));
+    }
+    uses.extend(date_phrases(text).into_iter().map(|d| (SuggestionMode::Date, d.to_string())));
+    uses
+}
@@ -3,4 +3,6 @@ pub enum TaskFilter {
     ActiveOnly,
     All,
     CompletedOnly,
+    AssignedToMe, // Active tasks assigned to the current user
+    Unassigned,   // Active tasks with no assignees
 }
@@ -1,5 +1,6 @@
 use super::state::App;
-use crate::tui::app::undoable_action::UndoableAction;
+use crate::tui::app::undoable_action::{UndoEntryInfo, UndoableAction};
+use chrono::Local;
 use crate::tui::app::pending_action::PendingAction;
 use crate::vikunja::models::Task;
 
@@ -32,15 +33,17 @@ impl App {
         Some(task_id)
     }
     pub async fn toggle_star_selected_task_async(&mut self, client: &crate::vikunja_client::VikunjaClient) -> Option<i64> {
-        let (task_id, task_title, is_favorite) = if let Some(task) = self.tasks.get_mut(self.selected_task_index) {
+        let (task_id, task_title, is_favorite, previous_task) = if let Some(task) = self.tasks.get_mut(self.selected_task_index) {
+            let previous_task = task.clone();
             task.is_favorite = !task.is_favorite;
-            (task.id, task.title.clone(), task.is_favorite)
+            (task.id, task.title.clone(), task.is_favorite, previous_task)
         } else {
             return None;
         };
         // Call API to update favorite status
         match client.set_task_favorite(task_id as u64, is_favorite).await {
             Ok(_) => {
+                self.add_task_edit_to_undo_stack(task_id, previous_task);
                 self.add_debug_message(format!("Task {}starred: {}", if is_favorite { "" } else { "un" }, task_title));
                 self.show_toast(format!("Task {}starred: {}", if is_favorite { "" } else { "un" }, task_title));
             },
@@ -117,202 +120,210 @@ impl App {
         }
     }
     pub fn cancel_confirmation(&mut self) { self.show_confirmation_dialog = false; self.pending_action = None; }
+    /// Move the task to the trash; it is deleted on the server once the grace
+    /// period is over, or right away when there is none
     pub async fn execute_delete_task_async(&mut self, task_id: i64, client: &crate::vikunja_client::VikunjaClient) {
-        match client.delete_task(task_id).await {
-            Ok(_) => {
-                if let Some(pos) = self.tasks.iter().position(|t| t.id == task_id) {
-                    let task = self.tasks.remove(pos);
-                    self.add_debug_message(format!("Task deleted: {}", task.title));
-                    self.show_toast(format!("Task deleted: {}", task.title));
-                    self.add_to_undo_stack(UndoableAction::TaskDeletion { task, position: pos });
-                }
-            },
-            Err(e) => {
-                self.add_debug_message(format!("Failed to delete task {}: {}", task_id, e));
-                self.show_toast(format!("Failed to delete task: {}", e));
-            }
+        self.execute_delete_task(task_id);
+        if self.config.get_trash_grace_seconds() == 0 {
+            self.flush_trash_async(client, true).await;
         }
     }
-    /// Test-only synchronous version of execute_delete_task for unit tests
-    #[allow(dead_code)]
     pub fn execute_delete_task(&mut self, task_id: i64) { 
-        if let Some(pos) = self.tasks.iter().position(|t| t.id == task_id) {
-            let task = self.tasks.remove(pos);
-            self.add_debug_message(format!("Task deleted: {}", task.title));
-            self.show_toast(format!("Task deleted: {}", task.title));
+        if let Some((task, pos)) = self.move_to_trash(task_id) {
+            self.add_debug_message(format!("Task moved to trash: {}", task.title));
+            self.show_toast(format!("Task deleted: {} (T: trash, Ctrl+Z: undo)", task.title));
             self.add_to_undo_stack(UndoableAction::TaskDeletion { task, position: pos });
         } 
     }
-    #[allow(dead_code)] // Future undo/redo feature
+    /// Undo the last action in the local task lists only; see `undo_last_action_async`
+    #[allow(dead_code)]
     pub fn undo_last_action(&mut self) -> Option<i64> {
-        if let Some(action) = self.undo_stack.pop() {
-            let result = match &action {
-                UndoableAction::TaskCompletion { task_id, previous_state } => {
-                    if let Some(task) = self.tasks.iter_mut().find(|t| t.id == *task_id) {
-                        let task_title = task.title.clone();
-                        let current_state = task.done;
-                        task.done = *previous_state;
-                        self.add_debug_message(format!(
-                            "Undid completion toggle for task '{}'", 
-                            task_title
-                        ));
-                        // Push the reverse action to redo stack
-                        self.redo_stack.push(UndoableAction::TaskCompletion {
-                            task_id: *task_id,
-                            previous_state: current_state,
-                        });
-                        Some(*task_id)
-                    } else {
-                        None
-                    }
-                }
-                UndoableAction::TaskDeletion { task, position } => {
-                    let tasks_len = self.tasks.len();
-                    let insert_position = (*position).min(tasks_len);
-                    self.tasks.insert(insert_position, task.clone());
-                    self.selected_task_index = insert_position;
-                    self.add_debug_message(format!("Undid deletion of task '{}'", task.title));
-                    // Push the reverse action to redo stack
-                    self.redo_stack.push(UndoableAction::TaskCreation {
-                        task_id: task.id,
-                    });
-                    Some(task.id)
-                }
-                UndoableAction::TaskCreation { task_id } => {
-                    if let Some(position) = self.tasks.iter().position(|t| t.id == *task_id) {
-                        let task = self.tasks.remove(position);
-                        if self.selected_task_index >= self.tasks.len() && !self.tasks.is_empty() {
-                            self.selected_task_index = self.tasks.len() - 1;
-                        }
-                        self.add_debug_message(format!("Undid creation of task '{}'", task.title));
-                        // Push the reverse action to redo stack
-                        self.redo_stack.push(UndoableAction::TaskDeletion {
-                            task: task.clone(),
-                            position,
-                        });
-                        Some(task.id)
-                    } else {
-                        None
-                    }
-                }
-                UndoableAction::TaskEdit { task_id, previous_task } => {
-                    if let Some(task) = self.tasks.iter_mut().find(|t| t.id == *task_id) {
-                        let current_task = task.clone();
-                        *task = previous_task.clone();
-                        self.add_debug_message(format!("Undid edit of task '{}'", previous_task.title));
-                        // Push the reverse action to redo stack
-                        self.redo_stack.push(UndoableAction::TaskEdit {
-                            task_id: *task_id,
-                            previous_task: current_task,
-                        });
-                        Some(*task_id)
-                    } else {
-                        None
-                    }
-                }
-            };
-            
-            // Limit redo stack size
-            if self.redo_stack.len() > self.max_undo_history {
-                self.redo_stack.remove(0);
-            }
-            
-            result
-        } else {
+        let Some((action, info)) = self.pop_history(false) else {
             self.add_debug_message("No actions to undo".to_string());
-            None
-        }
+            return None;
+        };
+        let (task_id, inverse) = self.revert_locally(&action, "Undid")?;
+        self.push_history(true, inverse, info);
+        Some(task_id)
     }
+    /// Redo the last undone action in the local task lists only; see `redo_last_action_async`
+    #[allow(dead_code)]
     pub fn redo_last_action(&mut self) -> Option<i64> {
-        if let Some(action) = self.redo_stack.pop() {
-            let result = match &action {
-                UndoableAction::TaskCompletion { task_id, previous_state } => {
-                    if let Some(task) = self.tasks.iter_mut().find(|t| t.id == *task_id) {
-                        let task_title = task.title.clone();
-                        let current_state = task.done;
-                        task.done = *previous_state;
-                        self.add_debug_message(format!(
-                            "Redid completion toggle for task '{}'", 
-                            task_title
-                        ));
-                        // Push the reverse action to undo stack
-                        self.undo_stack.push(UndoableAction::TaskCompletion {
-                            task_id: *task_id,
-                            previous_state: current_state,
-                        });
-                        Some(*task_id)
-                    } else {
-                        None
+        let Some((action, info)) = self.pop_history(true) else {
+            self.add_debug_message("No actions to redo".to_string());
+            return None;
+        };
+        let (task_id, inverse) = self.revert_locally(&action, "Redid")?;
+        self.push_history(false, inverse, info);
+        Some(task_id)
+    }
+    /// Apply the reverse of `action` to `tasks` and `all_tasks`. Returns the task it
+    /// touched and the action that reverses it again, or None if the task isn't loaded.
+    pub(crate) fn revert_locally(&mut self, action: &UndoableAction, verb: &str) -> Option<(i64, UndoableAction)> {
+        match action {
+            UndoableAction::TaskCompletion { task_id, previous_state } => {
+                let task = self.loaded_task(*task_id)?;
+                let (task_title, current_state) = (task.title.clone(), task.done);
+                self.update_loaded_task(*task_id, |task| task.done = *previous_state);
+                self.add_debug_message(format!("{} completion toggle for task '{}'", verb, task_title));
+                Some((*task_id, UndoableAction::TaskCompletion { task_id: *task_id, previous_state: current_state }))
+            }
+            UndoableAction::TaskDeletion { task, position } => {
+                self.trash.retain(|t| t.task.id != task.id);
+                let insert_position = (*position).min(self.tasks.len());
+                match self.tasks.iter().position(|t| t.id == task.id) {
+                    // Already back, restored from the trash view
+                    Some(listed) => self.selected_task_index = listed,
+                    None => {
+                        self.tasks.insert(insert_position, task.clone());
+                        self.selected_task_index = insert_position;
                     }
                 }
-                UndoableAction::TaskDeletion { task, position } => {
-                    let tasks_len = self.tasks.len();
-                    let insert_position = (*position).min(tasks_len);
-                    self.tasks.insert(insert_position, task.clone());
-                    self.selected_task_index = insert_position;
-                    self.add_debug_message(format!("Redid deletion of task '{}'", task.title));
-                    // Push the reverse action to undo stack
-                    self.undo_stack.push(UndoableAction::TaskCreation {
-                        task_id: task.id,
-                    });
-                    Some(task.id)
+                if !self.all_tasks.iter().any(|t| t.id == task.id) {
+                    self.all_tasks.push(task.clone());
                 }
-                UndoableAction::TaskCreation { task_id } => {
-                    if let Some(position) = self.tasks.iter().position(|t| t.id == *task_id) {
-                        let task = self.tasks.remove(position);
-                        if self.selected_task_index >= self.tasks.len() && !self.tasks.is_empty() {
-                            self.selected_task_index = self.tasks.len() - 1;
-                        }
-                        self.add_debug_message(format!("Redid creation of task '{}'", task.title));
-                        // Push the reverse action to undo stack
-                        self.undo_stack.push(UndoableAction::TaskDeletion {
-                            task: task.clone(),
-                            position,
-                        });
-                        Some(task.id)
-                    } else {
-                        None
-                    }
+                self.add_debug_message(format!("{} deletion of task '{}'", verb, task.title));
+                Some((task.id, UndoableAction::TaskCreation { task_id: task.id }))
+            }
+            UndoableAction::TaskCreation { task_id } => {
+                let listed = self.tasks.iter().position(|t| t.id == *task_id);
+                let in_all = self.all_tasks.iter().position(|t| t.id == *task_id);
+                let task = match (listed, in_all) {
+                    (Some(position), _) => self.tasks.remove(position),
+                    (None, Some(position)) => self.all_tasks[position].clone(),
+                    (None, None) => return None,
+                };
+                self.all_tasks.retain(|t| t.id != *task_id);
+                if self.selected_task_index >= self.tasks.len() && !self.tasks.is_empty() {
+                    self.selected_task_index = self.tasks.len() - 1;
                 }
-                UndoableAction::TaskEdit { task_id, previous_task } => {
-                    if let Some(task) = self.tasks.iter_mut().find(|t| t.id == *task_id) {
-                        let current_task = task.clone();
-                        *task = previous_task.clone();
-                        self.add_debug_message(format!("Redid edit of task '{}'", previous_task.title));
-                        // Push the reverse action to undo stack
-                        self.undo_stack.push(UndoableAction::TaskEdit {
-                            task_id: *task_id,
-                            previous_task: current_task,
-                        });
-                        Some(*task_id)
-                    } else {
-                        None
+                self.add_debug_message(format!("{} creation of task '{}'", verb, task.title));
+                let position = listed.unwrap_or(self.tasks.len());
+                Some((task.id, UndoableAction::TaskDeletion { task, position }))
+            }
+            UndoableAction::TaskEdit { task_id, previous_task } => {
+                let current_task = self.loaded_task(*task_id)?.clone();
+                self.update_loaded_task(*task_id, |task| *task = previous_task.clone());
+                self.add_debug_message(format!("{} edit of task '{}'", verb, previous_task.title));
+                Some((*task_id, UndoableAction::TaskEdit { task_id: *task_id, previous_task: current_task }))
+            }
+            UndoableAction::RelationAdded { task_id, other_task_id, kind } => {
+                self.add_debug_message(format!("{} relation between tasks {} and {}", verb, task_id, other_task_id));
+                Some((*task_id, UndoableAction::RelationRemoved { task_id: *task_id, other_task_id: *other_task_id, kind: kind.clone() }))
+            }
+            UndoableAction::RelationRemoved { task_id, other_task_id, kind } => {
+                self.add_debug_message(format!("{} relation removal between tasks {} and {}", verb, task_id, other_task_id));
+                Some((*task_id, UndoableAction::RelationAdded { task_id: *task_id, other_task_id: *other_task_id, kind: kind.clone() }))
+            }
+            UndoableAction::CommentAdded { task_id, comment_id, text } => {
+                self.update_loaded_task(*task_id, |task| {
+                    if let Some(comments) = task.comments.as_mut() {
+                        comments.retain(|c| c.id != *comment_id);
                     }
+                });
+                self.add_debug_message(format!("{} comment on task {}", verb, task_id));
+                Some((*task_id, UndoableAction::CommentDeleted { task_id: *task_id, text: text.clone() }))
+            }
+            UndoableAction::CommentDeleted { task_id, text } => {
+                self.add_debug_message(format!("{} comment deletion on task {}", verb, task_id));
+                // The server assigns the comment a new ID; the async undo fills it in
+                Some((*task_id, UndoableAction::CommentAdded { task_id: *task_id, comment_id: 0, text: text.clone() }))
+            }
+            UndoableAction::CommentEdited { task_id, comment_id, previous_text, text } => {
+                self.update_loaded_task(*task_id, |task| {
+                    if let Some(comment) = task.comments.as_mut().and_then(|c| c.iter_mut().find(|c| c.id == *comment_id)) {
+                        comment.comment = Some(previous_text.clone());
+                    }
+                });
+                self.add_debug_message(format!("{} comment edit on task {}", verb, task_id));
+                Some((*task_id, UndoableAction::CommentEdited {
+                    task_id: *task_id,
+                    comment_id: *comment_id,
+                    previous_text: text.clone(),
+                    text: previous_text.clone(),
+                }))
+            }
+            UndoableAction::BucketMoved { task_id, project_id, from_bucket_id, to_bucket_id } => {
+                self.update_loaded_task(*task_id, |task| task.bucket_id = Some(*from_bucket_id));
+                self.add_debug_message(format!("{} bucket move of task {}", verb, task_id));
+                Some((*task_id, UndoableAction::BucketMoved {
+                    task_id: *task_id,
+                    project_id: *project_id,
+                    from_bucket_id: *to_bucket_id,
+                    to_bucket_id: *from_bucket_id,
+                }))
+            }
+            UndoableAction::Batch { actions } => {
+                let mut inverses = Vec::new();
+                for action in actions.iter().rev() {
+                    inverses.push(self.revert_locally(action, verb)?.1);
                 }
-            };
-            
-            // Limit undo stack size
-            if self.undo_stack.len() > self.max_undo_history {
-                self.undo_stack.remove(0);
+                Some((action.task_id(), UndoableAction::Batch { actions: inverses }))
             }
-            
-            result
-        } else {
-            self.add_debug_message("No actions to redo".to_string());
-            None
         }
     }
+    fn loaded_task(&self, task_id: i64) -> Option<&Task> {
+        self.tasks.iter().chain(self.all_tasks.iter()).find(|t| t.id == task_id)
+    }
+    fn update_loaded_task(&mut self, task_id: i64, update: impl Fn(&mut Task)) {
+        self.tasks.iter_mut().chain(self.all_tasks.iter_mut())
+            .filter(|t| t.id == task_id)
+            .for_each(update);
+    }
     pub fn add_to_undo_stack(&mut self, action: UndoableAction) { 
         // Clear redo stack when a new action is performed
         self.redo_stack.clear();
-        
-        if self.undo_stack.len() == self.max_undo_history { 
-            self.undo_stack.remove(0); 
-        } 
-        self.undo_stack.push(action); 
+        self.redo_info.clear();
+        self.push_history(false, action, None);
+    }
+    /// Push onto the undo (or redo) stack and drop the oldest entry beyond the limit.
+    /// `info` (when and to which task) is carried over when an entry moves between stacks.
+    pub(crate) fn push_history(&mut self, redo: bool, action: UndoableAction, info: Option<UndoEntryInfo>) {
+        let info = match info {
+            Some(info) => UndoEntryInfo { failed: None, ..info },
+            None => {
+                let task_title = match &action {
+                    UndoableAction::TaskDeletion { task, .. } => task.title.clone(),
+                    other => self.loaded_task(other.task_id())
+                        .map(|t| t.title.clone())
+                        .unwrap_or_else(|| format!("#{}", other.task_id())),
+                };
+                UndoEntryInfo { at: Local::now(), task_title, failed: None }
+            }
+        };
+        let max = self.max_undo_history;
+        let (stack, infos) = if redo { (&mut self.redo_stack, &mut self.redo_info) } else { (&mut self.undo_stack, &mut self.undo_info) };
+        stack.push(action);
+        infos.push(info);
+        while stack.len() > max {
+            stack.remove(0);
+        }
+        while infos.len() > max {
+            infos.remove(0);
+        }
+    }
+    pub(crate) fn pop_history(&mut self, redo: bool) -> Option<(UndoableAction, Option<UndoEntryInfo>)> {
+        let (stack, infos) = if redo { (&mut self.redo_stack, &mut self.redo_info) } else { (&mut self.undo_stack, &mut self.undo_info) };
+        let action = stack.pop()?;
+        // Entries pushed straight onto the stack have no info
+        let info = if infos.len() > stack.len() { infos.pop() } else { None };
+        Some((action, info))
+    }
+    /// When, to which task, and whether reverting failed, for the entry at `index` of
+    /// the undo (or redo) stack. The info lines up with the top of the stack.
+    pub fn history_info(&self, redo: bool, index: usize) -> Option<&UndoEntryInfo> {
+        let (stack, infos) = if redo { (&self.redo_stack, &self.redo_info) } else { (&self.undo_stack, &self.undo_info) };
+        (index + infos.len()).checked_sub(stack.len()).and_then(|i| infos.get(i))
+    }
+    /// Note that the top entry of the undo (or redo) stack couldn't be reverted on the server
+    pub(crate) fn mark_history_failed(&mut self, redo: bool, error: String) {
+        let (stack, infos) = if redo { (&self.redo_stack, &mut self.redo_info) } else { (&self.undo_stack, &mut self.undo_info) };
+        if let (false, Some(info)) = (stack.is_empty(), infos.last_mut()) {
+            info.failed = Some(error);
+        }
     }
     #[allow(dead_code)] // Future undo/redo feature
     pub fn add_task_to_undo_stack(&mut self, task_id: i64) { if let Some(_task) = self.tasks.iter().find(|t| t.id == task_id) { let action = UndoableAction::TaskCreation { task_id }; self.add_to_undo_stack(action); } }
-    #[allow(dead_code)] // Future undo/redo feature
     pub fn add_task_edit_to_undo_stack(&mut self, task_id: i64, previous_task: Task) { let action = UndoableAction::TaskEdit { task_id, previous_task }; self.add_to_undo_stack(action); }
 }
+
@@ -0,0 +1,31 @@
+use crate::config::TaskTemplate;
+use crate::templates::{load_project_templates, project_templates_dir, TemplateEntry};
+use crate::tui::app::state::App;
+use crate::tui::modals::TemplatePickerModal;
+
+impl App {
+    /// The templates from config.yaml
+    pub fn templates(&self) -> &[TaskTemplate] {
+        self.config.templates.as_deref().unwrap_or(&[])
+    }
+
+    /// The task templates, then the project templates, read afresh so edits show up
+    pub fn template_entries(&self) -> Vec<TemplateEntry> {
+        let projects = project_templates_dir().map(|dir| load_project_templates(&dir)).unwrap_or_default();
+        self.templates().iter().cloned().map(TemplateEntry::Task)
+            .chain(projects.into_iter().map(TemplateEntry::Project))
+            .collect()
+    }
+
+    pub fn show_template_picker_modal(&mut self) {
+        let entries = self.template_entries();
+        self.close_all_modals();
+        self.show_template_picker_modal = true;
+        self.template_picker_modal = Some(TemplatePickerModal::new(entries));
+    }
+
+    pub fn hide_template_picker_modal(&mut self) {
+        self.show_template_picker_modal = false;
+        self.template_picker_modal = None;
+    }
+}
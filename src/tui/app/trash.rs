@@ -0,0 +1,167 @@
+use crate::tui::app::state::App;
+use crate::tui::app::undoable_action::UndoableAction;
+use crate::tui::modals::TrashModal;
+use crate::vikunja::models::Task;
+use crate::vikunja_client::VikunjaClient;
+use chrono::{DateTime, Duration, Local};
+
+/// A deleted task that hasn't been deleted on the server yet
+#[derive(Clone, Debug)]
+pub struct TrashedTask {
+    pub task: Task,
+    pub deleted_at: DateTime<Local>,
+    /// Server deletes that failed so far; each one pushes the next try further out
+    pub failed_attempts: u32,
+    pub retry_at: Option<DateTime<Local>>,
+    /// Whether the undo history already holds the server snapshot of the task
+    pub snapshotted: bool,
+}
+
+/// Wait before retrying a failed server delete: 30 s, doubling up to 10 minutes
+fn retry_delay(failed_attempts: u32) -> Duration {
+    Duration::seconds((30i64 << failed_attempts.saturating_sub(1).min(5)).min(600))
+}
+
+impl App {
+    /// Take a task out of the lists and into the trash. Returns it and where it was listed.
+    pub fn move_to_trash(&mut self, task_id: i64) -> Option<(Task, usize)> {
+        let listed = self.tasks.iter().position(|t| t.id == task_id);
+        let task = match listed {
+            Some(position) => self.tasks.remove(position),
+            None => self.all_tasks.iter().find(|t| t.id == task_id)?.clone(),
+        };
+        self.all_tasks.retain(|t| t.id != task_id);
+        if self.selected_task_index >= self.tasks.len() && !self.tasks.is_empty() {
+            self.selected_task_index = self.tasks.len() - 1;
+        }
+        self.trash.retain(|t| t.task.id != task_id);
+        self.trash.push(TrashedTask {
+            task: task.clone(),
+            deleted_at: Local::now(),
+            failed_attempts: 0,
+            retry_at: None,
+            snapshotted: false,
+        });
+        Some((task, listed.unwrap_or(self.tasks.len())))
+    }
+
+    pub fn is_trashed(&self, task_id: i64) -> bool {
+        self.trash.iter().any(|t| t.task.id == task_id)
+    }
+
+    /// Put a trashed task back in the lists. Nothing changed on the server, so
+    /// there is nothing to send.
+    pub fn restore_from_trash(&mut self, task_id: i64) -> Option<Task> {
+        let position = self.trash.iter().position(|t| t.task.id == task_id)?;
+        let task = self.trash.remove(position).task;
+        // A snapshot taken before a failed delete isn't needed any more
+        self.deleted_attachments.remove(&task_id);
+        if !self.all_tasks.iter().any(|t| t.id == task_id) {
+            self.all_tasks.push(task.clone());
+        }
+        Some(task)
+    }
+
+    /// When a trashed task gets deleted on the server: after the grace period, or once
+    /// the wait after a failed attempt is over
+    pub fn trash_expires_at(&self, trashed: &TrashedTask) -> DateTime<Local> {
+        let expires = trashed.deleted_at + Duration::seconds(self.config.get_trash_grace_seconds() as i64);
+        trashed.retry_at.map_or(expires, |retry_at| retry_at.max(expires))
+    }
+
+    pub fn has_expired_trash(&self) -> bool {
+        let now = Local::now();
+        self.trash.iter().any(|t| self.trash_expires_at(t) <= now)
+    }
+
+    /// Delete trashed tasks on the server: those past the grace period, or all of them
+    /// (on quit). Tasks that fail stay in the trash.
+    pub async fn flush_trash_async(&mut self, client: &VikunjaClient, everything: bool) {
+        let now = Local::now();
+        let ids: Vec<i64> = self.trash.iter()
+            .filter(|t| everything || self.trash_expires_at(t) <= now)
+            .map(|t| t.task.id)
+            .collect();
+        self.delete_from_trash_async(client, &ids).await;
+    }
+
+    /// Delete the given trashed tasks on the server now. Tasks still in the undo
+    /// history are snapshotted first, once, so undo can recreate them. A failed
+    /// delete is retried later, backing off; only the first failure is shown.
+    pub async fn delete_from_trash_async(&mut self, client: &VikunjaClient, task_ids: &[i64]) {
+        for &task_id in task_ids {
+            let snapshotted = self.trash.iter().any(|t| t.task.id == task_id && t.snapshotted);
+            if !snapshotted && self.history_mentions_deletion(task_id) {
+                match self.snapshot_task_for_undo(task_id, client).await {
+                    Ok(snapshot) => {
+                        self.replace_deleted_snapshot(&snapshot);
+                        if let Some(trashed) = self.trash.iter_mut().find(|t| t.task.id == task_id) {
+                            trashed.snapshotted = true;
+                        }
+                    }
+                    Err(e) => self.add_debug_message(format!("Couldn't snapshot task {} for undo: {}", task_id, e)),
+                }
+            }
+            match client.delete_task(task_id).await {
+                Ok(_) => {
+                    self.trash.retain(|t| t.task.id != task_id);
+                    self.add_debug_message(format!("Task {} deleted on the server", task_id));
+                }
+                Err(e) => {
+                    let Some(trashed) = self.trash.iter_mut().find(|t| t.task.id == task_id) else {
+                        continue;
+                    };
+                    trashed.failed_attempts += 1;
+                    let delay = retry_delay(trashed.failed_attempts);
+                    trashed.retry_at = Some(Local::now() + delay);
+                    let first_failure = trashed.failed_attempts == 1;
+                    self.add_debug_message(format!("Failed to delete task {}: {}; retrying in {}s", task_id, e, delay.num_seconds()));
+                    if first_failure {
+                        self.show_toast(format!("Failed to delete task: {}; it stays in the trash and is retried", e));
+                    }
+                }
+            }
+        }
+    }
+
+    fn history_mentions_deletion(&self, task_id: i64) -> bool {
+        self.undo_stack.iter().chain(self.redo_stack.iter())
+            .any(|action| matches!(action, UndoableAction::TaskDeletion { task, .. } if task.id == task_id))
+    }
+
+    /// Swap the copies of a deleted task in the history for the fuller server snapshot
+    fn replace_deleted_snapshot(&mut self, snapshot: &Task) {
+        for action in self.undo_stack.iter_mut().chain(self.redo_stack.iter_mut()) {
+            if let UndoableAction::TaskDeletion { task, .. } = action {
+                if task.id == snapshot.id {
+                    *task = snapshot.clone();
+                }
+            }
+        }
+    }
+
+    pub fn show_trash_modal(&mut self) {
+        self.close_all_modals();
+        self.show_trash_modal = true;
+        self.trash_modal = Some(TrashModal::new());
+    }
+
+    pub fn hide_trash_modal(&mut self) {
+        self.show_trash_modal = false;
+        self.trash_modal = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backs_off() {
+        assert_eq!(retry_delay(1).num_seconds(), 30);
+        assert_eq!(retry_delay(2).num_seconds(), 60);
+        assert_eq!(retry_delay(5).num_seconds(), 480);
+        assert_eq!(retry_delay(6).num_seconds(), 600);
+        assert_eq!(retry_delay(40).num_seconds(), 600);
+    }
+}
@@ -0,0 +1,351 @@
+use super::state::App;
+use crate::tui::app::undoable_action::{AttachmentCopy, UndoableAction};
+use crate::html::looks_like_html;
+use crate::vikunja::models::{Comment, Task};
+use chrono::{DateTime, Local};
+use crate::vikunja_client::relations::RelationKind;
+use crate::vikunja_client::VikunjaClient;
+use std::collections::HashSet;
+
+impl App {
+    /// Undo the last action on the server, then locally. If the server can't be
+    /// brought back the action stays on the stack, so it can be tried again.
+    pub async fn undo_last_action_async(&mut self, client: &VikunjaClient) -> Option<i64> {
+        self.step_history_async(client, false).await
+    }
+
+    /// Redo the last undone action on the server, then locally
+    pub async fn redo_last_action_async(&mut self, client: &VikunjaClient) -> Option<i64> {
+        self.step_history_async(client, true).await
+    }
+
+    async fn step_history_async(&mut self, client: &VikunjaClient, redo: bool) -> Option<i64> {
+        let (verb, name) = if redo { ("Redid", "redo") } else { ("Undid", "undo") };
+        let stack = if redo { &self.redo_stack } else { &self.undo_stack };
+        let Some(mut action) = stack.last().cloned() else {
+            self.show_toast(format!("Nothing to {}", name));
+            return None;
+        };
+        let inverse = match self.revert_on_server(&action, client).await {
+            Ok(inverse) => inverse,
+            Err(e) => {
+                self.add_debug_message(format!("Failed to {} {}: {}", name, action.describe(), e));
+                self.show_toast(format!("Couldn't {} {}: {}", name, action.describe(), e));
+                self.mark_history_failed(redo, e);
+                return None;
+            }
+        };
+        let info = self.pop_history(redo).and_then(|(_, info)| info);
+        // A recreated task has a new ID; the local copy takes it too
+        if let UndoableAction::TaskDeletion { task, .. } = &mut action {
+            task.id = inverse.task_id();
+        }
+        self.revert_locally(&action, verb);
+        let task_id = inverse.task_id();
+        self.push_history(!redo, inverse, info);
+        let failures = std::mem::take(&mut self.recreate_failures);
+        if failures.is_empty() {
+            self.show_toast(format!("{}: {}", verb, action.describe()));
+        } else {
+            // The task is back, but not all of it; say what is missing
+            let message = format!("{}: {}, but without {}", verb, action.describe(), failures.join(", "));
+            self.show_toast(message.clone());
+            self.mark_history_failed(!redo, message);
+        }
+        Some(task_id)
+    }
+
+    /// Undo (or redo) `count` actions in order, stopping at the first that can't be
+    /// reverted on the server. Returns how many were.
+    pub async fn step_history_many_async(&mut self, client: &VikunjaClient, redo: bool, count: usize) -> usize {
+        for done in 0..count {
+            if self.step_history_async(client, redo).await.is_none() {
+                return done;
+            }
+        }
+        count
+    }
+
+    /// Make the server match the state before `action`. Returns the action that
+    /// reverses this again, based on what the server held beforehand.
+    async fn revert_on_server(&mut self, action: &UndoableAction, client: &VikunjaClient) -> Result<UndoableAction, String> {
+        match action {
+            UndoableAction::TaskCompletion { task_id, previous_state } => {
+                let mut task = client.get_task_detailed(*task_id as u64).await.map_err(|e| e.to_string())?;
+                let current_state = task.done;
+                task.done = *previous_state;
+                update_fields(client, &task).await?;
+                Ok(UndoableAction::TaskCompletion { task_id: *task_id, previous_state: current_state })
+            }
+            UndoableAction::TaskEdit { task_id, previous_task } => {
+                let current = client.get_task_detailed(*task_id as u64).await.map_err(|e| e.to_string())?;
+                restore_task(client, *task_id, previous_task, Some(&current)).await?;
+                Ok(UndoableAction::TaskEdit { task_id: *task_id, previous_task: current })
+            }
+            UndoableAction::TaskCreation { task_id } => {
+                // Like any deletion this goes through the trash
+                let (task, position) = match self.move_to_trash(*task_id) {
+                    Some(trashed) => trashed,
+                    None => {
+                        let task = client.get_task_detailed(*task_id as u64).await.map_err(|e| e.to_string())?;
+                        self.all_tasks.push(task);
+                        self.move_to_trash(*task_id).ok_or("Task not found")?
+                    }
+                };
+                Ok(UndoableAction::TaskDeletion { task, position })
+            }
+            UndoableAction::TaskDeletion { task, .. } if self.is_trashed(task.id) || self.tasks.iter().any(|t| t.id == task.id) => {
+                // Still on the server; putting it back in the list is all there is to do
+                Ok(UndoableAction::TaskCreation { task_id: task.id })
+            }
+            UndoableAction::TaskDeletion { task, .. } => {
+                let new_id = self.recreate_task(task, client).await?;
+                self.remap_task_id(task.id, new_id);
+                Ok(UndoableAction::TaskCreation { task_id: new_id })
+            }
+            UndoableAction::RelationAdded { task_id, other_task_id, kind } => {
+                client.delete_task_relation(*task_id as u64, *other_task_id as u64, kind.clone()).await.map_err(|e| e.to_string())?;
+                Ok(UndoableAction::RelationRemoved { task_id: *task_id, other_task_id: *other_task_id, kind: kind.clone() })
+            }
+            UndoableAction::RelationRemoved { task_id, other_task_id, kind } => {
+                client.create_task_relation(*task_id as u64, *other_task_id as u64, kind.clone()).await.map_err(|e| e.to_string())?;
+                Ok(UndoableAction::RelationAdded { task_id: *task_id, other_task_id: *other_task_id, kind: kind.clone() })
+            }
+            UndoableAction::CommentAdded { task_id, comment_id, text } => {
+                client.delete_comment(*task_id as u64, *comment_id).await.map_err(|e| e.to_string())?;
+                Ok(UndoableAction::CommentDeleted { task_id: *task_id, text: text.clone() })
+            }
+            UndoableAction::CommentDeleted { task_id, text } => {
+                let comment = client.add_comment_to_task(*task_id as u64, text).await.map_err(|e| e.to_string())?;
+                Ok(UndoableAction::CommentAdded { task_id: *task_id, comment_id: comment.id, text: text.clone() })
+            }
+            UndoableAction::CommentEdited { task_id, comment_id, previous_text, text } => {
+                client.update_comment(*task_id as u64, *comment_id, previous_text).await.map_err(|e| e.to_string())?;
+                Ok(UndoableAction::CommentEdited {
+                    task_id: *task_id,
+                    comment_id: *comment_id,
+                    previous_text: text.clone(),
+                    text: previous_text.clone(),
+                })
+            }
+            UndoableAction::BucketMoved { task_id, project_id, from_bucket_id, to_bucket_id } => {
+                client.move_task_to_named_bucket(*project_id, *task_id, &from_bucket_id.to_string()).await?;
+                Ok(UndoableAction::BucketMoved {
+                    task_id: *task_id,
+                    project_id: *project_id,
+                    from_bucket_id: *to_bucket_id,
+                    to_bucket_id: *from_bucket_id,
+                })
+            }
+            UndoableAction::Batch { actions } => {
+                let mut inverses = Vec::new();
+                for action in actions.iter().rev() {
+                    match Box::pin(self.revert_on_server(action, client)).await {
+                        Ok(inverse) => inverses.push(inverse),
+                        Err(e) => {
+                            // Put back what was already reverted, so the entry stays whole
+                            for inverse in inverses.iter().rev() {
+                                if let Err(e) = Box::pin(self.revert_on_server(inverse, client)).await {
+                                    self.add_debug_message(format!("Failed to roll back '{}': {}", inverse.describe(), e));
+                                }
+                            }
+                            return Err(e);
+                        }
+                    }
+                }
+                Ok(UndoableAction::Batch { actions: inverses })
+            }
+        }
+    }
+
+    /// The task as the server has it, with relations and comments, and a copy of its
+    /// attachments kept aside, so it can be recreated after being deleted
+    pub async fn snapshot_task_for_undo(&mut self, task_id: i64, client: &VikunjaClient) -> Result<Task, String> {
+        let task = client.get_task_detailed(task_id as u64).await.map_err(|e| e.to_string())?;
+        let attachments = client.get_task_attachments(task_id).await.unwrap_or_default();
+        let mut copies = Vec::new();
+        let dir = undo_dir(task_id);
+        for attachment in attachments {
+            let file_name = attachment.file.as_ref()
+                .and_then(|f| f.name.clone())
+                .unwrap_or_else(|| format!("attachment-{}", attachment.id));
+            let path = dir.join(&file_name);
+            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            client.download_attachment(&attachment, &path).await.map_err(|e| format!("Failed to copy attachment '{}': {}", file_name, e))?;
+            let data = std::fs::read(&path).map_err(|e| e.to_string())?;
+            let _ = std::fs::remove_file(&path);
+            copies.push(AttachmentCopy { file_name, data });
+        }
+        if !copies.is_empty() {
+            self.deleted_attachments.insert(task_id, copies);
+        }
+        Ok(task)
+    }
+
+    /// Create `task` again with its labels, assignees, relations, comments and
+    /// attachments. Returns the new task's ID.
+    async fn recreate_task(&mut self, task: &Task, client: &VikunjaClient) -> Result<i64, String> {
+        let mut api_task = task.to_vikunja_task();
+        api_task.id = None;
+        api_task.labels = None;
+        api_task.assignees = None;
+        let created = client.create_task(&api_task).await.map_err(|e| e.to_string())?;
+        let new_id = created.id.ok_or("Server returned no task ID")? as i64;
+
+        let mut failures = Vec::new();
+        for label in task.labels.iter().flatten() {
+            if client.add_label_to_task(new_id as u64, label.id as u64).await.is_err() {
+                failures.push(format!("label {}", label.title));
+            }
+        }
+        for user in task.assignees.iter().flatten() {
+            if client.add_assignee_to_task(new_id as u64, user.id as u64).await.is_err() {
+                failures.push(format!("assignee {}", user.username));
+            }
+        }
+        for (kind, related) in task.related_tasks.iter().flatten() {
+            let Ok(kind) = serde_json::from_value::<RelationKind>(serde_json::Value::String(kind.clone())) else {
+                continue;
+            };
+            for other in related {
+                if client.create_task_relation(new_id as u64, other.id as u64, kind.clone()).await.is_err() {
+                    failures.push(format!("relation to #{}", other.id));
+                }
+            }
+        }
+        for comment in task.comments.iter().flatten() {
+            // The server posts it as the current user, now; keep who wrote it and when
+            let Some(text) = attributed_comment(comment) else { continue };
+            if client.add_comment_to_task(new_id as u64, &text).await.is_err() {
+                failures.push("a comment".to_string());
+            }
+        }
+        if let Some(copies) = self.deleted_attachments.remove(&task.id) {
+            let dir = undo_dir(new_id);
+            for copy in &copies {
+                let path = dir.join(&copy.file_name);
+                let uploaded = match std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&path, &copy.data)) {
+                    Ok(()) => client.upload_attachment(new_id, &path).await.is_ok(),
+                    Err(_) => false,
+                };
+                let _ = std::fs::remove_file(&path);
+                if !uploaded {
+                    failures.push(format!("attachment {}", copy.file_name));
+                }
+            }
+            let _ = std::fs::remove_dir(&dir);
+        }
+        if !failures.is_empty() {
+            self.add_debug_message(format!("Recreated task {} as {} without {}", task.id, new_id, failures.join(", ")));
+        }
+        self.recreate_failures = failures;
+        Ok(new_id)
+    }
+
+    /// Point the undo history and the task lists at a recreated task's new ID
+    fn remap_task_id(&mut self, old_id: i64, new_id: i64) {
+        for action in self.undo_stack.iter_mut().chain(self.redo_stack.iter_mut()) {
+            action.remap_task_id(old_id, new_id);
+        }
+        for task in self.tasks.iter_mut().chain(self.all_tasks.iter_mut()) {
+            if task.id == old_id {
+                task.id = new_id;
+            }
+        }
+        if let Some(copies) = self.deleted_attachments.remove(&old_id) {
+            self.deleted_attachments.insert(new_id, copies);
+        }
+    }
+}
+
+/// Send a local change to a task to the server, the same way undo sends a reverted one
+pub async fn write_task_changes(client: &VikunjaClient, before: &Task, after: &Task) -> Result<(), String> {
+    restore_task(client, after.id, after, Some(before)).await
+}
+
+/// Put `task_id` back to `previous` on the server: its fields, then the labels and
+/// assignees that differ from `current` (fetched when not given)
+async fn restore_task(client: &VikunjaClient, task_id: i64, previous: &Task, current: Option<&Task>) -> Result<(), String> {
+    let fetched;
+    let current = match current {
+        Some(current) => current,
+        None => {
+            fetched = client.get_task_detailed(task_id as u64).await.map_err(|e| e.to_string())?;
+            &fetched
+        }
+    };
+    update_fields(client, previous).await?;
+
+    let label_ids = |task: &Task| -> HashSet<i64> { task.labels.iter().flatten().map(|l| l.id).collect() };
+    let (had, has) = (label_ids(previous), label_ids(current));
+    for id in had.difference(&has) {
+        client.add_label_to_task(task_id as u64, *id as u64).await.map_err(|e| e.to_string())?;
+    }
+    for id in has.difference(&had) {
+        client.remove_label_from_task(task_id as u64, *id as u64).await.map_err(|e| e.to_string())?;
+    }
+
+    let assignee_ids = |task: &Task| -> HashSet<i64> { task.assignees.iter().flatten().map(|a| a.id).collect() };
+    let (had, has) = (assignee_ids(previous), assignee_ids(current));
+    for id in had.difference(&has) {
+        client.add_assignee_to_task(task_id as u64, *id as u64).await.map_err(|e| e.to_string())?;
+    }
+    for id in has.difference(&had) {
+        client.remove_assignee_from_task(task_id as u64, *id as u64).await.map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Write the task's own fields; labels and assignees have their own endpoints
+async fn update_fields(client: &VikunjaClient, task: &Task) -> Result<(), String> {
+    let mut api_task = task.to_vikunja_task();
+    api_task.labels = None;
+    api_task.assignees = None;
+    client.update_task(&api_task).await.map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// A deleted comment's text, headed with its original author and date
+fn attributed_comment(comment: &Comment) -> Option<String> {
+    let text = comment.comment.as_deref()?;
+    let author = comment.author.as_ref().map(|u| u.display_name()).unwrap_or("unknown");
+    let date = comment.created.as_deref()
+        .and_then(|created| DateTime::parse_from_rfc3339(created).ok())
+        .map(|created| format!(" on {}", created.with_timezone(&Local).format("%Y-%m-%d %H:%M")))
+        .unwrap_or_default();
+    Some(if looks_like_html(text) {
+        format!("<p><em>Originally posted by {}{}</em></p>{}", author, date, text)
+    } else {
+        format!("Originally posted by {}{}:\n\n{}", author, date, text)
+    })
+}
+
+/// Scratch directory for attachment copies passing through undo
+fn undo_dir(task_id: i64) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("cria-undo-{}", task_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vikunja::models::User;
+
+    #[test]
+    fn test_attributed_comment_names_author_and_date() {
+        let mut comment = Comment {
+            id: 4,
+            author: Some(User { id: 2, username: "bob".to_string(), name: None, email: None, created: None, updated: None }),
+            comment: Some("Ship it".to_string()),
+            created: Some("not a date".to_string()),
+            updated: None,
+            reactions: None,
+        };
+        assert_eq!(attributed_comment(&comment).unwrap(), "Originally posted by bob:\n\nShip it");
+
+        comment.comment = Some("<p>Ship it</p>".to_string());
+        comment.created = Some("2025-06-01T12:00:00Z".to_string());
+        let text = attributed_comment(&comment).unwrap();
+        assert!(text.starts_with("<p><em>Originally posted by bob on 2025-06-01"));
+        assert!(text.ends_with("</em></p><p>Ship it</p>"));
+    }
+}
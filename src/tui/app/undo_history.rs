@@ -0,0 +1,16 @@
+use crate::tui::app::state::App;
+use crate::tui::modals::UndoHistoryModal;
+
+impl App {
+    pub fn show_undo_history_modal(&mut self) {
+        let modal = UndoHistoryModal::new(self.redo_stack.len(), self.undo_stack.len());
+        self.close_all_modals();
+        self.show_undo_history_modal = true;
+        self.undo_history_modal = Some(modal);
+    }
+
+    pub fn hide_undo_history_modal(&mut self) {
+        self.show_undo_history_modal = false;
+        self.undo_history_modal = None;
+    }
+}
@@ -1,4 +1,6 @@
+use chrono::{DateTime, Local};
 use crate::vikunja::models::Task;
+use crate::vikunja_client::relations::RelationKind;
 
 #[derive(Clone, Debug)]
 pub enum UndoableAction {
@@ -7,6 +9,8 @@ pub enum UndoableAction {
         task_id: i64,
         previous_state: bool,
     },
+    /// `task` is the full snapshot taken before deleting, so the task can be
+    /// recreated with its labels, assignees, relations and comments
     TaskDeletion {
         task: Task,
         position: usize,
@@ -14,8 +18,139 @@ pub enum UndoableAction {
     TaskCreation {
         task_id: i64,
     },
+    /// Any change to a task's fields, labels, assignees, project, priority or favorite
     TaskEdit {
         task_id: i64,
         previous_task: Task,
     },
+    RelationAdded {
+        task_id: i64,
+        other_task_id: i64,
+        kind: RelationKind,
+    },
+    RelationRemoved {
+        task_id: i64,
+        other_task_id: i64,
+        kind: RelationKind,
+    },
+    CommentAdded {
+        task_id: i64,
+        comment_id: i64,
+        text: String,
+    },
+    CommentDeleted {
+        task_id: i64,
+        text: String,
+    },
+    CommentEdited {
+        task_id: i64,
+        comment_id: i64,
+        previous_text: String,
+        text: String,
+    },
+    /// A move between buckets of the project's kanban view
+    BucketMoved {
+        task_id: i64,
+        project_id: i64,
+        from_bucket_id: i64,
+        to_bucket_id: i64,
+    },
+    /// Several changes made by one command, like a quick action macro, in the order
+    /// they were made, undone and redone together
+    Batch {
+        actions: Vec<UndoableAction>,
+    },
+}
+
+/// The contents of an attachment on a deleted task, kept so undo can upload it again
+#[derive(Clone, Debug)]
+pub struct AttachmentCopy {
+    pub file_name: String,
+    pub data: Vec<u8>,
+}
+
+/// What the undo history shows about an entry besides the action itself
+#[derive(Clone, Debug)]
+pub struct UndoEntryInfo {
+    pub at: DateTime<Local>,
+    pub task_title: String,
+    /// Why reverting it on the server failed the last time it was tried
+    pub failed: Option<String>,
+}
+
+impl UndoableAction {
+    /// The task the action changed
+    pub fn task_id(&self) -> i64 {
+        match self {
+            UndoableAction::TaskCompletion { task_id, .. }
+            | UndoableAction::TaskCreation { task_id }
+            | UndoableAction::TaskEdit { task_id, .. }
+            | UndoableAction::RelationAdded { task_id, .. }
+            | UndoableAction::RelationRemoved { task_id, .. }
+            | UndoableAction::CommentAdded { task_id, .. }
+            | UndoableAction::CommentDeleted { task_id, .. }
+            | UndoableAction::CommentEdited { task_id, .. }
+            | UndoableAction::BucketMoved { task_id, .. } => *task_id,
+            UndoableAction::TaskDeletion { task, .. } => task.id,
+            UndoableAction::Batch { actions } => actions.first().map_or(0, |a| a.task_id()),
+        }
+    }
+
+    /// Short description, e.g. for "Undid: ..." messages
+    pub fn describe(&self) -> String {
+        match self {
+            UndoableAction::TaskCompletion { previous_state: true, .. } => "mark incomplete".to_string(),
+            UndoableAction::TaskCompletion { previous_state: false, .. } => "mark complete".to_string(),
+            UndoableAction::TaskDeletion { task, .. } => format!("delete '{}'", task.title),
+            UndoableAction::TaskCreation { .. } => "create task".to_string(),
+            UndoableAction::TaskEdit { previous_task, .. } => format!("edit '{}'", previous_task.title),
+            UndoableAction::RelationAdded { kind, .. } => format!("add relation ({})", kind.display_name()),
+            UndoableAction::RelationRemoved { kind, .. } => format!("remove relation ({})", kind.display_name()),
+            UndoableAction::CommentAdded { .. } => "add comment".to_string(),
+            UndoableAction::CommentDeleted { .. } => "delete comment".to_string(),
+            UndoableAction::CommentEdited { .. } => "edit comment".to_string(),
+            UndoableAction::BucketMoved { .. } => "move to bucket".to_string(),
+            UndoableAction::Batch { actions } => actions.iter().map(|a| a.describe()).collect::<Vec<_>>().join(", "),
+        }
+    }
+
+    /// Point every reference to `old_id` at `new_id`, after a deleted task is recreated
+    pub fn remap_task_id(&mut self, old_id: i64, new_id: i64) {
+        let remap = |id: &mut i64| if *id == old_id { *id = new_id };
+        match self {
+            UndoableAction::TaskCompletion { task_id, .. }
+            | UndoableAction::TaskCreation { task_id }
+            | UndoableAction::CommentAdded { task_id, .. }
+            | UndoableAction::CommentDeleted { task_id, .. }
+            | UndoableAction::CommentEdited { task_id, .. }
+            | UndoableAction::BucketMoved { task_id, .. } => remap(task_id),
+            UndoableAction::TaskEdit { task_id, previous_task } => {
+                remap(task_id);
+                remap(&mut previous_task.id);
+                remap_related(previous_task, old_id, new_id);
+            }
+            UndoableAction::TaskDeletion { task, .. } => {
+                remap(&mut task.id);
+                remap_related(task, old_id, new_id);
+            }
+            UndoableAction::RelationAdded { task_id, other_task_id, .. }
+            | UndoableAction::RelationRemoved { task_id, other_task_id, .. } => {
+                remap(task_id);
+                remap(other_task_id);
+            }
+            UndoableAction::Batch { actions } => {
+                for action in actions {
+                    action.remap_task_id(old_id, new_id);
+                }
+            }
+        }
+    }
+}
+
+fn remap_related(task: &mut Task, old_id: i64, new_id: i64) {
+    for related in task.related_tasks.iter_mut().flat_map(|r| r.values_mut()).flatten() {
+        if related.id == old_id {
+            related.id = new_id;
+        }
+    }
 }
@@ -23,13 +23,9 @@ pub async fn handle_confirmation_dialog(
                 match pending_action {
                     PendingAction::DeleteTask { task_id } => {
                         debug_log(&format!("Confirmed delete for task ID: {}", task_id));
-                        // Call delete API and refresh tasks (existing logic)
+                        // Delete (keeping an undo snapshot) and refresh tasks
                         let api_client_guard = api_client.lock().await;
-                        if let Err(e) = api_client_guard.delete_task(task_id).await {
-                            debug_log(&format!("ERROR: Failed to delete task from API: {}", e));
-                        } else {
-                            debug_log(&format!("Task {} deleted from API", task_id));
-                        }
+                        app.execute_delete_task_async(task_id, &api_client_guard).await;
                         drop(api_client_guard);
                         let (tasks, project_map, project_colors) = client_clone.lock().await.get_tasks_with_projects().await.unwrap_or_default();
                         app.all_tasks = tasks;
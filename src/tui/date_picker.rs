@@ -0,0 +1,356 @@
+// Calendar popup for date fields: move around a month grid, set or drop a time of day,
+// jump to presets, or type anything quick add reads and see what it means first.
+
+use crate::dates::DateSettings;
+use crate::tui::line_editor::{clamp_cursor, display_width, EditOutcome, KillRing, LineEditor};
+use crate::vikunja_parser::QuickAddParser;
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, NaiveTime, Timelike, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DatePickerAction {
+    None,
+    Cancel,
+    /// The picked date, or None to clear the field
+    Confirm(Option<DateTime<Utc>>),
+}
+
+/// Where keys go
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DatePickerFocus {
+    Calendar,
+    Time,
+    Text,
+}
+
+/// Minutes the time moves by with ↑↓
+const TIME_STEP: i64 = 15;
+
+#[derive(Debug, Clone)]
+pub struct DatePicker {
+    pub day: NaiveDate,
+    /// None is all day, i.e. the field's default time
+    pub time: Option<NaiveTime>,
+    pub focus: DatePickerFocus,
+    pub input: String,
+    pub input_cursor: usize,
+    /// What `input` reads as
+    pub parsed: Option<DateTime<Utc>>,
+    kill_ring: KillRing,
+    settings: DateSettings,
+    default_time: NaiveTime,
+    parser: QuickAddParser,
+}
+
+impl DatePicker {
+    /// Start at `current`, or today when the field is empty
+    pub fn new(current: Option<DateTime<Utc>>, settings: DateSettings, default_time: NaiveTime) -> Self {
+        let (day, time) = match current {
+            Some(date) => {
+                let local = settings.local(date);
+                let time = (!settings.is_all_day(date, default_time)).then(|| local.time());
+                (local.date(), time)
+            }
+            None => (settings.today(), None),
+        };
+        Self {
+            day,
+            time,
+            focus: DatePickerFocus::Calendar,
+            input: String::new(),
+            input_cursor: 0,
+            parsed: None,
+            kill_ring: KillRing::default(),
+            settings,
+            default_time,
+            parser: QuickAddParser::with_settings(settings),
+        }
+    }
+
+    /// The date as picked so far
+    pub fn result(&self) -> Option<DateTime<Utc>> {
+        self.settings.at(self.day, self.time.unwrap_or(self.default_time))
+    }
+
+    /// A date the way the form field shows it
+    pub fn format(&self, date: DateTime<Utc>) -> String {
+        self.settings.format(date, self.default_time)
+    }
+
+    /// Move to a date, taking its time of day unless it is all-day
+    fn jump_to(&mut self, date: DateTime<Utc>) {
+        self.day = self.settings.local(date).date();
+        self.time = (!self.settings.is_all_day(date, self.default_time)).then(|| self.settings.local(date).time());
+    }
+
+    /// Move to a preset like "tomorrow", read the way quick add reads it
+    fn preset(&mut self, text: &str) {
+        if let Some(date) = self.parser.parse_date(text, self.default_time) {
+            self.day = self.settings.local(date).date();
+        }
+    }
+
+    fn shift_months(&mut self, months: i32) {
+        let shifted = if months < 0 {
+            self.day.checked_sub_months(Months::new(months.unsigned_abs()))
+        } else {
+            self.day.checked_add_months(Months::new(months as u32))
+        };
+        self.day = shifted.unwrap_or(self.day);
+    }
+
+    fn shift_time(&mut self, minutes: i64) {
+        // Picking a time for an all-day date starts from 9:00
+        let time = self.time.unwrap_or_else(|| NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+        let (time, _) = time.overflowing_add_signed(Duration::minutes(minutes));
+        self.time = Some(time.with_second(0).unwrap_or(time));
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> DatePickerAction {
+        if key.code == KeyCode::Tab {
+            self.focus = match self.focus {
+                DatePickerFocus::Calendar => DatePickerFocus::Time,
+                DatePickerFocus::Time => DatePickerFocus::Text,
+                DatePickerFocus::Text => DatePickerFocus::Calendar,
+            };
+            return DatePickerAction::None;
+        }
+        match self.focus {
+            DatePickerFocus::Calendar => self.handle_calendar_key(key),
+            DatePickerFocus::Time => self.handle_time_key(key),
+            DatePickerFocus::Text => self.handle_text_key(key),
+        }
+    }
+
+    fn handle_calendar_key(&mut self, key: &KeyEvent) -> DatePickerAction {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return DatePickerAction::Cancel,
+            KeyCode::Enter => return DatePickerAction::Confirm(self.result()),
+            KeyCode::Left | KeyCode::Char('h') => self.day -= Duration::days(1),
+            KeyCode::Right | KeyCode::Char('l') => self.day += Duration::days(1),
+            KeyCode::Up | KeyCode::Char('k') => self.day -= Duration::weeks(1),
+            KeyCode::Down | KeyCode::Char('j') => self.day += Duration::weeks(1),
+            KeyCode::PageUp | KeyCode::Char('[') => self.shift_months(-1),
+            KeyCode::PageDown | KeyCode::Char(']') => self.shift_months(1),
+            KeyCode::Char('t') | KeyCode::Home => self.preset("today"),
+            KeyCode::Char('m') => self.preset("tomorrow"),
+            KeyCode::Char('w') => self.preset("next week"),
+            KeyCode::Char('c') | KeyCode::Delete | KeyCode::Backspace => return DatePickerAction::Confirm(None),
+            KeyCode::Char('/') | KeyCode::Char('i') => self.focus = DatePickerFocus::Text,
+            _ => {}
+        }
+        DatePickerAction::None
+    }
+
+    fn handle_time_key(&mut self, key: &KeyEvent) -> DatePickerAction {
+        match key.code {
+            KeyCode::Esc => self.focus = DatePickerFocus::Calendar,
+            KeyCode::Enter => return DatePickerAction::Confirm(self.result()),
+            KeyCode::Up | KeyCode::Char('k') => self.shift_time(TIME_STEP),
+            KeyCode::Down | KeyCode::Char('j') => self.shift_time(-TIME_STEP),
+            KeyCode::Right | KeyCode::Char('l') => self.shift_time(60),
+            KeyCode::Left | KeyCode::Char('h') => self.shift_time(-60),
+            KeyCode::Char('a') | KeyCode::Delete | KeyCode::Backspace => self.time = None,
+            _ => {}
+        }
+        DatePickerAction::None
+    }
+
+    fn handle_text_key(&mut self, key: &KeyEvent) -> DatePickerAction {
+        match key.code {
+            KeyCode::Esc => self.focus = DatePickerFocus::Calendar,
+            // The first Enter shows the typed date on the calendar, the next one confirms
+            KeyCode::Enter => {
+                if let Some(date) = self.parsed {
+                    self.jump_to(date);
+                    self.input.clear();
+                    self.input_cursor = 0;
+                    self.parsed = None;
+                    self.focus = DatePickerFocus::Calendar;
+                }
+            }
+            _ => {
+                let outcome = LineEditor::new(&mut self.input, &mut self.input_cursor, &mut self.kill_ring).handle_key(key);
+                if outcome == EditOutcome::Changed {
+                    // The form's own format first, then anything quick add understands
+                    self.parsed = self.settings.parse(&self.input, self.default_time)
+                        .or_else(|| self.parser.parse_date(&self.input, self.default_time));
+                }
+            }
+        }
+        DatePickerAction::None
+    }
+
+    /// A date as the picker shows it: weekday, date, and the time unless all-day
+    fn describe(&self, date: DateTime<Utc>) -> String {
+        let local = self.settings.local(date);
+        if self.settings.is_all_day(date, self.default_time) {
+            local.format("%a %Y-%m-%d, all day").to_string()
+        } else {
+            local.format("%a %Y-%m-%d %H:%M").to_string()
+        }
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect, title: &str) {
+        let width = 44.min(area.width);
+        let height = 17.min(area.height);
+        let x = area.x + (area.width - width) / 2;
+        let y = area.y + (area.height - height) / 2;
+        let popup = Rect::new(x, y, width, height);
+        f.render_widget(Clear, popup);
+
+        let focused = |focus: DatePickerFocus| if self.focus == focus { Color::Yellow } else { Color::DarkGray };
+        let today = self.settings.today();
+        let mut lines = vec![
+            Line::from(Span::styled(self.day.format("%B %Y").to_string(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))).alignment(Alignment::Center),
+            Line::from(Span::styled(" Mo  Tu  We  Th  Fr  Sa  Su", Style::default().fg(Color::DarkGray))),
+        ];
+
+        // Weeks of the month, Monday first
+        let first = self.day.with_day(1).unwrap_or(self.day);
+        let mut cursor = first - Duration::days(first.weekday().num_days_from_monday() as i64);
+        while cursor.month() == first.month() || cursor < first {
+            let mut spans = Vec::new();
+            for _ in 0..7 {
+                let mut style = if cursor.month() == first.month() { Style::default().fg(Color::White) } else { Style::default().fg(Color::DarkGray) };
+                if cursor == today {
+                    style = style.fg(Color::Green).add_modifier(Modifier::UNDERLINED);
+                }
+                if cursor == self.day {
+                    style = style.bg(if self.focus == DatePickerFocus::Calendar { Color::Yellow } else { Color::Gray }).fg(Color::Black).add_modifier(Modifier::BOLD);
+                }
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(format!("{:>2}", cursor.day()), style));
+                spans.push(Span::raw(" "));
+                cursor += Duration::days(1);
+            }
+            lines.push(Line::from(spans));
+        }
+        while lines.len() < 8 {
+            lines.push(Line::from(""));
+        }
+
+        let time_text = match self.time {
+            Some(time) => time.format("%H:%M").to_string(),
+            None => "all day".to_string(),
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Time: ", Style::default().fg(focused(DatePickerFocus::Time))),
+            Span::styled(time_text, Style::default().fg(Color::White)),
+        ]));
+        let typed = match (self.input.is_empty(), self.parsed) {
+            (true, _) => Span::styled("type e.g. \"next friday 3pm\"", Style::default().fg(Color::DarkGray)),
+            (false, Some(date)) => Span::styled(format!("  → {}", self.describe(date)), Style::default().fg(Color::Green)),
+            (false, None) => Span::styled("  → ?", Style::default().fg(Color::Red)),
+        };
+        let input_label = "Type: ";
+        lines.push(Line::from(vec![
+            Span::styled(input_label, Style::default().fg(focused(DatePickerFocus::Text))),
+            Span::styled(self.input.clone(), Style::default().fg(Color::White)),
+            typed,
+        ]));
+        let result = match self.result() {
+            Some(date) => self.describe(date),
+            None => "?".to_string(),
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Result: ", Style::default().fg(Color::Cyan)),
+            Span::styled(result, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+        ]));
+        lines.push(Line::from(Span::styled("t today · m tomorrow · w next week · c clear", Style::default().fg(Color::DarkGray))));
+        let help = match self.focus {
+            DatePickerFocus::Calendar => "←→ day · ↑↓ week · [ ] month · Tab time",
+            DatePickerFocus::Time => "↑↓ 15 min · ←→ hour · a all day · Tab type",
+            DatePickerFocus::Text => "Enter show on calendar · Esc back",
+        };
+        lines.push(Line::from(Span::styled(help, Style::default().fg(Color::Gray))));
+
+        let block = Block::default()
+            .title(format!(" {} ", title))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta));
+        let inner = block.inner(popup);
+        f.render_widget(Paragraph::new(lines).block(block), popup);
+
+        if self.focus == DatePickerFocus::Text {
+            let before_cursor = &self.input[..clamp_cursor(&self.input, self.input_cursor)];
+            let cursor_x = inner.x + (input_label.len() + display_width(before_cursor)) as u16;
+            let cursor_y = inner.y + 9;
+            if cursor_x < inner.right() && cursor_y < inner.bottom() {
+                f.set_cursor(cursor_x, cursor_y);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_calendar_navigation() {
+        let settings = DateSettings::default();
+        let start = settings.at(day(2025, 1, 31), settings.due_time);
+        let mut picker = DatePicker::new(start, settings, settings.due_time);
+        assert_eq!(picker.time, None);
+        picker.handle_key(&key(KeyCode::Right));
+        assert_eq!(picker.day, day(2025, 2, 1));
+        picker.handle_key(&key(KeyCode::Char('k')));
+        assert_eq!(picker.day, day(2025, 1, 25));
+        picker.handle_key(&key(KeyCode::Char(']')));
+        assert_eq!(picker.day, day(2025, 2, 25));
+        picker.handle_key(&key(KeyCode::Char('t')));
+        assert_eq!(picker.day, settings.today());
+        assert_eq!(picker.handle_key(&key(KeyCode::Char('c'))), DatePickerAction::Confirm(None));
+        assert_eq!(picker.handle_key(&key(KeyCode::Esc)), DatePickerAction::Cancel);
+    }
+
+    #[test]
+    fn test_time_and_typed_dates() {
+        let settings = DateSettings::default();
+        let mut picker = DatePicker::new(None, settings, settings.due_time);
+        picker.handle_key(&key(KeyCode::Tab));
+        assert_eq!(picker.focus, DatePickerFocus::Time);
+        picker.handle_key(&key(KeyCode::Up));
+        picker.handle_key(&key(KeyCode::Right));
+        assert_eq!(picker.time, NaiveTime::from_hms_opt(10, 15, 0));
+        assert_eq!(picker.handle_key(&key(KeyCode::Enter)), DatePickerAction::Confirm(settings.at(settings.today(), NaiveTime::from_hms_opt(10, 15, 0).unwrap())));
+        picker.handle_key(&key(KeyCode::Char('a')));
+        assert_eq!(picker.time, None);
+
+        // Typed dates are shown before they are used
+        picker.handle_key(&key(KeyCode::Tab));
+        for c in "2025-07-15 14:30".chars() {
+            picker.handle_key(&key(KeyCode::Char(c)));
+        }
+        let typed = settings.at(day(2025, 7, 15), NaiveTime::from_hms_opt(14, 30, 0).unwrap());
+        assert_eq!(picker.parsed, typed);
+        assert_eq!(picker.handle_key(&key(KeyCode::Enter)), DatePickerAction::None);
+        assert_eq!(picker.focus, DatePickerFocus::Calendar);
+        assert_eq!(picker.handle_key(&key(KeyCode::Enter)), DatePickerAction::Confirm(typed));
+
+        picker.handle_key(&key(KeyCode::Char('/')));
+        for c in "tomorrow".chars() {
+            picker.handle_key(&key(KeyCode::Char(c)));
+        }
+        assert_eq!(picker.parsed, settings.at(settings.today() + Duration::days(1), settings.due_time));
+        picker.handle_key(&key(KeyCode::Enter));
+        assert_eq!(picker.time, None);
+    }
+}
@@ -1,31 +1,49 @@
-use crossterm::event::{self, Event as CrosstermEvent, KeyEvent};
-use std::sync::mpsc;
+use crossterm::event::{self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 use std::thread;
 use std::time::{Duration, Instant};
 
 #[derive(Debug)]
 pub enum Event {
     Key(KeyEvent),
+    /// Text pasted in one go, when the terminal supports bracketed paste
+    Paste(String),
     Tick,
 }
 
 pub struct EventHandler {
-    #[allow(dead_code)]
     sender: mpsc::Sender<Event>,
     receiver: mpsc::Receiver<Event>,
     #[allow(dead_code)]
     handler: thread::JoinHandle<()>, 
+    /// Set while another program owns the terminal, e.g. an external editor
+    paused: Arc<AtomicBool>,
+    /// Set by the polling thread once it has stopped reading input
+    parked: Arc<AtomicBool>,
 }
 
 impl EventHandler {
     pub fn new(tick_rate: u64) -> Self {
         let tick_rate = Duration::from_millis(tick_rate);
         let (sender, receiver) = mpsc::channel();
+        let paused = Arc::new(AtomicBool::new(false));
+        let parked = Arc::new(AtomicBool::new(false));
         let handler = {
             let sender = sender.clone();
+            let paused = paused.clone();
+            let parked = parked.clone();
             thread::spawn(move || {
                 let mut last_tick = Instant::now();
                 loop {
+                    if paused.load(Ordering::SeqCst) {
+                        parked.store(true, Ordering::SeqCst);
+                        thread::sleep(Duration::from_millis(20));
+                        last_tick = Instant::now();
+                        continue;
+                    }
+                    parked.store(false, Ordering::SeqCst);
+
                     let timeout = tick_rate
                         .checked_sub(last_tick.elapsed())
                         .unwrap_or_else(|| Duration::from_secs(0));
@@ -33,6 +51,7 @@ impl EventHandler {
                     if event::poll(timeout).expect("event poll failed") {
                         match event::read().expect("event read failed") {
                             CrosstermEvent::Key(e) => sender.send(Event::Key(e)),
+                            CrosstermEvent::Paste(text) => sender.send(Event::Paste(text)),
                             _ => Ok(()),
                         }
                         .expect("failed to send terminal event");
@@ -45,7 +64,35 @@ impl EventHandler {
                 }
             })
         };
-        Self { sender, receiver, handler }
+        Self { sender, receiver, handler, paused, parked }
+    }
+
+    /// Stop reading terminal input so a child process can use it.
+    /// Waits for an in-flight poll to finish so no keystrokes are stolen.
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+        let deadline = Instant::now() + Duration::from_secs(1);
+        while !self.parked.load(Ordering::SeqCst) && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    pub fn resume(&self) {
+        self.parked.store(false, Ordering::SeqCst);
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    /// Replay pasted text as key presses, as terminals without bracketed paste send it.
+    /// Used for inputs that have no use for a paste as a whole.
+    pub fn type_text(&self, text: &str) {
+        for c in text.replace("\r\n", "\n").chars() {
+            let code = match c {
+                '\n' | '\r' => KeyCode::Enter,
+                '\t' => KeyCode::Tab,
+                c => KeyCode::Char(c),
+            };
+            let _ = self.sender.send(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+        }
     }
 
     pub fn next(&self) -> Result<Event, mpsc::RecvError> {
@@ -0,0 +1,81 @@
+// Suspend the TUI and edit text in the user's $VISUAL / $EDITOR
+
+use crate::debug::debug_log;
+use crate::tui::events::EventHandler;
+use crossterm::event::{DisableBracketedPaste, EnableBracketedPaste};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::ExecutableCommand;
+use ratatui::prelude::{CrosstermBackend, Terminal};
+use std::io::{self, stdout, Stdout};
+use std::process::Command;
+
+/// Editor program and arguments from `$VISUAL`, then `$EDITOR`, falling back to `vi`.
+/// Values like `code --wait` are split on whitespace.
+pub fn editor_command(visual: Option<&str>, editor: Option<&str>) -> (String, Vec<String>) {
+    let configured = [visual, editor].into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .unwrap_or("vi");
+    let mut parts = configured.split_whitespace().map(str::to_string);
+    let program = parts.next().unwrap_or_else(|| "vi".to_string());
+    (program, parts.collect())
+}
+
+/// Open `text` in the external editor and return the saved contents.
+/// Returns `Ok(None)` when the editor exits with an error, so the caller can keep the old text.
+pub fn edit_in_external_editor(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    events: &EventHandler,
+    text: &str,
+    extension: &str,
+) -> io::Result<Option<String>> {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    let path = std::env::temp_dir().join(format!("cria-{}-{}.{}", std::process::id(), nanos, extension));
+    std::fs::write(&path, text)?;
+
+    let visual = std::env::var("VISUAL").ok();
+    let editor = std::env::var("EDITOR").ok();
+    let (program, args) = editor_command(visual.as_deref(), editor.as_deref());
+    debug_log(&format!("Opening external editor: {} {:?} {}", program, args, path.display()));
+
+    events.pause();
+    let _ = disable_raw_mode();
+    let _ = stdout().execute(DisableBracketedPaste);
+    let _ = stdout().execute(LeaveAlternateScreen);
+
+    let status = Command::new(&program).args(&args).arg(&path).status();
+
+    // Always give the terminal back to the TUI, even if the editor failed to start
+    let _ = stdout().execute(EnterAlternateScreen);
+    let _ = stdout().execute(EnableBracketedPaste);
+    let _ = enable_raw_mode();
+    let _ = terminal.clear();
+    events.resume();
+
+    let result = match status {
+        Ok(status) if status.success() => std::fs::read_to_string(&path).map(Some),
+        Ok(status) => {
+            debug_log(&format!("External editor exited with {}", status));
+            Ok(None)
+        }
+        Err(e) => Err(io::Error::new(e.kind(), format!("could not run '{}': {}", program, e))),
+    };
+    let _ = std::fs::remove_file(&path);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_command_precedence() {
+        assert_eq!(editor_command(Some("nvim"), Some("nano")), ("nvim".to_string(), vec![]));
+        assert_eq!(editor_command(Some(" "), Some("code --wait")), ("code".to_string(), vec!["--wait".to_string()]));
+        assert_eq!(editor_command(None, None), ("vi".to_string(), vec![]));
+    }
+}
@@ -0,0 +1,285 @@
+// History and unsent drafts of the quick add, edit and comment inputs, and how often
+// each suggestion was used in them.
+// Kept in $XDG_STATE_HOME/cria/history.yaml (~/.local/state/cria/history.yaml), so
+// previous entries can be recalled after a restart.
+
+use crate::debug::debug_log;
+use crate::tui::app::suggestion_mode::SuggestionMode;
+use crate::tui::suggestions::SuggestionUsage;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// How many entries are kept per input
+const MAX_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryKind {
+    QuickAdd,
+    Edit,
+    Comment,
+}
+
+/// Direction of a history recall
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recall {
+    Older,
+    Newer,
+}
+
+impl Recall {
+    /// Ctrl+P / Ctrl+N. Up and Down are left to the inputs, which often use them for
+    /// suggestions or lists.
+    pub fn from_key(key: &KeyEvent) -> Option<Self> {
+        if !key.modifiers.contains(KeyModifiers::CONTROL) {
+            return None;
+        }
+        match key.code {
+            KeyCode::Char('p') => Some(Recall::Older),
+            KeyCode::Char('n') => Some(Recall::Newer),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Drafts {
+    pub quick_add: Option<String>,
+    /// Edit lines by task id
+    pub edit: HashMap<i64, String>,
+    /// Comments by task id
+    pub comment: HashMap<i64, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputHistory {
+    /// Oldest first
+    quick_add: Vec<String>,
+    edit: Vec<String>,
+    comment: Vec<String>,
+    drafts: Drafts,
+    usage: SuggestionUsage,
+    /// Where changes are saved; None keeps the history in memory only
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl InputHistory {
+    pub fn default_path() -> Option<PathBuf> {
+        match std::env::var("XDG_STATE_HOME") {
+            Ok(val) if !val.is_empty() => Some(PathBuf::from(val).join("cria/history.yaml")),
+            _ => {
+                let mut home = dirs::home_dir()?;
+                home.push(".local/state/cria/history.yaml");
+                Some(home)
+            }
+        }
+    }
+
+    /// Load the history from the default location. A missing or unreadable file
+    /// starts an empty history that is still saved there.
+    pub fn load() -> Self {
+        match Self::default_path() {
+            Some(path) => Self::load_from_path(path),
+            None => Self::default(),
+        }
+    }
+
+    pub fn load_from_path(path: PathBuf) -> Self {
+        let mut history: Self = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_yaml::from_str(&contents).ok())
+            .unwrap_or_default();
+        history.path = Some(path);
+        history
+    }
+
+    fn save(&self) {
+        let Some(ref path) = self.path else { return };
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let result = serde_yaml::to_string(self)
+            .map_err(|e| e.to_string())
+            .and_then(|yaml| fs::write(path, yaml).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            debug_log(&format!("Failed to save input history to {}: {}", path.display(), e));
+        }
+    }
+
+    pub fn entries(&self, kind: HistoryKind) -> &[String] {
+        match kind {
+            HistoryKind::QuickAdd => &self.quick_add,
+            HistoryKind::Edit => &self.edit,
+            HistoryKind::Comment => &self.comment,
+        }
+    }
+
+    /// Remember a submitted entry. Repeats move to the end instead of being stored twice.
+    pub fn push(&mut self, kind: HistoryKind, entry: &str) {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            return;
+        }
+        let entries = match kind {
+            HistoryKind::QuickAdd => &mut self.quick_add,
+            HistoryKind::Edit => &mut self.edit,
+            HistoryKind::Comment => &mut self.comment,
+        };
+        entries.retain(|e| e != entry);
+        entries.push(entry.to_string());
+        if entries.len() > MAX_ENTRIES {
+            let excess = entries.len() - MAX_ENTRIES;
+            entries.drain(..excess);
+        }
+        self.save();
+    }
+
+    pub fn usage(&self) -> &SuggestionUsage {
+        &self.usage
+    }
+
+    /// Count one use of each suggestion value at unix time `now`
+    pub fn record_usage(&mut self, uses: &[(SuggestionMode, String)], now: i64) {
+        if uses.is_empty() {
+            return;
+        }
+        for (mode, value) in uses {
+            self.usage.record(*mode, value, now);
+        }
+        self.save();
+    }
+
+    /// The unsent draft of an input; `task_id` is ignored for quick add
+    pub fn draft(&self, kind: HistoryKind, task_id: i64) -> Option<&str> {
+        match kind {
+            HistoryKind::QuickAdd => self.drafts.quick_add.as_deref(),
+            HistoryKind::Edit => self.drafts.edit.get(&task_id).map(String::as_str),
+            HistoryKind::Comment => self.drafts.comment.get(&task_id).map(String::as_str),
+        }
+    }
+
+    /// Keep `text` as the draft of an input, or drop the draft when it is blank
+    pub fn set_draft(&mut self, kind: HistoryKind, task_id: i64, text: &str) {
+        let draft = Some(text.to_string()).filter(|t| !t.trim().is_empty());
+        if self.draft(kind, task_id) == draft.as_deref() {
+            return;
+        }
+        match (kind, draft) {
+            (HistoryKind::QuickAdd, draft) => self.drafts.quick_add = draft,
+            (HistoryKind::Edit, Some(draft)) => { self.drafts.edit.insert(task_id, draft); }
+            (HistoryKind::Edit, None) => { self.drafts.edit.remove(&task_id); }
+            (HistoryKind::Comment, Some(draft)) => { self.drafts.comment.insert(task_id, draft); }
+            (HistoryKind::Comment, None) => { self.drafts.comment.remove(&task_id); }
+        }
+        self.save();
+    }
+
+    pub fn clear_draft(&mut self, kind: HistoryKind, task_id: i64) {
+        self.set_draft(kind, task_id, "");
+    }
+}
+
+/// Position of one input in its history while the user steps through it.
+/// The text typed before the first recall is the search prefix: only entries
+/// starting with it are recalled, and stepping past the newest brings it back.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryBrowser {
+    /// Index into the entries of the one being shown; None while editing
+    index: Option<usize>,
+    typed: String,
+}
+
+impl HistoryBrowser {
+    /// The text to show after stepping in `direction`, or None when there is nothing further
+    pub fn recall(&mut self, entries: &[String], current: &str, direction: Recall) -> Option<String> {
+        if self.index.is_none() {
+            if direction == Recall::Newer {
+                return None;
+            }
+            self.typed = current.to_string();
+        }
+        let matches = |i: &usize| entries[*i].starts_with(&self.typed) && entries[*i] != current;
+        match direction {
+            Recall::Older => {
+                let end = self.index.unwrap_or(entries.len());
+                let found = (0..end).rev().find(matches)?;
+                self.index = Some(found);
+                Some(entries[found].clone())
+            }
+            Recall::Newer => {
+                let start = self.index? + 1;
+                match (start..entries.len()).find(matches) {
+                    Some(found) => {
+                        self.index = Some(found);
+                        Some(entries[found].clone())
+                    }
+                    None => {
+                        self.index = None;
+                        Some(std::mem::take(&mut self.typed))
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn is_browsing(&self) -> bool {
+        self.index.is_some()
+    }
+
+    /// Stop browsing; the next recall searches with whatever is typed then
+    pub fn reset(&mut self) {
+        self.index = None;
+        self.typed.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recall_with_prefix_search() {
+        let mut history = InputHistory::default();
+        for entry in ["Buy milk *shopping", "Call Bob +work", "Buy eggs *shopping", "Call Bob +work"] {
+            history.push(HistoryKind::QuickAdd, entry);
+        }
+        let entries = history.entries(HistoryKind::QuickAdd).to_vec();
+        assert_eq!(entries, vec!["Buy milk *shopping", "Buy eggs *shopping", "Call Bob +work"]);
+
+        let mut browser = HistoryBrowser::default();
+        assert_eq!(browser.recall(&entries, "Buy", Recall::Newer), None);
+        assert_eq!(browser.recall(&entries, "Buy", Recall::Older).as_deref(), Some("Buy eggs *shopping"));
+        assert_eq!(browser.recall(&entries, "Buy eggs *shopping", Recall::Older).as_deref(), Some("Buy milk *shopping"));
+        assert_eq!(browser.recall(&entries, "Buy milk *shopping", Recall::Older), None);
+        assert_eq!(browser.recall(&entries, "Buy milk *shopping", Recall::Newer).as_deref(), Some("Buy eggs *shopping"));
+        assert_eq!(browser.recall(&entries, "Buy eggs *shopping", Recall::Newer).as_deref(), Some("Buy"));
+        assert!(!browser.is_browsing());
+
+        browser.reset();
+        assert_eq!(browser.recall(&entries, "", Recall::Older).as_deref(), Some("Call Bob +work"));
+    }
+
+    #[test]
+    fn test_drafts_persist() {
+        let path = std::env::temp_dir().join(format!("cria-history-test-{}.yaml", std::process::id()));
+        let mut history = InputHistory::load_from_path(path.clone());
+        history.set_draft(HistoryKind::QuickAdd, 0, "Half typed *label");
+        history.set_draft(HistoryKind::Comment, 42, "Looks good,\nbut");
+        history.push(HistoryKind::Edit, "Renamed task");
+
+        let reloaded = InputHistory::load_from_path(path.clone());
+        assert_eq!(reloaded.draft(HistoryKind::QuickAdd, 7), Some("Half typed *label"));
+        assert_eq!(reloaded.draft(HistoryKind::Comment, 42), Some("Looks good,\nbut"));
+        assert_eq!(reloaded.draft(HistoryKind::Comment, 1), None);
+        assert_eq!(reloaded.entries(HistoryKind::Edit), ["Renamed task".to_string()]);
+
+        history.clear_draft(HistoryKind::QuickAdd, 0);
+        assert_eq!(InputHistory::load_from_path(path.clone()).draft(HistoryKind::QuickAdd, 0), None);
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,490 @@
+// Line editing shared by every text input: quick add, edit, pickers, the form editor
+// and the modals' own inputs.
+// Text is edited by grapheme cluster, so `ö` typed as `o` + combining diaeresis or an
+// emoji with modifiers moves and deletes as one character. Cursors are byte offsets
+// that always sit on a grapheme boundary, so callers can keep slicing `&text[..cursor]`.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// How many killed texts are kept for yanking
+const KILL_RING_SIZE: usize = 16;
+
+/// What a key did to the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOutcome {
+    /// Not an editing key; the caller handles it
+    Ignored,
+    /// Only the cursor moved
+    Moved,
+    /// The text changed
+    Changed,
+}
+
+impl EditOutcome {
+    pub fn is_handled(self) -> bool {
+        self != EditOutcome::Ignored
+    }
+}
+
+/// Text removed by the kill commands (Ctrl+W/U/K, Alt+D, Alt+Backspace), yanked back
+/// with Ctrl+Y. Alt+Y right after a yank swaps in the next older kill.
+#[derive(Debug, Clone, Default)]
+pub struct KillRing {
+    /// Oldest first
+    entries: Vec<String>,
+    /// The previous edit was a kill, so the next one extends the same entry
+    appending: bool,
+    /// Byte range of the text inserted by the last yank and the entry it came from
+    last_yank: Option<(usize, usize, usize)>,
+}
+
+impl KillRing {
+    pub fn latest(&self) -> Option<&str> {
+        self.entries.last().map(String::as_str)
+    }
+
+    fn kill(&mut self, text: &str, before: bool) {
+        if text.is_empty() {
+            return;
+        }
+        match self.entries.last_mut() {
+            Some(last) if self.appending => {
+                if before {
+                    last.insert_str(0, text);
+                } else {
+                    last.push_str(text);
+                }
+            }
+            _ => {
+                self.entries.push(text.to_string());
+                if self.entries.len() > KILL_RING_SIZE {
+                    self.entries.remove(0);
+                }
+            }
+        }
+    }
+}
+
+/// Editing operations on a text and its cursor, borrowed from wherever the input keeps them
+pub struct LineEditor<'a> {
+    text: &'a mut String,
+    cursor: &'a mut usize,
+    kill_ring: &'a mut KillRing,
+    multiline: bool,
+}
+
+fn is_word(grapheme: &str) -> bool {
+    grapheme.chars().any(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn is_space(grapheme: &str) -> bool {
+    grapheme.chars().all(char::is_whitespace)
+}
+
+/// Snap `cursor` to the nearest grapheme boundary at or before it
+pub fn clamp_cursor(text: &str, cursor: usize) -> usize {
+    let cursor = cursor.min(text.len());
+    if cursor == text.len() {
+        return cursor;
+    }
+    text.grapheme_indices(true)
+        .map(|(i, _)| i)
+        .take_while(|&i| i <= cursor)
+        .last()
+        .unwrap_or(0)
+}
+
+/// Display width of `text`, counting wide characters as two columns
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Row and display column of `cursor`
+pub fn cursor_row_col(text: &str, cursor: usize) -> (usize, usize) {
+    let before = &text[..clamp_cursor(text, cursor)];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (before.matches('\n').count(), display_width(&before[line_start..]))
+}
+
+/// Scroll offset and screen position of the cursor for an input drawn in `area`
+/// (the inner area, without borders)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputViewport {
+    /// `(rows, columns)`, as taken by `Paragraph::scroll`
+    pub scroll: (u16, u16),
+    pub cursor_x: u16,
+    pub cursor_y: u16,
+}
+
+/// Scroll so the cursor stays visible: long lines scroll horizontally, and multi-line
+/// text vertically once it is taller than `area`
+pub fn input_viewport(text: &str, cursor: usize, area: Rect) -> InputViewport {
+    let (row, col) = cursor_row_col(text, cursor);
+    let (row, col) = (row as u16, col as u16);
+    let scroll_y = row.saturating_sub(area.height.saturating_sub(1));
+    let scroll_x = col.saturating_sub(area.width.saturating_sub(1));
+    InputViewport {
+        scroll: (scroll_y, scroll_x),
+        cursor_x: area.x + col - scroll_x,
+        cursor_y: area.y + row - scroll_y,
+    }
+}
+
+impl<'a> LineEditor<'a> {
+    pub fn new(text: &'a mut String, cursor: &'a mut usize, kill_ring: &'a mut KillRing) -> Self {
+        *cursor = clamp_cursor(text, *cursor);
+        Self { text, cursor, kill_ring, multiline: false }
+    }
+
+    /// Alt+Enter / Ctrl+J insert line breaks and Up/Down move between lines
+    pub fn multiline(mut self) -> Self {
+        self.multiline = true;
+        self
+    }
+
+    /// Apply an editing key. Readline bindings: Ctrl+A/E line start/end, Ctrl+B/F and
+    /// Alt+B/F by character and word, Ctrl+W/U/K and Alt+D/Backspace kill, Ctrl+Y yank.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> EditOutcome {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        match key.code {
+            // Ctrl+Alt is AltGr on some keyboards and types a character
+            KeyCode::Char(c) if ctrl == alt => self.insert_char(c),
+            KeyCode::Char(c) if ctrl => match c {
+                'a' => self.move_home(),
+                'e' => self.move_end(),
+                'b' => self.move_left(),
+                'f' => self.move_right(),
+                'd' => self.delete(),
+                'h' => self.backspace(),
+                'w' => self.kill_word_before_whitespace(),
+                'u' => self.kill_to_line_start(),
+                'k' => self.kill_to_line_end(),
+                'y' => self.yank(),
+                'j' if self.multiline => self.insert_char('\n'),
+                _ => EditOutcome::Ignored,
+            },
+            KeyCode::Char(c) => match c {
+                'b' => self.move_word_left(),
+                'f' => self.move_word_right(),
+                'd' => self.kill_word_after(),
+                'y' => self.yank_pop(),
+                _ => EditOutcome::Ignored,
+            },
+            KeyCode::Backspace if ctrl || alt => self.kill_word_before(),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete if ctrl || alt => self.kill_word_after(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left if ctrl || alt => self.move_word_left(),
+            KeyCode::Left => self.move_left(),
+            KeyCode::Right if ctrl || alt => self.move_word_right(),
+            KeyCode::Right => self.move_right(),
+            KeyCode::Home => self.move_home(),
+            KeyCode::End => self.move_end(),
+            KeyCode::Up if self.multiline => self.move_up(),
+            KeyCode::Down if self.multiline => self.move_down(),
+            KeyCode::Enter if self.multiline && alt => self.insert_char('\n'),
+            _ => EditOutcome::Ignored,
+        }
+    }
+
+    // Boundaries
+
+    fn prev_boundary(&self, pos: usize) -> usize {
+        self.text[..pos].grapheme_indices(true).next_back().map_or(0, |(i, _)| i)
+    }
+
+    fn next_boundary(&self, pos: usize) -> usize {
+        self.text[pos..].graphemes(true).next().map_or(pos, |g| pos + g.len())
+    }
+
+    fn line_start(&self, pos: usize) -> usize {
+        self.text[..pos].rfind('\n').map_or(0, |i| i + 1)
+    }
+
+    fn line_end(&self, pos: usize) -> usize {
+        self.text[pos..].find('\n').map_or(self.text.len(), |i| pos + i)
+    }
+
+    /// Start of the word before `pos`, skipping separators first
+    fn word_start_before(&self, pos: usize, in_word: fn(&str) -> bool) -> usize {
+        let mut graphemes = self.text[..pos].grapheme_indices(true).rev().peekable();
+        while graphemes.next_if(|(_, g)| !in_word(g)).is_some() {}
+        let mut start = graphemes.peek().map_or(0, |(i, g)| i + g.len());
+        while let Some((i, _)) = graphemes.next_if(|(_, g)| in_word(g)) {
+            start = i;
+        }
+        start
+    }
+
+    /// End of the word after `pos`, skipping separators first
+    fn word_end_after(&self, pos: usize) -> usize {
+        let mut graphemes = self.text[pos..].grapheme_indices(true).peekable();
+        while graphemes.next_if(|(_, g)| !is_word(g)).is_some() {}
+        while graphemes.next_if(|(_, g)| is_word(g)).is_some() {}
+        graphemes.peek().map_or(self.text.len(), |(i, _)| pos + i)
+    }
+
+    /// Byte offset in the line starting at `start` closest to display column `column`
+    fn offset_at_column(&self, start: usize, column: usize) -> usize {
+        let end = self.line_end(start);
+        let mut width = 0;
+        for (i, g) in self.text[start..end].grapheme_indices(true) {
+            width += display_width(g);
+            if width > column {
+                return start + i;
+            }
+        }
+        end
+    }
+
+    // Edits
+
+    fn set_cursor(&mut self, pos: usize) -> EditOutcome {
+        self.kill_ring.appending = false;
+        self.kill_ring.last_yank = None;
+        // Moving at either end of the text still counts as handled
+        *self.cursor = pos;
+        EditOutcome::Moved
+    }
+
+    fn replace(&mut self, start: usize, end: usize, with: &str) -> EditOutcome {
+        self.kill_ring.appending = false;
+        self.kill_ring.last_yank = None;
+        self.text.replace_range(start..end, with);
+        *self.cursor = start + with.len();
+        EditOutcome::Changed
+    }
+
+    /// Remove `start..end` into the kill ring
+    fn kill(&mut self, start: usize, end: usize) -> EditOutcome {
+        if start == end {
+            return EditOutcome::Moved;
+        }
+        let killed = self.text[start..end].to_string();
+        self.kill_ring.kill(&killed, end <= *self.cursor);
+        self.replace(start, end, "");
+        self.kill_ring.appending = true;
+        EditOutcome::Changed
+    }
+
+    pub fn insert_char(&mut self, c: char) -> EditOutcome {
+        let mut buf = [0; 4];
+        self.insert_str(c.encode_utf8(&mut buf))
+    }
+
+    pub fn insert_str(&mut self, s: &str) -> EditOutcome {
+        let s = if self.multiline { s.to_string() } else { s.replace(['\n', '\r'], " ") };
+        let at = *self.cursor;
+        self.replace(at, at, &s);
+        // Text typed before a combining mark joins its grapheme; keep the cursor after both
+        let end = clamp_cursor(self.text, *self.cursor);
+        if end != *self.cursor {
+            *self.cursor = self.next_boundary(end);
+        }
+        EditOutcome::Changed
+    }
+
+    /// Replace the whole text, with the cursor at the end
+    pub fn set_text(&mut self, text: &str) -> EditOutcome {
+        let len = self.text.len();
+        self.replace(0, len, text)
+    }
+
+    pub fn backspace(&mut self) -> EditOutcome {
+        if *self.cursor == 0 {
+            return EditOutcome::Moved;
+        }
+        let start = self.prev_boundary(*self.cursor);
+        let end = *self.cursor;
+        self.replace(start, end, "")
+    }
+
+    pub fn delete(&mut self) -> EditOutcome {
+        let start = *self.cursor;
+        let end = self.next_boundary(start);
+        if start == end {
+            return EditOutcome::Moved;
+        }
+        self.replace(start, end, "")
+    }
+
+    pub fn move_left(&mut self) -> EditOutcome {
+        let pos = self.prev_boundary(*self.cursor);
+        self.set_cursor(pos)
+    }
+
+    pub fn move_right(&mut self) -> EditOutcome {
+        let pos = self.next_boundary(*self.cursor);
+        self.set_cursor(pos)
+    }
+
+    pub fn move_home(&mut self) -> EditOutcome {
+        let pos = self.line_start(*self.cursor);
+        self.set_cursor(pos)
+    }
+
+    pub fn move_end(&mut self) -> EditOutcome {
+        let pos = self.line_end(*self.cursor);
+        self.set_cursor(pos)
+    }
+
+    pub fn move_word_left(&mut self) -> EditOutcome {
+        let pos = self.word_start_before(*self.cursor, is_word);
+        self.set_cursor(pos)
+    }
+
+    pub fn move_word_right(&mut self) -> EditOutcome {
+        let pos = self.word_end_after(*self.cursor);
+        self.set_cursor(pos)
+    }
+
+    /// Move to the line above; ignored on the first line so Up can reach the caller
+    pub fn move_up(&mut self) -> EditOutcome {
+        let start = self.line_start(*self.cursor);
+        if start == 0 {
+            return EditOutcome::Ignored;
+        }
+        let column = display_width(&self.text[start..*self.cursor]);
+        let pos = self.offset_at_column(self.line_start(start - 1), column);
+        self.set_cursor(pos)
+    }
+
+    /// Move to the line below; ignored on the last line so Down can reach the caller
+    pub fn move_down(&mut self) -> EditOutcome {
+        let end = self.line_end(*self.cursor);
+        if end == self.text.len() {
+            return EditOutcome::Ignored;
+        }
+        let column = display_width(&self.text[self.line_start(*self.cursor)..*self.cursor]);
+        let pos = self.offset_at_column(end + 1, column);
+        self.set_cursor(pos)
+    }
+
+    /// Alt+Backspace: kill back to the start of the word
+    pub fn kill_word_before(&mut self) -> EditOutcome {
+        let start = self.word_start_before(*self.cursor, is_word);
+        self.kill(start, *self.cursor)
+    }
+
+    /// Ctrl+W: kill back to the previous whitespace
+    pub fn kill_word_before_whitespace(&mut self) -> EditOutcome {
+        let start = self.word_start_before(*self.cursor, |g| !is_space(g));
+        self.kill(start, *self.cursor)
+    }
+
+    pub fn kill_word_after(&mut self) -> EditOutcome {
+        let end = self.word_end_after(*self.cursor);
+        self.kill(*self.cursor, end)
+    }
+
+    pub fn kill_to_line_start(&mut self) -> EditOutcome {
+        let start = self.line_start(*self.cursor);
+        self.kill(start, *self.cursor)
+    }
+
+    pub fn kill_to_line_end(&mut self) -> EditOutcome {
+        let end = self.line_end(*self.cursor);
+        self.kill(*self.cursor, end)
+    }
+
+    pub fn yank(&mut self) -> EditOutcome {
+        let Some(index) = self.kill_ring.entries.len().checked_sub(1) else {
+            return EditOutcome::Moved;
+        };
+        self.yank_entry(*self.cursor, *self.cursor, index)
+    }
+
+    /// Replace the text of the previous yank with the next older kill; without one, yank
+    pub fn yank_pop(&mut self) -> EditOutcome {
+        match self.kill_ring.last_yank {
+            Some((start, end, index)) if end <= self.text.len() && end == *self.cursor => {
+                let older = index.checked_sub(1).unwrap_or(self.kill_ring.entries.len() - 1);
+                self.yank_entry(start, end, older)
+            }
+            _ => self.yank(),
+        }
+    }
+
+    fn yank_entry(&mut self, start: usize, end: usize, index: usize) -> EditOutcome {
+        let entry = self.kill_ring.entries[index].clone();
+        let entry = if self.multiline { entry } else { entry.replace(['\n', '\r'], " ") };
+        self.replace(start, end, &entry);
+        self.kill_ring.last_yank = Some((start, start + entry.len(), index));
+        EditOutcome::Changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_graphemes_edit_as_one_character() {
+        let (mut text, mut cursor, mut ring) = (String::new(), 0, KillRing::default());
+        let mut editor = LineEditor::new(&mut text, &mut cursor, &mut ring);
+        for c in "Café o\u{308}k 👍🏽!".chars() {
+            editor.insert_char(c);
+        }
+        editor.move_left();
+        editor.backspace();
+        assert_eq!(text, "Café o\u{308}k !");
+        let mut editor = LineEditor::new(&mut text, &mut cursor, &mut ring);
+        editor.move_word_left();
+        editor.move_right();
+        editor.backspace();
+        assert_eq!(text, "Café k !");
+        assert_eq!(clamp_cursor("ö", 1), 0);
+    }
+
+    #[test]
+    fn test_readline_motions_kill_and_yank() {
+        let (mut text, mut cursor, mut ring) = ("buy milk tomorrow".to_string(), 17, KillRing::default());
+        let mut editor = LineEditor::new(&mut text, &mut cursor, &mut ring);
+        assert_eq!(editor.handle_key(&key(KeyCode::Char('w'), KeyModifiers::CONTROL)), EditOutcome::Changed);
+        editor.handle_key(&key(KeyCode::Char('w'), KeyModifiers::CONTROL));
+        assert_eq!(text, "buy ");
+        assert_eq!(ring.latest(), Some("milk tomorrow"));
+
+        let mut editor = LineEditor::new(&mut text, &mut cursor, &mut ring);
+        editor.handle_key(&key(KeyCode::Char('a'), KeyModifiers::CONTROL));
+        editor.handle_key(&key(KeyCode::Char('f'), KeyModifiers::ALT));
+        editor.handle_key(&key(KeyCode::Char('y'), KeyModifiers::CONTROL));
+        assert_eq!(text, "buymilk tomorrow ");
+        let mut editor = LineEditor::new(&mut text, &mut cursor, &mut ring);
+        editor.handle_key(&key(KeyCode::Char('u'), KeyModifiers::CONTROL));
+        editor.handle_key(&key(KeyCode::Char('k'), KeyModifiers::CONTROL));
+        assert_eq!(text, "");
+        // Ctrl+U and Ctrl+K in a row made one kill; Alt+Y reaches the one before
+        let mut editor = LineEditor::new(&mut text, &mut cursor, &mut ring);
+        editor.handle_key(&key(KeyCode::Char('y'), KeyModifiers::CONTROL));
+        assert_eq!(editor.handle_key(&key(KeyCode::Char('q'), KeyModifiers::CONTROL)), EditOutcome::Ignored);
+        assert_eq!(text, "buymilk tomorrow ");
+        let mut editor = LineEditor::new(&mut text, &mut cursor, &mut ring);
+        editor.handle_key(&key(KeyCode::Char('y'), KeyModifiers::ALT));
+        assert_eq!(text, "milk tomorrow");
+    }
+
+    #[test]
+    fn test_multiline_editing_and_viewport() {
+        let (mut text, mut cursor, mut ring) = ("first line\nsecond".to_string(), 3, KillRing::default());
+        let mut editor = LineEditor::new(&mut text, &mut cursor, &mut ring).multiline();
+        assert_eq!(editor.handle_key(&key(KeyCode::Up, KeyModifiers::NONE)), EditOutcome::Ignored);
+        editor.handle_key(&key(KeyCode::Down, KeyModifiers::NONE));
+        editor.handle_key(&key(KeyCode::Enter, KeyModifiers::ALT));
+        assert_eq!(text, "first line\nsec\nond");
+        assert_eq!(cursor_row_col(&text, cursor), (2, 0));
+
+        let single = "0123456789";
+        let viewport = input_viewport(single, single.len(), Rect::new(5, 1, 4, 1));
+        assert_eq!(viewport, InputViewport { scroll: (0, 7), cursor_x: 8, cursor_y: 1 });
+        assert_eq!(cursor_row_col("日本", 6), (0, 4));
+    }
+}
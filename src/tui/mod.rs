@@ -1,8 +1,13 @@
 pub mod app;
 pub mod events;
+pub mod external_editor;
+pub mod line_editor;
+pub mod input_history;
+pub mod suggestions;
 pub mod ui;
 pub mod handlers;
 pub mod modals;
 pub mod pickers;
 pub mod confirmation;
+pub mod date_picker;
 pub mod utils;
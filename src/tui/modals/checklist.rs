@@ -0,0 +1,229 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::checklist::{checklist_items, toggle_checklist_item, ChecklistItem};
+use crate::tui::app::state::App;
+use crate::vikunja_client::VikunjaClient;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChecklistAction {
+    None,
+    Close,
+    Toggle(usize),
+}
+
+/// Modal for checking off the checklist items in a task's description
+pub struct ChecklistModal {
+    pub task_id: i64,
+    pub task_title: String,
+    pub items: Vec<ChecklistItem>,
+    pub selected_index: usize,
+    pub status_message: String,
+}
+
+impl ChecklistModal {
+    pub fn new(task_id: i64, task_title: String, items: Vec<ChecklistItem>) -> Self {
+        Self {
+            task_id,
+            task_title,
+            items,
+            selected_index: 0,
+            status_message: String::new(),
+        }
+    }
+
+    pub fn set_items(&mut self, items: Vec<ChecklistItem>) {
+        self.items = items;
+        if self.selected_index >= self.items.len() {
+            self.selected_index = self.items.len().saturating_sub(1);
+        }
+    }
+
+    fn progress(&self) -> (usize, usize) {
+        (self.items.iter().filter(|i| i.checked).count(), self.items.len())
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> ChecklistAction {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => ChecklistAction::Close,
+            KeyCode::Up | KeyCode::Char('k') => {
+                if !self.items.is_empty() {
+                    self.selected_index = (self.selected_index + self.items.len() - 1) % self.items.len();
+                }
+                ChecklistAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if !self.items.is_empty() {
+                    self.selected_index = (self.selected_index + 1) % self.items.len();
+                }
+                ChecklistAction::None
+            }
+            KeyCode::Char(' ') | KeyCode::Char('x') | KeyCode::Enter if !self.items.is_empty() => {
+                ChecklistAction::Toggle(self.selected_index)
+            }
+            _ => ChecklistAction::None,
+        }
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let width = (area.width * 70) / 100;
+        let height = ((self.items.len() as u16) + 6).min((area.height * 80) / 100).max(8);
+        let x = (area.width - width) / 2 + area.x;
+        let y = (area.height.saturating_sub(height)) / 2 + area.y;
+        let modal_area = Rect::new(x, y, width, height.min(area.height));
+        f.render_widget(Clear, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),     // Items
+                Constraint::Length(1),  // Status
+                Constraint::Length(1),  // Help
+            ])
+            .split(modal_area);
+
+        let visible = chunks[0].height.saturating_sub(2) as usize;
+        let offset = if visible > 0 && self.selected_index >= visible { self.selected_index + 1 - visible } else { 0 };
+        let mut lines = Vec::new();
+        for (i, item) in self.items.iter().enumerate().skip(offset).take(visible.max(1)) {
+            let is_selected = i == self.selected_index;
+            let (box_text, box_color) = if item.checked { ("☑ ", Color::Green) } else { ("☐ ", Color::Cyan) };
+            let mut text_style = if item.checked { Style::default().fg(Color::DarkGray) } else { Style::default().fg(Color::White) };
+            if is_selected {
+                text_style = text_style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+            }
+            lines.push(Line::from(vec![
+                Span::styled(if is_selected { "▶ " } else { "  " }, Style::default().fg(Color::Yellow)),
+                Span::styled(box_text, Style::default().fg(box_color)),
+                Span::styled(item.text.clone(), text_style),
+            ]));
+        }
+
+        let (done, total) = self.progress();
+        let block = Block::default()
+            .title(format!(" Checklist: {} ({}/{}) ", self.task_title, done, total))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if done == total { Color::Green } else { Color::Cyan }));
+        f.render_widget(Paragraph::new(lines).block(block), chunks[0]);
+
+        let status = Paragraph::new(self.status_message.clone())
+            .style(Style::default().fg(Color::Cyan).bg(Color::Black));
+        f.render_widget(status, chunks[1]);
+        let help = Paragraph::new("↑↓/jk: Select | Space/x/Enter: Toggle | Esc: Close")
+            .style(Style::default().fg(Color::Gray).bg(Color::Black))
+            .alignment(Alignment::Center);
+        f.render_widget(help, chunks[2]);
+    }
+}
+
+/// Toggle an item on the server. The description is re-read first so the item is
+/// toggled in the current text, not in a copy someone else has since changed.
+async fn toggle_item_on_server(
+    client: &VikunjaClient,
+    task_id: i64,
+    index: usize,
+    expected: &ChecklistItem,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut task = client.get_task(task_id as u64).await?;
+    let description = task.description.clone().unwrap_or_default();
+    if checklist_items(&description).get(index).map(|item| &item.text) != Some(&expected.text) {
+        return Err("The checklist was changed elsewhere; reloaded it".into());
+    }
+    let updated = toggle_checklist_item(&description, index).ok_or("Checklist item not found")?;
+    task.description = Some(updated.clone());
+    client.update_task(&task).await?;
+    Ok(updated)
+}
+
+pub async fn handle_checklist_modal(
+    app: &mut App,
+    key: &KeyEvent,
+    api_client: &Arc<Mutex<VikunjaClient>>,
+) {
+    let (action, task_id) = match app.checklist_modal {
+        Some(ref mut modal) => (modal.handle_key(key), modal.task_id),
+        None => return,
+    };
+    let index = match action {
+        ChecklistAction::None => return,
+        ChecklistAction::Close => {
+            app.hide_checklist_modal();
+            return;
+        }
+        ChecklistAction::Toggle(index) => index,
+    };
+    let Some(expected) = app.checklist_modal.as_ref().and_then(|m| m.items.get(index).cloned()) else {
+        return;
+    };
+
+    let previous_task = app.all_tasks.iter().find(|t| t.id == task_id).cloned();
+    let client = api_client.lock().await;
+    let result = toggle_item_on_server(&client, task_id, index, &expected).await;
+    // On a mismatch show what the server has now
+    let server_description = match result {
+        Err(_) => client.get_task(task_id as u64).await.ok().and_then(|t| t.description),
+        Ok(_) => None,
+    };
+    drop(client);
+
+    match result {
+        Ok(description) => {
+            if let Some(previous_task) = previous_task {
+                app.add_task_edit_to_undo_stack(task_id, previous_task);
+            }
+            app.set_task_description(task_id, &description);
+            if let Some(ref mut modal) = app.checklist_modal {
+                let state = if expected.checked { "Unchecked" } else { "Checked" };
+                modal.status_message = format!("{}: {}", state, expected.text);
+                modal.set_items(checklist_items(&description));
+            }
+        }
+        Err(e) => {
+            app.add_debug_message(format!("Checklist toggle failed: {}", e));
+            if let Some(description) = server_description {
+                app.set_task_description(task_id, &description);
+                if let Some(ref mut modal) = app.checklist_modal {
+                    modal.set_items(checklist_items(&description));
+                }
+            }
+            if let Some(ref mut modal) = app.checklist_modal {
+                modal.status_message = format!("Failed: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_navigation_and_toggle() {
+        let items = vec![
+            ChecklistItem { text: "Tests pass".to_string(), checked: true },
+            ChecklistItem { text: "Docs updated".to_string(), checked: false },
+        ];
+        let mut modal = ChecklistModal::new(7, "Release".to_string(), items);
+        assert_eq!(modal.handle_key(&key(KeyCode::Char('j'))), ChecklistAction::None);
+        assert_eq!(modal.handle_key(&key(KeyCode::Char(' '))), ChecklistAction::Toggle(1));
+        modal.handle_key(&key(KeyCode::Down));
+        assert_eq!(modal.selected_index, 0);
+        assert_eq!(modal.handle_key(&key(KeyCode::Esc)), ChecklistAction::Close);
+
+        modal.selected_index = 1;
+        modal.set_items(vec![ChecklistItem { text: "Only".to_string(), checked: false }]);
+        assert_eq!(modal.selected_index, 0);
+    }
+}
@@ -5,9 +5,16 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::html::looks_like_html;
+use crate::markdown::{html_to_markdown, markdown_to_html};
 use crate::vikunja::models::{Comment, Attachment};
 use crate::tui::ui::attachment_viewer::AttachmentViewer;
+use crate::tui::ui::rich_text::render_rich_text;
+use crate::tui::line_editor::{input_viewport, EditOutcome, KillRing, LineEditor};
+use crate::tui::input_history::{HistoryBrowser, Recall};
+use crate::tui::app::suggestion_mode::SuggestionMode;
+use crate::tui::suggestions::{complete, rank, suggestion_context, SuggestionContext, SuggestionUsage};
 
 /// Enhanced modal for viewing and adding task comments with inline image previews
 pub struct CommentsModal {
@@ -15,11 +22,34 @@ pub struct CommentsModal {
     pub attachments: Vec<Attachment>,
     pub input: String,
     pub cursor_position: usize,
+    kill_ring: KillRing,
+    history: HistoryBrowser,
     pub task_id: i64,
     pub scroll_offset: usize,
     pub selected_comment: usize,
     pub view_mode: CommentViewMode,
     pub attachment_viewer: Option<AttachmentViewer>,
+    /// The task's URLs in URL modal order, for numbering link markers
+    pub links: Vec<String>,
+    /// Only the author may edit or delete a comment; unknown means the server decides
+    current_user_id: Option<i64>,
+    /// Usernames offered after `@`, ranked by how often they were used
+    users: Vec<String>,
+    usage: SuggestionUsage,
+    mention_suggestions: Vec<String>,
+    /// The comment being edited in the input box, if any
+    editing: Option<EditingComment>,
+    /// Comment awaiting confirmation before it is deleted
+    pending_delete: Option<i64>,
+    status_message: Option<String>,
+}
+
+/// An existing comment loaded into the input box. The new-comment draft is set
+/// aside meanwhile and comes back once the edit is saved or cancelled.
+struct EditingComment {
+    comment_id: i64,
+    previous_text: String,
+    draft: String,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -37,6 +67,10 @@ pub enum CommentsModalAction {
     Submit(String),
     ToggleMode,
     LoadAttachments(u64), // task_id for loading attachments
+    Edit { comment_id: i64, previous_text: String, text: String },
+    Delete { comment_id: i64, text: String },
+    /// Open the comment box in $VISUAL/$EDITOR
+    ExternalEditor,
 }
 
 impl CommentsModal {
@@ -46,11 +80,21 @@ impl CommentsModal {
             attachments: Vec::new(),
             input: String::new(),
             cursor_position: 0,
+            kill_ring: KillRing::default(),
+            history: HistoryBrowser::default(),
             task_id,
             scroll_offset: 0,
             selected_comment: 0,
             view_mode: CommentViewMode::List,
             attachment_viewer: None,
+            links: Vec::new(),
+            current_user_id: None,
+            users: Vec::new(),
+            usage: SuggestionUsage::default(),
+            mention_suggestions: Vec::new(),
+            editing: None,
+            pending_delete: None,
+            status_message: None,
         }
     }
 
@@ -62,6 +106,44 @@ impl CommentsModal {
         self
     }
 
+    pub fn with_links(mut self, links: Vec<String>) -> Self {
+        self.links = links;
+        self
+    }
+
+    /// Start with `input` in the comment box, e.g. a draft left when the modal was closed
+    pub fn with_input(mut self, input: String) -> Self {
+        self.cursor_position = input.len();
+        self.input = input;
+        self
+    }
+
+    pub fn with_current_user(mut self, user_id: Option<i64>) -> Self {
+        self.current_user_id = user_id;
+        self
+    }
+
+    /// Usernames to complete after `@`; comment authors are added to them
+    pub fn with_users(mut self, mut users: Vec<String>, usage: SuggestionUsage) -> Self {
+        users.extend(self.comments.iter().filter_map(|c| c.author.as_ref()).map(|u| u.username.clone()));
+        users.sort();
+        users.dedup();
+        self.users = users;
+        self.usage = usage;
+        self
+    }
+
+    /// Replace the comment box with an earlier comment (Ctrl+P/N)
+    pub fn recall_history(&mut self, entries: &[String], direction: Recall) {
+        if self.view_mode == CommentViewMode::AttachmentPreview {
+            return;
+        }
+        if let Some(text) = self.history.recall(entries, &self.input, direction) {
+            self.cursor_position = text.len();
+            self.input = text;
+        }
+    }
+
     pub fn draw(&self, f: &mut Frame, area: Rect) {
         // Safe area check
         if area.width < 40 || area.height < 10 {
@@ -89,7 +171,7 @@ impl CommentsModal {
             .constraints([
                 Constraint::Length(3),    // Header
                 Constraint::Min(8),       // Comments list
-                Constraint::Length(3),    // Input
+                Constraint::Length(self.input_height()), // Input
                 Constraint::Length(2),    // Help
             ])
             .split(area);
@@ -136,7 +218,7 @@ impl CommentsModal {
             .constraints([
                 Constraint::Length(3),    // Header
                 Constraint::Min(5),       // Selected comment detail
-                Constraint::Length(3),    // Input
+                Constraint::Length(self.input_height()), // Input
                 Constraint::Length(2),    // Help
             ])
             .split(chunks[0]);
@@ -173,7 +255,7 @@ impl CommentsModal {
             .constraints([
                 Constraint::Length(3),    // Header
                 Constraint::Min(10),      // Attachment viewer
-                Constraint::Length(3),    // Input
+                Constraint::Length(self.input_height()), // Input
                 Constraint::Length(2),    // Help
             ])
             .split(area);
@@ -246,19 +328,20 @@ impl CommentsModal {
 
             // Comment content
             let text = comment.comment.clone().unwrap_or_default();
-            let content_lines = text.lines().collect::<Vec<&str>>();
-            
-            for (line_idx, line) in content_lines.iter().enumerate() {
-                let content_style = if is_selected {
-                    Style::default().fg(Color::White).bg(Color::DarkGray)
-                } else {
-                    Style::default().fg(Color::White)
-                };
+            let content_style = if is_selected {
+                Style::default().fg(Color::White).bg(Color::DarkGray)
+            } else {
+                Style::default().fg(Color::White)
+            };
 
+            for (line_idx, line) in render_rich_text(&text, &self.links).into_iter().enumerate() {
                 let prefix = if line_idx == 0 { "💬 " } else { "   " };
-                lines.push(Line::from(vec![
-                    Span::styled(format!("{}{}", prefix, line), content_style)
-                ]));
+                let mut spans = vec![Span::styled(prefix, content_style)];
+                spans.extend(line.spans.into_iter().map(|span| {
+                    let style = content_style.patch(span.style);
+                    span.style(style)
+                }));
+                lines.push(Line::from(spans));
             }
 
             // Add spacing between comments
@@ -324,9 +407,7 @@ impl CommentsModal {
 
         // Comment content with proper formatting
         let text = comment.comment.clone().unwrap_or_default();
-        for line in text.lines() {
-            lines.push(Line::from(line));
-        }
+        lines.extend(render_rich_text(&text, &self.links));
 
         let detail_block = Block::default()
             .borders(Borders::ALL)
@@ -387,32 +468,54 @@ impl CommentsModal {
         f.render_widget(metadata_para, area);
     }
 
+    /// Height of the input box: it grows with the comment, up to five lines
+    fn input_height(&self) -> u16 {
+        self.input.split('\n').count().clamp(1, 5) as u16 + 2
+    }
+
     fn draw_input_section(&self, f: &mut Frame, area: Rect) {
+        let mut title = if self.editing.is_some() {
+            "✏️ Editing Comment (Enter: Save | Esc: Cancel)".to_string()
+        } else {
+            "💭 New Comment".to_string()
+        };
+        if !self.mention_suggestions.is_empty() {
+            let names: Vec<String> = self.mention_suggestions.iter().take(5).map(|u| format!("@{}", u)).collect();
+            title.push_str(&format!(" - Tab: {}", names.join(" ")));
+        }
         let input_block = Block::default()
-            .title("💭 New Comment")
+            .title(title)
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::Green));
+            .style(Style::default().fg(if self.editing.is_some() { Color::Yellow } else { Color::Green }));
+        let inner = input_block.inner(area);
+        let viewport = input_viewport(&self.input, self.cursor_position, inner);
         
         let input_para = Paragraph::new(self.input.as_str())
             .block(input_block)
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(Color::White))
+            .scroll(viewport.scroll);
         
         f.render_widget(input_para, area);
         
         // Set cursor
-        let cursor_x = area.x + 1 + self.cursor_position as u16;
-        let cursor_y = area.y + 1;
-        f.set_cursor(cursor_x, cursor_y);
+        f.set_cursor(viewport.cursor_x, viewport.cursor_y);
     }
 
     fn draw_help_section(&self, f: &mut Frame, area: Rect) {
         let help_text = match self.view_mode {
-            CommentViewMode::List => "Tab: Detail | A: Attachments | ↑↓: Select | Enter: Submit | Esc: Close",
-            CommentViewMode::Detail => "Tab: List | A: Attachments | ↑↓: Select | Enter: Submit | Esc: Close",
+            CommentViewMode::List => "Tab: Detail | A: Attachments | ↑↓: Select | Enter: Submit | Alt+Enter: New line | ^P/^N: History | Esc: Close",
+            CommentViewMode::Detail => "Tab: List | A: Attachments | ↑↓: Select | Enter: Submit | Alt+Enter: New line | ^P/^N: History | Esc: Close",
             CommentViewMode::AttachmentPreview => "Tab: List | ↑↓: Select | D: Download | R: Remove | Esc: Close",
         };
+        let second_line = match &self.status_message {
+            Some(message) => Line::from(Span::styled(message.clone(), Style::default().fg(Color::Yellow))),
+            None if self.view_mode != CommentViewMode::AttachmentPreview => Line::from(
+                "^R: Reply | ^O: Edit | ^X: Delete | ^G: External editor | @: Mention (Tab completes)",
+            ),
+            None => Line::from(""),
+        };
 
-        let help = Paragraph::new(help_text)
+        let help = Paragraph::new(vec![Line::from(help_text), second_line])
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Center);
         
@@ -420,22 +523,60 @@ impl CommentsModal {
     }
 
     pub fn handle_key(&mut self, key: &KeyEvent) -> CommentsModalAction {
-        match key.code {
-            KeyCode::Char(c) if self.view_mode != CommentViewMode::AttachmentPreview => {
-                self.input.insert(self.cursor_position, c);
-                self.cursor_position += 1;
-                CommentsModalAction::None
-            }
-            KeyCode::Backspace if self.view_mode != CommentViewMode::AttachmentPreview => {
-                if self.cursor_position > 0 && self.cursor_position <= self.input.len() {
-                    self.cursor_position -= 1;
-                    self.input.remove(self.cursor_position);
+        self.status_message = None;
+        if let Some(comment_id) = self.pending_delete.take() {
+            return self.confirm_delete(comment_id, key);
+        }
+        if self.view_mode != CommentViewMode::AttachmentPreview {
+            if key.modifiers.contains(KeyModifiers::CONTROL) {
+                match key.code {
+                    KeyCode::Char('r') => {
+                        self.reply_to_selected();
+                        return CommentsModalAction::None;
+                    }
+                    KeyCode::Char('o') => {
+                        self.edit_selected();
+                        return CommentsModalAction::None;
+                    }
+                    KeyCode::Char('x') => {
+                        self.request_delete();
+                        return CommentsModalAction::None;
+                    }
+                    // Not Ctrl+E, which the line editor uses for end of line
+                    KeyCode::Char('g') => return CommentsModalAction::ExternalEditor,
+                    _ => {}
                 }
-                CommentsModalAction::None
             }
+            if key.code == KeyCode::Tab && self.complete_mention() {
+                return CommentsModalAction::None;
+            }
+            // Alt+Enter starts a new line; Up/Down only reach the list from the first/last line
+            let outcome = LineEditor::new(&mut self.input, &mut self.cursor_position, &mut self.kill_ring)
+                .multiline()
+                .handle_key(key);
+            if outcome == EditOutcome::Changed {
+                self.history.reset();
+            }
+            if outcome.is_handled() {
+                self.update_mentions();
+                return CommentsModalAction::None;
+            }
+        }
+        match key.code {
             KeyCode::Enter if self.view_mode != CommentViewMode::AttachmentPreview => {
                 let text = self.input.clone();
-                CommentsModalAction::Submit(text)
+                match self.editing.as_ref() {
+                    Some(editing) => CommentsModalAction::Edit {
+                        comment_id: editing.comment_id,
+                        previous_text: editing.previous_text.clone(),
+                        text,
+                    },
+                    None => CommentsModalAction::Submit(text),
+                }
+            }
+            KeyCode::Esc if self.editing.is_some() => {
+                self.finish_editing();
+                CommentsModalAction::None
             }
             KeyCode::Tab => {
                 self.view_mode = match self.view_mode {
@@ -479,9 +620,322 @@ impl CommentsModal {
     pub fn clear_input(&mut self) {
         self.input.clear();
         self.cursor_position = 0;
+        self.history.reset();
+        self.mention_suggestions.clear();
     }
 
     pub fn add_comment(&mut self, comment: Comment) {
         self.comments.push(comment);
     }
+
+    /// Replace the comments after a refresh, keeping the selection in range
+    pub fn set_comments(&mut self, comments: Vec<Comment>) {
+        self.comments = comments;
+        self.selected_comment = self.selected_comment.min(self.comments.len().saturating_sub(1));
+    }
+
+    /// The unsent new comment, also while an existing one is being edited
+    pub fn draft(&self) -> &str {
+        match &self.editing {
+            Some(editing) => &editing.draft,
+            None => &self.input,
+        }
+    }
+
+    /// Leave edit mode and bring back the new-comment draft
+    pub fn finish_editing(&mut self) {
+        if let Some(editing) = self.editing.take() {
+            self.input = editing.draft;
+            self.cursor_position = self.input.len();
+            self.mention_suggestions.clear();
+        }
+    }
+
+    /// Replace the comment box, e.g. with text written in an external editor
+    pub fn set_input(&mut self, text: String) {
+        self.cursor_position = text.len();
+        self.input = text;
+        self.history.reset();
+        self.update_mentions();
+    }
+
+    fn selected(&self) -> Option<&Comment> {
+        self.comments.get(self.selected_comment)
+    }
+
+    fn is_own(&self, comment: &Comment) -> bool {
+        match (self.current_user_id, comment.author.as_ref()) {
+            (Some(user_id), Some(author)) => author.id == user_id,
+            _ => true,
+        }
+    }
+
+    /// Quote the selected comment at the end of the comment box
+    fn reply_to_selected(&mut self) {
+        let Some(comment) = self.selected() else {
+            self.status_message = Some("No comment to reply to".to_string());
+            return;
+        };
+        let quote = quote_comment(comment);
+        if !self.input.is_empty() && !self.input.ends_with("\n\n") {
+            self.input.push_str(if self.input.ends_with('\n') { "\n" } else { "\n\n" });
+        }
+        self.input.push_str(&quote);
+        self.cursor_position = self.input.len();
+        self.history.reset();
+        self.mention_suggestions.clear();
+    }
+
+    /// Load the selected comment into the comment box; Enter saves it
+    fn edit_selected(&mut self) {
+        let Some(comment) = self.selected() else {
+            self.status_message = Some("No comment to edit".to_string());
+            return;
+        };
+        if !self.is_own(comment) {
+            self.status_message = Some("Only your own comments can be edited".to_string());
+            return;
+        }
+        let comment_id = comment.id;
+        let previous_text = comment.comment.clone().unwrap_or_default();
+        let text = comment_source(&previous_text);
+        let draft = match self.editing.take() {
+            Some(editing) => editing.draft,
+            None => std::mem::take(&mut self.input),
+        };
+        self.editing = Some(EditingComment { comment_id, previous_text, draft });
+        self.cursor_position = text.len();
+        self.input = text;
+        self.mention_suggestions.clear();
+    }
+
+    fn request_delete(&mut self) {
+        let Some(comment) = self.selected() else {
+            self.status_message = Some("No comment to delete".to_string());
+            return;
+        };
+        if !self.is_own(comment) {
+            self.status_message = Some("Only your own comments can be deleted".to_string());
+            return;
+        }
+        self.pending_delete = Some(comment.id);
+        self.status_message = Some("Delete the selected comment? y: Delete | any other key: Cancel".to_string());
+    }
+
+    fn confirm_delete(&mut self, comment_id: i64, key: &KeyEvent) -> CommentsModalAction {
+        if !matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+            self.status_message = Some("Delete cancelled".to_string());
+            return CommentsModalAction::None;
+        }
+        let Some(text) = self.comments.iter().find(|c| c.id == comment_id).map(|c| c.comment.clone().unwrap_or_default()) else {
+            return CommentsModalAction::None;
+        };
+        if self.editing.as_ref().is_some_and(|e| e.comment_id == comment_id) {
+            self.finish_editing();
+        }
+        CommentsModalAction::Delete { comment_id, text }
+    }
+
+    fn mention_context(&self) -> Option<SuggestionContext> {
+        mention_context(&self.input[..self.cursor_position])
+    }
+
+    fn update_mentions(&mut self) {
+        self.mention_suggestions = match self.mention_context() {
+            Some(context) => rank(self.users.clone(), &context, &self.usage, chrono::Utc::now().timestamp()),
+            None => Vec::new(),
+        };
+    }
+
+    /// Complete the `@mention` before the cursor with the best match
+    fn complete_mention(&mut self) -> bool {
+        let (Some(context), Some(user)) = (self.mention_context(), self.mention_suggestions.first()) else {
+            return false;
+        };
+        let (input, cursor) = complete(&self.input, context.start, self.cursor_position, SuggestionMode::Assignee, user);
+        self.input = input;
+        self.cursor_position = cursor;
+        self.mention_suggestions.clear();
+        self.history.reset();
+        true
+    }
+}
+
+/// An `@mention` being typed at the end of `before_cursor`. The `@` has to start a
+/// word, so e-mail addresses are left alone.
+fn mention_context(before_cursor: &str) -> Option<SuggestionContext> {
+    let context = suggestion_context(before_cursor).filter(|c| c.mode == SuggestionMode::Assignee)?;
+    let starts_word = before_cursor[..context.start - 1].chars().next_back().is_none_or(char::is_whitespace);
+    (starts_word && !context.prefix.contains(char::is_whitespace)).then_some(context)
+}
+
+/// Comments written in the web UI are HTML; the comment box edits Markdown
+fn comment_source(text: &str) -> String {
+    if looks_like_html(text) {
+        html_to_markdown(text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// The HTML Vikunja stores for a comment written as Markdown, as for task descriptions
+pub fn comment_html(text: &str) -> String {
+    markdown_to_html(text.trim())
+}
+
+/// "@author wrote:" followed by the comment as a Markdown quote and a blank line
+fn quote_comment(comment: &Comment) -> String {
+    let author = comment.author.as_ref().map(|u| u.username.as_str()).unwrap_or("unknown");
+    let mut quote = format!("@{} wrote:\n", author);
+    for line in comment_source(comment.comment.as_deref().unwrap_or_default()).trim().lines() {
+        quote.push('>');
+        if !line.is_empty() {
+            quote.push(' ');
+            quote.push_str(line);
+        }
+        quote.push('\n');
+    }
+    quote.push('\n');
+    quote
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vikunja::models::User;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    fn user(id: i64, username: &str) -> User {
+        User { id, username: username.to_string(), name: None, email: None, created: None, updated: None }
+    }
+
+    fn comment(id: i64, author: &str, author_id: i64, text: &str) -> Comment {
+        Comment {
+            id,
+            author: Some(user(author_id, author)),
+            comment: Some(text.to_string()),
+            created: None,
+            updated: None,
+            reactions: None,
+        }
+    }
+
+    fn modal() -> CommentsModal {
+        CommentsModal::new(vec![comment(1, "alice", 1, "<p>First line</p><p>Second</p>"), comment(2, "bob", 2, "Mine")], 7)
+            .with_current_user(Some(2))
+            .with_users(vec!["carol".to_string()], SuggestionUsage::default())
+    }
+
+    fn type_text(modal: &mut CommentsModal, text: &str) {
+        for c in text.chars() {
+            modal.handle_key(&key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_reply_quotes_selected_comment() {
+        let mut modal = modal().with_input("draft".to_string());
+        modal.handle_key(&ctrl('r'));
+        assert_eq!(modal.input, "draft\n\n@alice wrote:\n> First line\n>\n> Second\n\n");
+        assert_eq!(modal.cursor_position, modal.input.len());
+    }
+
+    #[test]
+    fn test_edited_html_comment_is_saved_as_html() {
+        let mut modal = CommentsModal::new(vec![comment(1, "bob", 2, "<p>First line<br>Second</p><blockquote><p>Quoted</p></blockquote>")], 7)
+            .with_current_user(Some(2));
+        modal.handle_key(&ctrl('o'));
+        assert_eq!(modal.input, "First line\nSecond\n\n> Quoted");
+        modal.handle_key(&ctrl('e'));
+        type_text(&mut modal, "!");
+        match modal.handle_key(&key(KeyCode::Enter)) {
+            CommentsModalAction::Edit { text, .. } => assert_eq!(
+                comment_html(&text),
+                "<p>First line<br />\nSecond</p>\n<blockquote>\n<p>Quoted!</p>\n</blockquote>"
+            ),
+            other => panic!("Expected Edit, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ctrl_e_stays_end_of_line() {
+        let mut modal = modal().with_input("first\nsecond".to_string());
+        modal.cursor_position = 0;
+        assert!(matches!(modal.handle_key(&ctrl('e')), CommentsModalAction::None));
+        assert_eq!(modal.cursor_position, "first".len());
+        assert!(matches!(modal.handle_key(&ctrl('g')), CommentsModalAction::ExternalEditor));
+    }
+
+    #[test]
+    fn test_edit_own_comment_and_restore_draft() {
+        let mut modal = modal().with_input("draft".to_string());
+        // Someone else's comment can't be edited
+        modal.handle_key(&ctrl('o'));
+        assert_eq!(modal.input, "draft");
+        assert!(modal.status_message.is_some());
+
+        modal.handle_key(&key(KeyCode::Down));
+        modal.handle_key(&ctrl('o'));
+        assert_eq!(modal.input, "Mine");
+        assert_eq!(modal.draft(), "draft");
+        type_text(&mut modal, "!");
+        match modal.handle_key(&key(KeyCode::Enter)) {
+            CommentsModalAction::Edit { comment_id, previous_text, text } => {
+                assert_eq!(comment_id, 2);
+                assert_eq!(previous_text, "Mine");
+                assert_eq!(text, "Mine!");
+            }
+            other => panic!("Expected Edit, got {:?}", other),
+        }
+
+        modal.handle_key(&key(KeyCode::Esc));
+        assert_eq!(modal.input, "draft");
+        assert!(matches!(modal.handle_key(&key(KeyCode::Esc)), CommentsModalAction::Close));
+    }
+
+    #[test]
+    fn test_delete_needs_confirmation() {
+        let mut modal = modal();
+        modal.handle_key(&key(KeyCode::Down));
+        modal.handle_key(&ctrl('x'));
+        assert!(matches!(modal.handle_key(&key(KeyCode::Char('n'))), CommentsModalAction::None));
+        assert!(modal.input.is_empty());
+
+        modal.handle_key(&ctrl('x'));
+        match modal.handle_key(&key(KeyCode::Char('y'))) {
+            CommentsModalAction::Delete { comment_id, text } => {
+                assert_eq!(comment_id, 2);
+                assert_eq!(text, "Mine");
+            }
+            other => panic!("Expected Delete, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_mention_completion() {
+        let mut modal = modal();
+        type_text(&mut modal, "thanks @ca");
+        assert_eq!(modal.mention_suggestions, vec!["carol".to_string()]);
+        modal.handle_key(&key(KeyCode::Tab));
+        assert_eq!(modal.input, "thanks @carol ");
+        assert_eq!(modal.view_mode, CommentViewMode::List);
+
+        // Without a mention Tab still switches views
+        modal.handle_key(&key(KeyCode::Tab));
+        assert_eq!(modal.view_mode, CommentViewMode::Detail);
+    }
+
+    #[test]
+    fn test_mention_context_skips_email_addresses() {
+        assert!(mention_context("mail me@example").is_none());
+        assert!(mention_context("@bob and more").is_none());
+        assert_eq!(mention_context("hi @bo").map(|c| c.prefix), Some("bo".to_string()));
+    }
 }
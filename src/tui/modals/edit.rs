@@ -1,11 +1,14 @@
 use crate::tui::app::state::App;
+use crate::tui::input_history::Recall;
+use crate::tui::line_editor::EditOutcome;
 use crossterm::event::{KeyEvent, KeyModifiers};
 use crate::vikunja_client::VikunjaClient;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use crate::debug::debug_log;
 use chrono::Local;
-use crate::tui::app::suggestion_mode::SuggestionMode;
+use crate::tui::modals::{check_for_concurrent_edit, ConcurrencyCheck, ConflictSource};
+use crate::tui::modals::form_edit::save_form_task;
 
 pub async fn handle_edit_modal(
     app: &mut App,
@@ -15,12 +18,18 @@ pub async fn handle_edit_modal(
 ) {
     use crossterm::event::KeyCode;
     
+    // Ctrl+P/Ctrl+N step through earlier lines
+    if let Some(direction) = Recall::from_key(key) {
+        app.recall_edit_history(direction);
+        return;
+    }
+
     // Handle Ctrl+Z (undo) and Ctrl+Y (redo) in edit modal
     if key.modifiers.contains(KeyModifiers::CONTROL) {
         match key.code {
             KeyCode::Char('z') => {
                 debug_log("Edit Modal: Undo requested (Ctrl+Z)");
-                if let Some(_) = app.undo_last_action() {
+                if app.undo_last_action_async(&*api_client.lock().await).await.is_some() {
                     debug_log("Edit Modal: Undo successful");
                 } else {
                     debug_log("Edit Modal: No action to undo");
@@ -29,7 +38,7 @@ pub async fn handle_edit_modal(
             },
             KeyCode::Char('y') => {
                 debug_log("Edit Modal: Redo requested (Ctrl+Y)");
-                if let Some(_) = app.redo_last_action() {
+                if app.redo_last_action_async(&*api_client.lock().await).await.is_some() {
                     debug_log("Edit Modal: Redo successful");
                 } else {
                     debug_log("Edit Modal: No action to redo");
@@ -42,61 +51,16 @@ pub async fn handle_edit_modal(
     
     match key.code {
         KeyCode::Esc => {
-            app.hide_edit_modal();
+            app.cancel_edit_modal();
         },
         KeyCode::Enter => {
-            // Check if we should auto-complete or submit the task
-            let should_autocomplete = if app.suggestion_mode.is_some() && !app.suggestions.is_empty() {
-                // Only auto-complete if the current text exactly matches a suggestion prefix
-                // This prevents auto-completing when the user has typed a complete, valid label
-                let prefix = &app.suggestion_prefix;
-                
-                // If the suggestion prefix is not an exact match to any existing label/project,
-                // then we should auto-complete. If it is an exact match, the user might want to submit.
-                let is_exact_match = match app.suggestion_mode {
-                    Some(SuggestionMode::Label) => {
-                        app.label_map.values().any(|label| label.to_lowercase() == prefix.to_lowercase())
-                    },
-                    Some(SuggestionMode::Project) => {
-                        app.project_map.values().any(|project| project.to_lowercase() == prefix.to_lowercase())
-                    },
-                    _ => false
-                };
-                
-                // Auto-complete if it's not an exact match, or if the first suggestion is different from the prefix
-                !is_exact_match && !app.suggestions.is_empty() && app.suggestions[0].to_lowercase() != prefix.to_lowercase()
-            } else {
-                false
-            };
-            
-            if should_autocomplete {
+            // Complete a half-typed suggestion first; Enter again submits
+            if app.should_autocomplete_on_enter() {
                 debug_log(&format!("Auto-completing suggestion in edit modal: {}", app.suggestions[app.selected_suggestion]));
-                let suggestion = app.suggestions[app.selected_suggestion].clone();
-                let cursor = app.edit_cursor_position;
-                let input = app.get_edit_input();
-                if let Some(pos) = input[..cursor].rfind(|c| c == '*' || c == '+') {
-                    let mut new_input = String::new();
-                    new_input.push_str(&input[..pos]); // Include everything up to but not including the * or +
-                    new_input.push(input.chars().nth(pos).unwrap()); // Add the * or + character
-                    
-                    // Wrap multi-word suggestions in square brackets for proper parsing
-                    if suggestion.contains(' ') {
-                        new_input.push_str(&format!("[{}]", suggestion));
-                    } else {
-                        new_input.push_str(&suggestion);
-                    }
-                    
-                    if input.get(cursor..cursor+1).map_or(true, |c| c == " " || c == "") {
-                        new_input.push(' ');
-                        new_input.push_str(&input[cursor..]);
-                        app.edit_cursor_position = pos + 1 + 
-                            (if suggestion.contains(' ') { suggestion.len() + 2 } else { suggestion.len() }) + 1;
-                    } else {
-                        new_input.push_str(&input[cursor..]);
-                        app.edit_cursor_position = pos + 1 + 
-                            (if suggestion.contains(' ') { suggestion.len() + 2 } else { suggestion.len() });
-                    }
-                    app.edit_input = new_input;
+                let input = app.edit_input.clone();
+                if let Some((completed, cursor)) = app.accept_suggestion(&input, app.edit_cursor_position) {
+                    app.edit_input = completed;
+                    app.edit_cursor_position = cursor;
                 }
                 let input = app.edit_input.clone();
                 let cursor = app.edit_cursor_position;
@@ -108,12 +72,42 @@ pub async fn handle_edit_modal(
             let input = app.get_edit_input().to_string();
             let task_id = app.editing_task_id;
             if !input.trim().is_empty() && task_id.is_some() {
+                app.remember_edit(task_id.unwrap(), &input);
+                // Detect changes someone else made since the edit line was opened
+                if let Some(base) = app.edit_base_state.clone() {
+                    ensure_edit_line_labels(app, api_client, &input).await;
+                    app.form_edit_state = Some(app.form_state_from_edit_input(&base, &input));
+                    match check_for_concurrent_edit(app, api_client, ConflictSource::Edit).await {
+                        ConcurrencyCheck::Unchanged => app.form_edit_state = None,
+                        ConcurrencyCheck::Conflict => return,
+                        ConcurrencyCheck::Merged => {
+                            // The merged result can't be expressed as an edit line; save it field by field
+                            match save_form_task(app, api_client, client_clone).await {
+                                Ok(true) => {
+                                    app.form_edit_state = None;
+                                    app.hide_edit_modal();
+                                }
+                                Ok(false) => {}
+                                Err(e) => {
+                                    debug_log(&format!("ERROR: Failed to save merged edit: {}", e));
+                                    app.form_edit_state = None;
+                                    app.show_toast(format!("Failed to save: {}", e));
+                                }
+                            }
+                            return;
+                        }
+                    }
+                }
                 debug_log(&format!("Updating task ID {} with input: '{}'", task_id.unwrap(), input));
                 app.hide_edit_modal();
+                let previous_task = app.all_tasks.iter().find(|t| t.id == task_id.unwrap()).cloned();
                 let api_client_guard = api_client.lock().await;
                 match api_client_guard.update_task_with_magic(task_id.unwrap(), &input).await {
                     Ok(task) => {
                         debug_log(&format!("SUCCESS: Task updated successfully! ID: {:?}, Title: '{}'", task.id, task.title));
+                        if let Some(previous_task) = previous_task {
+                            app.add_task_edit_to_undo_stack(previous_task.id, previous_task);
+                        }
                         app.flash_task_id = task.id.map(|id| id as i64);
                         app.flash_start = Some(Local::now());
                         drop(api_client_guard);
@@ -133,36 +127,12 @@ pub async fn handle_edit_modal(
             }
         },
         KeyCode::Tab => {
-            if app.suggestion_mode.is_some() && !app.suggestions.is_empty() {
-                let suggestion = app.suggestions[app.selected_suggestion].clone();
-                let cursor = app.edit_cursor_position;
-                let input = app.get_edit_input();
-                if let Some(pos) = input[..cursor].rfind(|c| c == '*' || c == '+') {
-                    let mut new_input = String::new();
-                    new_input.push_str(&input[..pos]); // Include everything up to but not including the * or +
-                    new_input.push(input.chars().nth(pos).unwrap()); // Add the * or + character
-                    
-                    // Wrap multi-word suggestions in square brackets for proper parsing
-                    if suggestion.contains(' ') {
-                        new_input.push_str(&format!("[{}]", suggestion));
-                    } else {
-                        new_input.push_str(&suggestion);
-                    }
-                    
-                    if input.get(cursor..cursor+1).map_or(true, |c| c == " " || c == "") {
-                        new_input.push(' ');
-                        new_input.push_str(&input[cursor..]);
-                        app.edit_cursor_position = pos + 1 + 
-                            (if suggestion.contains(' ') { suggestion.len() + 2 } else { suggestion.len() }) + 1;
-                    } else {
-                        new_input.push_str(&input[cursor..]);
-                        app.edit_cursor_position = pos + 1 + 
-                            (if suggestion.contains(' ') { suggestion.len() + 2 } else { suggestion.len() });
-                    }
-                    app.edit_input = new_input;
-                }
+            // Repeated Tab cycles through the suggestions
+            let input = app.edit_input.clone();
+            if let Some((completed, cursor)) = app.tab_complete(&input, app.edit_cursor_position) {
+                app.edit_input = completed;
+                app.edit_cursor_position = cursor;
                 let input = app.edit_input.clone();
-                let cursor = app.edit_cursor_position;
                 app.update_suggestions(&input, cursor);
             }
         },
@@ -172,6 +142,8 @@ pub async fn handle_edit_modal(
                 let input = app.edit_input.clone();
                 let cursor = app.edit_cursor_position;
                 app.update_suggestions(&input, cursor);
+            } else {
+                app.recall_edit_history(Recall::Newer);
             }
         },
         KeyCode::Up => {
@@ -184,32 +156,45 @@ pub async fn handle_edit_modal(
                 let input = app.edit_input.clone();
                 let cursor = app.edit_cursor_position;
                 app.update_suggestions(&input, cursor);
+            } else {
+                app.recall_edit_history(Recall::Older);
             }
         },
-        KeyCode::Backspace => {
-            app.delete_char_from_edit();
-            let input = app.edit_input.clone();
-            let cursor = app.edit_cursor_position;
-            app.update_suggestions(&input, cursor);
-        },
-        KeyCode::Left => {
-            app.move_edit_cursor_left();
-            let input = app.edit_input.clone();
-            let cursor = app.edit_cursor_position;
-            app.update_suggestions(&input, cursor);
-        },
-        KeyCode::Right => {
-            app.move_edit_cursor_right();
-            let input = app.edit_input.clone();
-            let cursor = app.edit_cursor_position;
-            app.update_suggestions(&input, cursor);
-        },
-        KeyCode::Char(c) => {
-            app.add_char_to_edit(c);
-            let input = app.edit_input.clone();
-            let cursor = app.edit_cursor_position;
-            app.update_suggestions(&input, cursor);
+        _ => {
+            let outcome = app.edit_editor().handle_key(key);
+            if outcome == EditOutcome::Changed {
+                app.edit_history.reset();
+            }
+            if outcome.is_handled() {
+                let input = app.edit_input.clone();
+                let cursor = app.edit_cursor_position;
+                app.update_suggestions(&input, cursor);
+                if outcome == EditOutcome::Changed {
+                    super::quick_add::search_assignee_suggestions(app, &input, cursor);
+                }
+            }
         },
-        _ => {},
+    }
+}
+
+/// Create the labels an edit line names that don't exist yet, as `update_task_with_magic`
+/// does, so a merged save after a concurrent edit keeps them
+async fn ensure_edit_line_labels(app: &mut App, api_client: &Arc<Mutex<VikunjaClient>>, input: &str) {
+    let parsed = crate::vikunja_parser::QuickAddParser::with_settings(app.date_settings).parse(input);
+    for name in parsed.labels {
+        if app.label_map.values().any(|title| title.eq_ignore_ascii_case(&name)) {
+            continue;
+        }
+        match api_client.lock().await.ensure_label_exists(&name).await {
+            Ok(label) => {
+                if let Some(id) = label.id {
+                    app.label_map.insert(id as i64, label.title);
+                    if let Some(color) = label.hex_color {
+                        app.label_colors.insert(id as i64, color);
+                    }
+                }
+            }
+            Err(e) => debug_log(&format!("Failed to create label '{}': {}", name, e)),
+        }
     }
 }
@@ -0,0 +1,332 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::tui::app::edit_conflict::{merge_form_states, resolve_conflicts, ConflictField, FieldConflict, MergeChoice, MergeOutcome};
+use crate::tui::app::form_edit_state::FormEditState;
+use crate::tui::app::state::App;
+use crate::vikunja_client::VikunjaClient;
+
+/// Which editor the conflicting save came from, so Esc can return to it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictSource {
+    Form,
+    Edit,
+}
+
+/// Result of comparing the loaded task with the server's current version
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConcurrencyCheck {
+    /// Nobody else changed the task
+    Unchanged,
+    /// Someone else changed other fields; their changes were merged into the form
+    Merged,
+    /// Both sides changed the same fields; the merge modal is open
+    Conflict,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditConflictAction {
+    None,
+    Cancel,
+    Save,
+}
+
+/// One conflicting field with the three versions rendered for display
+#[derive(Debug, Clone)]
+struct ConflictRow {
+    original: String,
+    theirs: String,
+    mine: String,
+}
+
+/// Three-way merge modal shown when a task changed on the server while it was being edited
+pub struct EditConflictModal {
+    pub task_id: i64,
+    pub source: ConflictSource,
+    pub conflicts: Vec<FieldConflict>,
+    pub selected_index: usize,
+    base: FormEditState,
+    theirs: FormEditState,
+    merged: FormEditState,
+    rows: Vec<ConflictRow>,
+}
+
+impl EditConflictModal {
+    pub fn new<F: Fn(ConflictField, &FormEditState) -> String>(
+        task_id: i64,
+        source: ConflictSource,
+        base: FormEditState,
+        theirs: FormEditState,
+        outcome: MergeOutcome,
+        describe: F,
+    ) -> Self {
+        let rows = outcome.conflicts.iter()
+            .map(|c| ConflictRow {
+                original: describe(c.field, &base),
+                theirs: describe(c.field, &theirs),
+                mine: describe(c.field, &outcome.merged),
+            })
+            .collect();
+        Self {
+            task_id,
+            source,
+            conflicts: outcome.conflicts,
+            selected_index: 0,
+            base,
+            theirs,
+            merged: outcome.merged,
+            rows,
+        }
+    }
+
+    /// The form state with every conflict resolved according to the current choices
+    pub fn resolved(&self) -> FormEditState {
+        resolve_conflicts(&self.merged, &self.base, &self.theirs, &self.conflicts)
+    }
+
+    fn choose(&mut self, choice: MergeChoice) {
+        if let Some(conflict) = self.conflicts.get_mut(self.selected_index) {
+            conflict.choice = choice;
+        }
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> EditConflictAction {
+        match key.code {
+            KeyCode::Esc => return EditConflictAction::Cancel,
+            KeyCode::Enter => return EditConflictAction::Save,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.selected_index + 1 < self.conflicts.len() => {
+                self.selected_index += 1;
+            }
+            KeyCode::Char('m') => self.choose(MergeChoice::Mine),
+            KeyCode::Char('t') => self.choose(MergeChoice::Theirs),
+            KeyCode::Char('o') => self.choose(MergeChoice::Original),
+            KeyCode::Char(' ') | KeyCode::Tab => {
+                if let Some(conflict) = self.conflicts.get_mut(self.selected_index) {
+                    conflict.choice = conflict.choice.next();
+                }
+            }
+            KeyCode::Char('M') => self.conflicts.iter_mut().for_each(|c| c.choice = MergeChoice::Mine),
+            KeyCode::Char('T') => self.conflicts.iter_mut().for_each(|c| c.choice = MergeChoice::Theirs),
+            _ => {}
+        }
+        EditConflictAction::None
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let width = (area.width * 85) / 100;
+        let height = (area.height * 80) / 100;
+        let x = (area.width - width) / 2 + area.x;
+        let y = (area.height - height) / 2 + area.y;
+        let modal_area = Rect::new(x, y, width, height);
+        f.render_widget(Clear, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(self.conflicts.len() as u16 + 4), // Conflict table
+                Constraint::Min(5),                                   // Selected field in full
+                Constraint::Length(1),                                // Help
+            ])
+            .split(modal_area);
+
+        self.draw_conflict_table(f, chunks[0]);
+        self.draw_selected_field(f, chunks[1]);
+
+        let help = Paragraph::new("↑↓: Field | m/t/o: Mine/Theirs/Original | Space: Cycle | M/T: All mine/theirs | Enter: Save | Esc: Back")
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(help, chunks[2]);
+    }
+
+    fn draw_conflict_table(&self, f: &mut Frame, area: Rect) {
+        let inner_width = area.width.saturating_sub(2) as usize;
+        let name_width = 13;
+        let column_width = inner_width.saturating_sub(name_width + 2) / 3;
+        let cell = |text: &str, chosen: bool| {
+            let first_line = text.lines().next().unwrap_or("");
+            let mut shown: String = first_line.chars().take(column_width.saturating_sub(2)).collect();
+            if shown.chars().count() < text.chars().count() {
+                shown.pop();
+                shown.push('…');
+            }
+            let style = if chosen {
+                Style::default().fg(Color::Black).bg(Color::Green).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            Span::styled(format!("{:<width$}", shown, width = column_width), style)
+        };
+
+        let header_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+        let mut lines = vec![
+            Line::from(Span::styled(
+                "Someone else changed this task while you were editing. Pick a version for each field:",
+                Style::default().fg(Color::Yellow),
+            )),
+            Line::from(vec![
+                Span::raw(" ".repeat(name_width + 2)),
+                Span::styled(format!("{:<width$}", "Original", width = column_width), header_style),
+                Span::styled(format!("{:<width$}", "Theirs", width = column_width), header_style),
+                Span::styled(format!("{:<width$}", "Mine", width = column_width), header_style),
+            ]),
+        ];
+        for (i, (conflict, row)) in self.conflicts.iter().zip(&self.rows).enumerate() {
+            let is_selected = i == self.selected_index;
+            let marker = if is_selected { "▶ " } else { "  " };
+            let name_style = if is_selected {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(marker, Style::default().fg(Color::Yellow)),
+                Span::styled(format!("{:<width$}", conflict.field.name(), width = name_width), name_style),
+                cell(&row.original, conflict.choice == MergeChoice::Original),
+                cell(&row.theirs, conflict.choice == MergeChoice::Theirs),
+                cell(&row.mine, conflict.choice == MergeChoice::Mine),
+            ]));
+        }
+
+        let block = Block::default()
+            .title(" Edit Conflict ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red));
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn draw_selected_field(&self, f: &mut Frame, area: Rect) {
+        let (conflict, row) = match (self.conflicts.get(self.selected_index), self.rows.get(self.selected_index)) {
+            (Some(c), Some(r)) => (c, r),
+            _ => return,
+        };
+        let mut lines = Vec::new();
+        for (label, text, choice) in [
+            ("Original", &row.original, MergeChoice::Original),
+            ("Theirs", &row.theirs, MergeChoice::Theirs),
+            ("Mine", &row.mine, MergeChoice::Mine),
+        ] {
+            let label_style = if conflict.choice == choice {
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Cyan)
+            };
+            let marker = if conflict.choice == choice { " ✓" } else { "" };
+            lines.push(Line::from(Span::styled(format!("{}{}:", label, marker), label_style)));
+            for text_line in text.lines() {
+                lines.push(Line::from(format!("  {}", text_line)));
+            }
+            lines.push(Line::from(""));
+        }
+        let block = Block::default()
+            .title(format!(" {} ", conflict.field.name()))
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White));
+        f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+    }
+}
+
+/// Compare the server's `updated` timestamp with the one the form state was loaded with.
+/// Changes someone else made to other fields are merged into `app.form_edit_state`;
+/// conflicting changes open the merge modal.
+pub async fn check_for_concurrent_edit(
+    app: &mut App,
+    api_client: &Arc<Mutex<VikunjaClient>>,
+    source: ConflictSource,
+) -> ConcurrencyCheck {
+    let (task_id, updated, base) = match app.form_edit_state {
+        Some(ref form) => (form.task_id, form.updated.clone(), form.base.clone()),
+        None => return ConcurrencyCheck::Unchanged,
+    };
+    let base = match (updated.as_ref(), base) {
+        (Some(_), Some(base)) => *base,
+        // Without a loaded timestamp there is nothing to compare against
+        _ => return ConcurrencyCheck::Unchanged,
+    };
+    let server_task = match api_client.lock().await.get_task_detailed(task_id as u64).await {
+        Ok(task) => task,
+        Err(e) => {
+            app.add_debug_message(format!("Concurrency check skipped for task {}: {}", task_id, e));
+            return ConcurrencyCheck::Unchanged;
+        }
+    };
+    if server_task.updated == updated {
+        return ConcurrencyCheck::Unchanged;
+    }
+
+    app.add_debug_message(format!(
+        "Task {} changed on the server while editing ({:?} -> {:?})",
+        task_id, updated, server_task.updated
+    ));
+    let theirs = FormEditState::with_settings(&server_task, &app.date_settings);
+    let mine = match app.form_edit_state.clone() {
+        Some(form) => form,
+        None => return ConcurrencyCheck::Unchanged,
+    };
+    let outcome = merge_form_states(&base, &theirs, &mine);
+    if outcome.conflicts.is_empty() {
+        app.form_edit_state = Some(outcome.merged);
+        app.show_toast("Merged changes someone else made meanwhile".to_string());
+        return ConcurrencyCheck::Merged;
+    }
+    let modal = EditConflictModal::new(task_id, source, base, theirs, outcome, |field, state| app.describe_form_field(field, state));
+    app.show_edit_conflict_modal(modal);
+    ConcurrencyCheck::Conflict
+}
+
+pub async fn handle_edit_conflict_modal(
+    app: &mut App,
+    key: &KeyEvent,
+    api_client: &Arc<Mutex<VikunjaClient>>,
+    client_clone: &Arc<Mutex<VikunjaClient>>,
+) {
+    let (action, source) = match app.edit_conflict_modal {
+        Some(ref mut modal) => (modal.handle_key(key), modal.source),
+        None => return,
+    };
+    match action {
+        EditConflictAction::None => {}
+        EditConflictAction::Cancel => {
+            // Go back to the editor the save came from, with the edits intact
+            app.hide_edit_conflict_modal();
+            match source {
+                ConflictSource::Form => app.show_form_edit_modal = true,
+                ConflictSource::Edit => {
+                    app.form_edit_state = None;
+                    app.show_edit_modal = true;
+                }
+            }
+        }
+        EditConflictAction::Save => {
+            let resolved = match app.edit_conflict_modal {
+                Some(ref modal) => modal.resolved(),
+                None => return,
+            };
+            app.hide_edit_conflict_modal();
+            app.form_edit_state = Some(resolved);
+            match super::form_edit::save_form_task(app, api_client, client_clone).await {
+                Ok(true) => {
+                    app.hide_form_edit_modal();
+                    app.hide_edit_modal();
+                    app.show_toast("Merged changes saved".to_string());
+                }
+                // The task changed again; a new merge modal is open
+                Ok(false) => {}
+                Err(e) => {
+                    app.add_debug_message(format!("Failed to save merged task: {}", e));
+                    app.show_toast(format!("Failed to save: {}", e));
+                    app.show_form_edit_modal = true;
+                }
+            }
+        }
+    }
+}
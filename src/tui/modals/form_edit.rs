@@ -1,5 +1,8 @@
 use crate::tui::app::state::App;
 use crate::tui::app::form_edit_state::FormEditState;
+use crate::tui::modals::{check_for_concurrent_edit, ConcurrencyCheck, ConflictSource};
+use crate::tui::date_picker::{DatePicker, DatePickerAction};
+use crate::tui::line_editor::{EditOutcome, KillRing, LineEditor};
 use crossterm::event::KeyEvent;
 use crate::vikunja_client::VikunjaClient;
 use std::sync::Arc;
@@ -16,6 +19,10 @@ pub async fn handle_form_edit_modal(
     use crossterm::event::KeyCode;
     
     if let Some(form) = app.form_edit_state.as_mut() {
+        if form.date_picker.is_some() {
+            handle_date_picker_key(form, key);
+            return;
+        }
         match key.code {
             KeyCode::Tab => {
                 // Save current field before moving
@@ -59,29 +66,7 @@ pub async fn handle_form_edit_modal(
                 form.set_current_field_text(current_text);
                 // Validate the form before saving
                 if let Some(form) = app.form_edit_state.as_ref() {
-                    let mut errors: Vec<String> = Vec::new();
-                    // Title required
-                    if form.title.trim().is_empty() {
-                        errors.push("Title is required.".to_string());
-                    }
-                    // Due date format (optional, but if present, must be valid)
-                    if let Some(due) = &form.due_date {
-                        if !due.trim().is_empty() && chrono::NaiveDate::parse_from_str(due.trim(), "%Y-%m-%d").is_err() {
-                            errors.push("Due date must be in YYYY-MM-DD format.".to_string());
-                        }
-                    }
-                    // Start date format (optional, but if present, must be valid)
-                    if let Some(start) = &form.start_date {
-                        if !start.trim().is_empty() && chrono::NaiveDate::parse_from_str(start.trim(), "%Y-%m-%d").is_err() {
-                            errors.push("Start date must be in YYYY-MM-DD format.".to_string());
-                        }
-                    }
-                    // Priority (optional, but if present, must be 0-5)
-                    if let Some(priority) = form.priority {
-                        if priority < 0 || priority > 5 {
-                            errors.push("Priority must be between 0 and 5.".to_string());
-                        }
-                    }
+                    let mut errors = form.errors(&app.date_settings);
                     // Project ID (should be valid if set)
                     if form.project_id != 0 && !app.project_map.contains_key(&form.project_id) {
                         errors.push("Selected project does not exist.".to_string());
@@ -102,12 +87,15 @@ pub async fn handle_form_edit_modal(
                     }
                 }
                 // Save the task with updated values
-                if let Err(e) = save_form_task(app, api_client, client_clone).await {
-                    debug_log(&format!("Failed to save task from form: {}", e));
-                    app.toast_notification = Some(format!("Failed to save: {}", e));
-                    app.toast_notification_start = Some(Local::now());
-                } else {
-                    app.hide_form_edit_modal();
+                match save_form_task(app, api_client, client_clone).await {
+                    Ok(true) => app.hide_form_edit_modal(),
+                    // Someone else changed the task; the merge modal is open
+                    Ok(false) => {}
+                    Err(e) => {
+                        debug_log(&format!("Failed to save task from form: {}", e));
+                        app.toast_notification = Some(format!("Failed to save: {}", e));
+                        app.toast_notification_start = Some(Local::now());
+                    }
                 }
             }
             KeyCode::Char(' ') => {
@@ -121,34 +109,53 @@ pub async fn handle_form_edit_modal(
                         // Label picker - always wire up: use App method to open label picker from form
                         app.open_label_picker_from_form();
                     }
+                    7 => {
+                        // Assignee picker - search users and toggle assignees
+                        app.open_assignee_picker_from_form();
+                    }
                     8 => {
                         // Toggle favorite
                         form.is_favorite = !form.is_favorite;
                     }
+                    13 => {
+                        // Color palette
+                        form.next_palette_color();
+                        update_cursor_position(form);
+                    }
                     _ => {
                         // For text fields, add space normally
-                        add_char_to_current_field(form, ' ');
+                        edit_focused_field(form, &mut app.kill_ring, key);
                     }
                 }
             }
-            KeyCode::Char(c) => {
-                add_char_to_current_field(form, c);
-            }
-            KeyCode::Backspace => {
-                delete_char_from_current_field(form);
-            }
-            KeyCode::Left => {
-                if form.cursor_position > 0 {
-                    form.cursor_position -= 1;
-                }
-            }
-            KeyCode::Right => {
+            KeyCode::Char('g') if key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) && form.field_index == 1 => {
+                // Keep what was typed so far; the UI loop opens the editor on the description
                 let current_text = form.get_current_field_text();
-                if form.cursor_position < current_text.len() {
-                    form.cursor_position += 1;
-                }
+                form.set_current_field_text(current_text);
+                app.external_editor_requested = true;
             }
-            _ => {}
+            KeyCode::Char('t') if key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) && FormEditState::is_date_field(form.field_index) => {
+                let settings = app.date_settings;
+                let default_time = if form.field_index == 3 { settings.start_time } else { settings.due_time };
+                let current = settings.parse(&form.get_current_field_text(), default_time);
+                form.date_picker = Some(DatePicker::new(current, settings, default_time));
+            }
+            _ => edit_focused_field(form, &mut app.kill_ring, key),
+        }
+    }
+}
+
+/// Keys go to the date picker while it is open; confirming writes the date into the field
+fn handle_date_picker_key(form: &mut FormEditState, key: &KeyEvent) {
+    let Some(picker) = form.date_picker.as_mut() else { return };
+    match picker.handle_key(key) {
+        DatePickerAction::None => {}
+        DatePickerAction::Cancel => form.date_picker = None,
+        DatePickerAction::Confirm(date) => {
+            let text = date.map(|d| picker.format(d)).unwrap_or_default();
+            form.date_picker = None;
+            form.set_current_field_text(text);
+            update_cursor_position(form);
         }
     }
 }
@@ -158,124 +165,76 @@ fn update_cursor_position(form: &mut FormEditState) {
     form.cursor_position = current_text.len();
 }
 
-fn add_char_to_current_field(form: &mut FormEditState, c: char) {
+/// Apply an editing key to the focused field
+fn edit_focused_field(form: &mut FormEditState, kill_ring: &mut KillRing, key: &KeyEvent) {
+    use crossterm::event::KeyCode;
+
     match form.field_index {
-        0 => {
-            form.title.insert(form.cursor_position, c);
-            form.cursor_position += 1;
-        }
-        1 => {
-            form.description.insert(form.cursor_position, c);
-            form.cursor_position += 1;
-        }
-        2 => {
-            let date_str = form.due_date.get_or_insert_with(String::new);
-            date_str.insert(form.cursor_position, c);
-            form.cursor_position += 1;
-        }
-        3 => {
-            let date_str = form.start_date.get_or_insert_with(String::new);
-            date_str.insert(form.cursor_position, c);
-            form.cursor_position += 1;
-        }
-        4 => {
+        4 => match key.code {
             // Priority field - only accept digits 0-5
-            if c.is_ascii_digit() {
-                let digit = c.to_digit(10).unwrap() as i32;
-                if digit <= 5 {
-                    form.priority = Some(digit);
+            KeyCode::Char(c) => {
+                if let Some(digit) = c.to_digit(10).filter(|d| *d <= 5) {
+                    form.priority = Some(digit as i32);
                     form.cursor_position = 1;
                 }
             }
-        }
-        9 => {
-            form.comment.insert(form.cursor_position, c);
-            form.cursor_position += 1;
-        }
-        _ => {}
-    }
-}
-
-fn delete_char_from_current_field(form: &mut FormEditState) {
-    match form.field_index {
-        0 => {
-            if form.cursor_position > 0 && form.cursor_position <= form.title.len() {
-                form.cursor_position -= 1;
-                form.title.remove(form.cursor_position);
-            }
-        }
-        1 => {
-            if form.cursor_position > 0 && form.cursor_position <= form.description.len() {
-                form.cursor_position -= 1;
-                form.description.remove(form.cursor_position);
-            }
-        }
-        2 => {
-            if let Some(ref mut date_str) = form.due_date {
-                if form.cursor_position > 0 && form.cursor_position <= date_str.len() {
-                    form.cursor_position -= 1;
-                    date_str.remove(form.cursor_position);
-                    if date_str.is_empty() {
-                        form.due_date = None;
-                    }
-                }
-            }
-        }
-        3 => {
-            if let Some(ref mut date_str) = form.start_date {
-                if form.cursor_position > 0 && form.cursor_position <= date_str.len() {
-                    form.cursor_position -= 1;
-                    date_str.remove(form.cursor_position);
-                    if date_str.is_empty() {
-                        form.start_date = None;
-                    }
-                }
+            KeyCode::Backspace | KeyCode::Delete => {
+                form.priority = None;
+                form.cursor_position = 0;
             }
-        }
-        4 => {
-            form.priority = None;
-            form.cursor_position = 0;
-        }
-        9 => {
-            if form.cursor_position > 0 && form.cursor_position <= form.comment.len() {
-                form.cursor_position -= 1;
-                form.comment.remove(form.cursor_position);
+            _ => {}
+        },
+        12 => match key.code {
+            // Progress slider, in steps of 10%
+            KeyCode::Left | KeyCode::Char('-') | KeyCode::Char('h') => form.adjust_percent_done(-10),
+            KeyCode::Right | KeyCode::Char('+') | KeyCode::Char('l') => form.adjust_percent_done(10),
+            KeyCode::Home | KeyCode::Backspace | KeyCode::Delete => form.percent_done = 0,
+            KeyCode::End => form.percent_done = 100,
+            KeyCode::Char(c @ '0'..='9') => form.percent_done = c.to_digit(10).unwrap_or(0) as u8 * 10,
+            _ => {}
+        },
+        index if FormEditState::is_text_field(index) => {
+            let mut text = form.get_current_field_text();
+            if LineEditor::new(&mut text, &mut form.cursor_position, kill_ring).handle_key(key) == EditOutcome::Changed {
+                form.set_current_field_text(text);
             }
         }
         _ => {}
     }
 }
 
-async fn save_form_task(
+/// Save the form to the server. Returns `Ok(false)` without saving when the task was
+/// changed by someone else in a way that needs the user to merge.
+pub(crate) async fn save_form_task(
     app: &mut App,
     api_client: &Arc<Mutex<VikunjaClient>>,
     client_clone: &Arc<Mutex<VikunjaClient>>,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<bool, Box<dyn std::error::Error>> {
+    if check_for_concurrent_edit(app, api_client, ConflictSource::Form).await == ConcurrencyCheck::Conflict {
+        return Ok(false);
+    }
     if let Some(form) = &app.form_edit_state {
         debug_log(&format!("Saving task from form: ID {}", form.task_id));
+        let previous_task = app.all_tasks.iter().find(|t| t.id == form.task_id).cloned();
+        let changes = form.changes(&app.date_settings);
+        if changes.is_empty() {
+            app.show_toast("Nothing changed".to_string());
+            return Ok(true);
+        }
         
         let api_client_guard = api_client.lock().await;
         
-        // Update the task using the form data
-        let result = api_client_guard.update_task_from_form(
-            form.task_id,
-            &form.title,
-            form.description.as_str(),
-            form.due_date.as_deref(),
-            form.start_date.as_deref(),
-            form.priority,
-            form.project_id,
-            &form.label_ids,
-            &[], // Remove assignees from form mode
-            form.is_favorite,
-            if form.comment.is_empty() { None } else { Some(&form.comment) },
-        ).await;
+        // Send only what the form changed
+        let result = api_client_guard.update_task_from_form(form.task_id, &changes).await;
         
         drop(api_client_guard);
         
         match result {
             Ok(task) => {
                 debug_log(&format!("SUCCESS: Task updated from form! ID: {:?}, Title: '{}' Description: {:?}", task.id, task.title, task.description));
+                if let Some(previous_task) = previous_task {
+                    app.add_task_edit_to_undo_stack(task.id, previous_task);
+                }
                 
                 // Refresh tasks and inject updated task details
                 let (mut tasks, project_map, project_colors) = client_clone.lock().await.get_tasks_with_projects().await.unwrap_or_default();
@@ -297,7 +256,7 @@ async fn save_form_task(
                 app.flash_cycle_max = 6;
                 
                 debug_log(&format!("Tasks refreshed. Total tasks: {}", app.tasks.len()));
-                Ok(())
+                Ok(true)
             }
             Err(e) => {
                 debug_log(&format!("ERROR: Failed to update task from form: {}", e));
@@ -0,0 +1,709 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::color_helper::ColorHelper;
+use crate::tui::app::state::App;
+use crate::tui::line_editor::{clamp_cursor, display_width, KillRing, LineEditor};
+use crate::tui::utils::fuzzy_match_score;
+use crate::vikunja_client::VikunjaClient;
+
+/// A label as shown in the label manager, with the number of tasks using it
+#[derive(Debug, Clone)]
+pub struct LabelEntry {
+    pub id: i64,
+    pub title: String,
+    pub hex_color: Option<String>,
+    pub usage: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LabelManagerMode {
+    Browse,
+    Rename,
+    Recolor,
+    ConfirmDelete,
+    MergePick,
+    ConfirmMerge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LabelManagerFocus {
+    Labels,
+    Duplicates,
+}
+
+/// Actions that require an API call, returned to the caller of `handle_key`
+#[derive(Debug, Clone, PartialEq)]
+pub enum LabelManagerAction {
+    None,
+    Close,
+    Rename { label_id: i64, title: String },
+    Recolor { label_id: i64, hex_color: String },
+    Delete(i64),
+    Merge { source_id: i64, target_id: i64 },
+}
+
+/// Modal for renaming, recoloring, deleting and merging labels
+pub struct LabelManagerModal {
+    pub labels: Vec<LabelEntry>,
+    pub duplicates: Vec<(i64, i64)>, // (keep, merge away)
+    pub selected_index: usize,
+    pub selected_duplicate: usize,
+    pub focus: LabelManagerFocus,
+    pub mode: LabelManagerMode,
+    pub input: String,
+    pub input_cursor: usize,
+    pub merge_source: Option<i64>,
+    pub merge_target: Option<i64>,
+    pub status_message: String,
+    color_helper: ColorHelper,
+    kill_ring: KillRing,
+}
+
+impl LabelManagerModal {
+    pub fn new(labels: Vec<LabelEntry>) -> Self {
+        let mut modal = Self {
+            labels: Vec::new(),
+            duplicates: Vec::new(),
+            selected_index: 0,
+            selected_duplicate: 0,
+            focus: LabelManagerFocus::Labels,
+            mode: LabelManagerMode::Browse,
+            input: String::new(),
+            input_cursor: 0,
+            merge_source: None,
+            merge_target: None,
+            status_message: String::new(),
+            color_helper: ColorHelper::default(),
+            kill_ring: KillRing::default(),
+        };
+        modal.set_labels(labels);
+        modal
+    }
+
+    /// Replace the label list (e.g. after an API change), keeping the selection in bounds
+    pub fn set_labels(&mut self, mut labels: Vec<LabelEntry>) {
+        labels.sort_by(|a, b| b.usage.cmp(&a.usage).then_with(|| a.title.to_lowercase().cmp(&b.title.to_lowercase())));
+        self.duplicates = find_duplicate_labels(&labels);
+        self.labels = labels;
+        self.selected_index = self.selected_index.min(self.labels.len().saturating_sub(1));
+        self.selected_duplicate = self.selected_duplicate.min(self.duplicates.len().saturating_sub(1));
+        if self.duplicates.is_empty() {
+            self.focus = LabelManagerFocus::Labels;
+        }
+        self.mode = LabelManagerMode::Browse;
+        self.input.clear();
+        self.merge_source = None;
+        self.merge_target = None;
+    }
+
+    pub fn selected_label(&self) -> Option<&LabelEntry> {
+        self.labels.get(self.selected_index)
+    }
+
+    fn label(&self, id: i64) -> Option<&LabelEntry> {
+        self.labels.iter().find(|l| l.id == id)
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> LabelManagerAction {
+        match self.mode {
+            LabelManagerMode::Browse => self.handle_browse_key(key),
+            LabelManagerMode::Rename | LabelManagerMode::Recolor => self.handle_input_key(key),
+            LabelManagerMode::ConfirmDelete => match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.mode = LabelManagerMode::Browse;
+                    match self.selected_label() {
+                        Some(label) => LabelManagerAction::Delete(label.id),
+                        None => LabelManagerAction::None,
+                    }
+                }
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    self.mode = LabelManagerMode::Browse;
+                    LabelManagerAction::None
+                }
+                _ => LabelManagerAction::None,
+            },
+            LabelManagerMode::MergePick => match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.move_up();
+                    LabelManagerAction::None
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.move_down();
+                    LabelManagerAction::None
+                }
+                KeyCode::Enter => {
+                    let target = self.selected_label().map(|l| l.id);
+                    if target.is_some() && target != self.merge_source {
+                        self.merge_target = target;
+                        self.mode = LabelManagerMode::ConfirmMerge;
+                    } else {
+                        self.status_message = "Pick a different label to merge into".to_string();
+                    }
+                    LabelManagerAction::None
+                }
+                KeyCode::Esc => {
+                    self.cancel();
+                    LabelManagerAction::None
+                }
+                _ => LabelManagerAction::None,
+            },
+            LabelManagerMode::ConfirmMerge => match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    let action = match (self.merge_source, self.merge_target) {
+                        (Some(source_id), Some(target_id)) => LabelManagerAction::Merge { source_id, target_id },
+                        _ => LabelManagerAction::None,
+                    };
+                    self.cancel();
+                    action
+                }
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    self.cancel();
+                    LabelManagerAction::None
+                }
+                _ => LabelManagerAction::None,
+            },
+        }
+    }
+
+    fn handle_browse_key(&mut self, key: &KeyEvent) -> LabelManagerAction {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => LabelManagerAction::Close,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.move_up();
+                LabelManagerAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.move_down();
+                LabelManagerAction::None
+            }
+            KeyCode::Tab => {
+                if !self.duplicates.is_empty() {
+                    self.focus = match self.focus {
+                        LabelManagerFocus::Labels => LabelManagerFocus::Duplicates,
+                        LabelManagerFocus::Duplicates => LabelManagerFocus::Labels,
+                    };
+                }
+                LabelManagerAction::None
+            }
+            KeyCode::Enter if self.focus == LabelManagerFocus::Duplicates => {
+                if let Some(&(keep, merge)) = self.duplicates.get(self.selected_duplicate) {
+                    self.merge_source = Some(merge);
+                    self.merge_target = Some(keep);
+                    self.mode = LabelManagerMode::ConfirmMerge;
+                }
+                LabelManagerAction::None
+            }
+            KeyCode::Char('r') if self.focus == LabelManagerFocus::Labels => {
+                if let Some(label) = self.selected_label() {
+                    self.input = label.title.clone();
+                    self.input_cursor = self.input.len();
+                    self.mode = LabelManagerMode::Rename;
+                }
+                LabelManagerAction::None
+            }
+            KeyCode::Char('c') if self.focus == LabelManagerFocus::Labels => {
+                if let Some(label) = self.selected_label() {
+                    self.input = label.hex_color.clone().unwrap_or_default();
+                    self.input_cursor = self.input.len();
+                    self.mode = LabelManagerMode::Recolor;
+                }
+                LabelManagerAction::None
+            }
+            KeyCode::Char('d') if self.focus == LabelManagerFocus::Labels => {
+                if self.selected_label().is_some() {
+                    self.mode = LabelManagerMode::ConfirmDelete;
+                }
+                LabelManagerAction::None
+            }
+            KeyCode::Char('m') if self.focus == LabelManagerFocus::Labels => {
+                if self.labels.len() > 1 {
+                    self.merge_source = self.selected_label().map(|l| l.id);
+                    self.mode = LabelManagerMode::MergePick;
+                }
+                LabelManagerAction::None
+            }
+            _ => LabelManagerAction::None,
+        }
+    }
+
+    fn handle_input_key(&mut self, key: &KeyEvent) -> LabelManagerAction {
+        match key.code {
+            KeyCode::Esc => {
+                self.cancel();
+                LabelManagerAction::None
+            }
+            KeyCode::Enter => {
+                let label_id = match self.selected_label() {
+                    Some(label) => label.id,
+                    None => return LabelManagerAction::None,
+                };
+                if self.mode == LabelManagerMode::Rename {
+                    let title = self.input.trim().to_string();
+                    if title.is_empty() {
+                        self.status_message = "Label name cannot be empty".to_string();
+                        return LabelManagerAction::None;
+                    }
+                    self.cancel();
+                    LabelManagerAction::Rename { label_id, title }
+                } else {
+                    match normalize_hex_color(&self.input) {
+                        Some(hex_color) => {
+                            self.cancel();
+                            LabelManagerAction::Recolor { label_id, hex_color }
+                        }
+                        None => {
+                            self.status_message = "Color must be 6 hex digits, e.g. e8a33d".to_string();
+                            LabelManagerAction::None
+                        }
+                    }
+                }
+            }
+            _ => {
+                LineEditor::new(&mut self.input, &mut self.input_cursor, &mut self.kill_ring).handle_key(key);
+                LabelManagerAction::None
+            }
+        }
+    }
+
+    fn cancel(&mut self) {
+        self.mode = LabelManagerMode::Browse;
+        self.input.clear();
+        self.merge_source = None;
+        self.merge_target = None;
+    }
+
+    fn move_up(&mut self) {
+        if self.focus == LabelManagerFocus::Duplicates && self.mode == LabelManagerMode::Browse {
+            self.selected_duplicate = self.selected_duplicate.saturating_sub(1);
+        } else {
+            self.selected_index = self.selected_index.saturating_sub(1);
+        }
+    }
+
+    fn move_down(&mut self) {
+        if self.focus == LabelManagerFocus::Duplicates && self.mode == LabelManagerMode::Browse {
+            if self.selected_duplicate + 1 < self.duplicates.len() {
+                self.selected_duplicate += 1;
+            }
+        } else if self.selected_index + 1 < self.labels.len() {
+            self.selected_index += 1;
+        }
+    }
+
+    fn label_chip(&self, title: &str, hex_color: Option<&str>) -> Span<'static> {
+        let style = match hex_color {
+            Some(hex) if normalize_hex_color(hex).is_some() => self.color_helper.get_contrasting_style(hex),
+            _ => Style::default().fg(Color::White).bg(Color::DarkGray),
+        };
+        Span::styled(format!(" {} ", title), style)
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let width = (area.width * 80) / 100;
+        let height = (area.height * 80) / 100;
+        let x = (area.width - width) / 2 + area.x;
+        let y = (area.height - height) / 2 + area.y;
+        let modal_area = Rect::new(x, y, width, height);
+        f.render_widget(Clear, modal_area);
+
+        let duplicates_height = if self.duplicates.is_empty() { 0 } else { (self.duplicates.len() as u16 + 2).min(7) };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(5),                     // Labels
+                Constraint::Length(duplicates_height),  // Possible duplicates
+                Constraint::Length(3),                  // Input / preview
+                Constraint::Length(1),                  // Help
+            ])
+            .split(modal_area);
+
+        self.draw_label_list(f, chunks[0]);
+        if !self.duplicates.is_empty() {
+            self.draw_duplicates(f, chunks[1]);
+        }
+        self.draw_input_section(f, chunks[2]);
+
+        let help_text = match self.mode {
+            LabelManagerMode::Browse => "↑↓: Select | r: Rename | c: Color | d: Delete | m: Merge | Tab: Duplicates | Esc: Close",
+            LabelManagerMode::Rename | LabelManagerMode::Recolor => "Enter: Save | Esc: Cancel",
+            LabelManagerMode::MergePick => "↑↓: Pick target | Enter: Choose | Esc: Cancel",
+            LabelManagerMode::ConfirmDelete | LabelManagerMode::ConfirmMerge => "y: Confirm | n/Esc: Cancel",
+        };
+        let help = Paragraph::new(help_text)
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(help, chunks[3]);
+    }
+
+    fn draw_label_list(&self, f: &mut Frame, area: Rect) {
+        let visible = area.height.saturating_sub(2) as usize;
+        let offset = if visible > 0 && self.selected_index >= visible { self.selected_index + 1 - visible } else { 0 };
+        let list_focused = self.focus == LabelManagerFocus::Labels || self.mode != LabelManagerMode::Browse;
+
+        let mut lines = Vec::new();
+        if self.labels.is_empty() {
+            lines.push(Line::from(Span::styled("No labels yet", Style::default().fg(Color::Gray))));
+        }
+        for (i, label) in self.labels.iter().enumerate().skip(offset).take(visible.max(1)) {
+            let is_selected = i == self.selected_index && list_focused;
+            let marker = if is_selected { "▶ " } else { "  " };
+            let usage_style = if is_selected {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            let mut spans = vec![
+                Span::styled(marker, Style::default().fg(Color::Yellow)),
+                Span::styled(format!("{:>4} ", label.usage), usage_style),
+                self.label_chip(&label.title, label.hex_color.as_deref()),
+            ];
+            if let Some(ref hex) = label.hex_color {
+                spans.push(Span::styled(format!("  #{}", hex.trim_start_matches('#')), Style::default().fg(Color::DarkGray)));
+            }
+            if self.merge_source == Some(label.id) {
+                spans.push(Span::styled("  (merging)", Style::default().fg(Color::Magenta)));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let block = Block::default()
+            .title(format!(" Labels ({}) - tasks / label ", self.labels.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if list_focused { Color::Green } else { Color::Gray }));
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn draw_duplicates(&self, f: &mut Frame, area: Rect) {
+        let focused = self.focus == LabelManagerFocus::Duplicates && self.mode == LabelManagerMode::Browse;
+        let mut lines = Vec::new();
+        for (i, (keep, merge)) in self.duplicates.iter().enumerate() {
+            let (keep, merge) = match (self.label(*keep), self.label(*merge)) {
+                (Some(k), Some(m)) => (k, m),
+                _ => continue,
+            };
+            let marker = if focused && i == self.selected_duplicate { "▶ " } else { "  " };
+            lines.push(Line::from(vec![
+                Span::styled(marker, Style::default().fg(Color::Yellow)),
+                self.label_chip(&merge.title, merge.hex_color.as_deref()),
+                Span::styled(format!(" ({}) → ", merge.usage), Style::default().fg(Color::Gray)),
+                self.label_chip(&keep.title, keep.hex_color.as_deref()),
+                Span::styled(format!(" ({})", keep.usage), Style::default().fg(Color::Gray)),
+            ]));
+        }
+        let block = Block::default()
+            .title(" Possible duplicates (Enter to merge) ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if focused { Color::Green } else { Color::Yellow }));
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn draw_input_section(&self, f: &mut Frame, area: Rect) {
+        let selected = self.selected_label();
+        let selected_title = selected.map(|l| l.title.clone()).unwrap_or_default();
+        let (title, line) = match self.mode {
+            LabelManagerMode::Browse => (
+                " Label Manager ",
+                Line::from(Span::styled(self.status_message.clone(), Style::default().fg(Color::Cyan))),
+            ),
+            LabelManagerMode::Rename => {
+                let mut spans = vec![Span::raw("Name: "), Span::styled(self.input.clone(), Style::default().fg(Color::Yellow))];
+                if self.input.trim().is_empty() {
+                    spans.push(Span::styled("   name required", Style::default().fg(Color::Red)));
+                }
+                (" Rename ", Line::from(spans))
+            }
+            LabelManagerMode::Recolor => {
+                let mut spans = vec![Span::raw("Hex: #"), Span::styled(self.input.trim_start_matches('#').to_string(), Style::default().fg(Color::Yellow)), Span::raw("   ")];
+                match normalize_hex_color(&self.input) {
+                    Some(hex) => {
+                        spans.push(Span::raw("Preview: "));
+                        spans.push(self.label_chip(&selected_title, Some(&hex)));
+                        if self.color_helper.is_color_problematic(&hex) {
+                            spans.push(Span::styled("  low contrast", Style::default().fg(Color::Yellow)));
+                        }
+                    }
+                    None => spans.push(Span::styled("enter 6 hex digits", Style::default().fg(Color::DarkGray))),
+                }
+                (" Recolor ", Line::from(spans))
+            }
+            LabelManagerMode::ConfirmDelete => (
+                " Delete ",
+                Line::from(Span::styled(
+                    format!("Delete '{}' (used by {} tasks)? y/n", selected_title, selected.map(|l| l.usage).unwrap_or(0)),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                )),
+            ),
+            LabelManagerMode::MergePick => (
+                " Merge ",
+                Line::from(format!(
+                    "Merge '{}' into... (pick target and press Enter)",
+                    self.merge_source.and_then(|id| self.label(id)).map(|l| l.title.as_str()).unwrap_or("")
+                )),
+            ),
+            LabelManagerMode::ConfirmMerge => {
+                let source = self.merge_source.and_then(|id| self.label(id));
+                let target = self.merge_target.and_then(|id| self.label(id));
+                (
+                    " Merge ",
+                    Line::from(Span::styled(
+                        format!(
+                            "Move {} tasks from '{}' to '{}' and delete '{}'? y/n",
+                            source.map(|l| l.usage).unwrap_or(0),
+                            source.map(|l| l.title.as_str()).unwrap_or(""),
+                            target.map(|l| l.title.as_str()).unwrap_or(""),
+                            source.map(|l| l.title.as_str()).unwrap_or(""),
+                        ),
+                        Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+                    )),
+                )
+            }
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White));
+        f.render_widget(Paragraph::new(line).block(block), area);
+
+        if self.mode == LabelManagerMode::Rename || self.mode == LabelManagerMode::Recolor {
+            // Both "Name: " and "Hex: #" prefixes are six columns wide
+            let before_cursor = &self.input[..clamp_cursor(&self.input, self.input_cursor)];
+            let cursor_x = area.x + 1 + 6 + display_width(before_cursor.trim_start_matches('#')) as u16;
+            if cursor_x < area.x + area.width - 1 {
+                f.set_cursor(cursor_x, area.y + 1);
+            }
+        }
+    }
+}
+
+/// Normalize user input into a 6-digit lowercase hex color without the leading '#'
+pub fn normalize_hex_color(input: &str) -> Option<String> {
+    let hex = input.trim().trim_start_matches('#');
+    if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(hex.to_ascii_lowercase())
+    } else {
+        None
+    }
+}
+
+/// Edit distance counting insertions, deletions, substitutions and adjacent transpositions
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Whether two label titles look like the same label (case/punctuation variants, plurals, typos)
+pub fn labels_look_alike(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| s.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+    let (na, nb) = (normalize(a), normalize(b));
+    if na.is_empty() || nb.is_empty() {
+        return false;
+    }
+    if na == nb {
+        return true;
+    }
+    let (shorter, longer) = if na.len() <= nb.len() { (&na, &nb) } else { (&nb, &na) };
+    // Prefix match with a short tail covers plurals like "bug"/"bugs"
+    if shorter.chars().count() >= 3
+        && longer.chars().count() - shorter.chars().count() <= 2
+        && fuzzy_match_score(longer, shorter) >= 0.9
+    {
+        return true;
+    }
+    // One edit apart covers typos like "urgent"/"urgnet"
+    shorter.chars().count() >= 4 && edit_distance(&na, &nb) <= 1
+}
+
+/// Find pairs of labels that are probably duplicates.
+/// Each pair is (label to keep, label to merge away); the more used label is kept.
+pub fn find_duplicate_labels(labels: &[LabelEntry]) -> Vec<(i64, i64)> {
+    let mut pairs = Vec::new();
+    for (i, a) in labels.iter().enumerate() {
+        for b in labels.iter().skip(i + 1) {
+            if labels_look_alike(&a.title, &b.title) {
+                if a.usage >= b.usage {
+                    pairs.push((a.id, b.id));
+                } else {
+                    pairs.push((b.id, a.id));
+                }
+            }
+        }
+    }
+    pairs
+}
+
+pub async fn handle_label_manager_modal(
+    app: &mut App,
+    key: &KeyEvent,
+    api_client: &Arc<Mutex<VikunjaClient>>,
+) {
+    let action = match app.label_manager_modal {
+        Some(ref mut modal) => modal.handle_key(key),
+        None => return,
+    };
+    let client = api_client.lock().await;
+    match action {
+        LabelManagerAction::None => {}
+        LabelManagerAction::Close => app.hide_label_manager_modal(),
+        LabelManagerAction::Rename { label_id, title } => {
+            let hex_color = app.label_colors.get(&label_id).cloned();
+            match client.update_label(label_id as u64, &title, hex_color.as_deref()).await {
+                Ok(_) => {
+                    app.apply_label_rename(label_id, &title);
+                    app.show_toast(format!("Label renamed to '{}'", title));
+                }
+                Err(e) => {
+                    app.add_debug_message(format!("Failed to rename label {}: {}", label_id, e));
+                    app.show_toast(format!("Rename failed: {}", e));
+                }
+            }
+        }
+        LabelManagerAction::Recolor { label_id, hex_color } => {
+            let title = app.label_map.get(&label_id).cloned().unwrap_or_default();
+            match client.update_label(label_id as u64, &title, Some(&hex_color)).await {
+                Ok(_) => {
+                    app.apply_label_color(label_id, &hex_color);
+                    app.show_toast(format!("Label '{}' recolored", title));
+                }
+                Err(e) => {
+                    app.add_debug_message(format!("Failed to recolor label {}: {}", label_id, e));
+                    app.show_toast(format!("Recolor failed: {}", e));
+                }
+            }
+        }
+        LabelManagerAction::Delete(label_id) => {
+            let title = app.label_map.get(&label_id).cloned().unwrap_or_default();
+            match client.delete_label(label_id as u64).await {
+                Ok(_) => {
+                    app.apply_label_deletion(label_id);
+                    app.show_toast(format!("Label '{}' deleted", title));
+                }
+                Err(e) => {
+                    app.add_debug_message(format!("Failed to delete label {}: {}", label_id, e));
+                    app.show_toast(format!("Delete failed: {}", e));
+                }
+            }
+        }
+        LabelManagerAction::Merge { source_id, target_id } => {
+            // Only loaded tasks are known locally; the server has the full list
+            let server_tasks = match client.get_tasks_with_label(source_id as u64).await {
+                Ok(tasks) => tasks,
+                Err(e) => {
+                    app.add_debug_message(format!("Failed to list tasks with label {}: {}", source_id, e));
+                    app.show_toast("Merge cancelled: couldn't list every task with the label".to_string());
+                    return;
+                }
+            };
+            let plan = app.label_merge_plan(&server_tasks, source_id, target_id);
+            match client.merge_labels(source_id as u64, target_id as u64, &plan).await {
+                Ok(failures) if failures.is_empty() => {
+                    app.apply_label_merge(source_id, target_id, &[]);
+                    app.show_toast(format!("Merged label into '{}' ({} tasks)", app.label_map.get(&target_id).cloned().unwrap_or_default(), plan.len()));
+                }
+                Ok(failures) => {
+                    for (task_id, e) in &failures {
+                        app.add_debug_message(format!("Failed to move task {} from label {} to {}: {}", task_id, source_id, target_id, e));
+                    }
+                    let failed_ids: Vec<u64> = failures.iter().map(|(task_id, _)| *task_id).collect();
+                    app.apply_label_merge(source_id, target_id, &failed_ids);
+                    app.show_toast(format!("Merge incomplete: {} of {} tasks failed; the label was kept", failures.len(), plan.len()));
+                }
+                Err(e) => {
+                    app.add_debug_message(format!("Failed to merge label {} into {}: {}", source_id, target_id, e));
+                    app.show_toast(format!("Merge failed: {}", e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn entry(id: i64, title: &str, usage: usize) -> LabelEntry {
+        LabelEntry { id, title: title.to_string(), hex_color: None, usage }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_labels_look_alike() {
+        assert!(labels_look_alike("Bug", "bugs"));
+        assert!(labels_look_alike("follow-up", "Follow Up"));
+        assert!(labels_look_alike("urgent", "urgnet"));
+        assert!(!labels_look_alike("work", "homework"));
+        assert!(!labels_look_alike("ui", "ux"));
+    }
+
+    #[test]
+    fn test_find_duplicate_labels_keeps_most_used() {
+        let labels = vec![entry(1, "bugs", 2), entry(2, "Bug", 9), entry(3, "docs", 4)];
+        assert_eq!(find_duplicate_labels(&labels), vec![(2, 1)]);
+    }
+
+    #[test]
+    fn test_normalize_hex_color() {
+        assert_eq!(normalize_hex_color("#E8A33D"), Some("e8a33d".to_string()));
+        assert_eq!(normalize_hex_color("e8a33d"), Some("e8a33d".to_string()));
+        assert_eq!(normalize_hex_color("e8a3"), None);
+        assert_eq!(normalize_hex_color("zzzzzz"), None);
+    }
+
+    #[test]
+    fn test_merge_flow_returns_merge_action() {
+        let mut modal = LabelManagerModal::new(vec![entry(1, "home", 5), entry(2, "house", 1)]);
+        // Select "house" (sorted after "home" by usage) and merge into "home"
+        modal.handle_key(&key(KeyCode::Down));
+        modal.handle_key(&key(KeyCode::Char('m')));
+        assert_eq!(modal.mode, LabelManagerMode::MergePick);
+        modal.handle_key(&key(KeyCode::Up));
+        modal.handle_key(&key(KeyCode::Enter));
+        assert_eq!(modal.mode, LabelManagerMode::ConfirmMerge);
+        let action = modal.handle_key(&key(KeyCode::Char('y')));
+        assert_eq!(action, LabelManagerAction::Merge { source_id: 2, target_id: 1 });
+    }
+
+    #[test]
+    fn test_recolor_rejects_invalid_hex() {
+        let mut modal = LabelManagerModal::new(vec![entry(1, "home", 5)]);
+        modal.handle_key(&key(KeyCode::Char('c')));
+        modal.handle_key(&key(KeyCode::Char('f')));
+        assert_eq!(modal.handle_key(&key(KeyCode::Enter)), LabelManagerAction::None);
+        assert_eq!(modal.mode, LabelManagerMode::Recolor);
+        for c in "f0000".chars() {
+            modal.handle_key(&key(KeyCode::Char(c)));
+        }
+        assert_eq!(
+            modal.handle_key(&key(KeyCode::Enter)),
+            LabelManagerAction::Recolor { label_id: 1, hex_color: "ff0000".to_string() }
+        );
+    }
+}
@@ -7,21 +7,41 @@ pub mod utils;
 
 mod quick_add;
 mod edit;
-mod form_edit;
+pub(crate) mod form_edit;
+mod edit_conflict;
 mod attachments;
 mod file_picker;
 mod comments;
+mod label_manager;
+mod project_sharing;
+mod checklist;
+mod paste_preview;
+mod undo_history;
+mod trash;
+mod template_picker;
+mod snooze;
+mod reschedule;
 pub mod url_modal;
 // Relations - DISABLED: Incomplete feature
 // mod relations;
 
 
-pub use quick_add::handle_quick_add_modal;
+pub use quick_add::{handle_quick_add_modal, handle_quick_add_paste};
 pub use edit::handle_edit_modal;
 pub use form_edit::handle_form_edit_modal;
+pub use edit_conflict::{EditConflictModal, ConflictSource, ConcurrencyCheck, check_for_concurrent_edit, handle_edit_conflict_modal};
 pub use attachments::{AttachmentModal, AttachmentModalAction};
 pub use file_picker::{FilePickerModal, FilePickerAction};
-pub use comments::{CommentsModal, CommentsModalAction};
+pub use comments::{comment_html, CommentsModal, CommentsModalAction};
 pub use url_modal::{UrlModal, UrlModalAction};
+pub use label_manager::{LabelManagerModal, LabelEntry, handle_label_manager_modal, normalize_hex_color};
+pub use project_sharing::{ProjectSharingModal, handle_project_sharing_modal, load_project_shares};
+pub use checklist::{ChecklistModal, handle_checklist_modal};
+pub use paste_preview::{PastePreviewModal, handle_paste_preview_modal};
+pub use undo_history::{UndoHistoryModal, handle_undo_history_modal};
+pub use trash::{TrashModal, handle_trash_modal};
+pub use template_picker::{TemplatePickerModal, handle_template_picker_modal};
+pub use snooze::{SnoozeModal, handle_snooze_modal};
+pub use reschedule::{RescheduleModal, handle_reschedule_modal};
 // Relations - DISABLED: Incomplete feature  
 // pub use relations::{handle_relations_modal, handle_add_relation_modal};
\ No newline at end of file
@@ -0,0 +1,298 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::dates::DateSettings;
+use crate::tui::app::state::App;
+use crate::tui::line_editor::{input_viewport, KillRing, LineEditor};
+use crate::vikunja_client::VikunjaClient;
+use crate::vikunja_parser::{OutlineEntry, ParsedTask, QuickAddParser};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PastePreviewAction {
+    None,
+    Close,
+    Submit,
+}
+
+/// One pasted line and what quick add makes of it
+pub struct PastedLine {
+    pub entry: OutlineEntry,
+    /// Nesting level in the outline
+    pub depth: usize,
+    pub parsed: ParsedTask,
+    /// Unchecked lines are dropped on submit
+    pub included: bool,
+}
+
+/// Preview of a multi-line paste into quick add: one task per line, nested lines as
+/// subtasks. Lines can be dropped, and magic syntax typed into `shared` (a project,
+/// labels, a date) is added to every line.
+pub struct PastePreviewModal {
+    pub lines: Vec<PastedLine>,
+    pub selected_index: usize,
+    pub shared: String,
+    pub shared_cursor: usize,
+    /// Keys go to the shared syntax input instead of the list
+    pub editing_shared: bool,
+    kill_ring: KillRing,
+    parser: QuickAddParser,
+}
+
+impl PastePreviewModal {
+    pub fn new(outline: Vec<OutlineEntry>, settings: DateSettings) -> Self {
+        let parser = QuickAddParser::with_settings(settings);
+        let mut depths: Vec<usize> = Vec::with_capacity(outline.len());
+        let lines = outline.into_iter().map(|entry| {
+            let depth = entry.parent.and_then(|p| depths.get(p)).map_or(0, |d| d + 1);
+            depths.push(depth);
+            PastedLine { parsed: parser.parse(&entry.text), entry, depth, included: true }
+        }).collect();
+        Self {
+            lines,
+            selected_index: 0,
+            shared: String::new(),
+            shared_cursor: 0,
+            editing_shared: false,
+            kill_ring: KillRing::default(),
+            parser,
+        }
+    }
+
+    /// The line as it will be submitted, with the shared syntax added
+    fn line_text(&self, entry: &OutlineEntry) -> String {
+        match self.shared.trim() {
+            "" => entry.text.clone(),
+            shared => format!("{} {}", entry.text, shared),
+        }
+    }
+
+    fn reparse(&mut self) {
+        for i in 0..self.lines.len() {
+            let text = self.line_text(&self.lines[i].entry);
+            self.lines[i].parsed = self.parser.parse(&text);
+        }
+    }
+
+    /// The included lines to create. A dropped line's subtasks move up to its nearest
+    /// included ancestor.
+    pub fn outline(&self) -> Vec<OutlineEntry> {
+        let mut new_index: Vec<Option<usize>> = Vec::with_capacity(self.lines.len());
+        let mut outline = Vec::new();
+        for line in &self.lines {
+            if !line.included {
+                new_index.push(None);
+                continue;
+            }
+            let mut parent = line.entry.parent;
+            while let Some(p) = parent {
+                if new_index[p].is_some() {
+                    break;
+                }
+                parent = self.lines[p].entry.parent;
+            }
+            outline.push(OutlineEntry {
+                text: self.line_text(&line.entry),
+                parent: parent.and_then(|p| new_index[p]),
+            });
+            new_index.push(Some(outline.len() - 1));
+        }
+        outline
+    }
+
+    fn included_count(&self) -> usize {
+        self.lines.iter().filter(|l| l.included).count()
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> PastePreviewAction {
+        if self.editing_shared {
+            match key.code {
+                KeyCode::Enter => return PastePreviewAction::Submit,
+                KeyCode::Esc | KeyCode::Tab => {
+                    self.editing_shared = false;
+                    return PastePreviewAction::None;
+                }
+                _ => {}
+            }
+            let outcome = LineEditor::new(&mut self.shared, &mut self.shared_cursor, &mut self.kill_ring).handle_key(key);
+            if outcome == crate::tui::line_editor::EditOutcome::Changed {
+                self.reparse();
+            }
+            return PastePreviewAction::None;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => PastePreviewAction::Close,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                PastePreviewAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected_index + 1 < self.lines.len() {
+                    self.selected_index += 1;
+                }
+                PastePreviewAction::None
+            }
+            KeyCode::Char(' ') | KeyCode::Char('x') | KeyCode::Char('d') => {
+                if let Some(line) = self.lines.get_mut(self.selected_index) {
+                    line.included = !line.included;
+                }
+                PastePreviewAction::None
+            }
+            KeyCode::Tab | KeyCode::Char('s') => {
+                self.editing_shared = true;
+                PastePreviewAction::None
+            }
+            KeyCode::Enter if self.included_count() > 0 => PastePreviewAction::Submit,
+            _ => PastePreviewAction::None,
+        }
+    }
+
+    /// Project, labels and dates quick add found in a line
+    fn summary(parsed: &ParsedTask, settings: &DateSettings) -> Vec<Span<'static>> {
+        let mut spans = Vec::new();
+        if let Some(ref project) = parsed.project {
+            spans.push(Span::styled(format!(" +{}", project), Style::default().fg(Color::Magenta)));
+        }
+        for label in &parsed.labels {
+            spans.push(Span::styled(format!(" *{}", label), Style::default().fg(Color::Red)));
+        }
+        if let Some(priority) = parsed.priority {
+            spans.push(Span::styled(format!(" !{}", priority), Style::default().fg(Color::Yellow)));
+        }
+        if let Some(due) = parsed.due_date {
+            spans.push(Span::styled(format!(" due {}", settings.format(due, settings.due_time)), Style::default().fg(Color::Cyan)));
+        }
+        spans
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect, settings: &DateSettings) {
+        let width = (area.width * 80) / 100;
+        let height = ((self.lines.len() as u16) + 9).min((area.height * 80) / 100).max(12);
+        let x = (area.width - width) / 2 + area.x;
+        let y = (area.height.saturating_sub(height)) / 2 + area.y;
+        let modal_area = Rect::new(x, y, width, height.min(area.height));
+        f.render_widget(Clear, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),     // Lines
+                Constraint::Length(3),  // Shared syntax
+                Constraint::Length(1),  // Help
+            ])
+            .split(modal_area);
+
+        let visible = chunks[0].height.saturating_sub(2) as usize;
+        let offset = if visible > 0 && self.selected_index >= visible { self.selected_index + 1 - visible } else { 0 };
+        let mut lines = Vec::new();
+        for (i, line) in self.lines.iter().enumerate().skip(offset).take(visible.max(1)) {
+            let is_selected = i == self.selected_index && !self.editing_shared;
+            let (box_text, box_color) = if line.included { ("☑ ", Color::Green) } else { ("☐ ", Color::DarkGray) };
+            let mut title_style = if line.included { Style::default().fg(Color::White) } else { Style::default().fg(Color::DarkGray).add_modifier(Modifier::CROSSED_OUT) };
+            if is_selected {
+                title_style = title_style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+            }
+            let mut spans = vec![
+                Span::styled(if is_selected { "▶ " } else { "  " }, Style::default().fg(Color::Yellow)),
+                Span::raw("  ".repeat(line.depth)),
+                Span::styled(box_text, Style::default().fg(box_color)),
+                Span::styled(line.parsed.title.clone(), title_style),
+            ];
+            if line.included {
+                spans.extend(Self::summary(&line.parsed, settings));
+            }
+            lines.push(Line::from(spans));
+        }
+        let block = Block::default()
+            .title(format!(" Create {} of {} pasted tasks ", self.included_count(), self.lines.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        f.render_widget(Paragraph::new(lines).block(block), chunks[0]);
+
+        let shared_block = Block::default()
+            .title(" Add to every task (e.g. +project *label tomorrow) ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if self.editing_shared { Color::Yellow } else { Color::Gray }));
+        let inner = shared_block.inner(chunks[1]);
+        let viewport = input_viewport(&self.shared, self.shared_cursor, inner);
+        f.render_widget(Paragraph::new(self.shared.as_str()).block(shared_block).scroll(viewport.scroll), chunks[1]);
+        if self.editing_shared {
+            f.set_cursor(viewport.cursor_x, viewport.cursor_y);
+        }
+
+        let help_text = if self.editing_shared {
+            "Type magic syntax | Tab/Esc: Back to list | Enter: Create"
+        } else {
+            "↑↓/jk: Select | Space/x/d: Drop/keep | Tab/s: Add to all | Enter: Create | Esc: Cancel"
+        };
+        let help = Paragraph::new(help_text)
+            .style(Style::default().fg(Color::Gray).bg(Color::Black))
+            .alignment(Alignment::Center);
+        f.render_widget(help, chunks[2]);
+    }
+}
+
+pub async fn handle_paste_preview_modal(
+    app: &mut App,
+    key: &KeyEvent,
+    api_client: &Arc<Mutex<VikunjaClient>>,
+) {
+    let action = match app.paste_preview_modal {
+        Some(ref mut modal) => modal.handle_key(key),
+        None => return,
+    };
+    match action {
+        PastePreviewAction::None => {}
+        PastePreviewAction::Close => app.hide_paste_preview_modal(),
+        PastePreviewAction::Submit => {
+            let Some(outline) = app.paste_preview_modal.as_ref().map(|m| m.outline()) else {
+                return;
+            };
+            app.hide_paste_preview_modal();
+            super::quick_add::create_outline(app, &outline, api_client).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vikunja_parser::parse_outline;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_drop_lines_and_share_syntax() {
+        let outline = parse_outline("Offsite\n  Book venue\n    Call hotel\n  Plan agenda\nSend notes *followup");
+        let mut modal = PastePreviewModal::new(outline, DateSettings::default());
+        assert_eq!(modal.lines.iter().map(|l| l.depth).collect::<Vec<_>>(), vec![0, 1, 2, 1, 0]);
+
+        // Drop "Book venue"; "Call hotel" moves up under "Offsite"
+        modal.handle_key(&key(KeyCode::Down));
+        assert_eq!(modal.handle_key(&key(KeyCode::Char('d'))), PastePreviewAction::None);
+        modal.handle_key(&key(KeyCode::Tab));
+        for c in "+Work *q3".chars() {
+            modal.handle_key(&key(KeyCode::Char(c)));
+        }
+        assert_eq!(modal.lines[4].parsed.labels, vec!["followup", "q3"]);
+        assert_eq!(modal.lines[0].parsed.project.as_deref(), Some("Work"));
+        assert_eq!(modal.handle_key(&key(KeyCode::Enter)), PastePreviewAction::Submit);
+
+        let created: Vec<(String, Option<usize>)> = modal.outline().into_iter().map(|e| (e.text, e.parent)).collect();
+        assert_eq!(created, vec![
+            ("Offsite +Work *q3".to_string(), None),
+            ("Call hotel +Work *q3".to_string(), Some(0)),
+            ("Plan agenda +Work *q3".to_string(), Some(0)),
+            ("Send notes *followup +Work *q3".to_string(), None),
+        ]);
+    }
+}
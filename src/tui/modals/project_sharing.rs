@@ -0,0 +1,519 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::tui::app::state::App;
+use crate::tui::line_editor::{clamp_cursor, display_width, KillRing, LineEditor};
+use crate::vikunja_client::VikunjaClient;
+use crate::vikunja_client::sharing::SharePermission;
+
+/// Who or what a project share grants access to
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShareTarget {
+    User { username: String },
+    Team { team_id: i64 },
+    Link { share_id: i64, url: String },
+}
+
+/// One row in the sharing list
+#[derive(Debug, Clone)]
+pub struct ShareEntry {
+    pub target: ShareTarget,
+    pub label: String,
+    pub permission: SharePermission,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProjectSharingMode {
+    Browse,
+    AddUser,
+    AddTeam,
+    AddLink,
+    ConfirmRemove,
+}
+
+/// Actions that require an API call, returned to the caller of `handle_key`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProjectSharingAction {
+    None,
+    Close,
+    Refresh,
+    AddUser { username: String, permission: SharePermission },
+    AddTeam { query: String, permission: SharePermission },
+    CreateLink { name: String, permission: SharePermission },
+    SetPermission { target: ShareTarget, permission: SharePermission },
+    Remove(ShareTarget),
+}
+
+/// Modal listing the users, teams and link shares of a project
+pub struct ProjectSharingModal {
+    pub project_id: i64,
+    pub project_name: String,
+    pub entries: Vec<ShareEntry>,
+    pub selected_index: usize,
+    pub mode: ProjectSharingMode,
+    pub input: String,
+    pub input_cursor: usize,
+    pub new_permission: SharePermission,
+    pub loading: bool,
+    pub status_message: String,
+    kill_ring: KillRing,
+}
+
+impl ProjectSharingModal {
+    pub fn new(project_id: i64, project_name: String) -> Self {
+        Self {
+            project_id,
+            project_name,
+            entries: Vec::new(),
+            selected_index: 0,
+            mode: ProjectSharingMode::Browse,
+            input: String::new(),
+            input_cursor: 0,
+            new_permission: SharePermission::Read,
+            loading: true,
+            status_message: "Loading shares...".to_string(),
+            kill_ring: KillRing::default(),
+        }
+    }
+
+    /// Replace the share list after loading, keeping the selection in bounds
+    pub fn set_entries(&mut self, entries: Vec<ShareEntry>) {
+        self.entries = entries;
+        self.selected_index = self.selected_index.min(self.entries.len().saturating_sub(1));
+        self.loading = false;
+    }
+
+    pub fn selected_entry(&self) -> Option<&ShareEntry> {
+        self.entries.get(self.selected_index)
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> ProjectSharingAction {
+        match self.mode {
+            ProjectSharingMode::Browse => self.handle_browse_key(key),
+            ProjectSharingMode::AddUser | ProjectSharingMode::AddTeam | ProjectSharingMode::AddLink => self.handle_input_key(key),
+            ProjectSharingMode::ConfirmRemove => match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.mode = ProjectSharingMode::Browse;
+                    match self.selected_entry() {
+                        Some(entry) => ProjectSharingAction::Remove(entry.target.clone()),
+                        None => ProjectSharingAction::None,
+                    }
+                }
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    self.mode = ProjectSharingMode::Browse;
+                    ProjectSharingAction::None
+                }
+                _ => ProjectSharingAction::None,
+            },
+        }
+    }
+
+    fn handle_browse_key(&mut self, key: &KeyEvent) -> ProjectSharingAction {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => ProjectSharingAction::Close,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                ProjectSharingAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected_index + 1 < self.entries.len() {
+                    self.selected_index += 1;
+                }
+                ProjectSharingAction::None
+            }
+            KeyCode::Char('u') => self.start_input(ProjectSharingMode::AddUser),
+            KeyCode::Char('t') => self.start_input(ProjectSharingMode::AddTeam),
+            KeyCode::Char('n') => self.start_input(ProjectSharingMode::AddLink),
+            KeyCode::Char('r') => ProjectSharingAction::Refresh,
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('p') | KeyCode::Left | KeyCode::Char('h') => {
+                let raise = !matches!(key.code, KeyCode::Left | KeyCode::Char('h'));
+                match self.selected_entry() {
+                    // Vikunja cannot change the permission of an existing link share
+                    Some(entry) if matches!(entry.target, ShareTarget::Link { .. }) => {
+                        self.status_message = "Link share permissions are fixed; revoke and create a new link".to_string();
+                        ProjectSharingAction::None
+                    }
+                    Some(entry) => {
+                        let permission = if raise { entry.permission.raised() } else { entry.permission.lowered() };
+                        match permission {
+                            Some(permission) => ProjectSharingAction::SetPermission { target: entry.target.clone(), permission },
+                            None => {
+                                self.status_message = format!("Already {}", entry.permission.label());
+                                ProjectSharingAction::None
+                            }
+                        }
+                    }
+                    None => ProjectSharingAction::None,
+                }
+            }
+            KeyCode::Char('d') => {
+                if self.selected_entry().is_some() {
+                    self.mode = ProjectSharingMode::ConfirmRemove;
+                }
+                ProjectSharingAction::None
+            }
+            _ => ProjectSharingAction::None,
+        }
+    }
+
+    fn start_input(&mut self, mode: ProjectSharingMode) -> ProjectSharingAction {
+        self.mode = mode;
+        self.input.clear();
+        self.new_permission = SharePermission::Read;
+        ProjectSharingAction::None
+    }
+
+    fn handle_input_key(&mut self, key: &KeyEvent) -> ProjectSharingAction {
+        match key.code {
+            KeyCode::Esc => {
+                self.cancel();
+                ProjectSharingAction::None
+            }
+            KeyCode::Tab => {
+                self.new_permission = self.new_permission.next();
+                ProjectSharingAction::None
+            }
+            KeyCode::BackTab => {
+                self.new_permission = self.new_permission.previous();
+                ProjectSharingAction::None
+            }
+            KeyCode::Enter => {
+                let value = self.input.trim().trim_start_matches('@').to_string();
+                let permission = self.new_permission;
+                let action = match self.mode {
+                    ProjectSharingMode::AddUser if value.is_empty() => {
+                        self.status_message = "Enter a username".to_string();
+                        return ProjectSharingAction::None;
+                    }
+                    ProjectSharingMode::AddTeam if value.is_empty() => {
+                        self.status_message = "Enter a team name".to_string();
+                        return ProjectSharingAction::None;
+                    }
+                    ProjectSharingMode::AddUser => ProjectSharingAction::AddUser { username: value, permission },
+                    ProjectSharingMode::AddTeam => ProjectSharingAction::AddTeam { query: value, permission },
+                    // Link names are optional
+                    ProjectSharingMode::AddLink => ProjectSharingAction::CreateLink { name: self.input.trim().to_string(), permission },
+                    _ => ProjectSharingAction::None,
+                };
+                self.cancel();
+                action
+            }
+            _ => {
+                LineEditor::new(&mut self.input, &mut self.input_cursor, &mut self.kill_ring).handle_key(key);
+                ProjectSharingAction::None
+            }
+        }
+    }
+
+    fn cancel(&mut self) {
+        self.mode = ProjectSharingMode::Browse;
+        self.input.clear();
+    }
+
+    fn permission_style(permission: SharePermission) -> Style {
+        match permission {
+            SharePermission::Read => Style::default().fg(Color::Gray),
+            SharePermission::ReadWrite => Style::default().fg(Color::Cyan),
+            SharePermission::Admin => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        }
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let width = (area.width * 80) / 100;
+        let height = (area.height * 80) / 100;
+        let x = (area.width - width) / 2 + area.x;
+        let y = (area.height - height) / 2 + area.y;
+        let modal_area = Rect::new(x, y, width, height);
+        f.render_widget(Clear, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(5),     // Shares
+                Constraint::Length(3),  // Input / status
+                Constraint::Length(1),  // Help
+            ])
+            .split(modal_area);
+
+        self.draw_share_list(f, chunks[0]);
+        self.draw_input_section(f, chunks[1]);
+
+        let help_text = match self.mode {
+            ProjectSharingMode::Browse => "↑↓: Select | ←→: Permission | u: Add user | t: Add team | n: New link | d: Remove | r: Reload | Esc: Close",
+            ProjectSharingMode::AddUser | ProjectSharingMode::AddTeam | ProjectSharingMode::AddLink => "Tab: Permission | Enter: Share | Esc: Cancel",
+            ProjectSharingMode::ConfirmRemove => "y: Confirm | n/Esc: Cancel",
+        };
+        let help = Paragraph::new(help_text)
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(help, chunks[2]);
+    }
+
+    fn draw_share_list(&self, f: &mut Frame, area: Rect) {
+        let visible = area.height.saturating_sub(2) as usize;
+        let offset = if visible > 0 && self.selected_index >= visible { self.selected_index + 1 - visible } else { 0 };
+
+        let mut lines = Vec::new();
+        if self.loading {
+            lines.push(Line::from(Span::styled("Loading...", Style::default().fg(Color::Gray))));
+        } else if self.entries.is_empty() {
+            lines.push(Line::from(Span::styled("Not shared with anyone yet", Style::default().fg(Color::Gray))));
+        }
+        for (i, entry) in self.entries.iter().enumerate().skip(offset).take(visible.max(1)) {
+            let is_selected = i == self.selected_index;
+            let marker = if is_selected { "▶ " } else { "  " };
+            let (kind, kind_color) = match entry.target {
+                ShareTarget::User { .. } => ("user", Color::Green),
+                ShareTarget::Team { .. } => ("team", Color::Blue),
+                ShareTarget::Link { .. } => ("link", Color::Magenta),
+            };
+            let label_style = if is_selected {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let mut spans = vec![
+                Span::styled(marker, Style::default().fg(Color::Yellow)),
+                Span::styled(format!("{:<5}", kind), Style::default().fg(kind_color)),
+                Span::styled(format!("{:<12}", entry.permission.label()), Self::permission_style(entry.permission)),
+                Span::styled(entry.label.clone(), label_style),
+            ];
+            if let ShareTarget::Link { ref url, .. } = entry.target {
+                spans.push(Span::styled(format!("  {}", url), Style::default().fg(Color::DarkGray)));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let block = Block::default()
+            .title(format!(" Sharing: {} ", self.project_name))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green));
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn draw_input_section(&self, f: &mut Frame, area: Rect) {
+        let (title, prefix) = match self.mode {
+            ProjectSharingMode::AddUser => (" Add user ", "Username: "),
+            ProjectSharingMode::AddTeam => (" Add team ", "Team: "),
+            ProjectSharingMode::AddLink => (" New link share ", "Name: "),
+            _ => (" Project Sharing ", ""),
+        };
+        let line = match self.mode {
+            ProjectSharingMode::Browse => Line::from(Span::styled(self.status_message.clone(), Style::default().fg(Color::Cyan))),
+            ProjectSharingMode::ConfirmRemove => {
+                let what = match self.selected_entry() {
+                    Some(ShareEntry { target: ShareTarget::Link { .. }, label, .. }) => format!("Revoke link share '{}'? y/n", label),
+                    Some(entry) => format!("Remove '{}' from {}? y/n", entry.label, self.project_name),
+                    None => String::new(),
+                };
+                Line::from(Span::styled(what, Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)))
+            }
+            _ => Line::from(vec![
+                Span::raw(prefix),
+                Span::styled(self.input.clone(), Style::default().fg(Color::Yellow)),
+                Span::raw("   "),
+                Span::styled(format!("[{}]", self.new_permission.label()), Self::permission_style(self.new_permission)),
+            ]),
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White));
+        f.render_widget(Paragraph::new(line).block(block), area);
+
+        if !prefix.is_empty() {
+            let before_cursor = &self.input[..clamp_cursor(&self.input, self.input_cursor)];
+            let cursor_x = area.x + 1 + display_width(prefix) as u16 + display_width(before_cursor) as u16;
+            if cursor_x < area.x + area.width - 1 {
+                f.set_cursor(cursor_x, area.y + 1);
+            }
+        }
+    }
+}
+
+/// Fetch users, teams and link shares of the modal's project and show them
+pub async fn load_project_shares(app: &mut App, api_client: &Arc<Mutex<VikunjaClient>>) {
+    let project_id = match app.project_sharing_modal {
+        Some(ref modal) => modal.project_id,
+        None => return,
+    };
+    let client = api_client.lock().await;
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    match client.get_project_users(project_id).await {
+        Ok(users) => entries.extend(users.into_iter().map(|u| ShareEntry {
+            label: match u.name.as_deref() {
+                Some(name) if !name.is_empty() => format!("{} (@{})", name, u.username),
+                _ => format!("@{}", u.username),
+            },
+            target: ShareTarget::User { username: u.username },
+            permission: SharePermission::from_i64(u.permission),
+        })),
+        Err(e) => errors.push(format!("users: {}", e)),
+    }
+    match client.get_project_teams(project_id).await {
+        Ok(teams) => entries.extend(teams.into_iter().map(|t| ShareEntry {
+            target: ShareTarget::Team { team_id: t.id },
+            label: t.name,
+            permission: SharePermission::from_i64(t.permission),
+        })),
+        Err(e) => errors.push(format!("teams: {}", e)),
+    }
+    match client.get_link_shares(project_id).await {
+        Ok(links) => entries.extend(links.into_iter().map(|l| ShareEntry {
+            label: l.name.filter(|n| !n.is_empty()).unwrap_or_else(|| "Unnamed link".to_string()),
+            target: ShareTarget::Link { share_id: l.id, url: client.link_share_url(&l.hash) },
+            permission: SharePermission::from_i64(l.permission),
+        })),
+        Err(e) => errors.push(format!("links: {}", e)),
+    }
+    drop(client);
+
+    for error in &errors {
+        app.add_debug_message(format!("Project sharing: failed to load {}", error));
+    }
+    if let Some(ref mut modal) = app.project_sharing_modal {
+        modal.status_message = if errors.is_empty() {
+            format!("{} shares", entries.len())
+        } else {
+            format!("Some shares could not be loaded ({})", errors.len())
+        };
+        modal.set_entries(entries);
+    }
+}
+
+pub async fn handle_project_sharing_modal(
+    app: &mut App,
+    key: &KeyEvent,
+    api_client: &Arc<Mutex<VikunjaClient>>,
+) {
+    let (action, project_id) = match app.project_sharing_modal {
+        Some(ref mut modal) => (modal.handle_key(key), modal.project_id),
+        None => return,
+    };
+    let client = api_client.lock().await;
+    let result: Result<String, Box<dyn std::error::Error>> = match action {
+        ProjectSharingAction::None => return,
+        ProjectSharingAction::Close => {
+            app.hide_project_sharing_modal();
+            return;
+        }
+        ProjectSharingAction::Refresh => Ok("Shares reloaded".to_string()),
+        ProjectSharingAction::AddUser { username, permission } => client
+            .share_project_with_user(project_id, &username, permission).await
+            .map(|_| format!("Shared with @{} ({})", username, permission.label())),
+        ProjectSharingAction::AddTeam { query, permission } => match client.search_teams(&query).await {
+            Ok(teams) => {
+                // Prefer an exact name match, otherwise require the search to be unambiguous
+                let team = teams.iter().find(|t| t.name.eq_ignore_ascii_case(&query))
+                    .or(if teams.len() == 1 { teams.first() } else { None });
+                match team {
+                    Some(team) => client
+                        .share_project_with_team(project_id, team.id, permission).await
+                        .map(|_| format!("Shared with team {} ({})", team.name, permission.label())),
+                    None if teams.is_empty() => Err(format!("No team matches '{}'", query).into()),
+                    None => Err(format!("'{}' matches {} teams, be more specific", query, teams.len()).into()),
+                }
+            }
+            Err(e) => Err(e),
+        },
+        ProjectSharingAction::CreateLink { name, permission } => client
+            .create_link_share(project_id, &name, permission).await
+            .map(|share| format!("Link created: {}", client.link_share_url(&share.hash))),
+        ProjectSharingAction::SetPermission { target, permission } => match target {
+            ShareTarget::User { ref username } => client
+                .update_project_user_permission(project_id, username, permission).await
+                .map(|_| format!("@{} now has {} access", username, permission.label())),
+            ShareTarget::Team { team_id } => client
+                .update_project_team_permission(project_id, team_id, permission).await
+                .map(|_| format!("Team now has {} access", permission.label())),
+            ShareTarget::Link { .. } => return,
+        },
+        ProjectSharingAction::Remove(target) => match target {
+            ShareTarget::User { ref username } => client
+                .remove_project_user(project_id, username).await
+                .map(|_| format!("Removed @{}", username)),
+            ShareTarget::Team { team_id } => client
+                .remove_project_team(project_id, team_id).await
+                .map(|_| "Removed team".to_string()),
+            ShareTarget::Link { share_id, .. } => client
+                .delete_link_share(project_id, share_id).await
+                .map(|_| "Link share revoked".to_string()),
+        },
+    };
+    drop(client);
+
+    match result {
+        Ok(message) => {
+            load_project_shares(app, api_client).await;
+            app.add_debug_message(format!("Project sharing: {}", message));
+            if let Some(ref mut modal) = app.project_sharing_modal {
+                modal.status_message = message;
+            }
+        }
+        Err(e) => {
+            app.add_debug_message(format!("Project sharing failed: {}", e));
+            if let Some(ref mut modal) = app.project_sharing_modal {
+                modal.status_message = format!("Failed: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn modal_with_entries() -> ProjectSharingModal {
+        let mut modal = ProjectSharingModal::new(1, "Inbox".to_string());
+        modal.set_entries(vec![
+            ShareEntry { target: ShareTarget::User { username: "sam".to_string() }, label: "@sam".to_string(), permission: SharePermission::Read },
+            ShareEntry { target: ShareTarget::Link { share_id: 9, url: String::new() }, label: "Public".to_string(), permission: SharePermission::Read },
+        ]);
+        modal
+    }
+
+    #[test]
+    fn test_add_user_with_permission() {
+        let mut modal = modal_with_entries();
+        modal.handle_key(&key(KeyCode::Char('u')));
+        for c in "@alex".chars() {
+            modal.handle_key(&key(KeyCode::Char(c)));
+        }
+        modal.handle_key(&key(KeyCode::Tab));
+        assert_eq!(
+            modal.handle_key(&key(KeyCode::Enter)),
+            ProjectSharingAction::AddUser { username: "alex".to_string(), permission: SharePermission::ReadWrite }
+        );
+        assert_eq!(modal.mode, ProjectSharingMode::Browse);
+    }
+
+    #[test]
+    fn test_change_permission_and_remove() {
+        let mut modal = modal_with_entries();
+        // Lowering stops at read instead of wrapping around to admin
+        assert_eq!(modal.handle_key(&key(KeyCode::Left)), ProjectSharingAction::None);
+        assert_eq!(modal.entries[0].permission, SharePermission::Read);
+        assert_eq!(
+            modal.handle_key(&key(KeyCode::Right)),
+            ProjectSharingAction::SetPermission { target: ShareTarget::User { username: "sam".to_string() }, permission: SharePermission::ReadWrite }
+        );
+        // Link shares cannot be changed, only revoked
+        modal.handle_key(&key(KeyCode::Down));
+        assert_eq!(modal.handle_key(&key(KeyCode::Right)), ProjectSharingAction::None);
+        modal.handle_key(&key(KeyCode::Char('d')));
+        assert_eq!(modal.handle_key(&key(KeyCode::Char('y'))), ProjectSharingAction::Remove(ShareTarget::Link { share_id: 9, url: String::new() }));
+    }
+}
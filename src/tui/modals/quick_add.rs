@@ -1,11 +1,16 @@
 // Quick Add Modal event handler split from modals.rs
 use crate::tui::app::state::App;
+use crate::tui::app::undoable_action::UndoableAction;
+use crate::tui::input_history::{HistoryKind, Recall};
+use crate::tui::line_editor::EditOutcome;
 use crate::tui::app::suggestion_mode::SuggestionMode;
+use crate::tui::suggestions::suggestion_context;
 use crossterm::event::{KeyEvent, KeyModifiers};
 use crate::vikunja_client::VikunjaClient;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use crate::debug::debug_log;
+use crate::vikunja_parser::{parse_outline, OutlineEntry};
 use chrono::Local;
 
 pub async fn handle_quick_add_modal(
@@ -16,12 +21,18 @@ pub async fn handle_quick_add_modal(
 ) {
     use crossterm::event::KeyCode;
     
+    // Ctrl+P/Ctrl+N step through earlier lines
+    if let Some(direction) = Recall::from_key(key) {
+        app.recall_quick_add_history(direction);
+        return;
+    }
+
     // Handle Ctrl+Z (undo) and Ctrl+Y (redo) in quick add modal
     if key.modifiers.contains(KeyModifiers::CONTROL) {
         match key.code {
             KeyCode::Char('z') => {
                 debug_log("Quick Add Modal: Undo requested (Ctrl+Z)");
-                if let Some(_) = app.undo_last_action() {
+                if app.undo_last_action_async(&*api_client.lock().await).await.is_some() {
                     debug_log("Quick Add Modal: Undo successful");
                 } else {
                     debug_log("Quick Add Modal: No action to undo");
@@ -30,7 +41,7 @@ pub async fn handle_quick_add_modal(
             },
             KeyCode::Char('y') => {
                 debug_log("Quick Add Modal: Redo requested (Ctrl+Y)");
-                if let Some(_) = app.redo_last_action() {
+                if app.redo_last_action_async(&*api_client.lock().await).await.is_some() {
                     debug_log("Quick Add Modal: Redo successful");
                 } else {
                     debug_log("Quick Add Modal: No action to redo");
@@ -43,7 +54,7 @@ pub async fn handle_quick_add_modal(
     
     match key.code {
         KeyCode::Esc => {
-            app.hide_quick_add_modal();
+            app.cancel_quick_add_modal();
         },
         KeyCode::Enter => {
             let original_input = app.get_quick_add_input().to_string();
@@ -204,58 +215,13 @@ pub async fn handle_quick_add_modal(
                 app.quick_add_cursor_position = input.len();
                 app.update_suggestions(&input, input.len());
             }
-            // Check if we should auto-complete or submit the task
-            let should_autocomplete = if app.suggestion_mode.is_some() && !app.suggestions.is_empty() {
-                // Only auto-complete if the current text exactly matches a suggestion prefix
-                // This prevents auto-completing when the user has typed a complete, valid label
-                let prefix = &app.suggestion_prefix;
-                
-                // If the suggestion prefix is not an exact match to any existing label/project,
-                // then we should auto-complete. If it is an exact match, the user might want to submit.
-                let is_exact_match = match app.suggestion_mode {
-                    Some(SuggestionMode::Label) => {
-                        app.label_map.values().any(|label| label.to_lowercase() == prefix.to_lowercase())
-                    },
-                    Some(SuggestionMode::Project) => {
-                        app.project_map.values().any(|project| project.to_lowercase() == prefix.to_lowercase())
-                    },
-                    _ => false
-                };
-                
-                // Auto-complete if it's not an exact match, or if the first suggestion is different from the prefix
-                !is_exact_match && !app.suggestions.is_empty() && app.suggestions[0].to_lowercase() != prefix.to_lowercase()
-            } else {
-                false
-            };
-            
-            if should_autocomplete {
+            // Complete a half-typed suggestion first; Enter again submits
+            if app.should_autocomplete_on_enter() {
                 debug_log(&format!("Auto-completing suggestion: {}", app.suggestions[app.selected_suggestion]));
-                let suggestion = app.suggestions[app.selected_suggestion].clone();
-                let cursor = app.quick_add_cursor_position;
-                let input = app.get_quick_add_input();
-                if let Some(pos) = input[..cursor].rfind(|c| c == '*' || c == '+') {
-                    let mut new_input = String::new();
-                    new_input.push_str(&input[..pos]); // Include everything up to but not including the * or +
-                    new_input.push(input.chars().nth(pos).unwrap()); // Add the * or + character
-                    
-                    // Wrap multi-word suggestions in square brackets for proper parsing
-                    if suggestion.contains(' ') {
-                        new_input.push_str(&format!("[{}]", suggestion));
-                    } else {
-                        new_input.push_str(&suggestion);
-                    }
-                    
-                    if input.get(cursor..cursor+1).map_or(true, |c| c == " " || c == "") {
-                        new_input.push(' ');
-                        new_input.push_str(&input[cursor..]);
-                        app.quick_add_cursor_position = pos + 1 + 
-                            (if suggestion.contains(' ') { suggestion.len() + 2 } else { suggestion.len() }) + 1;
-                    } else {
-                        new_input.push_str(&input[cursor..]);
-                        app.quick_add_cursor_position = pos + 1 + 
-                            (if suggestion.contains(' ') { suggestion.len() + 2 } else { suggestion.len() });
-                    }
-                    app.quick_add_input = new_input;
+                let input = app.quick_add_input.clone();
+                if let Some((completed, cursor)) = app.accept_suggestion(&input, app.quick_add_cursor_position) {
+                    app.quick_add_input = completed;
+                    app.quick_add_cursor_position = cursor;
                 }
                 let input = app.quick_add_input.clone();
                 let cursor = app.quick_add_cursor_position;
@@ -268,65 +234,23 @@ pub async fn handle_quick_add_modal(
             if !input.trim().is_empty() {
                 debug_log(&format!("QUICK_ADD: Creating task with input: '{}'", input));
                 debug_log(&format!("QUICK_ADD: Input length: {}, trimmed length: {}", input.len(), input.trim().len()));
+                app.remember_quick_add(&input);
                 app.hide_quick_add_modal();
-                let default_project_name = app.get_active_default_project();
-                debug_log(&format!("QUICK_ADD: Active default project: '{}'", default_project_name));
-                debug_log(&format!("QUICK_ADD: Project override active: {:?}", app.active_project_override));
-                debug_log(&format!("QUICK_ADD: Current filter ID: {:?}", app.current_filter_id));
                 let api_client_guard = api_client.lock().await;
-                let default_project_id: Option<u64>;
-                // Try to resolve project name to ID
-                match app.project_map.iter().find_map(|(id, name)| {
-                    if name.trim().eq_ignore_ascii_case(&default_project_name) { Some(*id) } else { None }
-                }) {
-                    Some(id) => {
-                        default_project_id = Some(id as u64);
-                        debug_log(&format!("QUICK_ADD: Resolved project '{}' to ID {} via project_map", default_project_name, id));
-                    },
-                    None => {
-                        debug_log(&format!("QUICK_ADD: Project '{}' not found in project_map, trying API lookup...", default_project_name));
-                        match api_client_guard.find_or_get_project_id(&default_project_name).await {
-                            Ok(Some(api_id)) => {
-                                default_project_id = Some(api_id as u64);
-                                debug_log(&format!("QUICK_ADD: Resolved project '{}' to ID {} via API", default_project_name, api_id));
-                            },
-                            Ok(None) => {
-                                debug_log(&format!("QUICK_ADD ERROR: Project '{}' not found via API, falling back to project ID 1", default_project_name));
-                                default_project_id = Some(1);
-                            },
-                            Err(e) => {
-                                debug_log(&format!("QUICK_ADD ERROR: Exception while looking up project '{}': {}. Falling back to project ID 1", default_project_name, e));
-                                default_project_id = Some(1);
-                            }
-                        }
-                    }
-                }
-                let default_project_id = default_project_id.unwrap_or(1);
-                debug_log(&format!("QUICK_ADD: Using default project ID: {} (name: '{}')", default_project_id, default_project_name));
+                let default_project_id = resolve_default_project_id(app, &api_client_guard).await;
                 debug_log("QUICK_ADD: Calling create_task_with_magic...");
                 match api_client_guard.create_task_with_magic(&input, default_project_id as i64).await {
                     Ok(task) => {
                         debug_log(&format!("SUCCESS: Task created successfully! ID: {:?}, Title: '{}'", task.id, task.title));
+                        if let Some(task_id) = task.id {
+                            app.add_to_undo_stack(UndoableAction::TaskCreation { task_id: task_id as i64 });
+                        }
                         app.flash_task_id = task.id.map(|id| id as i64);
                         app.flash_start = Some(Local::now());
                         app.flash_cycle_count = 0;
                         app.flash_cycle_max = 6;
                         drop(api_client_guard);
-                        let (tasks, project_map, project_colors) = client_clone.lock().await.get_tasks_with_projects().await.unwrap_or_default();
-                        app.all_tasks = tasks;
-                        app.project_map = project_map;
-                        app.project_colors = project_colors;
-                        app.apply_task_filter();
-                        debug_log(&format!("Tasks refreshed. Total tasks: {}", app.tasks.len()));
-                        if let Some(new_id) = task.id.map(|id| id as i64) {
-                            if let Some(idx) = app.tasks.iter().position(|t| t.id == new_id) {
-                                app.selected_task_index = idx;
-                                app.flash_task_id = Some(new_id);
-                                app.flash_start = Some(Local::now());
-                                app.flash_cycle_count = 0;
-                                app.flash_cycle_max = 6;
-                            }
-                        }
+                        refresh_and_select(app, client_clone, task.id).await;
                     }
                     Err(e) => {
                         debug_log(&format!("ERROR: Failed to create task: {}", e));
@@ -337,36 +261,12 @@ pub async fn handle_quick_add_modal(
             }
         },
         KeyCode::Tab => {
-            if app.suggestion_mode.is_some() && !app.suggestions.is_empty() {
-                let suggestion = app.suggestions[app.selected_suggestion].clone();
-                let cursor = app.quick_add_cursor_position;
-                let input = app.get_quick_add_input();
-                if let Some(pos) = input[..cursor].rfind(|c| c == '*' || c == '+') {
-                    let mut new_input = String::new();
-                    new_input.push_str(&input[..pos]); // Include everything up to but not including the * or +
-                    new_input.push(input.chars().nth(pos).unwrap()); // Add the * or + character
-                    
-                    // Wrap multi-word suggestions in square brackets for proper parsing
-                    if suggestion.contains(' ') {
-                        new_input.push_str(&format!("[{}]", suggestion));
-                    } else {
-                        new_input.push_str(&suggestion);
-                    }
-                    
-                    if input.get(cursor..cursor+1).map_or(true, |c| c == " " || c == "") {
-                        new_input.push(' ');
-                        new_input.push_str(&input[cursor..]);
-                        app.quick_add_cursor_position = pos + 1 + 
-                            (if suggestion.contains(' ') { suggestion.len() + 2 } else { suggestion.len() }) + 1;
-                    } else {
-                        new_input.push_str(&input[cursor..]);
-                        app.quick_add_cursor_position = pos + 1 + 
-                            (if suggestion.contains(' ') { suggestion.len() + 2 } else { suggestion.len() });
-                    }
-                    app.quick_add_input = new_input;
-                }
+            // Repeated Tab cycles through the suggestions
+            let input = app.quick_add_input.clone();
+            if let Some((completed, cursor)) = app.tab_complete(&input, app.quick_add_cursor_position) {
+                app.quick_add_input = completed;
+                app.quick_add_cursor_position = cursor;
                 let input = app.quick_add_input.clone();
-                let cursor = app.quick_add_cursor_position;
                 app.update_suggestions(&input, cursor);
             }
         },
@@ -376,6 +276,8 @@ pub async fn handle_quick_add_modal(
                 let input = app.quick_add_input.clone();
                 let cursor = app.quick_add_cursor_position;
                 app.update_suggestions(&input, cursor);
+            } else {
+                app.recall_quick_add_history(Recall::Newer);
             }
         },
         KeyCode::Up => {
@@ -388,32 +290,128 @@ pub async fn handle_quick_add_modal(
                 let input = app.quick_add_input.clone();
                 let cursor = app.quick_add_cursor_position;
                 app.update_suggestions(&input, cursor);
+            } else {
+                app.recall_quick_add_history(Recall::Older);
             }
         },
-        KeyCode::Backspace => {
-            app.delete_char_from_quick_add();
-            let input = app.quick_add_input.clone();
-            let cursor = app.quick_add_cursor_position;
-            app.update_suggestions(&input, cursor);
-        },
-        KeyCode::Left => {
-            app.move_cursor_left();
-            let input = app.quick_add_input.clone();
-            let cursor = app.quick_add_cursor_position;
-            app.update_suggestions(&input, cursor);
-        },
-        KeyCode::Right => {
-            app.move_cursor_right();
-            let input = app.quick_add_input.clone();
-            let cursor = app.quick_add_cursor_position;
-            app.update_suggestions(&input, cursor);
+        _ => {
+            let outcome = app.quick_add_editor().handle_key(key);
+            if outcome == EditOutcome::Changed {
+                app.quick_add_history.reset();
+            }
+            if outcome.is_handled() {
+                let input = app.quick_add_input.clone();
+                let cursor = app.quick_add_cursor_position;
+                app.update_suggestions(&input, cursor);
+                if outcome == EditOutcome::Changed {
+                    search_assignee_suggestions(app, &input, cursor);
+                }
+            }
         },
-        KeyCode::Char(c) => {
-            app.add_char_to_quick_add(c);
-            let input = app.quick_add_input.clone();
-            let cursor = app.quick_add_cursor_position;
-            app.update_suggestions(&input, cursor);
+    }
+}
+
+/// While an `@user` is typed, queue a server search for it once typing pauses; users
+/// who never appeared on a task aren't known otherwise
+pub(crate) fn search_assignee_suggestions(app: &mut App, input: &str, cursor: usize) {
+    match suggestion_context(&input[..cursor]) {
+        Some(context) if context.mode == SuggestionMode::Assignee => app.queue_user_search(&context.prefix),
+        _ => app.pending_user_search = None,
+    }
+}
+
+/// Id of the project new tasks go to unless they name one: the active default
+/// project, found in the project map or else through the API
+pub(crate) async fn resolve_default_project_id(app: &App, api_client: &VikunjaClient) -> u64 {
+    let default_project_name = app.get_active_default_project();
+    debug_log(&format!("QUICK_ADD: Active default project: '{}'", default_project_name));
+    debug_log(&format!("QUICK_ADD: Project override active: {:?}", app.active_project_override));
+    debug_log(&format!("QUICK_ADD: Current filter ID: {:?}", app.current_filter_id));
+    let default_project_id: Option<u64>;
+    // Try to resolve project name to ID
+    match app.project_map.iter().find_map(|(id, name)| {
+        if name.trim().eq_ignore_ascii_case(&default_project_name) { Some(*id) } else { None }
+    }) {
+        Some(id) => {
+            default_project_id = Some(id as u64);
+            debug_log(&format!("QUICK_ADD: Resolved project '{}' to ID {} via project_map", default_project_name, id));
         },
-        _ => {},
+        None => {
+            debug_log(&format!("QUICK_ADD: Project '{}' not found in project_map, trying API lookup...", default_project_name));
+            match api_client.find_or_get_project_id(&default_project_name).await {
+                Ok(Some(api_id)) => {
+                    default_project_id = Some(api_id as u64);
+                    debug_log(&format!("QUICK_ADD: Resolved project '{}' to ID {} via API", default_project_name, api_id));
+                },
+                Ok(None) => {
+                    debug_log(&format!("QUICK_ADD ERROR: Project '{}' not found via API, falling back to project ID 1", default_project_name));
+                    default_project_id = Some(1);
+                },
+                Err(e) => {
+                    debug_log(&format!("QUICK_ADD ERROR: Exception while looking up project '{}': {}. Falling back to project ID 1", default_project_name, e));
+                    default_project_id = Some(1);
+                }
+            }
+        }
+    }
+    let default_project_id = default_project_id.unwrap_or(1);
+    debug_log(&format!("QUICK_ADD: Using default project ID: {} (name: '{}')", default_project_id, default_project_name));
+    default_project_id
+}
+
+/// Reload tasks after creating some and select and flash `new_id`
+pub(crate) async fn refresh_and_select(app: &mut App, client: &Arc<Mutex<VikunjaClient>>, new_id: Option<u64>) {
+    let (tasks, project_map, project_colors) = client.lock().await.get_tasks_with_projects().await.unwrap_or_default();
+    app.all_tasks = tasks;
+    app.project_map = project_map;
+    app.project_colors = project_colors;
+    app.apply_task_filter();
+    debug_log(&format!("Tasks refreshed. Total tasks: {}", app.tasks.len()));
+    if let Some(new_id) = new_id.map(|id| id as i64) {
+        if let Some(idx) = app.tasks.iter().position(|t| t.id == new_id) {
+            app.selected_task_index = idx;
+            app.flash_task_id = Some(new_id);
+            app.flash_start = Some(Local::now());
+            app.flash_cycle_count = 0;
+            app.flash_cycle_max = 6;
+        }
+    }
+}
+
+/// A multi-line paste into quick add is an outline: every line becomes a task, nested
+/// lines subtasks of the line above them. Returns false for single lines, which are
+/// typed into the input as usual.
+pub async fn handle_quick_add_paste(app: &mut App, text: &str) -> bool {
+    // Whatever was typed before pasting starts the first line
+    let text = format!("{}{}", app.get_quick_add_input(), text);
+    let outline = parse_outline(&text);
+    if outline.len() < 2 {
+        return false;
+    }
+    app.input_history.clear_draft(HistoryKind::QuickAdd, 0);
+    app.hide_quick_add_modal();
+    app.show_paste_preview_modal(outline);
+    true
+}
+
+/// Create the tasks of a pasted outline, nested lines as subtasks of the line above
+pub(crate) async fn create_outline(app: &mut App, outline: &[OutlineEntry], client: &Arc<Mutex<VikunjaClient>>) {
+    let client_guard = client.lock().await;
+    let default_project_id = resolve_default_project_id(app, &client_guard).await;
+    match client_guard.create_outline_with_magic(outline, default_project_id as i64).await {
+        Ok(created) => {
+            drop(client_guard);
+            app.show_toast(format!("Created {} tasks", created.len()));
+            for task_id in created.iter().filter_map(|t| t.id) {
+                app.add_to_undo_stack(UndoableAction::TaskCreation { task_id: task_id as i64 });
+            }
+            refresh_and_select(app, client, created.first().and_then(|t| t.id)).await;
+        }
+        Err(e) => {
+            drop(client_guard);
+            debug_log(&format!("ERROR: Failed to create outline: {}", e));
+            app.show_toast(format!("Failed to create tasks: {}", e));
+            refresh_and_select(app, client, None).await;
+        }
     }
 }
@@ -0,0 +1,174 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::tui::app::state::App;
+use crate::vikunja::models::Task;
+use crate::vikunja_client::VikunjaClient;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RescheduleAction {
+    None,
+    Close,
+    Apply,
+}
+
+/// An overdue task and where rescheduling puts it
+pub struct RescheduledTask {
+    pub before: Task,
+    pub after: Task,
+    /// Unchecked tasks are left as they are
+    pub included: bool,
+}
+
+/// Preview of moving every overdue task to today before anything is saved
+pub struct RescheduleModal {
+    pub tasks: Vec<RescheduledTask>,
+    pub selected_index: usize,
+}
+
+impl RescheduleModal {
+    pub fn new(plan: Vec<(Task, Task)>) -> Self {
+        let tasks = plan.into_iter()
+            .map(|(before, after)| RescheduledTask { before, after, included: true })
+            .collect();
+        Self { tasks, selected_index: 0 }
+    }
+
+    /// The checked tasks, before and after
+    pub fn changes(&self) -> Vec<(Task, Task)> {
+        self.tasks.iter().filter(|t| t.included).map(|t| (t.before.clone(), t.after.clone())).collect()
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> RescheduleAction {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => RescheduleAction::Close,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                RescheduleAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected_index + 1 < self.tasks.len() {
+                    self.selected_index += 1;
+                }
+                RescheduleAction::None
+            }
+            KeyCode::Char(' ') | KeyCode::Char('x') => {
+                if let Some(task) = self.tasks.get_mut(self.selected_index) {
+                    task.included = !task.included;
+                }
+                RescheduleAction::None
+            }
+            KeyCode::Enter if self.tasks.iter().any(|t| t.included) => RescheduleAction::Apply,
+            _ => RescheduleAction::None,
+        }
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect, app: &App) {
+        let width = (area.width * 80) / 100;
+        let height = ((self.tasks.len() as u16) + 4).min((area.height * 80) / 100).max(8);
+        let x = (area.width - width) / 2 + area.x;
+        let y = (area.height.saturating_sub(height)) / 2 + area.y;
+        let modal_area = Rect::new(x, y, width, height.min(area.height));
+        f.render_widget(Clear, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),     // Tasks
+                Constraint::Length(1),  // Help
+            ])
+            .split(modal_area);
+
+        let settings = &app.date_settings;
+        let show = |date: Option<chrono::DateTime<chrono::Utc>>| date.map(|d| settings.format(d, settings.due_time)).unwrap_or_default();
+        let visible = chunks[0].height.saturating_sub(2) as usize;
+        let offset = if visible > 0 && self.selected_index >= visible { self.selected_index + 1 - visible } else { 0 };
+        let mut lines = Vec::new();
+        for (i, task) in self.tasks.iter().enumerate().skip(offset).take(visible.max(1)) {
+            let is_selected = i == self.selected_index;
+            let (box_text, box_color) = if task.included { ("☑ ", Color::Green) } else { ("☐ ", Color::DarkGray) };
+            let mut title_style = if task.included { Style::default().fg(Color::White) } else { Style::default().fg(Color::DarkGray) };
+            if is_selected {
+                title_style = title_style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+            }
+            let mut spans = vec![
+                Span::styled(if is_selected { "▶ " } else { "  " }, Style::default().fg(Color::Yellow)),
+                Span::styled(box_text, Style::default().fg(box_color)),
+                Span::styled(task.before.title.clone(), title_style),
+                Span::styled(format!("  {} → {}", show(task.before.due_date), show(task.after.due_date)), Style::default().fg(Color::Cyan)),
+            ];
+            if task.before.start_date != task.after.start_date {
+                spans.push(Span::styled(format!("  start {} → {}", show(task.before.start_date), show(task.after.start_date)), Style::default().fg(Color::DarkGray)));
+            }
+            lines.push(Line::from(spans));
+        }
+        let included = self.tasks.iter().filter(|t| t.included).count();
+        let block = Block::default()
+            .title(format!(" Reschedule overdue to today ({} of {}) ", included, self.tasks.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red));
+        f.render_widget(Paragraph::new(lines).block(block), chunks[0]);
+
+        let help = Paragraph::new("↑↓/jk: Select | Space: Include/skip | Enter: Reschedule | Esc: Cancel")
+            .style(Style::default().fg(Color::Gray).bg(Color::Black))
+            .alignment(Alignment::Center);
+        f.render_widget(help, chunks[1]);
+    }
+}
+
+pub async fn handle_reschedule_modal(
+    app: &mut App,
+    key: &KeyEvent,
+    api_client: &Arc<Mutex<VikunjaClient>>,
+) {
+    let changes = match app.reschedule_modal {
+        Some(ref mut modal) => match modal.handle_key(key) {
+            RescheduleAction::None => return,
+            RescheduleAction::Close => {
+                app.hide_reschedule_modal();
+                return;
+            }
+            RescheduleAction::Apply => modal.changes(),
+        },
+        None => return,
+    };
+    let client = api_client.lock().await;
+    let (saved, error) = app.reschedule_tasks_async(&client, &changes).await;
+    drop(client);
+    app.hide_reschedule_modal();
+    app.show_toast(match error {
+        None => format!("Rescheduled {} overdue task{} to today", saved, if saved == 1 { "" } else { "s" }),
+        Some(e) => format!("Rescheduled {} of {} tasks; failed: {}", saved, changes.len(), e),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_skipping_tasks() {
+        let task = |id| Task { id, ..Default::default() };
+        let mut modal = RescheduleModal::new(vec![(task(1), task(1)), (task(2), task(2))]);
+        modal.handle_key(&key(KeyCode::Char(' ')));
+        assert_eq!(modal.changes().iter().map(|(t, _)| t.id).collect::<Vec<_>>(), vec![2]);
+        modal.handle_key(&key(KeyCode::Char('j')));
+        modal.handle_key(&key(KeyCode::Char(' ')));
+        assert_eq!(modal.handle_key(&key(KeyCode::Enter)), RescheduleAction::None);
+        modal.handle_key(&key(KeyCode::Char(' ')));
+        assert_eq!(modal.handle_key(&key(KeyCode::Enter)), RescheduleAction::Apply);
+        assert_eq!(modal.handle_key(&key(KeyCode::Esc)), RescheduleAction::Close);
+    }
+}
@@ -0,0 +1,265 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::dates::DateSettings;
+use crate::tui::app::snooze::snoozed_task;
+use crate::tui::app::state::App;
+use crate::tui::line_editor::{clamp_cursor, display_width, KillRing, LineEditor};
+use crate::vikunja::models::Task;
+use crate::vikunja_client::VikunjaClient;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnoozeAction {
+    None,
+    Close,
+    Snooze(String),
+}
+
+/// Modal offering the configured snooze choices and a date of your own, for the
+/// marked tasks or the selected one
+#[derive(Default)]
+pub struct SnoozeModal {
+    pub task_ids: Vec<i64>,
+    /// The task's title when there is just one
+    pub title: Option<String>,
+    pub options: Vec<String>,
+    /// With one task, where each choice puts its due date
+    pub previews: Vec<Option<String>>,
+    /// Index into `options`; one past the end is "pick a date"
+    pub selected_index: usize,
+    /// Keys go to the date input instead of the list
+    pub picking_date: bool,
+    pub input: String,
+    pub input_cursor: usize,
+    pub status_message: String,
+    kill_ring: KillRing,
+}
+
+impl SnoozeModal {
+    pub fn new(tasks: &[Task], options: Vec<String>, settings: &DateSettings) -> Self {
+        let previews = match tasks {
+            [task] => options.iter()
+                .map(|option| {
+                    let due = snoozed_task(settings, task, option).ok()?.due_date?;
+                    Some(settings.local(due).format("%a %Y-%m-%d").to_string())
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        Self {
+            task_ids: tasks.iter().map(|t| t.id).collect(),
+            title: match tasks {
+                [task] => Some(task.title.clone()),
+                _ => None,
+            },
+            options,
+            previews,
+            ..Self::default()
+        }
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> SnoozeAction {
+        if self.picking_date {
+            return match key.code {
+                KeyCode::Esc => {
+                    self.picking_date = false;
+                    SnoozeAction::None
+                }
+                KeyCode::Enter if !self.input.trim().is_empty() => SnoozeAction::Snooze(self.input.trim().to_string()),
+                _ => {
+                    LineEditor::new(&mut self.input, &mut self.input_cursor, &mut self.kill_ring).handle_key(key);
+                    SnoozeAction::None
+                }
+            };
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => SnoozeAction::Close,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                SnoozeAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected_index < self.options.len() {
+                    self.selected_index += 1;
+                }
+                SnoozeAction::None
+            }
+            KeyCode::Char(c @ '1'..='9') => match self.options.get(c as usize - '1' as usize) {
+                Some(option) => SnoozeAction::Snooze(option.clone()),
+                None => SnoozeAction::None,
+            },
+            KeyCode::Char('p') => {
+                self.picking_date = true;
+                SnoozeAction::None
+            }
+            KeyCode::Enter => match self.options.get(self.selected_index) {
+                Some(option) => SnoozeAction::Snooze(option.clone()),
+                None => {
+                    self.picking_date = true;
+                    SnoozeAction::None
+                }
+            },
+            _ => SnoozeAction::None,
+        }
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let width = (area.width * 60) / 100;
+        let height = ((self.options.len() as u16) + 10).min((area.height * 80) / 100).max(10);
+        let x = (area.width - width) / 2 + area.x;
+        let y = (area.height.saturating_sub(height)) / 2 + area.y;
+        let modal_area = Rect::new(x, y, width, height.min(area.height));
+        f.render_widget(Clear, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),     // Choices
+                Constraint::Length(3),  // Date input
+                Constraint::Length(1),  // Status
+                Constraint::Length(1),  // Help
+            ])
+            .split(modal_area);
+
+        let mut lines = Vec::new();
+        let labels = self.options.iter().map(String::as_str).chain(std::iter::once("Pick a date…"));
+        for (i, label) in labels.enumerate() {
+            let is_selected = i == self.selected_index && !self.picking_date;
+            let mut text_style = Style::default().fg(Color::White);
+            if is_selected {
+                text_style = text_style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+            }
+            let key = if i < self.options.len() && i < 9 { format!("{} ", i + 1) } else if i == self.options.len() { "p ".to_string() } else { "  ".to_string() };
+            let mut spans = vec![
+                Span::styled(if is_selected { "▶ " } else { "  " }, Style::default().fg(Color::Yellow)),
+                Span::styled(key, Style::default().fg(Color::DarkGray)),
+                Span::styled(label.to_string(), text_style),
+            ];
+            if let Some(Some(preview)) = self.previews.get(i) {
+                spans.push(Span::styled(format!("  → {}", preview), Style::default().fg(Color::Cyan)));
+            }
+            lines.push(Line::from(spans));
+        }
+        let title = match self.title {
+            Some(ref title) => format!(" Snooze: {} ", title),
+            None => format!(" Snooze {} tasks ", self.task_ids.len()),
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta));
+        f.render_widget(Paragraph::new(lines).block(block), chunks[0]);
+
+        let border = if self.picking_date { Color::Yellow } else { Color::DarkGray };
+        let input = Paragraph::new(self.input.clone())
+            .block(Block::default().title(" Date (\"friday\", \"+3d\", \"2025-03-04\") ").borders(Borders::ALL).border_style(Style::default().fg(border)));
+        f.render_widget(input, chunks[1]);
+        if self.picking_date {
+            let before_cursor = &self.input[..clamp_cursor(&self.input, self.input_cursor)];
+            let cursor_x = chunks[1].x + 1 + display_width(before_cursor) as u16;
+            if cursor_x < chunks[1].x + chunks[1].width - 1 {
+                f.set_cursor(cursor_x, chunks[1].y + 1);
+            }
+        }
+
+        let status = Paragraph::new(self.status_message.clone())
+            .style(Style::default().fg(Color::Cyan).bg(Color::Black));
+        f.render_widget(status, chunks[2]);
+        let help_text = if self.picking_date {
+            "Enter: Snooze | Esc: Back to choices"
+        } else {
+            "↑↓/jk: Select | 1-9/Enter: Snooze | p: Pick a date | Esc: Close"
+        };
+        let help = Paragraph::new(help_text)
+            .style(Style::default().fg(Color::Gray).bg(Color::Black))
+            .alignment(Alignment::Center);
+        f.render_widget(help, chunks[3]);
+    }
+}
+
+pub async fn handle_snooze_modal(
+    app: &mut App,
+    key: &KeyEvent,
+    api_client: &Arc<Mutex<VikunjaClient>>,
+) {
+    let (task_ids, target) = match app.snooze_modal {
+        Some(ref mut modal) => match modal.handle_key(key) {
+            SnoozeAction::None => return,
+            SnoozeAction::Close => {
+                app.hide_snooze_modal();
+                return;
+            }
+            SnoozeAction::Snooze(target) => (modal.task_ids.clone(), target),
+        },
+        None => return,
+    };
+
+    let tasks: Vec<_> = task_ids.iter()
+        .filter_map(|id| app.all_tasks.iter().chain(app.tasks.iter()).find(|t| t.id == *id).cloned())
+        .collect();
+    let changes: Result<Vec<_>, String> = tasks.into_iter()
+        .map(|task| snoozed_task(&app.date_settings, &task, &target).map(|snoozed| (task, snoozed)))
+        .collect();
+    let changes = match changes {
+        Ok(changes) => changes,
+        Err(e) => {
+            if let Some(ref mut modal) = app.snooze_modal {
+                modal.status_message = e;
+            }
+            return;
+        }
+    };
+
+    let client = api_client.lock().await;
+    let (saved, error) = app.reschedule_tasks_async(&client, &changes).await;
+    drop(client);
+    app.hide_snooze_modal();
+    app.clear_marks();
+    app.show_toast(match error {
+        None => format!("Snoozed {} task{} ({})", saved, if saved == 1 { "" } else { "s" }, target),
+        Some(e) => format!("Snoozed {} of {} tasks; failed: {}", saved, changes.len(), e),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_snooze_keys() {
+        let tasks = vec![Task { id: 1, title: "Water plants".to_string(), ..Default::default() }];
+        let mut modal = SnoozeModal::new(&tasks, vec!["+1d".to_string(), "+1w".to_string()], &DateSettings::default());
+        assert_eq!(modal.previews.len(), 2);
+        assert_eq!(modal.handle_key(&key(KeyCode::Char('2'))), SnoozeAction::Snooze("+1w".to_string()));
+        assert_eq!(modal.handle_key(&key(KeyCode::Char('3'))), SnoozeAction::None);
+        assert_eq!(modal.handle_key(&key(KeyCode::Enter)), SnoozeAction::Snooze("+1d".to_string()));
+
+        // Past the choices is "pick a date"
+        modal.handle_key(&key(KeyCode::Char('j')));
+        modal.handle_key(&key(KeyCode::Char('j')));
+        modal.handle_key(&key(KeyCode::Char('j')));
+        assert_eq!(modal.selected_index, 2);
+        assert_eq!(modal.handle_key(&key(KeyCode::Enter)), SnoozeAction::None);
+        assert!(modal.picking_date);
+        // Typing goes to the input, even keys the list uses
+        assert_eq!(modal.handle_key(&key(KeyCode::Enter)), SnoozeAction::None);
+        for c in "fri".chars() {
+            modal.handle_key(&key(KeyCode::Char(c)));
+        }
+        assert_eq!(modal.handle_key(&key(KeyCode::Enter)), SnoozeAction::Snooze("fri".to_string()));
+        modal.handle_key(&key(KeyCode::Esc));
+        assert_eq!(modal.handle_key(&key(KeyCode::Esc)), SnoozeAction::Close);
+    }
+}
@@ -0,0 +1,319 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::debug::debug_log;
+use crate::templates::{plan_project, plan_template, resolve_anchor, TemplateEntry};
+use crate::tui::app::state::App;
+use crate::tui::app::undoable_action::UndoableAction;
+use crate::tui::line_editor::{clamp_cursor, display_width, KillRing, LineEditor};
+use crate::vikunja_client::VikunjaClient;
+use super::quick_add::{refresh_and_select, resolve_default_project_id};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplatePickerAction {
+    None,
+    Close,
+    /// Create the template with these placeholder values
+    Create(usize, HashMap<String, String>),
+}
+
+/// Asked first for project templates: the date their offsets count from. Not a
+/// valid placeholder name, so it can't clash with one.
+pub const ANCHOR_PROMPT: &str = ":anchor";
+
+/// Asking for a template's placeholders, one at a time
+struct Prompt {
+    template_index: usize,
+    names: Vec<String>,
+    values: Vec<String>,
+}
+
+/// Modal listing the task templates from config.yaml and the project templates next to
+/// it; picking one asks for its placeholders and creates it
+#[derive(Default)]
+pub struct TemplatePickerModal {
+    pub entries: Vec<TemplateEntry>,
+    pub selected_index: usize,
+    pub status_message: String,
+    pub input: String,
+    pub input_cursor: usize,
+    kill_ring: KillRing,
+    prompt: Option<Prompt>,
+}
+
+impl TemplatePickerModal {
+    pub fn new(entries: Vec<TemplateEntry>) -> Self {
+        Self { entries, ..Self::default() }
+    }
+
+    /// The placeholder being asked for, if any
+    pub fn prompting_for(&self) -> Option<&str> {
+        let prompt = self.prompt.as_ref()?;
+        prompt.names.get(prompt.values.len()).map(String::as_str)
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> TemplatePickerAction {
+        if self.prompt.is_some() {
+            return self.handle_prompt_key(key);
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => TemplatePickerAction::Close,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                TemplatePickerAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected_index + 1 < self.entries.len() {
+                    self.selected_index += 1;
+                }
+                TemplatePickerAction::None
+            }
+            KeyCode::Enter if self.selected_index < self.entries.len() => {
+                let entry = &self.entries[self.selected_index];
+                let mut names = entry.prompted_placeholders();
+                if matches!(entry, TemplateEntry::Project(_)) {
+                    names.insert(0, ANCHOR_PROMPT.to_string());
+                }
+                if names.is_empty() {
+                    return TemplatePickerAction::Create(self.selected_index, HashMap::new());
+                }
+                self.prompt = Some(Prompt { template_index: self.selected_index, names, values: Vec::new() });
+                TemplatePickerAction::None
+            }
+            _ => TemplatePickerAction::None,
+        }
+    }
+
+    fn handle_prompt_key(&mut self, key: &KeyEvent) -> TemplatePickerAction {
+        match key.code {
+            // Back to the list
+            KeyCode::Esc => {
+                self.prompt = None;
+                self.input.clear();
+                self.input_cursor = 0;
+                TemplatePickerAction::None
+            }
+            KeyCode::Enter => {
+                let Some(prompt) = self.prompt.as_mut() else { return TemplatePickerAction::None };
+                prompt.values.push(self.input.trim().to_string());
+                self.input.clear();
+                self.input_cursor = 0;
+                if prompt.values.len() < prompt.names.len() {
+                    return TemplatePickerAction::None;
+                }
+                let prompt = self.prompt.take().unwrap();
+                let values = prompt.names.into_iter().zip(prompt.values).collect();
+                TemplatePickerAction::Create(prompt.template_index, values)
+            }
+            _ => {
+                LineEditor::new(&mut self.input, &mut self.input_cursor, &mut self.kill_ring).handle_key(key);
+                TemplatePickerAction::None
+            }
+        }
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let templates = &self.entries;
+        let width = (area.width * 70) / 100;
+        let height = ((self.entries.len() as u16) + 9).min((area.height * 80) / 100).max(10);
+        let x = (area.width - width) / 2 + area.x;
+        let y = (area.height.saturating_sub(height)) / 2 + area.y;
+        let modal_area = Rect::new(x, y, width, height.min(area.height));
+        f.render_widget(Clear, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),     // Templates
+                Constraint::Length(3),  // Placeholder input
+                Constraint::Length(1),  // Status
+                Constraint::Length(1),  // Help
+            ])
+            .split(modal_area);
+
+        let visible = chunks[0].height.saturating_sub(2) as usize;
+        let offset = if visible > 0 && self.selected_index >= visible { self.selected_index + 1 - visible } else { 0 };
+        let mut lines = Vec::new();
+        for (i, template) in templates.iter().enumerate().skip(offset).take(visible.max(1)) {
+            let is_selected = i == self.selected_index;
+            let mut text_style = Style::default().fg(Color::White);
+            if is_selected {
+                text_style = text_style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+            }
+            let count = template.task_count();
+            let mut spans = vec![
+                Span::styled(if is_selected { "▶ " } else { "  " }, Style::default().fg(Color::Yellow)),
+                Span::styled(template.display_name().to_string(), text_style),
+                Span::styled(format!("  {} task{}", count, if count == 1 { "" } else { "s" }), Style::default().fg(Color::DarkGray)),
+            ];
+            match template {
+                TemplateEntry::Task(task) => {
+                    if let Some(ref project) = task.project {
+                        spans.push(Span::styled(format!("  {}", project), Style::default().fg(Color::Cyan)));
+                    }
+                }
+                TemplateEntry::Project(project) => {
+                    let projects = 1 + project.projects.len();
+                    spans.push(Span::styled(format!("  new project{}", if projects == 1 { String::new() } else { format!("s ({})", projects) }), Style::default().fg(Color::Magenta)));
+                }
+            }
+            lines.push(Line::from(spans));
+        }
+        if templates.is_empty() {
+            lines.push(Line::from(Span::styled("No templates: add task templates under templates: in config.yaml, or project templates to project-templates/ next to it", Style::default().fg(Color::DarkGray))));
+        }
+        let block = Block::default()
+            .title(" New From Template ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta));
+        f.render_widget(Paragraph::new(lines).block(block), chunks[0]);
+
+        let (title, border) = match self.prompting_for() {
+            Some(ANCHOR_PROMPT) => (" Start date, offsets count from it (empty: today) ".to_string(), Color::Yellow),
+            Some(name) => (format!(" {{{}}} ", name), Color::Yellow),
+            None => (" Placeholder ".to_string(), Color::DarkGray),
+        };
+        let input = Paragraph::new(self.input.clone())
+            .block(Block::default().title(title).borders(Borders::ALL).border_style(Style::default().fg(border)));
+        f.render_widget(input, chunks[1]);
+        if self.prompting_for().is_some() {
+            let before_cursor = &self.input[..clamp_cursor(&self.input, self.input_cursor)];
+            let cursor_x = chunks[1].x + 1 + display_width(before_cursor) as u16;
+            if cursor_x < chunks[1].x + chunks[1].width - 1 {
+                f.set_cursor(cursor_x, chunks[1].y + 1);
+            }
+        }
+
+        let status = Paragraph::new(self.status_message.clone())
+            .style(Style::default().fg(Color::Cyan).bg(Color::Black));
+        f.render_widget(status, chunks[2]);
+        let help_text = if self.prompting_for().is_some() {
+            "Enter: Next | Esc: Back to templates"
+        } else {
+            "↑↓/jk: Select | Enter: Create | Esc: Close"
+        };
+        let help = Paragraph::new(help_text)
+            .style(Style::default().fg(Color::Gray).bg(Color::Black))
+            .alignment(Alignment::Center);
+        f.render_widget(help, chunks[3]);
+    }
+}
+
+pub async fn handle_template_picker_modal(
+    app: &mut App,
+    key: &KeyEvent,
+    api_client: &Arc<Mutex<VikunjaClient>>,
+) {
+    let (entry, mut values) = match app.template_picker_modal {
+        Some(ref mut modal) => match modal.handle_key(key) {
+            TemplatePickerAction::None => return,
+            TemplatePickerAction::Close => {
+                app.hide_template_picker_modal();
+                return;
+            }
+            TemplatePickerAction::Create(index, values) => (modal.entries[index].clone(), values),
+        },
+        None => return,
+    };
+
+    let client = api_client.lock().await;
+    let result = match &entry {
+        TemplateEntry::Task(template) => {
+            let planned = plan_template(template, &values, &app.date_settings);
+            let default_project_id = resolve_default_project_id(app, &client).await;
+            client.create_planned_tasks(&planned, default_project_id as i64, &[]).await
+                .map(|created| (None, created))
+        }
+        TemplateEntry::Project(template) => {
+            let anchor_text = values.remove(ANCHOR_PROMPT).unwrap_or_default();
+            let Some(anchor) = resolve_anchor(&app.date_settings, &anchor_text) else {
+                drop(client);
+                if let Some(ref mut modal) = app.template_picker_modal {
+                    modal.status_message = format!("Can't read '{}' as a date", anchor_text);
+                }
+                return;
+            };
+            let (projects, tasks) = plan_project(template, &values, &app.date_settings, anchor);
+            client.create_planned_project(&projects, &tasks, 0).await
+                .map(|(project_ids, created)| (Some(project_ids.len()), created))
+        }
+    };
+    drop(client);
+    match result {
+        Ok((projects, created)) => {
+            // A new project isn't undoable, so neither are the tasks that came with it
+            if projects.is_none() {
+                for task_id in created.iter().filter_map(|t| t.id) {
+                    app.add_to_undo_stack(UndoableAction::TaskCreation { task_id: task_id as i64 });
+                }
+            }
+            app.hide_template_picker_modal();
+            let tasks = format!("{} task{}", created.len(), if created.len() == 1 { "" } else { "s" });
+            app.show_toast(match projects {
+                Some(count) => format!("Created {} project{} and {} from '{}'", count, if count == 1 { "" } else { "s" }, tasks, entry.display_name()),
+                None => format!("Created {} from '{}'", tasks, entry.display_name()),
+            });
+            refresh_and_select(app, api_client, created.first().and_then(|t| t.id)).await;
+        }
+        Err(e) => {
+            debug_log(&format!("ERROR: Failed to create from template: {}", e));
+            if let Some(ref mut modal) = app.template_picker_modal {
+                modal.status_message = format!("Failed to create from template: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TaskTemplate;
+    use crate::templates::ProjectTemplate;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_picking_and_prompting() {
+        let entries = vec![
+            TemplateEntry::Task(TaskTemplate { title: "Inbox zero".to_string(), ..Default::default() }),
+            TemplateEntry::Task(TaskTemplate { title: "Release {version}".to_string(), description: Some("By {owner}".to_string()), ..Default::default() }),
+            TemplateEntry::Project(ProjectTemplate { title: "{client} onboarding".to_string(), ..Default::default() }),
+        ];
+        let mut modal = TemplatePickerModal::new(entries);
+        assert_eq!(modal.handle_key(&key(KeyCode::Enter)), TemplatePickerAction::Create(0, HashMap::new()));
+
+        modal.handle_key(&key(KeyCode::Char('j')));
+        assert_eq!(modal.handle_key(&key(KeyCode::Enter)), TemplatePickerAction::None);
+        assert_eq!(modal.prompting_for(), Some("version"));
+        // Typing goes to the input, not the list
+        modal.handle_key(&key(KeyCode::Char('q')));
+        modal.handle_key(&key(KeyCode::Backspace));
+        modal.handle_key(&key(KeyCode::Char('2')));
+        assert_eq!(modal.handle_key(&key(KeyCode::Enter)), TemplatePickerAction::None);
+        assert_eq!(modal.prompting_for(), Some("owner"));
+        modal.handle_key(&key(KeyCode::Char('a')));
+        let values = HashMap::from([("version".to_string(), "2".to_string()), ("owner".to_string(), "a".to_string())]);
+        assert_eq!(modal.handle_key(&key(KeyCode::Enter)), TemplatePickerAction::Create(1, values));
+        assert_eq!(modal.prompting_for(), None);
+
+        // Project templates ask for the start date first
+        modal.handle_key(&key(KeyCode::Char('j')));
+        modal.handle_key(&key(KeyCode::Enter));
+        assert_eq!(modal.prompting_for(), Some(ANCHOR_PROMPT));
+        modal.handle_key(&key(KeyCode::Enter));
+        assert_eq!(modal.prompting_for(), Some("client"));
+        modal.handle_key(&key(KeyCode::Esc));
+        assert_eq!(modal.handle_key(&key(KeyCode::Esc)), TemplatePickerAction::Close);
+    }
+}
@@ -0,0 +1,184 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use chrono::Local;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::tui::app::state::App;
+use crate::tui::app::undoable_action::UndoableAction;
+use crate::vikunja_client::VikunjaClient;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrashAction {
+    None,
+    Close,
+    Restore(usize),
+    DeleteNow(usize),
+    Empty,
+}
+
+/// Modal listing deleted tasks that are still waiting to be deleted on the server
+#[derive(Default)]
+pub struct TrashModal {
+    pub selected_index: usize,
+    pub status_message: String,
+}
+
+impl TrashModal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent, items: usize) -> TrashAction {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => TrashAction::Close,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                TrashAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected_index + 1 < items {
+                    self.selected_index += 1;
+                }
+                TrashAction::None
+            }
+            KeyCode::Enter | KeyCode::Char('r') if self.selected_index < items => TrashAction::Restore(self.selected_index),
+            KeyCode::Char('d') | KeyCode::Delete if self.selected_index < items => TrashAction::DeleteNow(self.selected_index),
+            KeyCode::Char('D') if items > 0 => TrashAction::Empty,
+            _ => TrashAction::None,
+        }
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect, app: &App) {
+        let width = (area.width * 70) / 100;
+        let height = ((app.trash.len() as u16) + 6).min((area.height * 80) / 100).max(8);
+        let x = (area.width - width) / 2 + area.x;
+        let y = (area.height.saturating_sub(height)) / 2 + area.y;
+        let modal_area = Rect::new(x, y, width, height.min(area.height));
+        f.render_widget(Clear, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),     // Items
+                Constraint::Length(1),  // Status
+                Constraint::Length(1),  // Help
+            ])
+            .split(modal_area);
+
+        let now = Local::now();
+        let visible = chunks[0].height.saturating_sub(2) as usize;
+        let offset = if visible > 0 && self.selected_index >= visible { self.selected_index + 1 - visible } else { 0 };
+        let mut lines = Vec::new();
+        for (i, trashed) in app.trash.iter().enumerate().skip(offset).take(visible.max(1)) {
+            let is_selected = i == self.selected_index;
+            let left = (app.trash_expires_at(trashed) - now).num_seconds().max(0);
+            let project = app.project_map.get(&trashed.task.project_id).cloned().unwrap_or_default();
+            let mut text_style = Style::default().fg(Color::White);
+            if is_selected {
+                text_style = text_style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+            }
+            lines.push(Line::from(vec![
+                Span::styled(if is_selected { "▶ " } else { "  " }, Style::default().fg(Color::Yellow)),
+                Span::styled(trashed.task.title.clone(), text_style),
+                Span::styled(format!("  {}", project), Style::default().fg(Color::Cyan)),
+                Span::styled(format!("  deleted in {}s", left), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        if app.trash.is_empty() {
+            lines.push(Line::from(Span::styled("The trash is empty", Style::default().fg(Color::DarkGray))));
+        }
+        let block = Block::default()
+            .title(format!(" Trash ({}) ", app.trash.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red));
+        f.render_widget(Paragraph::new(lines).block(block), chunks[0]);
+
+        let status = Paragraph::new(self.status_message.clone())
+            .style(Style::default().fg(Color::Cyan).bg(Color::Black));
+        f.render_widget(status, chunks[1]);
+        let help = Paragraph::new("↑↓/jk: Select | Enter/r: Restore | d: Delete now | D: Empty trash | Esc: Close")
+            .style(Style::default().fg(Color::Gray).bg(Color::Black))
+            .alignment(Alignment::Center);
+        f.render_widget(help, chunks[2]);
+    }
+}
+
+pub async fn handle_trash_modal(
+    app: &mut App,
+    key: &KeyEvent,
+    api_client: &Arc<Mutex<VikunjaClient>>,
+) {
+    let items = app.trash.len();
+    let action = match app.trash_modal {
+        Some(ref mut modal) => modal.handle_key(key, items),
+        None => return,
+    };
+    let status = match action {
+        TrashAction::None => return,
+        TrashAction::Close => {
+            app.hide_trash_modal();
+            return;
+        }
+        TrashAction::Restore(index) => {
+            let task_id = app.trash[index].task.id;
+            match app.restore_from_trash(task_id) {
+                Some(task) => {
+                    app.apply_task_filter();
+                    // Undoing the restore puts it back in the trash
+                    app.add_to_undo_stack(UndoableAction::TaskCreation { task_id });
+                    format!("Restored: {}", task.title)
+                }
+                None => String::new(),
+            }
+        }
+        TrashAction::DeleteNow(index) => {
+            let task = app.trash[index].task.clone();
+            let client = api_client.lock().await;
+            app.delete_from_trash_async(&client, &[task.id]).await;
+            if app.is_trashed(task.id) {
+                format!("Couldn't delete: {}", task.title)
+            } else {
+                format!("Deleted: {}", task.title)
+            }
+        }
+        TrashAction::Empty => {
+            let client = api_client.lock().await;
+            app.flush_trash_async(&client, true).await;
+            "Emptied the trash".to_string()
+        }
+    };
+    let items = app.trash.len();
+    if let Some(ref mut modal) = app.trash_modal {
+        modal.selected_index = modal.selected_index.min(items.saturating_sub(1));
+        modal.status_message = status;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_trash_keys() {
+        let mut modal = TrashModal::new();
+        assert_eq!(modal.handle_key(&key(KeyCode::Enter), 0), TrashAction::None);
+        assert_eq!(modal.handle_key(&key(KeyCode::Char('j')), 2), TrashAction::None);
+        assert_eq!(modal.handle_key(&key(KeyCode::Char('j')), 2), TrashAction::None);
+        assert_eq!(modal.selected_index, 1);
+        assert_eq!(modal.handle_key(&key(KeyCode::Char('r')), 2), TrashAction::Restore(1));
+        assert_eq!(modal.handle_key(&key(KeyCode::Char('d')), 2), TrashAction::DeleteNow(1));
+        assert_eq!(modal.handle_key(&key(KeyCode::Char('D')), 2), TrashAction::Empty);
+        assert_eq!(modal.handle_key(&key(KeyCode::Esc), 2), TrashAction::Close);
+    }
+}
@@ -0,0 +1,212 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::tui::app::state::App;
+use crate::vikunja_client::VikunjaClient;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UndoHistoryAction {
+    None,
+    Close,
+    /// Undo or redo everything up to and including this row
+    Jump(usize),
+}
+
+/// One row of the history: an entry of the redo or undo stack
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistoryRow {
+    pub redo: bool,
+    /// Index into the stack
+    pub index: usize,
+}
+
+/// The rows top to bottom: redo entries from the furthest to the next one, then undo
+/// entries from the last action back to the oldest. The present sits between the two.
+pub fn history_rows(redo_len: usize, undo_len: usize) -> Vec<HistoryRow> {
+    (0..redo_len).map(|index| HistoryRow { redo: true, index })
+        .chain((0..undo_len).rev().map(|index| HistoryRow { redo: false, index }))
+        .collect()
+}
+
+/// Modal listing the undo and redo stacks, for jumping to any point in the history
+pub struct UndoHistoryModal {
+    pub selected_index: usize,
+    pub status_message: String,
+}
+
+impl UndoHistoryModal {
+    /// Start on the last action, the one Ctrl+Z would undo
+    pub fn new(redo_len: usize, undo_len: usize) -> Self {
+        let selected_index = if undo_len > 0 { redo_len } else { redo_len.saturating_sub(1) };
+        Self { selected_index, status_message: String::new() }
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent, rows: usize) -> UndoHistoryAction {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => UndoHistoryAction::Close,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                UndoHistoryAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected_index + 1 < rows {
+                    self.selected_index += 1;
+                }
+                UndoHistoryAction::None
+            }
+            KeyCode::Enter if self.selected_index < rows => UndoHistoryAction::Jump(self.selected_index),
+            _ => UndoHistoryAction::None,
+        }
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect, app: &App) {
+        let rows = history_rows(app.redo_stack.len(), app.undo_stack.len());
+        let width = (area.width * 80) / 100;
+        let height = ((rows.len() as u16) + 7).min((area.height * 80) / 100).max(8);
+        let x = (area.width - width) / 2 + area.x;
+        let y = (area.height.saturating_sub(height)) / 2 + area.y;
+        let modal_area = Rect::new(x, y, width, height.min(area.height));
+        f.render_widget(Clear, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),     // Entries
+                Constraint::Length(1),  // Status
+                Constraint::Length(1),  // Help
+            ])
+            .split(modal_area);
+
+        let mut lines = Vec::new();
+        let mut selected_line = 0;
+        for (i, row) in rows.iter().enumerate() {
+            if !row.redo && (i == 0 || rows[i - 1].redo) {
+                lines.push(Line::from(Span::styled("──── now ────", Style::default().fg(Color::DarkGray))));
+            }
+            let is_selected = i == self.selected_index;
+            if is_selected {
+                selected_line = lines.len();
+            }
+            let action = if row.redo { &app.redo_stack[row.index] } else { &app.undo_stack[row.index] };
+            let info = app.history_info(row.redo, row.index);
+            let (marker, marker_color) = if row.redo { ("redo ", Color::Blue) } else { ("undo ", Color::Cyan) };
+            let mut text_style = Style::default().fg(if row.redo { Color::Gray } else { Color::White });
+            if is_selected {
+                text_style = text_style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+            }
+            let time = info.map(|i| i.at.format("%b %d %H:%M:%S").to_string()).unwrap_or_else(|| "—".repeat(15));
+            let mut spans = vec![
+                Span::styled(if is_selected { "▶ " } else { "  " }, Style::default().fg(Color::Yellow)),
+                Span::styled(format!("{} ", time), Style::default().fg(Color::DarkGray)),
+                Span::styled(marker, Style::default().fg(marker_color)),
+                Span::styled(action.describe(), text_style),
+            ];
+            if let Some(info) = info {
+                spans.push(Span::styled(format!("  — {}", info.task_title), Style::default().fg(Color::DarkGray)));
+                if let Some(ref error) = info.failed {
+                    spans.push(Span::styled(format!("  ✗ failed on server: {}", error), Style::default().fg(Color::Red)));
+                }
+            }
+            lines.push(Line::from(spans));
+        }
+        if rows.is_empty() {
+            lines.push(Line::from(Span::styled("Nothing to undo or redo", Style::default().fg(Color::DarkGray))));
+        } else if rows.last().is_some_and(|row| row.redo) {
+            lines.push(Line::from(Span::styled("──── now ────", Style::default().fg(Color::DarkGray))));
+        }
+
+        let visible = chunks[0].height.saturating_sub(2) as usize;
+        let offset = if visible > 0 && selected_line >= visible { selected_line + 1 - visible } else { 0 };
+        let lines: Vec<Line> = lines.into_iter().skip(offset).collect();
+        let block = Block::default()
+            .title(format!(" Undo History ({} undo, {} redo) ", app.undo_stack.len(), app.redo_stack.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        f.render_widget(Paragraph::new(lines).block(block), chunks[0]);
+
+        let status = Paragraph::new(self.status_message.clone())
+            .style(Style::default().fg(Color::Cyan).bg(Color::Black));
+        f.render_widget(status, chunks[1]);
+        let help = Paragraph::new("↑↓/jk: Select | Enter: Undo/redo up to here | Esc: Close")
+            .style(Style::default().fg(Color::Gray).bg(Color::Black))
+            .alignment(Alignment::Center);
+        f.render_widget(help, chunks[2]);
+    }
+}
+
+pub async fn handle_undo_history_modal(
+    app: &mut App,
+    key: &KeyEvent,
+    api_client: &Arc<Mutex<VikunjaClient>>,
+) {
+    let rows = history_rows(app.redo_stack.len(), app.undo_stack.len());
+    let action = match app.undo_history_modal {
+        Some(ref mut modal) => modal.handle_key(key, rows.len()),
+        None => return,
+    };
+    let row = match action {
+        UndoHistoryAction::None => return,
+        UndoHistoryAction::Close => {
+            app.hide_undo_history_modal();
+            return;
+        }
+        UndoHistoryAction::Jump(row) => rows[row],
+    };
+
+    // Undo every later action too, newest first; redo every earlier undo, oldest first
+    let (redo, count) = if row.redo {
+        (true, app.redo_stack.len() - row.index)
+    } else {
+        (false, app.undo_stack.len() - row.index)
+    };
+    let client = api_client.lock().await;
+    let done = app.step_history_many_async(&client, redo, count).await;
+    drop(client);
+
+    let verb = if redo { "Redid" } else { "Undid" };
+    let status = if done == count {
+        format!("{} {} action{}", verb, done, if done == 1 { "" } else { "s" })
+    } else {
+        format!("{} {} of {}; stopped at an entry that failed on the server", verb, done, count)
+    };
+    let (redo_len, undo_len) = (app.redo_stack.len(), app.undo_stack.len());
+    if let Some(ref mut modal) = app.undo_history_modal {
+        *modal = UndoHistoryModal::new(redo_len, undo_len);
+        modal.status_message = status;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_rows_and_selection() {
+        let rows = history_rows(2, 3);
+        let order: Vec<(bool, usize)> = rows.iter().map(|r| (r.redo, r.index)).collect();
+        assert_eq!(order, vec![(true, 0), (true, 1), (false, 2), (false, 1), (false, 0)]);
+
+        // Opens on the last action, the one Ctrl+Z undoes
+        let mut modal = UndoHistoryModal::new(2, 3);
+        assert_eq!(modal.selected_index, 2);
+        assert_eq!(modal.handle_key(&key(KeyCode::Char('j')), rows.len()), UndoHistoryAction::None);
+        assert_eq!(modal.handle_key(&key(KeyCode::Enter), rows.len()), UndoHistoryAction::Jump(3));
+        modal.selected_index = 4;
+        modal.handle_key(&key(KeyCode::Down), rows.len());
+        assert_eq!(modal.selected_index, 4);
+        assert_eq!(UndoHistoryModal::new(1, 0).selected_index, 0);
+        assert_eq!(modal.handle_key(&key(KeyCode::Esc), rows.len()), UndoHistoryAction::Close);
+    }
+}
@@ -51,6 +51,14 @@ impl UrlModal {
                 }
                 UrlModalAction::None
             }
+            // Digits open the link with that `[n]` marker directly
+            '1'..='9' => {
+                let index = key.to_digit(10).unwrap_or(0) as usize - 1;
+                match self.urls.get(index) {
+                    Some(url_context) => UrlModalAction::OpenUrl(url_context.url.clone()),
+                    None => UrlModalAction::None,
+                }
+            }
             _ => UrlModalAction::None,
         }
     }
@@ -139,6 +147,8 @@ pub fn draw_url_modal(f: &mut Frame, modal: &UrlModal, area: Rect) {
             Span::raw(" navigate • "),
             Span::styled("Enter", Style::default().fg(Color::Green)),
             Span::raw(" open • "),
+            Span::styled("1-9", Style::default().fg(Color::Green)),
+            Span::raw(" open [n] • "),
             Span::styled("Esc", Style::default().fg(Color::Red)),
             Span::raw(" cancel"),
         ])
@@ -159,7 +169,7 @@ pub fn draw_url_modal(f: &mut Frame, modal: &UrlModal, area: Rect) {
             };
             
             // Truncate URL if too long for display
-            let display_width = chunks[1].width.saturating_sub(4) as usize; // Account for padding
+            let display_width = chunks[1].width.saturating_sub(9) as usize; // Account for padding and the [n] marker
             let truncated_url = if url_ctx.url.len() > display_width {
                 format!("{}...", &url_ctx.url[..display_width.saturating_sub(3)])
             } else {
@@ -168,6 +178,7 @@ pub fn draw_url_modal(f: &mut Frame, modal: &UrlModal, area: Rect) {
             
             ListItem::new(vec![
                 Line::from(vec![
+                    Span::styled(format!("[{}] ", i + 1), Style::default().fg(Color::DarkGray)),
                     Span::styled(truncated_url, Style::default().fg(Color::Cyan)),
                 ]),
                 Line::from(vec![
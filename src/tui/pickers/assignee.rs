@@ -0,0 +1,35 @@
+// Assignee Picker event handler
+use crate::tui::app::state::App;
+use crossterm::event::{KeyEvent, KeyModifiers};
+
+pub fn handle_assignee_picker(app: &mut App, key: &KeyEvent) {
+    use crossterm::event::KeyCode;
+    match key.code {
+        KeyCode::Esc => {
+            app.hide_assignee_picker();
+        },
+        KeyCode::Enter => {
+            app.select_assignee_picker();
+        },
+        KeyCode::Up => {
+            app.move_assignee_picker_up();
+        },
+        KeyCode::Down => {
+            app.move_assignee_picker_down();
+        },
+        // Plain spaces are typed, so names like "Jane Doe" can be searched
+        KeyCode::Tab => {
+            app.toggle_assignee_picker();
+        },
+        KeyCode::Char(' ') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.toggle_assignee_picker();
+        },
+        _ => {
+            if app.edit_assignee_picker_input(key) {
+                // Users that never appeared on a task are only found through the server search
+                let query = app.assignee_picker_input.clone();
+                app.queue_user_search(&query);
+            }
+        },
+    }
+}
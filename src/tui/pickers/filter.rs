@@ -34,9 +34,6 @@ pub async fn handle_filter_picker(app: &mut App, key: &KeyEvent, api_client: &Ar
             }
             app.hide_filter_picker();
         },
-        KeyCode::Backspace => {
-            app.delete_char_from_filter_picker();
-        },
         KeyCode::Delete => {
             // Quick clear filter if a filter is currently active
             if app.current_filter_id.is_some() {
@@ -52,9 +49,6 @@ pub async fn handle_filter_picker(app: &mut App, key: &KeyEvent, api_client: &Ar
         KeyCode::Down => {
             app.move_filter_picker_down();
         },
-        KeyCode::Char(c) => {
-            app.add_char_to_filter_picker(c);
-        },
-        _ => {},
+        _ => app.edit_filter_picker_input(key),
     }
 }
@@ -12,9 +12,6 @@ pub fn handle_label_picker(app: &mut App, key: &KeyEvent) {
         KeyCode::Enter => {
             app.select_label_picker();
         },
-        KeyCode::Backspace => {
-            app.delete_char_from_label_picker();
-        },
         KeyCode::Up => {
             app.move_label_picker_up();
         },
@@ -24,9 +21,6 @@ pub fn handle_label_picker(app: &mut App, key: &KeyEvent) {
         KeyCode::Char(' ') => {
             app.toggle_label_picker();
         },
-        KeyCode::Char(c) => {
-            app.add_char_to_label_picker(c);
-        },
-        _ => {},
+        _ => app.edit_label_picker_input(key),
     }
 }
@@ -1,3 +1,4 @@
 pub mod project;
 pub mod filter;
 pub mod label;
+pub mod assignee;
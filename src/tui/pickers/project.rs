@@ -12,18 +12,12 @@ pub fn handle_project_picker(app: &mut App, key: &KeyEvent) {
         KeyCode::Enter => {
             app.select_project_picker();
         },
-        KeyCode::Backspace => {
-            app.delete_char_from_project_picker();
-        },
         KeyCode::Up => {
             app.move_project_picker_up();
         },
         KeyCode::Down => {
             app.move_project_picker_down();
         },
-        KeyCode::Char(c) => {
-            app.add_char_to_project_picker(c);
-        },
-        _ => {},
+        _ => app.edit_project_picker_input(key),
     }
 }
@@ -0,0 +1,270 @@
+// Suggestions for the quick add and edit inputs: what is being typed at the cursor,
+// how candidates are ranked, and how an accepted one is written into the input.
+// Candidates used recently and often come first; usage is kept with the input history.
+
+use crate::tui::app::suggestion_mode::SuggestionMode;
+use crate::tui::utils::fuzzy_match_score;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Phrases offered after `due` and `start`, most useful first
+pub const DATE_SUGGESTIONS: &[&str] = &[
+    "today", "tomorrow", "eod", "eow", "eom", "this weekend", "next week", "next month",
+    "monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday",
+    "in 3 days", "in 2 weeks", "end of month",
+];
+
+/// Intervals offered after `every`
+pub const REPEAT_SUGGESTIONS: &[&str] = &["day", "week", "2 weeks", "month", "3 months", "year"];
+
+/// Days after which a use counts half as much
+const HALF_LIFE_DAYS: f64 = 14.0;
+
+static DATE_CONTEXT: OnceLock<Regex> = OnceLock::new();
+static REPEAT_CONTEXT: OnceLock<Regex> = OnceLock::new();
+static DATE_KEYWORD: OnceLock<Regex> = OnceLock::new();
+
+/// A date phrase being typed after `due` or `start`
+fn date_context_regex() -> &'static Regex {
+    DATE_CONTEXT.get_or_init(|| Regex::new(r"(?i)(?:^|\s)(?:due|start)(?::|\s+)([a-z0-9 ]*)$").expect("Invalid date context regex"))
+}
+
+/// An interval being typed after `every`
+fn repeat_context_regex() -> &'static Regex {
+    REPEAT_CONTEXT.get_or_init(|| Regex::new(r"(?i)(?:^|\s)every\s+([a-z0-9 ]*)$").expect("Invalid repeat context regex"))
+}
+
+fn date_keyword_regex() -> &'static Regex {
+    DATE_KEYWORD.get_or_init(|| Regex::new(r"(?i)(?:^|\s)(?:due|start)(?::|\s+)").expect("Invalid date keyword regex"))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Usage {
+    pub count: u32,
+    /// Unix time of the latest use
+    pub last_used: i64,
+}
+
+/// How often and how recently each suggestion was used, keyed by lowercase value
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SuggestionUsage {
+    label: HashMap<String, Usage>,
+    project: HashMap<String, Usage>,
+    assignee: HashMap<String, Usage>,
+    date: HashMap<String, Usage>,
+    repeat: HashMap<String, Usage>,
+}
+
+impl SuggestionUsage {
+    fn map(&self, mode: SuggestionMode) -> &HashMap<String, Usage> {
+        match mode {
+            SuggestionMode::Label => &self.label,
+            SuggestionMode::Project => &self.project,
+            SuggestionMode::Assignee => &self.assignee,
+            SuggestionMode::Date => &self.date,
+            SuggestionMode::Repeat => &self.repeat,
+        }
+    }
+
+    pub fn record(&mut self, mode: SuggestionMode, value: &str, now: i64) {
+        let map = match mode {
+            SuggestionMode::Label => &mut self.label,
+            SuggestionMode::Project => &mut self.project,
+            SuggestionMode::Assignee => &mut self.assignee,
+            SuggestionMode::Date => &mut self.date,
+            SuggestionMode::Repeat => &mut self.repeat,
+        };
+        let usage = map.entry(value.to_lowercase()).or_default();
+        usage.count += 1;
+        usage.last_used = now;
+    }
+
+    /// Use count, decayed by the time since the last use
+    pub fn score(&self, mode: SuggestionMode, value: &str, now: i64) -> f64 {
+        let Some(usage) = self.map(mode).get(&value.to_lowercase()) else {
+            return 0.0;
+        };
+        let age_days = (now - usage.last_used).max(0) as f64 / 86_400.0;
+        usage.count as f64 * 0.5f64.powf(age_days / HALF_LIFE_DAYS)
+    }
+}
+
+/// What is being typed before the cursor
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuggestionContext {
+    pub mode: SuggestionMode,
+    /// Byte offset where the text an accepted suggestion replaces begins
+    pub start: usize,
+    /// The text typed so far, without an opening bracket
+    pub prefix: String,
+}
+
+/// The suggestion context at the end of `before_cursor`, if any. When several apply,
+/// the one typed last wins, so "*home due tom" suggests dates.
+pub fn suggestion_context(before_cursor: &str) -> Option<SuggestionContext> {
+    // Certain delimiters or control characters end a label, project or user name
+    fn is_suggestion_char(c: char) -> bool {
+        !matches!(c, '\n' | '\r' | '\t' | '#' | '@' | '!' | '&' | '|' | '(' | ')' | '{' | '}' | '"' | '\'')
+    }
+
+    let marker = before_cursor.rfind(['*', '+', '@']).and_then(|pos| {
+        let start = pos + 1;
+        let after = &before_cursor[start..];
+        let prefix = after.strip_prefix('[').unwrap_or(after);
+        if !prefix.chars().all(is_suggestion_char) {
+            return None;
+        }
+        let mode = match &before_cursor[pos..start] {
+            "*" => SuggestionMode::Label,
+            "+" => SuggestionMode::Project,
+            _ => SuggestionMode::Assignee,
+        };
+        // Usernames have no spaces unless bracketed, and an `@` inside a word is
+        // part of an e-mail address
+        if mode == SuggestionMode::Assignee {
+            let starts_word = before_cursor[..pos].chars().next_back().is_none_or(char::is_whitespace);
+            if !starts_word || (!after.starts_with('[') && prefix.contains(char::is_whitespace)) {
+                return None;
+            }
+        }
+        Some(SuggestionContext { mode, start, prefix: prefix.to_string() })
+    });
+    let phrase = [(SuggestionMode::Date, date_context_regex()), (SuggestionMode::Repeat, repeat_context_regex())]
+        .into_iter()
+        .filter_map(|(mode, regex)| {
+            let m = regex.captures(before_cursor)?.get(1)?;
+            Some(SuggestionContext { mode, start: m.start(), prefix: m.as_str().to_string() })
+        })
+        .max_by_key(|context| context.start);
+    match (marker, phrase) {
+        (Some(marker), Some(phrase)) => Some(if phrase.start > marker.start { phrase } else { marker }),
+        (marker, phrase) => marker.or(phrase),
+    }
+}
+
+/// Candidates matching `prefix`, best first: prefix matches before looser ones, then by
+/// usage, then by how well they match. Ties keep the order of `candidates`.
+pub fn rank(candidates: Vec<String>, context: &SuggestionContext, usage: &SuggestionUsage, now: i64) -> Vec<String> {
+    let prefix = context.prefix.trim();
+    let fuzzy = matches!(context.mode, SuggestionMode::Label | SuggestionMode::Project | SuggestionMode::Assignee);
+    let mut scored: Vec<(String, f32, f64)> = candidates.into_iter()
+        .filter_map(|candidate| {
+            let score = if fuzzy {
+                fuzzy_match_score(&candidate, prefix)
+            } else if candidate.to_lowercase().starts_with(&context.prefix.to_lowercase()) {
+                // Dates and intervals are phrases; a trailing space means the phrase is done
+                1.0
+            } else {
+                0.0
+            };
+            let used = usage.score(context.mode, &candidate, now);
+            (score > 0.0).then_some((candidate, score, used))
+        })
+        .collect();
+    scored.sort_by(|a, b| {
+        (b.1 >= 0.9).cmp(&(a.1 >= 0.9))
+            .then_with(|| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal))
+            .then_with(|| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal))
+    });
+    scored.into_iter().map(|(candidate, _, _)| candidate).collect()
+}
+
+/// Replace `input[start..cursor]` with `value`, followed by a space. Names with spaces
+/// are bracketed so the parser reads them whole. Returns the new input and cursor.
+pub fn complete(input: &str, start: usize, cursor: usize, mode: SuggestionMode, value: &str) -> (String, usize) {
+    let text = match mode {
+        SuggestionMode::Label | SuggestionMode::Project | SuggestionMode::Assignee if value.contains(' ') => format!("[{}]", value),
+        _ => value.to_string(),
+    };
+    let rest = &input[cursor..];
+    let space = if rest.starts_with(' ') { "" } else { " " };
+    let completed = format!("{}{}{}{}", &input[..start], text, space, rest);
+    (completed, start + text.len() + 1)
+}
+
+/// Date phrases from `DATE_SUGGESTIONS` written after `due` or `start` in `text`
+pub fn date_phrases(text: &str) -> Vec<&'static str> {
+    date_keyword_regex().find_iter(text)
+        .filter_map(|m| {
+            let rest = text[m.end()..].to_lowercase();
+            DATE_SUGGESTIONS.iter()
+                .filter(|phrase| {
+                    rest.starts_with(*phrase)
+                        && !rest[phrase.len()..].starts_with(|c: char| c.is_alphanumeric())
+                })
+                .max_by_key(|phrase| phrase.len())
+                .copied()
+        })
+        .collect()
+}
+
+/// Tab completions in progress: a repeated Tab swaps the completed text for the next candidate
+#[derive(Debug, Clone)]
+pub struct CompletionCycle {
+    pub mode: SuggestionMode,
+    pub start: usize,
+    pub candidates: Vec<String>,
+    pub index: usize,
+    /// The input and cursor right after the last completion. Anything else typed in
+    /// between ends the cycle.
+    pub input: String,
+    pub cursor: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(text: &str) -> Option<(SuggestionMode, String)> {
+        suggestion_context(text).map(|c| (c.mode, c.prefix))
+    }
+
+    #[test]
+    fn test_suggestion_context() {
+        assert_eq!(context("Buy milk *gro"), Some((SuggestionMode::Label, "gro".to_string())));
+        assert_eq!(context("Plan +[Work St"), Some((SuggestionMode::Project, "Work St".to_string())));
+        assert_eq!(context("Review PR @jo"), Some((SuggestionMode::Assignee, "jo".to_string())));
+        assert_eq!(context("Review PR @[Jane D"), Some((SuggestionMode::Assignee, "Jane D".to_string())));
+        assert_eq!(context("Review PR @jo and the rest"), None);
+        assert_eq!(context("Mail bob@exa"), None);
+        assert_eq!(context("Pay rent *home due next w"), Some((SuggestionMode::Date, "next w".to_string())));
+        assert_eq!(context("Start gym start:"), Some((SuggestionMode::Date, String::new())));
+        assert_eq!(context("Water plants every 2"), Some((SuggestionMode::Repeat, "2".to_string())));
+        assert_eq!(context("due tomorrow *ur"), Some((SuggestionMode::Label, "ur".to_string())));
+        assert_eq!(context("Call Bob!"), None);
+        assert_eq!(context("Overdue items"), None);
+    }
+
+    #[test]
+    fn test_rank_by_usage() {
+        let now = 1_700_000_000;
+        let mut usage = SuggestionUsage::default();
+        let labels = vec!["urgent".to_string(), "shopping".to_string(), "someday".to_string()];
+        let any_label = SuggestionContext { mode: SuggestionMode::Label, start: 1, prefix: String::new() };
+        assert_eq!(rank(labels.clone(), &any_label, &usage, now), labels);
+
+        usage.record(SuggestionMode::Label, "Someday", now - 60 * 86_400);
+        usage.record(SuggestionMode::Label, "Someday", now - 60 * 86_400);
+        usage.record(SuggestionMode::Label, "shopping", now);
+        assert_eq!(rank(labels.clone(), &any_label, &usage, now), vec!["shopping", "someday", "urgent"]);
+
+        // A prefix match beats a more used fuzzy match
+        let so = SuggestionContext { prefix: "so".to_string(), ..any_label };
+        assert_eq!(rank(labels, &so, &usage, now), vec!["someday", "shopping"]);
+
+        let dates = DATE_SUGGESTIONS.iter().map(|d| d.to_string()).collect();
+        let next = SuggestionContext { mode: SuggestionMode::Date, start: 4, prefix: "next ".to_string() };
+        assert_eq!(rank(dates, &next, &usage, now), vec!["next week", "next month"]);
+    }
+
+    #[test]
+    fn test_complete_and_date_phrases() {
+        assert_eq!(complete("Plan +wo", 6, 8, SuggestionMode::Project, "Work Stuff"), ("Plan +[Work Stuff] ".to_string(), 19));
+        assert_eq!(complete("Gym due tom *health", 8, 11, SuggestionMode::Date, "tomorrow"), ("Gym due tomorrow *health".to_string(), 17));
+        assert_eq!(date_phrases("Pay due next week, start:friday"), vec!["next week", "friday"]);
+        assert_eq!(date_phrases("Pay due todayish"), Vec::<&str>::new());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::tui::app::state::App;
 use crate::tui::app::form_edit_state::FormEditState;
+use crate::tui::line_editor::cursor_row_col;
 use ratatui::prelude::*;
 use ratatui::style::{Color, Style, Modifier};
 use ratatui::widgets::{Paragraph, Block, Borders, Clear, Wrap};
@@ -16,8 +17,14 @@ pub fn draw_form_edit_modal(f: &mut Frame, app: &App) {
         
         f.render_widget(Clear, modal_area);
         
+        let changed = form.changed_fields().len();
+        let title = if changed == 0 {
+            " Task Editor (Form Mode) ".to_string()
+        } else {
+            format!(" Task Editor (Form Mode) — {} field{} changed ", changed, if changed == 1 { "" } else { "s" })
+        };
         let block = Block::default()
-            .title(" Task Editor (Form Mode) ")
+            .title(title)
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Blue));
         
@@ -34,7 +41,7 @@ pub fn draw_form_edit_modal(f: &mut Frame, app: &App) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Min(15),    // Main form area
+                Constraint::Min(16),    // Main form area
                 Constraint::Length(6),  // Help section
             ])
             .split(inner_area);
@@ -47,133 +54,77 @@ pub fn draw_form_edit_modal(f: &mut Frame, app: &App) {
     }
 }
 
-fn render_form_fields(f: &mut Frame, area: Rect, app: &App, form: &FormEditState) {
-    let mut lines = Vec::new();
-    
-    // Title field
-    let title_style = if form.field_index == 0 {
+/// One field's line: marker, label (with `*` when changed), value, then its error or hint
+fn field_line<'a>(form: &FormEditState, index: usize, value: Vec<Span<'a>>, hint: &'a str, error: Option<String>) -> Line<'a> {
+    let focused = form.field_index == index;
+    let label_style = if focused {
         Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
     } else {
         Style::default().fg(Color::Cyan)
     };
-    let title_prefix = if form.field_index == 0 { "► " } else { "  " };
-    lines.push(Line::from(vec![
-        Span::styled(title_prefix, Style::default().fg(Color::Yellow)),
-        Span::styled("Title: ", title_style),
-        Span::styled(&form.title, if form.field_index == 0 { 
-            Style::default().fg(Color::White).bg(Color::DarkGray) 
-        } else { 
-            Style::default().fg(Color::Gray) 
-        }),
-    ]));
-    
-    // Description field
-    let desc_style = if form.field_index == 1 {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+    let value_style = if focused {
+        Style::default().fg(Color::White).bg(Color::DarkGray)
     } else {
-        Style::default().fg(Color::Cyan)
+        Style::default().fg(Color::Gray)
     };
-    let desc_prefix = if form.field_index == 1 { "► " } else { "  " };
+    let mut spans = vec![
+        Span::styled(if focused { "► " } else { "  " }, Style::default().fg(Color::Yellow)),
+        Span::styled(FormEditState::field_label(index), label_style),
+    ];
+    if form.is_field_changed(index) {
+        spans.push(Span::styled("*", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)));
+    }
+    spans.push(Span::styled(": ", label_style));
+    spans.extend(value.into_iter().map(|span| if span.style == Style::default() { span.style(value_style) } else { span }));
+    match error {
+        Some(error) => spans.push(Span::styled(format!("  ⚠ {}", error), Style::default().fg(Color::Red))),
+        None if !hint.is_empty() => spans.push(Span::styled(format!(" {}", hint), Style::default().fg(Color::DarkGray))),
+        None => {}
+    }
+    Line::from(spans)
+}
+
+fn render_form_fields(f: &mut Frame, area: Rect, app: &App, form: &FormEditState) {
+    let settings = &app.date_settings;
+    let error = |index: usize| form.field_error(index, settings);
+    let or_not_set = |date: &Option<String>| date.clone().unwrap_or_else(|| "<not set>".to_string());
+    let or_none = |text: &str| if text.trim().is_empty() { "<none>".to_string() } else { text.to_string() };
+    let mut lines = Vec::new();
+    
+    // Title field
+    lines.push(field_line(form, 0, vec![Span::raw(form.title.clone())], "", error(0)));
+    
+    // Description field
+    // Multi-line descriptions show one line, the cursor's while focused; Ctrl+G edits the whole text
+    let desc_line_count = form.description.split('\n').count();
+    let desc_row = if form.field_index == 1 { cursor_row_col(&form.description, form.cursor_position).0 } else { 0 };
+    let desc_line = form.description.split('\n').nth(desc_row).unwrap_or_default();
     let desc_text = if form.description.is_empty() { 
-        "<empty>" 
+        "<empty>".to_string()
+    } else if desc_line_count > 1 && form.field_index == 1 {
+        format!("{} (line {}/{}, Ctrl+G to edit)", desc_line, desc_row + 1, desc_line_count)
+    } else if desc_line_count > 1 {
+        format!("{} (+{} lines, Ctrl+G to edit)", desc_line, desc_line_count - 1)
     } else { 
-        &form.description 
-    };
-    lines.push(Line::from(vec![
-        Span::styled(desc_prefix, Style::default().fg(Color::Yellow)),
-        Span::styled("Description: ", desc_style),
-        Span::styled(desc_text, if form.field_index == 1 { 
-            Style::default().fg(Color::White).bg(Color::DarkGray) 
-        } else { 
-            Style::default().fg(Color::Gray) 
-        }),
-    ]));
-    
-    // Due Date field
-    let due_style = if form.field_index == 2 {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::Cyan)
+        form.description.clone()
     };
-    let due_prefix = if form.field_index == 2 { "► " } else { "  " };
-    let due_text = form.due_date.as_deref().unwrap_or("<not set>");
-    lines.push(Line::from(vec![
-        Span::styled(due_prefix, Style::default().fg(Color::Yellow)),
-        Span::styled("Due Date: ", due_style),
-        Span::styled(due_text, if form.field_index == 2 { 
-            Style::default().fg(Color::White).bg(Color::DarkGray) 
-        } else { 
-            Style::default().fg(Color::Gray) 
-        }),
-        Span::styled(" (YYYY-MM-DD)", Style::default().fg(Color::DarkGray)),
-    ]));
+    lines.push(field_line(form, 1, vec![Span::raw(desc_text)], "", None));
     
-    // Start Date field
-    let start_style = if form.field_index == 3 {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::Cyan)
-    };
-    let start_prefix = if form.field_index == 3 { "► " } else { "  " };
-    let start_text = form.start_date.as_deref().unwrap_or("<not set>");
-    lines.push(Line::from(vec![
-        Span::styled(start_prefix, Style::default().fg(Color::Yellow)),
-        Span::styled("Start Date: ", start_style),
-        Span::styled(start_text, if form.field_index == 3 { 
-            Style::default().fg(Color::White).bg(Color::DarkGray) 
-        } else { 
-            Style::default().fg(Color::Gray) 
-        }),
-        Span::styled(" (YYYY-MM-DD)", Style::default().fg(Color::DarkGray)),
-    ]));
+    // Dates
+    lines.push(field_line(form, 2, vec![Span::raw(or_not_set(&form.due_date))], "(YYYY-MM-DD [HH:MM], Ctrl+T calendar)", error(2)));
+    lines.push(field_line(form, 3, vec![Span::raw(or_not_set(&form.start_date))], "(YYYY-MM-DD [HH:MM], Ctrl+T calendar)", error(3)));
     
     // Priority field
-    let prio_style = if form.field_index == 4 {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::Cyan)
-    };
-    let prio_prefix = if form.field_index == 4 { "► " } else { "  " };
     let prio_text = form.priority.map(|p| p.to_string()).unwrap_or_else(|| "0".to_string());
-    lines.push(Line::from(vec![
-        Span::styled(prio_prefix, Style::default().fg(Color::Yellow)),
-        Span::styled("Priority: ", prio_style),
-        Span::styled(&prio_text, if form.field_index == 4 { 
-            Style::default().fg(Color::White).bg(Color::DarkGray) 
-        } else { 
-            Style::default().fg(Color::Gray) 
-        }),
-        Span::styled(" (0-5, 0=none)", Style::default().fg(Color::DarkGray)),
-    ]));
+    lines.push(field_line(form, 4, vec![Span::raw(prio_text)], "(0-5, 0=none)", error(4)));
     
     // Project field (with name lookup)
-    let proj_style = if form.field_index == 5 {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::Cyan)
-    };
-    let proj_prefix = if form.field_index == 5 { "► " } else { "  " };
     let project_name = app.project_map.get(&form.project_id)
         .map(|name| format!("{} (ID: {})", name, form.project_id))
         .unwrap_or_else(|| format!("Unknown Project (ID: {})", form.project_id));
-    lines.push(Line::from(vec![
-        Span::styled(proj_prefix, Style::default().fg(Color::Yellow)),
-        Span::styled("Project: ", proj_style),
-        Span::styled(&project_name, if form.field_index == 5 { 
-            Style::default().fg(Color::White).bg(Color::DarkGray) 
-        } else { 
-            Style::default().fg(Color::Gray) 
-        }),
-        Span::styled(" (Space to pick)", Style::default().fg(Color::DarkGray)),
-    ]));
+    lines.push(field_line(form, 5, vec![Span::raw(project_name)], "(Space to pick)", None));
     
     // Labels field (with name lookup)
-    let labels_style = if form.field_index == 6 {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::Cyan)
-    };
-    let labels_prefix = if form.field_index == 6 { "► " } else { "  " };
     let labels_text = if form.label_ids.is_empty() {
         "<none>".to_string()
     } else {
@@ -183,103 +134,67 @@ fn render_form_fields(f: &mut Frame, area: Rect, app: &App, form: &FormEditState
             .collect::<Vec<_>>()
             .join(", ")
     };
-    lines.push(Line::from(vec![
-        Span::styled(labels_prefix, Style::default().fg(Color::Yellow)),
-        Span::styled("Labels: ", labels_style),
-        Span::styled(&labels_text, if form.field_index == 6 { 
-            Style::default().fg(Color::White).bg(Color::DarkGray) 
-        } else { 
-            Style::default().fg(Color::Gray) 
-        }),
-        Span::styled(" (Space to pick)", Style::default().fg(Color::DarkGray)),
-    ]));
+    lines.push(field_line(form, 6, vec![Span::raw(labels_text)], "(Space to pick)", None));
     
     // Assignees field
-    let assign_style = if form.field_index == 7 {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+    let assign_text = if form.assignee_ids.is_empty() {
+        "<none>".to_string()
     } else {
-        Style::default().fg(Color::Cyan)
-    };
-    let assign_prefix = if form.field_index == 7 { "► " } else { "  " };
-    let assign_text = if form.assignee_ids.is_empty() { 
-        "<none>" 
-    } else { 
-        &format!("{:?}", form.assignee_ids) 
+        form.assignee_ids.iter()
+            .map(|id| app.user_display_name(*id))
+            .collect::<Vec<_>>()
+            .join(", ")
     };
-    lines.push(Line::from(vec![
-        Span::styled(assign_prefix, Style::default().fg(Color::Yellow)),
-        Span::styled("Assignees: ", assign_style),
-        Span::styled(assign_text, if form.field_index == 7 { 
-            Style::default().fg(Color::White).bg(Color::DarkGray) 
-        } else { 
-            Style::default().fg(Color::Gray) 
-        }),
-    ]));
+    lines.push(field_line(form, 7, vec![Span::raw(assign_text)], "(Space to pick)", None));
     
     // Is Favorite field
-    let fav_style = if form.field_index == 8 {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::Cyan)
-    };
-    let fav_prefix = if form.field_index == 8 { "► " } else { "  " };
     let fav_text = if form.is_favorite { "★ Yes" } else { "☆ No" };
-    lines.push(Line::from(vec![
-        Span::styled(fav_prefix, Style::default().fg(Color::Yellow)),
-        Span::styled("Favorite: ", fav_style),
-        Span::styled(fav_text, if form.field_index == 8 { 
-            Style::default().fg(Color::White).bg(Color::DarkGray) 
-        } else { 
-            Style::default().fg(Color::Gray) 
-        }),
-        Span::styled(" (Space to toggle)", Style::default().fg(Color::DarkGray)),
-    ]));
+    lines.push(field_line(form, 8, vec![Span::raw(fav_text)], "(Space to toggle)", None));
+
+    lines.push(field_line(form, 9, vec![Span::raw(or_not_set(&form.end_date))], "(YYYY-MM-DD [HH:MM], Ctrl+T calendar)", error(9)));
+    lines.push(field_line(form, 10, vec![Span::raw(or_none(&form.repeat))], "(e.g. every 2 weeks, monthly)", error(10)));
+    lines.push(field_line(form, 11, vec![Span::raw(or_none(&form.reminders))], "(e.g. 1d before due, 2025-07-15 09:00)", error(11)));
+
+    // Progress slider
+    let filled = (form.percent_done / 10) as usize;
+    lines.push(field_line(form, 12, vec![
+        Span::styled("█".repeat(filled), Style::default().fg(Color::Green)),
+        Span::styled("░".repeat(10 - filled), Style::default().fg(Color::DarkGray)),
+        Span::raw(format!(" {:>3}%", form.percent_done)),
+    ], "(←→ by 10%, 0-9)", None));
+
+    // Color with a swatch
+    let mut color_spans = vec![Span::raw(or_none(&form.hex_color))];
+    if let Some(color) = crate::tui::utils::hex_to_color(&form.hex_color) {
+        color_spans.push(Span::styled(" ██", Style::default().fg(color)));
+    }
+    lines.push(field_line(form, 13, color_spans, "(hex, Space for palette)", error(13)));
+    lines.push(field_line(form, 14, vec![Span::raw(or_none(&form.bucket))], "(kanban bucket title or id)", None));
     
     // Comment field
-    let comment_style = if form.field_index == 9 {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::Cyan)
-    };
-    let comment_prefix = if form.field_index == 9 { "► " } else { "  " };
     let comment_text = if form.comment.is_empty() { 
-        "Type your comment, then press Enter to save" 
+        "Type your comment, then press Enter to save".to_string()
     } else { 
-        &form.comment 
+        form.comment.clone()
     };
-    lines.push(Line::from(vec![
-        Span::styled(comment_prefix, Style::default().fg(Color::Yellow)),
-        Span::styled("Add Comment: ", comment_style),
-        Span::styled(comment_text, if form.field_index == 9 { 
-            Style::default().fg(Color::White).bg(Color::DarkGray) 
-        } else { 
-            Style::default().fg(Color::Gray) 
-        }),
-    ]));
+    lines.push(field_line(form, 15, vec![Span::raw(comment_text)], "", None));
     
     let paragraph = Paragraph::new(lines)
-        .wrap(Wrap { trim: false })
         .style(Style::default().fg(Color::White));
     
     f.render_widget(paragraph, area);
-    // Position the terminal cursor at the current field's edit position
-    // Only for text/editable fields: title (0), description (1), due_date (2), start_date (3), priority (4), comment (9)
-    if (0..=4).contains(&form.field_index) || form.field_index == 9 {
-        // Determine label length for cursor offset
-        let label = match form.field_index {
-            0 => "Title: ",
-            1 => "Description: ",
-            2 => "Due Date: ",
-            3 => "Start Date: ",
-            4 => "Priority: ",
-            9 => "Add Comment: ",
-            _ => "",
-        };
+    // Position the terminal cursor at the current field's edit position, for text fields
+    if FormEditState::is_text_field(form.field_index) {
+        let label = FormEditState::field_label(form.field_index);
+        let changed_marker = if form.is_field_changed(form.field_index) { 1 } else { 0 };
         let prefix_width = 2; // "► " or "  "
-        let offset = prefix_width + label.len();
-        let cursor_x = area.x + offset as u16 + form.cursor_position as u16;
+        let offset = prefix_width + label.len() + changed_marker + 2;
+        let (_, column) = cursor_row_col(&form.get_current_field_text(), form.cursor_position);
+        let cursor_x = (area.x + (offset + column) as u16).min(area.right().saturating_sub(1));
         let cursor_y = area.y + form.field_index as u16;
-        f.set_cursor(cursor_x, cursor_y);
+        if cursor_y < area.bottom() {
+            f.set_cursor(cursor_x, cursor_y);
+        }
     }
 }
 
@@ -319,10 +234,20 @@ fn render_help_section(f: &mut Frame, area: Rect, form: &FormEditState) {
     
     // Field-specific help
     match form.field_index {
-        2 | 3 => {
+        1 => {
+            help_lines.push(Line::from(vec![
+                Span::styled("Description:", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+                Span::raw("  Markdown. Press "),
+                Span::styled("Ctrl+G", Style::default().fg(Color::Yellow)),
+                Span::raw(" to edit it in $VISUAL/$EDITOR"),
+            ]));
+        }
+        2 | 3 | 9 => {
             help_lines.push(Line::from(vec![
                 Span::styled("Date Format:", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
-                Span::raw("  YYYY-MM-DD (e.g., 2025-07-15) or leave empty for no date"),
+                Span::raw("  YYYY-MM-DD for all day, YYYY-MM-DD HH:MM with a time, or empty. "),
+                Span::styled("Ctrl+T", Style::default().fg(Color::Yellow)),
+                Span::raw(" opens a calendar"),
             ]));
         }
         4 => {
@@ -347,6 +272,14 @@ fn render_help_section(f: &mut Frame, area: Rect, form: &FormEditState) {
                 Span::raw(" to open label picker"),
             ]));
         }
+        7 => {
+            help_lines.push(Line::from(vec![
+                Span::styled("Assignees:", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+                Span::raw("    Press "),
+                Span::styled("Space", Style::default().fg(Color::Yellow)),
+                Span::raw(" to search users and pick assignees"),
+            ]));
+        }
         8 => {
             help_lines.push(Line::from(vec![
                 Span::styled("Favorite:", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
@@ -355,6 +288,44 @@ fn render_help_section(f: &mut Frame, area: Rect, form: &FormEditState) {
                 Span::raw(" to toggle favorite status"),
             ]));
         }
+        10 => {
+            help_lines.push(Line::from(vec![
+                Span::styled("Repeat:", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+                Span::raw("       every N hours/days/weeks, daily, weekly, monthly; add \"from completion\" to repeat from when it is done"),
+            ]));
+        }
+        11 => {
+            help_lines.push(Line::from(vec![
+                Span::styled("Reminders:", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+                Span::raw("    Comma-separated dates, or 30m/2h/1d/1w before/after due/start/end, or \"at due\""),
+            ]));
+        }
+        12 => {
+            help_lines.push(Line::from(vec![
+                Span::styled("Progress:", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+                Span::raw("     "),
+                Span::styled("←→", Style::default().fg(Color::Yellow)),
+                Span::raw(" by 10%, "),
+                Span::styled("0-9", Style::default().fg(Color::Yellow)),
+                Span::raw(" for 0-90%, "),
+                Span::styled("End", Style::default().fg(Color::Yellow)),
+                Span::raw(" for 100%"),
+            ]));
+        }
+        13 => {
+            help_lines.push(Line::from(vec![
+                Span::styled("Color:", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+                Span::raw("        Type a hex color or press "),
+                Span::styled("Space", Style::default().fg(Color::Yellow)),
+                Span::raw(" to cycle through the palette; empty for none"),
+            ]));
+        }
+        14 => {
+            help_lines.push(Line::from(vec![
+                Span::styled("Bucket:", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+                Span::raw("       Title or id of a bucket in the project's kanban view"),
+            ]));
+        }
         _ => {
             help_lines.push(Line::from(vec![
                 Span::styled("Tip:", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
@@ -1,5 +1,6 @@
 // Main layout and entry point for TUI drawing
 
+use crate::tui::app::form_edit_state::FormEditState;
 use crate::tui::app::state::App;
 use ratatui::prelude::*;
 use ratatui::style::{Color, Style, Modifier};
@@ -11,7 +12,7 @@ use super::modals::{draw_quick_add_modal, draw_edit_modal, draw_confirmation_dia
 // Relations modals - DISABLED: Incomplete feature
 // use super::modals::{draw_relations_modal, draw_add_relation_modal};
 use super::form_edit::draw_form_edit_modal;
-use super::pickers::{draw_project_picker_modal, draw_filter_picker_modal, draw_label_picker_modal};
+use super::pickers::{draw_project_picker_modal, draw_filter_picker_modal, draw_label_picker_modal, draw_assignee_picker_modal};
 
 pub fn hex_to_color(hex: &str) -> Color {
     let hex = hex.trim_start_matches('#');
@@ -83,11 +84,20 @@ pub fn draw(f: &mut Frame, app: &App) {
             draw_project_picker_modal(f, app);
         } else if app.show_label_picker {
             draw_label_picker_modal(f, app);
+        } else if app.show_assignee_picker {
+            draw_assignee_picker_modal(f, app);
+        } else if let Some(form) = app.form_edit_state.as_ref() {
+            if let Some(ref picker) = form.date_picker {
+                let title = FormEditState::field_label(form.field_index);
+                picker.draw(f, f.size(), title);
+            }
         }
     } else if app.show_project_picker {
         draw_project_picker_modal(f, app);
     } else if app.show_label_picker {
         draw_label_picker_modal(f, app);
+    } else if app.show_assignee_picker {
+        draw_assignee_picker_modal(f, app);
     } else if app.show_quick_add_modal {
         draw_quick_add_modal(f, app);
     } else if app.show_edit_modal {
@@ -115,6 +125,46 @@ pub fn draw(f: &mut Frame, app: &App) {
         }
     } else if let Some(ref modal) = app.comments_modal {
         modal.draw(f, f.size());
+    } else if app.show_label_manager_modal {
+        if let Some(ref modal) = app.label_manager_modal {
+            modal.draw(f, f.size());
+        }
+    } else if app.show_edit_conflict_modal {
+        if let Some(ref modal) = app.edit_conflict_modal {
+            modal.draw(f, f.size());
+        }
+    } else if app.show_project_sharing_modal {
+        if let Some(ref modal) = app.project_sharing_modal {
+            modal.draw(f, f.size());
+        }
+    } else if app.show_checklist_modal {
+        if let Some(ref modal) = app.checklist_modal {
+            modal.draw(f, f.size());
+        }
+    } else if app.show_template_picker_modal {
+        if let Some(ref modal) = app.template_picker_modal {
+            modal.draw(f, f.size());
+        }
+    } else if app.show_snooze_modal {
+        if let Some(ref modal) = app.snooze_modal {
+            modal.draw(f, f.size());
+        }
+    } else if app.show_reschedule_modal {
+        if let Some(ref modal) = app.reschedule_modal {
+            modal.draw(f, f.size(), app);
+        }
+    } else if app.show_trash_modal {
+        if let Some(ref modal) = app.trash_modal {
+            modal.draw(f, f.size(), app);
+        }
+    } else if app.show_undo_history_modal {
+        if let Some(ref modal) = app.undo_history_modal {
+            modal.draw(f, f.size(), app);
+        }
+    } else if app.show_paste_preview_modal {
+        if let Some(ref modal) = app.paste_preview_modal {
+            modal.draw(f, f.size(), &app.date_settings);
+        }
     } else if app.show_subtask_modal {
         draw_subtask_modal(f, app);
     } else if app.show_add_subtask_modal {
@@ -5,5 +5,6 @@ pub mod modals;
 pub mod pickers;
 pub mod form_edit;
 pub mod attachment_viewer;
+pub mod rich_text;
 
 pub use main::hex_to_color;
@@ -1,5 +1,6 @@
 use crate::tui::app::state::App;
 use crate::tui::app::suggestion_mode::SuggestionMode;
+use crate::tui::line_editor::input_viewport;
 use crate::tui::utils::{get_label_color, get_project_color};
 use ratatui::prelude::*;
 use ratatui::style::{Color, Style, Modifier};
@@ -97,15 +98,13 @@ pub fn draw_quick_add_modal(f: &mut Frame, app: &App) {
         .title("Quick Add Task")
         .title_alignment(Alignment::Center)
         .style(Style::default().fg(Color::Green));
+    let viewport = input_viewport(&app.quick_add_input, app.quick_add_cursor_position, input_block.inner(modal_chunks[0]));
     let input_paragraph = Paragraph::new(vec![Line::from(input_spans)])
         .block(input_block)
-        .style(Style::default().fg(Color::Yellow));
+        .style(Style::default().fg(Color::Yellow))
+        .scroll(viewport.scroll);
     f.render_widget(input_paragraph, modal_chunks[0]);
-    let cursor_x = modal_chunks[0].x + 1 + app.quick_add_cursor_position as u16;
-    let cursor_y = modal_chunks[0].y + 1;
-    if cursor_x < modal_chunks[0].x + modal_chunks[0].width - 1 {
-        f.set_cursor(cursor_x, cursor_y);
-    }
+    f.set_cursor(viewport.cursor_x, viewport.cursor_y);
     // Render suggestions in the reserved chunk
     if app.suggestion_mode.is_some() && !app.suggestions.is_empty() {
         let max_visible = 4; // Match the visible lines in the UI
@@ -122,7 +121,10 @@ pub fn draw_quick_add_modal(f: &mut Frame, app: &App) {
                 let (color, prefix) = match app.suggestion_mode {
                     Some(SuggestionMode::Label) => (get_label_color(s, app), "*"),
                     Some(SuggestionMode::Project) => (get_project_color(s, app), "+"),
-                    _ => (Color::Gray, "")
+                    Some(SuggestionMode::Assignee) => (Color::Blue, "@"),
+                    Some(SuggestionMode::Date) => (Color::Cyan, ""),
+                    Some(SuggestionMode::Repeat) => (Color::Cyan, "every "),
+                    None => (Color::Gray, "")
                 };
                 let styled = Span::styled(format!("{}{}", prefix, s), Style::default().fg(color));
                 let absolute_index = start + i;
@@ -172,6 +174,8 @@ pub fn draw_quick_add_modal(f: &mut Frame, app: &App) {
         Line::from(vec![Span::raw("• "), Span::styled("Team meeting every Monday", Style::default().fg(Color::White)), Span::raw(" - recurring task")]),
         Line::from(vec![Span::raw("• "), Span::styled("new-label:urgent Buy groceries", Style::default().fg(Color::White)), Span::raw(" - creates & applies new label")]),
         Line::from(vec![Span::raw("• "), Span::styled("new-project:[Work Stuff] Plan meeting", Style::default().fg(Color::White)), Span::raw(" - creates & assigns new project")]),
+        Line::from(vec![Span::raw("• "), Span::styled("Book venue ^#12 blocks:#45", Style::default().fg(Color::White)), Span::raw(" - subtask of #12, blocks #45 (also after:, related:, ^\"Title\")")]),
+        Line::from(vec![Span::raw("• "), Span::styled("Paste an indented list", Style::default().fg(Color::White)), Span::raw(" - previews one task per line, nested lines as subtasks")]),
         Line::from("") ,
         Line::from(vec![
             Span::styled("Syntax: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
@@ -185,8 +189,12 @@ pub fn draw_quick_add_modal(f: &mut Frame, app: &App) {
         Line::from(vec![
             Span::styled("Enter", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
             Span::raw(" to create • "),
+            Span::styled("Tab", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::raw(" complete (again: next) • "),
+            Span::styled("↑↓/Ctrl+P/N", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::raw(" history • "),
             Span::styled("Escape", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
-            Span::raw(" to cancel")
+            Span::raw(" to cancel (keeps a draft)")
         ]),
     ];
     let help_block = Block::default()
@@ -223,15 +231,13 @@ pub fn draw_edit_modal(f: &mut Frame, app: &App) {
         .title("Edit Task")
         .title_alignment(Alignment::Center)
         .style(Style::default().fg(Color::Green));
+    let viewport = input_viewport(&app.edit_input, app.edit_cursor_position, input_block.inner(modal_chunks[0]));
     let input_paragraph = Paragraph::new(vec![Line::from(input_spans)])
         .block(input_block)
-        .style(Style::default().fg(Color::Yellow));
+        .style(Style::default().fg(Color::Yellow))
+        .scroll(viewport.scroll);
     f.render_widget(input_paragraph, modal_chunks[0]);
-    let cursor_x = modal_chunks[0].x + 1 + app.edit_cursor_position as u16;
-    let cursor_y = modal_chunks[0].y + 1;
-    if cursor_x < modal_chunks[0].x + modal_chunks[0].width - 1 {
-        f.set_cursor(cursor_x, cursor_y);
-    }
+    f.set_cursor(viewport.cursor_x, viewport.cursor_y);
     // Render suggestions in the reserved chunk
     if app.suggestion_mode.is_some() && !app.suggestions.is_empty() {
         let max_visible = 4;
@@ -248,7 +254,10 @@ pub fn draw_edit_modal(f: &mut Frame, app: &App) {
                 let (color, prefix) = match app.suggestion_mode {
                     Some(SuggestionMode::Label) => (get_label_color(s, app), "*"),
                     Some(SuggestionMode::Project) => (get_project_color(s, app), "+"),
-                    _ => (Color::Gray, "")
+                    Some(SuggestionMode::Assignee) => (Color::Blue, "@"),
+                    Some(SuggestionMode::Date) => (Color::Cyan, ""),
+                    Some(SuggestionMode::Repeat) => (Color::Cyan, "every "),
+                    None => (Color::Gray, "")
                 };
                 let styled = Span::styled(format!("{}{}", prefix, s), Style::default().fg(color));
                 let absolute_index = start + i;
@@ -305,8 +314,12 @@ pub fn draw_edit_modal(f: &mut Frame, app: &App) {
         Line::from(vec![
             Span::styled("Enter", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
             Span::raw(" to update • "),
+            Span::styled("Tab", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::raw(" complete (again: next) • "),
+            Span::styled("↑↓/Ctrl+P/N", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::raw(" history • "),
             Span::styled("Escape", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
-            Span::raw(" to cancel")
+            Span::raw(" to cancel (keeps a draft)")
         ]),
     ];
     let help_block = Block::default()
@@ -372,17 +385,24 @@ pub fn draw_help_modal(f: &mut Frame, app: &App) {
         Line::from(vec![Span::styled("D", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Delete task")]),
         Line::from(vec![Span::styled("a", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Add task (quick add modal)")]),
         Line::from(vec![Span::styled("e", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Edit task (magic syntax)")]),
-        Line::from(vec![Span::styled("E", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Edit task (form mode)")]),
+        Line::from(vec![Span::styled("E", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Edit task (form mode; Ctrl+G edits the description in $EDITOR)")]),
         Line::from(vec![Span::styled("f", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Show filter picker")]),
         Line::from(vec![Span::styled("p", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Project picker")]),
         Line::from(vec![Span::styled("o", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Open URLs from selected task")]),
         Line::from(vec![Span::styled("S", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Add subtask to selected task")]),
+        Line::from(vec![Span::styled("N", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": New task from a template")]),
         Line::from(vec![Span::styled("r", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Refresh tasks/projects/filters")]),
         Line::from(vec![Span::styled("s", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Star/unstar task")]),
+        Line::from(vec![Span::styled("Ctrl+Z / Ctrl+Y", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Undo / redo, on the server too")]),
+        Line::from(vec![Span::styled("U", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Undo history (jump back to any point)")]),
+        Line::from(vec![Span::styled("T", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Trash (restore deleted tasks before they are deleted on the server)")]),
+        Line::from(vec![Span::styled("v / V", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Mark/unmark task for bulk commands / clear marks")]),
+        Line::from(vec![Span::styled("z", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Snooze marked (or selected) tasks, start dates move along")]),
+        Line::from(vec![Span::styled("O", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Reschedule all overdue tasks to today, with preview")]),
         Line::from(vec![Span::styled("i", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Toggle info pane")]),
         Line::from(vec![Span::styled("x", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Toggle debug pane")]),
         Line::from(vec![Span::styled("h / l", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Switch layouts backward/forward")]),
-        Line::from(vec![Span::styled("H / L", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Cycle task filters (active/all/etc)")]),
+        Line::from(vec![Span::styled("H / L", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Cycle task filters (active/all/completed/assigned to me/unassigned)")]),
         Line::from(vec![Span::styled("Space", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Quick actions modal")]),
         Line::from(vec![Span::styled(".", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Advanced features modal")]),
         Line::raw("")
@@ -451,6 +471,9 @@ pub fn draw_advanced_help_modal(f: &mut Frame, _app: &App) {
         Line::from(vec![Span::styled(".a", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Attachment management")]),
         Line::from(vec![Span::styled(".c", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Comments")]),
         Line::from(vec![Span::styled(".r", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Task relations (coming soon)")]),
+        Line::from(vec![Span::styled(".l", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Label manager")]),
+        Line::from(vec![Span::styled(".u", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Project sharing")]),
+        Line::from(vec![Span::styled(".x", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Description checklist")]),
         Line::from(vec![Span::styled(".h", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Task history (coming soon)")]),
         Line::from(vec![Span::styled(".s", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Subtasks (coming soon)")]),
         Line::from(vec![Span::styled(".t", Style::default().add_modifier(Modifier::BOLD)), Span::raw(": Time tracking (coming soon)")]),
@@ -473,8 +496,11 @@ pub fn draw_advanced_features_modal(f: &mut Frame, app: &App) {
     // Define advanced features
     let advanced_features = vec![
         ("a", "Attachment Management", "View and manage task attachments", true),
-        ("c", "Comments", "View and add task comments", true),
+        ("c", "Comments", "View, add, edit and reply to task comments", true),
         ("r", "Task Relations", "Manage task dependencies and links", false),
+        ("l", "Label Manager", "Rename, recolor, merge and delete labels", true),
+        ("u", "Project Sharing", "Share the project with users, teams and links", true),
+        ("x", "Checklist", "Check off items of the description's checklist", true),
     ];
     
     // Calculate modal size
@@ -996,11 +1022,14 @@ pub fn draw_subtask_modal(f: &mut Frame, app: &App) {
         .title("Filter Tasks")
         .style(Style::default().fg(Color::Green));
     
+    let viewport = input_viewport(&app.subtask_picker_input, app.picker_cursor_position, input_block.inner(chunks[0]));
     let input_paragraph = Paragraph::new(app.subtask_picker_input.as_str())
         .block(input_block)
-        .style(Style::default().fg(Color::Yellow));
+        .style(Style::default().fg(Color::Yellow))
+        .scroll(viewport.scroll);
     
     f.render_widget(input_paragraph, chunks[0]);
+    f.set_cursor(viewport.cursor_x, viewport.cursor_y);
     
     // Task list
     let list_block = Block::default()
@@ -1113,18 +1142,16 @@ pub fn draw_add_subtask_modal(f: &mut Frame, app: &App) {
         .title_alignment(Alignment::Center)
         .style(Style::default().fg(Color::Cyan));
     
+    let viewport = input_viewport(input_text, app.add_subtask_cursor_position, input_block.inner(modal_chunks[1]));
     let input_paragraph = Paragraph::new(vec![Line::from(input_spans)])
         .block(input_block)
-        .style(Style::default().fg(Color::Yellow));
+        .style(Style::default().fg(Color::Yellow))
+        .scroll(viewport.scroll);
     
     f.render_widget(input_paragraph, modal_chunks[1]);
     
     // Set cursor position
-    let cursor_x = modal_chunks[1].x + 1 + app.add_subtask_cursor_position as u16;
-    let cursor_y = modal_chunks[1].y + 1;
-    if cursor_x < modal_chunks[1].x + modal_chunks[1].width - 1 {
-        f.set_cursor(cursor_x, cursor_y);
-    }
+    f.set_cursor(viewport.cursor_x, viewport.cursor_y);
     
     // Help text
     let help_lines = vec![
@@ -1,6 +1,7 @@
 // Picker rendering (project picker, filter picker)
 
 use crate::tui::app::state::App;
+use crate::tui::line_editor::input_viewport;
 use ratatui::prelude::*;
 use ratatui::style::{Color, Style, Modifier};
 use ratatui::widgets::{Paragraph, Block, Borders, Clear, Wrap};
@@ -28,9 +29,11 @@ pub fn draw_project_picker_modal(f: &mut Frame, app: &App) {
         .title("Filter Projects (type to search)")
         .title_alignment(Alignment::Center)
         .style(Style::default().fg(Color::Magenta));
+    let viewport = input_viewport(&app.project_picker_input, app.picker_cursor_position, input_block.inner(modal_chunks[0]));
     let input_paragraph = Paragraph::new(app.project_picker_input.as_str())
         .block(input_block)
-        .style(Style::default().fg(Color::Yellow));
+        .style(Style::default().fg(Color::Yellow))
+        .scroll(viewport.scroll);
     f.render_widget(input_paragraph, modal_chunks[0]);
     // Project list
     let mut project_lines = Vec::new();
@@ -56,11 +59,7 @@ pub fn draw_project_picker_modal(f: &mut Frame, app: &App) {
         .wrap(Wrap { trim: false });
     f.render_widget(list_paragraph, modal_chunks[1]);
     // Position cursor in input
-    let cursor_x = modal_chunks[0].x + 1 + app.project_picker_input.len() as u16;
-    let cursor_y = modal_chunks[0].y + 1;
-    if cursor_x < modal_chunks[0].x + modal_chunks[0].width - 1 {
-        f.set_cursor(cursor_x, cursor_y);
-    }
+    f.set_cursor(viewport.cursor_x, viewport.cursor_y);
 }
 
 pub fn draw_label_picker_modal(f: &mut Frame, app: &App) {
@@ -88,9 +87,11 @@ pub fn draw_label_picker_modal(f: &mut Frame, app: &App) {
         .title("Filter Labels (type to search)")
         .title_alignment(Alignment::Center)
         .style(Style::default().fg(Color::Green));
+    let viewport = input_viewport(&app.label_picker_input, app.picker_cursor_position, input_block.inner(modal_chunks[0]));
     let input_paragraph = Paragraph::new(app.label_picker_input.as_str())
         .block(input_block)
-        .style(Style::default().fg(Color::Yellow));
+        .style(Style::default().fg(Color::Yellow))
+        .scroll(viewport.scroll);
     f.render_widget(input_paragraph, modal_chunks[0]);
     
     // Label list
@@ -133,11 +134,7 @@ pub fn draw_label_picker_modal(f: &mut Frame, app: &App) {
     f.render_widget(instructions_paragraph, modal_chunks[2]);
     
     // Position cursor in input
-    let cursor_x = modal_chunks[0].x + 1 + app.label_picker_input.len() as u16;
-    let cursor_y = modal_chunks[0].y + 1;
-    if cursor_x < modal_chunks[0].x + modal_chunks[0].width - 1 {
-        f.set_cursor(cursor_x, cursor_y);
-    }
+    f.set_cursor(viewport.cursor_x, viewport.cursor_y);
 }
 
 pub fn draw_filter_picker_modal(f: &mut Frame, app: &App) {
@@ -161,9 +158,11 @@ pub fn draw_filter_picker_modal(f: &mut Frame, app: &App) {
         .title("Filter Saved Views (type to search)")
         .title_alignment(Alignment::Center)
         .style(Style::default().fg(Color::Magenta));
+    let viewport = input_viewport(&app.filter_picker_input, app.picker_cursor_position, input_block.inner(modal_chunks[0]));
     let input_paragraph = Paragraph::new(app.filter_picker_input.as_str())
         .block(input_block)
-        .style(Style::default().fg(Color::Yellow));
+        .style(Style::default().fg(Color::Yellow))
+        .scroll(viewport.scroll);
     f.render_widget(input_paragraph, modal_chunks[0]);
     // Filter list
     let mut filter_lines = Vec::new();
@@ -197,9 +196,91 @@ pub fn draw_filter_picker_modal(f: &mut Frame, app: &App) {
         .wrap(Wrap { trim: false });
     f.render_widget(list_paragraph, modal_chunks[1]);
     // Position cursor in input
-    let cursor_x = modal_chunks[0].x + 1 + app.filter_picker_input.len() as u16;
-    let cursor_y = modal_chunks[0].y + 1;
-    if cursor_x < modal_chunks[0].x + modal_chunks[0].width - 1 {
-        f.set_cursor(cursor_x, cursor_y);
+    f.set_cursor(viewport.cursor_x, viewport.cursor_y);
+}
+
+pub fn draw_assignee_picker_modal(f: &mut Frame, app: &App) {
+    let area = f.size();
+    let modal_width = (area.width as f32 * 0.6) as u16;
+    let modal_height = (area.height as f32 * 0.7) as u16;
+    let x = (area.width.saturating_sub(modal_width)) / 2;
+    let y = (area.height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect { x, y, width: modal_width, height: modal_height };
+
+    f.render_widget(Clear, modal_area);
+
+    let modal_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Input
+            Constraint::Min(3),   // List
+            Constraint::Length(3), // Instructions
+        ])
+        .split(modal_area);
+
+    // Input field
+    let input_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Search Users (type to search)")
+        .title_alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Blue));
+    let viewport = input_viewport(&app.assignee_picker_input, app.picker_cursor_position, input_block.inner(modal_chunks[0]));
+    let input_paragraph = Paragraph::new(app.assignee_picker_input.as_str())
+        .block(input_block)
+        .style(Style::default().fg(Color::Yellow))
+        .scroll(viewport.scroll);
+    f.render_widget(input_paragraph, modal_chunks[0]);
+
+    // User list
+    let current_id = app.current_user.as_ref().map(|u| u.id);
+    let mut user_lines = Vec::new();
+    for (i, user) in app.filtered_assignees.iter().enumerate() {
+        let is_selected = i == app.selected_assignee_picker_index;
+        let is_checked = app.selected_assignee_ids.contains(&user.id);
+
+        let mut style = Style::default().fg(Color::White);
+        if is_selected {
+            style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+        }
+
+        let checkbox = if is_checked { "[✓] " } else { "[ ] " };
+        let mut spans = vec![Span::styled(format!("{}{}", checkbox, user.display_name()), style)];
+        if user.display_name() != user.username {
+            spans.push(Span::styled(format!(" @{}", user.username), Style::default().fg(Color::DarkGray)));
+        }
+        if Some(user.id) == current_id {
+            spans.push(Span::styled(" (me)", Style::default().fg(Color::Green)));
+        }
+        user_lines.push(Line::from(spans));
+    }
+    if user_lines.is_empty() {
+        user_lines.push(Line::from(Span::styled("No users found - keep typing to search the server", Style::default().fg(Color::Gray))));
     }
+
+    let list_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Select Assignees (Tab/Ctrl+Space to toggle, Enter to confirm, Esc to cancel)")
+        .title_alignment(Alignment::Center);
+    let list_paragraph = Paragraph::new(user_lines)
+        .block(list_block)
+        .wrap(Wrap { trim: false });
+    f.render_widget(list_paragraph, modal_chunks[1]);
+
+    // Instructions
+    let selected_names = app.selected_assignee_ids.iter()
+        .map(|id| app.user_display_name(*id))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let instructions_text = format!("Selected: {} assignees {}", app.selected_assignee_ids.len(), if selected_names.is_empty() { String::new() } else { format!("({})", selected_names) });
+    let instructions_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Instructions")
+        .title_alignment(Alignment::Center);
+    let instructions_paragraph = Paragraph::new(instructions_text)
+        .block(instructions_block)
+        .style(Style::default().fg(Color::Cyan));
+    f.render_widget(instructions_paragraph, modal_chunks[2]);
+
+    // Position cursor in input
+    f.set_cursor(viewport.cursor_x, viewport.cursor_y);
 }
@@ -0,0 +1,318 @@
+// Rendering of HTML descriptions and comments as styled ratatui lines
+
+use crate::html::{collapse_whitespace, looks_like_html, parse_html, HtmlNode};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+/// Render a description or comment. `links` is the task's URL list as shown in the
+/// URL modal; every link found in the text gets a `[n]` marker pointing into it.
+pub fn render_rich_text(text: &str, links: &[String]) -> Vec<Line<'static>> {
+    let mut renderer = Renderer::new(links);
+    if looks_like_html(text) {
+        renderer.blocks(&parse_html(text), Style::default());
+    } else {
+        // Plain text keeps its line breaks
+        for line in text.lines() {
+            renderer.text(line, Style::default(), false);
+            renderer.break_line();
+        }
+    }
+    renderer.finish()
+}
+
+/// Indentation for one level of nesting: `first` is used once, for the line that
+/// starts the block (a list bullet), `rest` for every following line
+struct Gutter {
+    first: Option<String>,
+    rest: String,
+    style: Style,
+}
+
+struct Renderer<'a> {
+    links: &'a [String],
+    lines: Vec<Line<'static>>,
+    current: Vec<Span<'static>>,
+    gutters: Vec<Gutter>,
+    /// Inside a list item blocks are not separated by blank lines
+    list_depth: usize,
+}
+
+fn link_style() -> Style {
+    Style::default().fg(Color::Blue).add_modifier(Modifier::UNDERLINED)
+}
+
+fn marker_style() -> Style {
+    Style::default().fg(Color::DarkGray)
+}
+
+fn code_style() -> Style {
+    Style::default().fg(Color::Yellow)
+}
+
+impl<'a> Renderer<'a> {
+    fn new(links: &'a [String]) -> Self {
+        Self { links, lines: Vec::new(), current: Vec::new(), gutters: Vec::new(), list_depth: 0 }
+    }
+
+    fn finish(mut self) -> Vec<Line<'static>> {
+        self.end_line();
+        while self.lines.last().is_some_and(|l| l.width() == 0) {
+            self.lines.pop();
+        }
+        self.lines
+    }
+
+    fn line_started(&self) -> bool {
+        !self.current.is_empty()
+    }
+
+    fn start_line(&mut self) {
+        if self.line_started() {
+            return;
+        }
+        for gutter in &mut self.gutters {
+            let text = gutter.first.take().unwrap_or_else(|| gutter.rest.clone());
+            self.current.push(Span::styled(text, gutter.style));
+        }
+        // Keep `line_started` true even when there is no gutter
+        if self.current.is_empty() {
+            self.current.push(Span::raw(""));
+        }
+    }
+
+    fn push_span(&mut self, text: String, style: Style) {
+        self.start_line();
+        self.current.push(Span::styled(text, style));
+    }
+
+    /// End the current line if anything was written to it
+    fn end_line(&mut self) {
+        if self.line_started() {
+            let spans = std::mem::take(&mut self.current);
+            let spans = if spans.len() == 1 && spans[0].content.is_empty() { Vec::new() } else { spans };
+            self.lines.push(Line::from(spans));
+        }
+    }
+
+    /// End the current line, writing an empty (gutter only) line if nothing was written
+    fn break_line(&mut self) {
+        self.start_line();
+        self.end_line();
+    }
+
+    /// Separate a new block from what came before
+    fn block_gap(&mut self) {
+        self.end_line();
+        if self.list_depth == 0 && self.lines.last().is_some_and(|l| l.width() > 0) {
+            self.lines.push(Line::from(""));
+        }
+    }
+
+    fn link_marker(&self, url: &str) -> Option<Span<'static>> {
+        self.links.iter()
+            .position(|l| l == url)
+            .map(|i| Span::styled(format!("[{}]", i + 1), marker_style()))
+    }
+
+    /// Write inline text; whitespace collapses unless `preformatted`.
+    /// Bare URLs are styled as links and get their marker.
+    fn text(&mut self, text: &str, style: Style, preformatted: bool) {
+        let text = if preformatted { text.to_string() } else { collapse_whitespace(text) };
+        let text = if self.line_started() { text.as_str() } else { text.trim_start() };
+        if text.is_empty() {
+            return;
+        }
+        let mut rest = text;
+        for url in crate::url_utils::extract_urls(text) {
+            let Some(at) = rest.find(&url) else { continue };
+            if at > 0 {
+                self.push_span(rest[..at].to_string(), style);
+            }
+            self.push_span(url.clone(), style.patch(link_style()));
+            if let Some(marker) = self.link_marker(&url) {
+                self.current.push(marker);
+            }
+            rest = &rest[at + url.len()..];
+        }
+        if !rest.is_empty() {
+            self.push_span(rest.to_string(), style);
+        }
+    }
+
+    fn blocks(&mut self, nodes: &[HtmlNode], style: Style) {
+        for node in nodes {
+            self.node(node, style);
+        }
+    }
+
+    fn node(&mut self, node: &HtmlNode, style: Style) {
+        let name = match node {
+            HtmlNode::Text(text) => return self.text(text, style, false),
+            HtmlNode::Element { name, .. } => name.as_str(),
+        };
+        match name {
+            "p" | "div" => {
+                if name == "p" {
+                    self.block_gap();
+                } else {
+                    self.end_line();
+                }
+                self.blocks(node.children(), style);
+                self.end_line();
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.block_gap();
+                let heading = match name {
+                    "h1" | "h2" => style.fg(Color::Cyan).add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                    _ => style.fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                };
+                self.blocks(node.children(), heading);
+                self.end_line();
+            }
+            "ul" | "ol" => self.list(node, style),
+            "pre" => {
+                self.block_gap();
+                self.gutters.push(Gutter { first: None, rest: "│ ".to_string(), style: marker_style() });
+                for line in node.text_content().trim_end_matches('\n').lines() {
+                    self.start_line();
+                    self.text(line, style.patch(code_style()), true);
+                    self.break_line();
+                }
+                self.gutters.pop();
+            }
+            "blockquote" => {
+                self.block_gap();
+                self.gutters.push(Gutter { first: None, rest: "▎ ".to_string(), style: marker_style() });
+                self.blocks(node.children(), style.add_modifier(Modifier::ITALIC));
+                self.end_line();
+                self.gutters.pop();
+            }
+            "hr" => {
+                self.block_gap();
+                self.push_span("─".repeat(20), marker_style());
+                self.end_line();
+            }
+            "table" => self.table(node, style),
+            "br" => self.break_line(),
+            "strong" | "b" => self.blocks(node.children(), style.add_modifier(Modifier::BOLD)),
+            "em" | "i" => self.blocks(node.children(), style.add_modifier(Modifier::ITALIC)),
+            "u" => self.blocks(node.children(), style.add_modifier(Modifier::UNDERLINED)),
+            "s" | "del" | "strike" => self.blocks(node.children(), style.add_modifier(Modifier::CROSSED_OUT)),
+            "code" => self.text(&node.text_content(), style.patch(code_style()), true),
+            "a" => {
+                let href = node.attr("href").unwrap_or_default().to_string();
+                let label = node.text_content();
+                if label.trim().is_empty() || label.trim() == href {
+                    // A bare link; `text` styles it and adds the marker
+                    self.text(&href, style, false);
+                } else {
+                    self.blocks(node.children(), style.patch(link_style()));
+                    if let Some(marker) = self.link_marker(&href) {
+                        self.current.push(marker);
+                    }
+                }
+            }
+            "img" => {
+                let alt = node.attr("alt").filter(|a| !a.is_empty()).unwrap_or("image");
+                self.push_span(format!("[{}]", alt), marker_style());
+            }
+            "label" | "input" | "script" | "style" => {}
+            _ => self.blocks(node.children(), style),
+        }
+    }
+
+    fn list(&mut self, list: &HtmlNode, style: Style) {
+        self.block_gap();
+        let ordered = list.name() == Some("ol");
+        let start = list.attr("start").and_then(|s| s.parse::<usize>().ok()).unwrap_or(1);
+        let items = list.children().iter().filter(|c| c.name() == Some("li"));
+        for (index, item) in items.enumerate() {
+            self.end_line();
+            let checked = item.checklist_state();
+            let marker = match (ordered, checked) {
+                (_, Some(true)) => "☑ ".to_string(),
+                (_, Some(false)) => "☐ ".to_string(),
+                (true, None) => format!("{}. ", start + index),
+                (false, None) => "• ".to_string(),
+            };
+            let width = marker.chars().count();
+            let marker_color = if checked == Some(true) { Color::Green } else { Color::Cyan };
+            self.gutters.push(Gutter { first: Some(marker), rest: " ".repeat(width), style: Style::default().fg(marker_color) });
+            self.list_depth += 1;
+            let item_style = if checked == Some(true) { style.fg(Color::DarkGray) } else { style };
+            self.blocks(item.children(), item_style);
+            // An empty item still shows its bullet
+            if self.gutters.last().is_some_and(|g| g.first.is_some()) {
+                self.start_line();
+            }
+            self.end_line();
+            self.list_depth -= 1;
+            self.gutters.pop();
+        }
+    }
+
+    fn table(&mut self, table: &HtmlNode, style: Style) {
+        fn rows<'n>(node: &'n HtmlNode, out: &mut Vec<&'n HtmlNode>) {
+            for child in node.children() {
+                match child.name() {
+                    Some("tr") => out.push(child),
+                    Some(_) => rows(child, out),
+                    None => {}
+                }
+            }
+        }
+        self.block_gap();
+        let mut table_rows = Vec::new();
+        rows(table, &mut table_rows);
+        for row in table_rows {
+            let cells: Vec<&HtmlNode> = row.children().iter().filter(|c| matches!(c.name(), Some("td" | "th"))).collect();
+            for (i, cell) in cells.iter().enumerate() {
+                if i > 0 {
+                    self.push_span(" │ ".to_string(), marker_style());
+                }
+                let cell_style = if cell.name() == Some("th") { style.add_modifier(Modifier::BOLD) } else { style };
+                self.text(cell.text_content().trim(), cell_style, false);
+            }
+            self.end_line();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(lines: &[Line]) -> Vec<String> {
+        lines.iter().map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect()).collect()
+    }
+
+    #[test]
+    fn test_renders_blocks_lists_and_links() {
+        let html = "<h1>Release</h1><p>See <a href=\"https://example.com/notes\">the notes</a> and https://ci.example.com</p>\
+                    <ul><li><p>build</p><ol><li>test</li></ol></li></ul><pre><code>cargo run\n</code></pre><blockquote><p>ship it</p></blockquote>";
+        let links = vec!["https://example.com/notes".to_string(), "https://ci.example.com".to_string()];
+        let lines = render_rich_text(html, &links);
+        assert_eq!(plain(&lines), vec![
+            "Release",
+            "",
+            "See the notes[1] and https://ci.example.com[2]",
+            "",
+            "• build",
+            "  1. test",
+            "",
+            "│ cargo run",
+            "",
+            "▎ ship it",
+        ]);
+        let notes = lines[2].spans.iter().find(|s| s.content == "the notes").unwrap();
+        assert!(notes.style.add_modifier.contains(Modifier::UNDERLINED));
+    }
+
+    #[test]
+    fn test_renders_checklists_and_plain_text() {
+        let html = "<ul data-type=\"taskList\"><li data-checked=\"true\" data-type=\"taskItem\"><label><input type=\"checkbox\" checked=\"checked\"><span></span></label><div><p>pack</p></div></li>\
+                    <li data-checked=\"false\" data-type=\"taskItem\"><label><input type=\"checkbox\"><span></span></label><div><p>leave</p></div></li></ul>";
+        assert_eq!(plain(&render_rich_text(html, &[])), vec!["☑ pack", "☐ leave"]);
+        assert_eq!(plain(&render_rich_text("line one\nline two", &[])), vec!["line one", "line two"]);
+    }
+}
@@ -5,12 +5,15 @@ use ratatui::prelude::*;
 use ratatui::style::{Color, Style, Modifier};
 use ratatui::widgets::{Paragraph, Block, Borders, Wrap};
 use ratatui::text::{Line, Span};
-use chrono::{Datelike, Local};
+use chrono::{Datelike, Utc};
 use super::hex_to_color;
+use super::rich_text::render_rich_text;
 
 
 pub fn draw_task_details(f: &mut Frame, app: &App, area: Rect) {
     let selected_task = app.get_selected_task();
+    // Dates are shown in the configured time zone
+    let settings = &app.date_settings;
     
     let details = if let Some(basic_task) = selected_task {
         // Check if we have detailed task data with comments
@@ -28,14 +31,26 @@ pub fn draw_task_details(f: &mut Frame, app: &App, area: Rect) {
             Line::from(""),
         ];
 
+        // Links are numbered like in the URL modal ('o')
+        let links: Vec<String> = crate::url_utils::extract_urls_from_task(task).into_iter().map(|u| u.url).collect();
+
         // Description
         if let Some(description) = &task.description {
             if !description.is_empty() {
                 details_lines.push(Line::from(vec![
                     Span::styled("Description: ", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(description)
                 ]));
+                details_lines.extend(render_rich_text(description, &links));
                 details_lines.push(Line::from(""));
+                if let Some((done, total)) = crate::checklist::checklist_progress(description) {
+                    let color = if done == total { Color::Green } else { Color::Yellow };
+                    details_lines.push(Line::from(vec![
+                        Span::styled("Checklist: ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled(format!("{}/{} done", done, total), Style::default().fg(color)),
+                        Span::styled("  (.x to check off items)", Style::default().fg(Color::DarkGray)),
+                    ]));
+                    details_lines.push(Line::from(""));
+                }
             }
         }
 
@@ -170,11 +185,9 @@ pub fn draw_task_details(f: &mut Frame, app: &App, area: Rect) {
         // Due date (relative + calendar)
         if let Some(due_date) = &task.due_date {
             if due_date.year() > 1900 {
-                // Convert to local time
-                let local_dt = due_date.with_timezone(&Local);
                 // Compute relative days
-                let now = Local::now();
-                let days = local_dt.date_naive().signed_duration_since(now.date_naive()).num_days();
+                let local_dt = settings.local(*due_date);
+                let days = local_dt.date().signed_duration_since(settings.today()).num_days();
                 let rel = if days == 0 {
                     "Today".to_string()
                 } else if days > 0 {
@@ -182,7 +195,8 @@ pub fn draw_task_details(f: &mut Frame, app: &App, area: Rect) {
                 } else {
                     format!("{}d ago", -days)
                 };
-                let cal = local_dt.format("%Y-%m-%d %H:%M").to_string();
+                // All-day dates are shown without a time
+                let cal = settings.format(*due_date, settings.due_time);
                 details_lines.push(Line::from(vec![
                     Span::styled("Due Date: ", Style::default().add_modifier(Modifier::BOLD)),
                     Span::raw(format!("{} ({})", rel, cal)),
@@ -195,8 +209,8 @@ pub fn draw_task_details(f: &mut Frame, app: &App, area: Rect) {
         if let Some(start_date) = &task.start_date {
             if start_date.year() > 1900 {
                 // Start date (relative + calendar)
-                let local_dt = start_date.with_timezone(&Local);
-                let days = local_dt.date_naive().signed_duration_since(Local::now().date_naive()).num_days();
+                let local_dt = settings.local(*start_date);
+                let days = local_dt.date().signed_duration_since(settings.today()).num_days();
                 let rel = if days == 0 {
                     "Today".to_string()
                 } else if days > 0 {
@@ -204,7 +218,7 @@ pub fn draw_task_details(f: &mut Frame, app: &App, area: Rect) {
                 } else {
                     format!("{}d ago", -days)
                 };
-                let cal = local_dt.format("%Y-%m-%d %H:%M").to_string();
+                let cal = settings.format(*start_date, settings.start_time);
                 details_lines.push(Line::from(vec![
                     Span::styled("Start Date: ", Style::default().add_modifier(Modifier::BOLD)),
                     Span::raw(format!("{} ({})", rel, cal)),
@@ -221,7 +235,7 @@ pub fn draw_task_details(f: &mut Frame, app: &App, area: Rect) {
                     if parsed_date.year() > 1900 {
                         details_lines.push(Line::from(vec![
                             Span::styled("End Date: ", Style::default().add_modifier(Modifier::BOLD)),
-                            Span::raw(parsed_date.format("%Y-%m-%d %H:%M").to_string())
+                            Span::raw(settings.format(parsed_date.with_timezone(&Utc), settings.due_time))
                         ]));
                         details_lines.push(Line::from(""));
                     }
@@ -244,7 +258,7 @@ pub fn draw_task_details(f: &mut Frame, app: &App, area: Rect) {
                         if parsed_date.year() > 1900 {
                             details_lines.push(Line::from(vec![
                                 Span::styled("Completed: ", Style::default().add_modifier(Modifier::BOLD)),
-                                Span::raw(parsed_date.format("%Y-%m-%d %H:%M").to_string())
+                                Span::raw(settings.local(parsed_date.with_timezone(&Utc)).format("%Y-%m-%d %H:%M").to_string())
                             ]));
                             details_lines.push(Line::from(""));
                         }
@@ -458,7 +472,7 @@ pub fn draw_task_details(f: &mut Frame, app: &App, area: Rect) {
                     let date_str = if let Some(created) = &comment.created {
                         if !created.is_empty() {
                             if let Ok(parsed_date) = chrono::DateTime::parse_from_rfc3339(created) {
-                                parsed_date.format("%Y-%m-%d %H:%M").to_string()
+                                settings.local(parsed_date.with_timezone(&Utc)).format("%Y-%m-%d %H:%M").to_string()
                             } else {
                                 created.clone()
                             }
@@ -477,10 +491,11 @@ pub fn draw_task_details(f: &mut Frame, app: &App, area: Rect) {
                         Span::raw("  "),
                         Span::styled(date_str.clone(), Style::default().fg(Color::DarkGray)),
                     ]));
-                    details_lines.push(Line::from(vec![
-                        Span::raw("     "),
-                        Span::raw(text),
-                    ]));
+                    for line in render_rich_text(text, &links) {
+                        let mut spans = vec![Span::raw("     ")];
+                        spans.extend(line.spans);
+                        details_lines.push(Line::from(spans));
+                    }
                     details_lines.push(Line::from(""));
                 }
             }
@@ -591,8 +606,8 @@ pub fn draw_task_details(f: &mut Frame, app: &App, area: Rect) {
                     // Try to parse and format the date nicely
                     if let Ok(parsed_date) = chrono::DateTime::parse_from_rfc3339(created) {
                         // Created date (relative + calendar)
-                        let local_dt = parsed_date.with_timezone(&Local);
-                        let days = local_dt.date_naive().signed_duration_since(Local::now().date_naive()).num_days();
+                        let local_dt = settings.local(parsed_date.with_timezone(&Utc));
+                        let days = local_dt.date().signed_duration_since(settings.today()).num_days();
                         let rel = if days == 0 {
                             "Today".to_string()
                         } else if days > 0 {
@@ -618,8 +633,8 @@ pub fn draw_task_details(f: &mut Frame, app: &App, area: Rect) {
             if !created.is_empty() {
                 if let Ok(parsed_date) = chrono::DateTime::parse_from_rfc3339(created) {
                     // Created date (relative + calendar)
-                    let local_dt = parsed_date.with_timezone(&Local);
-                    let days = local_dt.date_naive().signed_duration_since(Local::now().date_naive()).num_days();
+                    let local_dt = settings.local(parsed_date.with_timezone(&Utc));
+                    let days = local_dt.date().signed_duration_since(settings.today()).num_days();
                     let rel = if days == 0 {
                         "Today".to_string()
                     } else if days > 0 {
@@ -647,8 +662,8 @@ pub fn draw_task_details(f: &mut Frame, app: &App, area: Rect) {
             if !updated.is_empty() {
                 if let Ok(parsed_date) = chrono::DateTime::parse_from_rfc3339(updated) {
                     // Updated date (relative + calendar)
-                    let local_dt = parsed_date.with_timezone(&Local);
-                    let days = local_dt.date_naive().signed_duration_since(Local::now().date_naive()).num_days();
+                    let local_dt = settings.local(parsed_date.with_timezone(&Utc));
+                    let days = local_dt.date().signed_duration_since(settings.today()).num_days();
                     let rel = if days == 0 {
                         "Today".to_string()
                     } else if days > 0 {
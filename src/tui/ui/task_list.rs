@@ -7,27 +7,38 @@ use ratatui::style::{Color, Style, Modifier};
 use ratatui::widgets::{Table, Row, Cell, Block, Borders};
 use ratatui::layout::Constraint;
 use super::hex_to_color;
-use chrono::{DateTime, Utc, Local};
+use crate::dates::DateSettings;
+use chrono::{DateTime, Utc, Local, NaiveTime};
 use ratatui::text::{Line, Span};
 
-fn format_date(date: &Option<DateTime<Utc>>) -> String {
+/// Calendar date, with the time of day for dates that aren't all-day
+fn format_date(date: &Option<DateTime<Utc>>, settings: &DateSettings, default_time: NaiveTime) -> String {
     match date {
         Some(dt) => {
-            let local: DateTime<Local> = dt.with_timezone(&Local);
-            local.format("%m/%d/%y").to_string()
+            let local = settings.local(*dt);
+            if settings.is_all_day(*dt, default_time) {
+                local.format("%m/%d/%y").to_string()
+            } else {
+                local.format("%m/%d %H:%M").to_string()
+            }
         }
         None => "-".to_string(),
     }
 }
 
-fn format_date_relative(date: &Option<DateTime<Utc>>) -> (String, Color) {
+/// Days from today in the configured time zone. Timed dates today show their time
+/// and turn overdue once it has passed.
+fn format_date_relative(date: &Option<DateTime<Utc>>, settings: &DateSettings, default_time: NaiveTime) -> (String, Color) {
     match date {
         Some(dt) => {
-            let local: DateTime<Local> = dt.with_timezone(&Local);
-            let now = Local::now();
-            let diff = local.signed_duration_since(now).num_days();
+            let local = settings.local(*dt);
+            let now = settings.now();
+            let diff = local.date().signed_duration_since(now.date()).num_days();
+            let all_day = settings.is_all_day(*dt, default_time);
             
-            let formatted = if diff == 0 {
+            let formatted = if diff == 0 && !all_day {
+                local.format("Today %H:%M").to_string()
+            } else if diff == 0 {
                 "Today".to_string()
             } else if diff == 1 {
                 "Tomorrow".to_string()
@@ -40,7 +51,7 @@ fn format_date_relative(date: &Option<DateTime<Utc>>) -> (String, Color) {
                 format!("{}d ago", -diff)
             };
             
-            let color = if diff < 0 {
+            let color = if diff < 0 || (!all_day && local < now) {
                 Color::Red // Overdue
             } else if diff == 0 {
                 Color::Yellow // Due today
@@ -249,11 +260,13 @@ fn create_wrapped_cell_for_column<'a>(
             }
         }
         TaskColumn::DueDate => {
-            let (formatted, color) = format_date_relative(&task.due_date);
+            let settings = &app.date_settings;
+            let (formatted, color) = format_date_relative(&task.due_date, settings, settings.due_time);
             Cell::from(formatted).style(Style::default().fg(color))
         }
         TaskColumn::StartDate => {
-            let formatted = format_date(&task.start_date);
+            let settings = &app.date_settings;
+            let formatted = format_date(&task.start_date, settings, settings.start_time);
             Cell::from(formatted).style(Style::default().fg(Color::Cyan))
         }
         TaskColumn::Priority => {
@@ -314,6 +327,15 @@ fn create_wrapped_cell_for_column<'a>(
             // Note: updated is a string in the model, would need similar parsing  
             Cell::from(task.updated.as_ref().map(|_| "N/A").unwrap_or("-"))
         }
+        TaskColumn::Checklist => {
+            match task.description.as_deref().and_then(crate::checklist::checklist_progress) {
+                Some((done, total)) => {
+                    let color = if done == total { Color::Green } else { Color::Yellow };
+                    Cell::from(format!("{}/{}", done, total)).style(Style::default().fg(color))
+                }
+                None => Cell::from("-"),
+            }
+        }
     }
 }
 
@@ -383,6 +405,7 @@ pub fn draw_tasks_table(f: &mut Frame, app: &App, area: Rect) {
         .take(end - start)
         .map(|(i, task)| {
             // Create cells for each enabled column with proper width and wrapping
+            let settings = &app.date_settings;
             let cells: Vec<Cell> = enabled_columns.iter()
                 .zip(column_widths.iter())
             .map(|(col, &width)| {
@@ -390,17 +413,17 @@ pub fn draw_tasks_table(f: &mut Frame, app: &App, area: Rect) {
                     if matches!(col.column_type, TaskColumn::DueDate) {
                         // DueDate column: relative by default, calendar on hover
                         if i == app.selected_task_index {
-                            Cell::from(format_date(&task.due_date))
+                            Cell::from(format_date(&task.due_date, settings, settings.due_time))
                         } else {
-                            let (rel, color) = format_date_relative(&task.due_date);
+                            let (rel, color) = format_date_relative(&task.due_date, settings, settings.due_time);
                             Cell::from(rel).style(Style::default().fg(color))
                         }
                     } else if matches!(col.column_type, TaskColumn::StartDate) {
                         // StartDate column: relative by default, calendar on hover
                         if i == app.selected_task_index {
-                            Cell::from(format_date(&task.start_date)).style(Style::default().fg(Color::Cyan))
+                            Cell::from(format_date(&task.start_date, settings, settings.start_time)).style(Style::default().fg(Color::Cyan))
                         } else {
-                            let (rel, color) = format_date_relative(&task.start_date);
+                            let (rel, color) = format_date_relative(&task.start_date, settings, settings.start_time);
                             Cell::from(rel).style(Style::default().fg(color))
                         }
                     } else {
@@ -450,6 +473,8 @@ pub fn draw_tasks_table(f: &mut Frame, app: &App, area: Rect) {
             } else if let Some(bg) = flash_bg {
                 // Flash effect takes priority over alternating colors
                 row = row.style(Style::default().bg(bg).add_modifier(Modifier::BOLD));
+            } else if app.marked_task_ids.contains(&task.id) {
+                row = row.style(Style::default().bg(Color::Rgb(70, 40, 90)));
             } else {
                 // Apply alternating row highlighting for easier scanning
                 if i % 2 == 1 {
@@ -474,6 +499,11 @@ pub fn draw_tasks_table(f: &mut Frame, app: &App, area: Rect) {
         title = format!("Tasks ({}) - Default Project: {}", app.get_filter_display_name(), active_project);
     }
     
+    let marked = app.tasks.iter().filter(|t| app.marked_task_ids.contains(&t.id)).count();
+    if marked > 0 {
+        title = format!("{} - {} marked", title, marked);
+    }
+
     let table = Table::new(rows, constraints)
         .header(header)
         .block(Block::default().borders(Borders::ALL).title(title));
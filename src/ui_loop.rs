@@ -1,16 +1,18 @@
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use crossterm::event::{KeyEvent, KeyCode, KeyEventKind};
+use crossterm::event::{DisableBracketedPaste, EnableBracketedPaste, KeyEvent, KeyCode, KeyEventKind};
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
 use crossterm::ExecutableCommand;
 use ratatui::prelude::{CrosstermBackend, Terminal};
 use std::io::stdout;
 use crate::tui::app::state::App;
+use crate::tui::app::undoable_action::UndoableAction;
 use crate::tui::events::EventHandler;
 use crate::tui::ui::main::draw;
 use crate::vikunja_client::VikunjaClient;
 // dispatch_key and refresh_from_api moved here from main.rs
-use crate::tui::modals::{handle_quick_add_modal, handle_edit_modal, handle_form_edit_modal};
+use crate::tui::modals::{handle_quick_add_modal, handle_quick_add_paste, handle_edit_modal, handle_form_edit_modal};
+use crate::tui::input_history::{HistoryKind, Recall};
 
 /// Run the main UI event loop
 pub async fn run_ui(
@@ -20,6 +22,8 @@ pub async fn run_ui(
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
     stdout().execute(EnterAlternateScreen)?;
     enable_raw_mode()?;
+    // Pastes arrive whole, so a pasted outline can be told apart from typing
+    stdout().execute(EnableBracketedPaste)?;
 
     let event_handler = EventHandler::new(250);
 
@@ -47,16 +51,30 @@ pub async fn run_ui(
                     let mut app_guard = app.lock().await;
                     handle_edit_modal(&mut *app_guard, &key, &client_clone, &client_clone).await;
                     continue;
+                } else if app_guard.show_edit_conflict_modal {
+                    drop(app_guard);
+                    let mut app_guard = app.lock().await;
+                    crate::tui::modals::handle_edit_conflict_modal(&mut *app_guard, &key, &client_clone, &client_clone).await;
+                    continue;
                 } else if app_guard.show_form_edit_modal {
                     drop(app_guard);
                     let mut app_guard = app.lock().await;
                     handle_form_edit_modal(&mut *app_guard, &key, &client_clone, &client_clone).await;
+                    if app_guard.external_editor_requested {
+                        app_guard.external_editor_requested = false;
+                        edit_form_description_externally(&mut app_guard, &mut terminal, &event_handler);
+                    }
                     continue;
                 } else if app_guard.show_label_picker {
                     drop(app_guard);
                     let mut app_guard = app.lock().await;
                     crate::tui::pickers::label::handle_label_picker(&mut *app_guard, &key);
                     continue;
+                } else if app_guard.show_assignee_picker {
+                    drop(app_guard);
+                    let mut app_guard = app.lock().await;
+                    crate::tui::pickers::assignee::handle_assignee_picker(&mut app_guard, &key);
+                    continue;
                 } else if app_guard.show_project_picker {
                     drop(app_guard);
                     let mut app_guard = app.lock().await;
@@ -206,18 +224,27 @@ pub async fn run_ui(
                 } else if app_guard.show_comments_modal {
                     drop(app_guard);
                     let mut app_guard = app.lock().await;
-                    if let Some(ref mut modal) = app_guard.comments_modal {
+                    let app_ref = &mut *app_guard;
+                    if let (Some(direction), Some(ref mut modal)) = (Recall::from_key(&key), app_ref.comments_modal.as_mut()) {
+                        modal.recall_history(app_ref.input_history.entries(HistoryKind::Comment), direction);
+                        continue;
+                    }
+                    if let Some(ref mut modal) = app_ref.comments_modal {
                         let action = modal.handle_key(&key);
                         match action {
                             crate::tui::modals::CommentsModalAction::Close => {
-                                app_guard.hide_comments_modal();
+                                // Keep an unsent comment for the next time the modal opens
+                                app_ref.input_history.set_draft(HistoryKind::Comment, modal.task_id, modal.draft());
+                                app_ref.hide_comments_modal();
                             }
                             crate::tui::modals::CommentsModalAction::Submit(comment_text) => {
                                 if !comment_text.trim().is_empty() {
                                     let task_id = modal.task_id as u64;
+                                    app_ref.input_history.push(HistoryKind::Comment, &comment_text);
+                                    app_ref.input_history.clear_draft(HistoryKind::Comment, modal.task_id);
                                     let client_clone = client_clone.clone();
                                     let app_clone = app.clone();
-                                    let comment_clone = comment_text.clone();
+                                    let comment_clone = crate::tui::modals::comment_html(&comment_text);
                                     
                                     // Clear the input immediately
                                     modal.clear_input();
@@ -232,24 +259,17 @@ pub async fn run_ui(
                                         // Update UI based on result
                                         let mut app_guard = app_clone.lock().await;
                                         match submit_result {
-                                            Ok(_) => {
+                                            Ok(comment) => {
+                                                app_guard.add_to_undo_stack(UndoableAction::CommentAdded {
+                                                    task_id: task_id as i64,
+                                                    comment_id: comment.id,
+                                                    text: comment_clone.clone(),
+                                                });
                                                 app_guard.add_debug_message("Comment added successfully".to_string());
                                                 app_guard.show_toast("Comment added!".to_string());
                                                 
-                                                // Refresh comments in modal
                                                 let client = client_clone.lock().await;
-                                                match client.get_comments(task_id).await {
-                                                    Ok(comments_list) => {
-                                                        // Update modal comments directly
-                                                        if let Some(ref mut modal) = app_guard.comments_modal {
-                                                            modal.comments = comments_list.clone();
-                                                        }
-                                                        app_guard.add_debug_message("Comments refreshed in modal".to_string());
-                                                    }
-                                                    Err(e) => {
-                                                        app_guard.add_debug_message(format!("Failed to refresh comments: {}", e));
-                                                    }
-                                                }
+                                                refresh_comments(&mut app_guard, &client, task_id).await;
                                             }
                                             Err(e) => {
                                                 app_guard.add_debug_message(format!("Failed to add comment: {}", e));
@@ -259,6 +279,68 @@ pub async fn run_ui(
                                     });
                                 }
                             }
+                            crate::tui::modals::CommentsModalAction::Edit { comment_id, previous_text, text } => {
+                                if text.trim().is_empty() {
+                                    app_ref.show_toast("A comment can't be empty; use Ctrl+X to delete it".to_string());
+                                    continue;
+                                }
+                                let task_id = modal.task_id as u64;
+                                let text = crate::tui::modals::comment_html(&text);
+                                modal.finish_editing();
+                                let client_clone = client_clone.clone();
+                                let app_clone = app.clone();
+                                tokio::spawn(async move {
+                                    let result = {
+                                        let client = client_clone.lock().await;
+                                        client.update_comment(task_id, comment_id, &text).await
+                                    };
+                                    let mut app_guard = app_clone.lock().await;
+                                    match result {
+                                        Ok(_) => {
+                                            app_guard.add_to_undo_stack(UndoableAction::CommentEdited {
+                                                task_id: task_id as i64,
+                                                comment_id,
+                                                previous_text,
+                                                text,
+                                            });
+                                            app_guard.show_toast("Comment updated".to_string());
+                                            let client = client_clone.lock().await;
+                                            refresh_comments(&mut app_guard, &client, task_id).await;
+                                        }
+                                        Err(e) => {
+                                            app_guard.add_debug_message(format!("Failed to update comment: {}", e));
+                                            app_guard.show_toast("Failed to update comment".to_string());
+                                        }
+                                    }
+                                });
+                            }
+                            crate::tui::modals::CommentsModalAction::Delete { comment_id, text } => {
+                                let task_id = modal.task_id as u64;
+                                let client_clone = client_clone.clone();
+                                let app_clone = app.clone();
+                                tokio::spawn(async move {
+                                    let result = {
+                                        let client = client_clone.lock().await;
+                                        client.delete_comment(task_id, comment_id).await
+                                    };
+                                    let mut app_guard = app_clone.lock().await;
+                                    match result {
+                                        Ok(()) => {
+                                            app_guard.add_to_undo_stack(UndoableAction::CommentDeleted { task_id: task_id as i64, text });
+                                            app_guard.show_toast("Comment deleted".to_string());
+                                            let client = client_clone.lock().await;
+                                            refresh_comments(&mut app_guard, &client, task_id).await;
+                                        }
+                                        Err(e) => {
+                                            app_guard.add_debug_message(format!("Failed to delete comment: {}", e));
+                                            app_guard.show_toast("Failed to delete comment".to_string());
+                                        }
+                                    }
+                                });
+                            }
+                            crate::tui::modals::CommentsModalAction::ExternalEditor => {
+                                edit_comment_externally(app_ref, &mut terminal, &event_handler);
+                            }
                             crate::tui::modals::CommentsModalAction::LoadAttachments(task_id) => {
                                 // Handle loading attachments for image preview
                                 app_guard.add_debug_message(format!("Loading attachments for task {}", task_id));
@@ -270,6 +352,51 @@ pub async fn run_ui(
                         }
                     }
                     continue;
+                } else if app_guard.show_label_manager_modal {
+                    drop(app_guard);
+                    let mut app_guard = app.lock().await;
+                    crate::tui::modals::handle_label_manager_modal(&mut *app_guard, &key, &client_clone).await;
+                    continue;
+                } else if app_guard.show_project_sharing_modal {
+                    drop(app_guard);
+                    let mut app_guard = app.lock().await;
+                    crate::tui::modals::handle_project_sharing_modal(&mut *app_guard, &key, &client_clone).await;
+                    continue;
+                } else if app_guard.show_checklist_modal {
+                    drop(app_guard);
+                    let mut app_guard = app.lock().await;
+                    crate::tui::modals::handle_checklist_modal(&mut *app_guard, &key, &client_clone).await;
+                    continue;
+                } else if app_guard.show_template_picker_modal {
+                    drop(app_guard);
+                    let mut app_guard = app.lock().await;
+                    crate::tui::modals::handle_template_picker_modal(&mut app_guard, &key, &client_clone).await;
+                    continue;
+                } else if app_guard.show_snooze_modal {
+                    drop(app_guard);
+                    let mut app_guard = app.lock().await;
+                    crate::tui::modals::handle_snooze_modal(&mut app_guard, &key, &client_clone).await;
+                    continue;
+                } else if app_guard.show_reschedule_modal {
+                    drop(app_guard);
+                    let mut app_guard = app.lock().await;
+                    crate::tui::modals::handle_reschedule_modal(&mut app_guard, &key, &client_clone).await;
+                    continue;
+                } else if app_guard.show_trash_modal {
+                    drop(app_guard);
+                    let mut app_guard = app.lock().await;
+                    crate::tui::modals::handle_trash_modal(&mut app_guard, &key, &client_clone).await;
+                    continue;
+                } else if app_guard.show_undo_history_modal {
+                    drop(app_guard);
+                    let mut app_guard = app.lock().await;
+                    crate::tui::modals::handle_undo_history_modal(&mut app_guard, &key, &client_clone).await;
+                    continue;
+                } else if app_guard.show_paste_preview_modal {
+                    drop(app_guard);
+                    let mut app_guard = app.lock().await;
+                    crate::tui::modals::handle_paste_preview_modal(&mut app_guard, &key, &client_clone).await;
+                    continue;
                 // Relations modals - DISABLED: Incomplete feature
                 // } else if app_guard.show_relations_modal {
                 //     if app_guard.show_add_relation_modal {
@@ -359,6 +486,11 @@ pub async fn run_ui(
                                                             crate::vikunja_client::relations::RelationKind::Subtask
                                                         ).await {
                                                             Ok(_) => {
+                                                                app_guard.add_to_undo_stack(UndoableAction::RelationAdded {
+                                                                    task_id: current_task_id,
+                                                                    other_task_id: target_task_id,
+                                                                    kind: crate::vikunja_client::relations::RelationKind::Subtask,
+                                                                });
                                                                 app_guard.show_toast("Task made into subtask successfully!".to_string());
                                                                 app_guard.add_debug_message(format!("Task {} is now a subtask of {}", current_task_id, target_task_id));
                                                             }
@@ -376,6 +508,11 @@ pub async fn run_ui(
                                                             crate::vikunja_client::relations::RelationKind::Subtask
                                                         ).await {
                                                             Ok(_) => {
+                                                                app_guard.add_to_undo_stack(UndoableAction::RelationAdded {
+                                                                    task_id: target_task_id,
+                                                                    other_task_id: current_task_id,
+                                                                    kind: crate::vikunja_client::relations::RelationKind::Subtask,
+                                                                });
                                                                 app_guard.show_toast("Subtask added successfully!".to_string());
                                                                 app_guard.add_debug_message(format!("Task {} is now a subtask of {}", target_task_id, current_task_id));
                                                             }
@@ -397,13 +534,7 @@ pub async fn run_ui(
                             // Toggle selection for bulk operations
                             app_guard.toggle_subtask_task_selection();
                         }
-                        KeyCode::Char(c) => {
-                            app_guard.add_char_to_subtask_input(c);
-                        }
-                        KeyCode::Backspace => {
-                            app_guard.delete_char_from_subtask_input();
-                        }
-                        _ => {}
+                        _ => app_guard.edit_subtask_picker_input(&key),
                     }
                     continue;
                 } else if app_guard.show_add_subtask_modal {
@@ -435,11 +566,15 @@ pub async fn run_ui(
                                         labels: None,
                                         assignees: None,
                                         is_favorite: Some(false),
+                                        ..Default::default()
                                     };
                                     
                                     match client.create_task(&subtask).await {
                                         Ok(new_task) => {
                                             app_guard.add_debug_message(format!("Created new task: {}", new_task.title));
+                                            if let Some(new_task_id) = new_task.id {
+                                                app_guard.add_to_undo_stack(UndoableAction::TaskCreation { task_id: new_task_id as i64 });
+                                            }
                                             
                                             // Create the subtask relation (make new task a subtask of selected task)
                                             if let Some(new_task_id) = new_task.id {
@@ -472,25 +607,9 @@ pub async fn run_ui(
                                 }
                             }
                         }
-                        KeyCode::Left => {
-                            app_guard.move_add_subtask_cursor_left();
-                        }
-                        KeyCode::Right => {
-                            app_guard.move_add_subtask_cursor_right();
-                        }
-                        KeyCode::Home => {
-                            app_guard.add_subtask_cursor_position = 0;
+                        _ => {
+                            app_guard.add_subtask_editor().handle_key(&key);
                         }
-                        KeyCode::End => {
-                            app_guard.add_subtask_cursor_position = app_guard.add_subtask_input.len();
-                        }
-                        KeyCode::Char(c) => {
-                            app_guard.add_char_to_add_subtask(c);
-                        }
-                        KeyCode::Backspace => {
-                            app_guard.delete_char_from_add_subtask();
-                        }
-                        _ => {}
                     }
                     continue;
                 }
@@ -499,36 +618,18 @@ pub async fn run_ui(
                 if key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) {
                     match key.code {
                         KeyCode::Char('z') => {
-                            // Ctrl+Z - Undo
-                            if let Some(task_id) = app_guard.undo_last_action() {
-                                // Update the corresponding task in all_tasks
-                                let updated_task = app_guard.tasks.iter().find(|t| t.id == task_id).cloned();
-                                if let Some(updated_task) = updated_task {
-                                    if let Some(task) = app_guard.all_tasks.iter_mut().find(|t| t.id == task_id) {
-                                        *task = updated_task;
-                                    }
-                                }
-                                // Show visual feedback
+                            // Ctrl+Z - Undo, on the server as well as locally
+                            let client = client_clone.lock().await;
+                            if app_guard.undo_last_action_async(&client).await.is_some() {
                                 app_guard.add_debug_message("Undo operation completed".to_string());
-                            } else {
-                                app_guard.add_debug_message("Nothing to undo".to_string());
                             }
                             continue;
                         },
                         KeyCode::Char('y') => {
-                            // Ctrl+Y - Redo
-                            if let Some(task_id) = app_guard.redo_last_action() {
-                                // Update the corresponding task in all_tasks
-                                let updated_task = app_guard.tasks.iter().find(|t| t.id == task_id).cloned();
-                                if let Some(updated_task) = updated_task {
-                                    if let Some(task) = app_guard.all_tasks.iter_mut().find(|t| t.id == task_id) {
-                                        *task = updated_task;
-                                    }
-                                }
-                                // Show visual feedback
+                            // Ctrl+Y - Redo, on the server as well as locally
+                            let client = client_clone.lock().await;
+                            if app_guard.redo_last_action_async(&client).await.is_some() {
                                 app_guard.add_debug_message("Redo operation completed".to_string());
-                            } else {
-                                app_guard.add_debug_message("Nothing to redo".to_string());
                             }
                             continue;
                         },
@@ -562,19 +663,7 @@ pub async fn run_ui(
                         // Sync with API
                         let client = client_clone.lock().await;
                         if let Some(task) = app_guard.tasks.iter().find(|t| t.id == task_id) {
-                            let api_task = crate::vikunja_client::VikunjaTask {
-                                id: Some(task.id as u64),
-                                title: task.title.clone(),
-                                description: task.description.clone(),
-                                done: Some(task.done),
-                                priority: task.priority.map(|p| p as u8),
-                                due_date: task.due_date,
-                                project_id: task.project_id as u64,
-                                labels: None,
-                                assignees: None,
-                                is_favorite: Some(task.is_favorite),
-                                start_date: task.start_date,
-                            };
+                            let api_task = task.to_vikunja_task();
                             match client.update_task(&api_task).await {
                                 Ok(_) => {
                                     app_guard.add_debug_message(format!("Task completion synced to API for task {}", task_id));
@@ -745,6 +834,11 @@ pub async fn run_ui(
                 
                 // handle dispatch_key and refresh
                 let key_handled = dispatch_key(&mut *app_guard, key, &terminal);
+
+                // A freshly opened sharing modal needs its shares loaded
+                if app_guard.project_sharing_modal.as_ref().is_some_and(|m| m.loading) {
+                    crate::tui::modals::load_project_shares(&mut *app_guard, &client_clone).await;
+                }
                 
                 // After any navigation key, check if we need to fetch detailed task data
                 if key_handled && (key.code == KeyCode::Up || key.code == KeyCode::Down || 
@@ -779,8 +873,41 @@ pub async fn run_ui(
                 // other modal and Ctrl/quick-action handling...
                 // TODO: move remaining branches here
             }
+            crate::tui::events::Event::Paste(text) => {
+                let mut app_guard = app.lock().await;
+                let handled = app_guard.show_quick_add_modal
+                    && handle_quick_add_paste(&mut app_guard, &text).await;
+                if !handled {
+                    event_handler.type_text(&text);
+                }
+            }
             crate::tui::events::Event::Tick => {
-                let app_guard = app.lock().await;
+                let mut app_guard = app.lock().await;
+                // Deleted tasks past their grace period are deleted on the server
+                if app_guard.has_expired_trash() {
+                    let client = client_clone.lock().await;
+                    app_guard.flush_trash_async(&client, false).await;
+                }
+                // Server user search, once typing in a user input has paused
+                if let Some(query) = app_guard.take_due_user_search() {
+                    let client_clone = client_clone.clone();
+                    let app_clone = app.clone();
+                    tokio::spawn(async move {
+                        let result = client_clone.lock().await.search_users(&query).await;
+                        let mut app_guard = app_clone.lock().await;
+                        match result {
+                            Ok(users) => {
+                                app_guard.add_debug_message(format!("User search: {} users match '{}'", users.len(), query));
+                                app_guard.apply_user_search_results(users);
+                            }
+                            Err(e) => {
+                                app_guard.add_debug_message(format!("User search for '{}' failed: {}", query, e));
+                                // Let the same query be tried again
+                                app_guard.searched_user_queries.remove(&query);
+                            }
+                        }
+                    });
+                }
                 // TODO: clear expired notifications / flash
                 terminal.draw(|f| draw(f, &app_guard))?;
             }
@@ -794,11 +921,81 @@ pub async fn run_ui(
         }
     }
 
+    // Whatever is left in the trash is deleted on the way out
+    {
+        let mut app_guard = app.lock().await;
+        if !app_guard.trash.is_empty() {
+            let client = client_clone.lock().await;
+            app_guard.flush_trash_async(&client, true).await;
+        }
+    }
+
+    stdout().execute(DisableBracketedPaste)?;
     disable_raw_mode()?;
     stdout().execute(LeaveAlternateScreen)?;
     Ok(())
 }
 
+/// Suspend the TUI and edit the form's description as Markdown in $VISUAL/$EDITOR
+fn edit_form_description_externally(
+    app: &mut App,
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    events: &EventHandler,
+) {
+    let Some(description) = app.form_edit_state.as_ref().map(|form| form.description.clone()) else {
+        return;
+    };
+    match crate::tui::external_editor::edit_in_external_editor(terminal, events, &description, "md") {
+        Ok(Some(text)) => {
+            if let Some(form) = app.form_edit_state.as_mut() {
+                form.description = text.trim_end().to_string();
+                form.field_index = 1;
+                form.cursor_position = form.description.len();
+            }
+        }
+        Ok(None) => app.show_toast("Editor exited with an error; description unchanged".to_string()),
+        Err(e) => app.show_toast(format!("Failed to open editor: {}", e)),
+    }
+}
+
+/// Suspend the TUI and write the comment box's text as Markdown in $VISUAL/$EDITOR
+fn edit_comment_externally(
+    app: &mut App,
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    events: &EventHandler,
+) {
+    let Some(input) = app.comments_modal.as_ref().map(|modal| modal.input.clone()) else {
+        return;
+    };
+    match crate::tui::external_editor::edit_in_external_editor(terminal, events, &input, "md") {
+        Ok(Some(text)) => {
+            if let Some(modal) = app.comments_modal.as_mut() {
+                modal.set_input(text.trim_end().to_string());
+            }
+        }
+        Ok(None) => app.show_toast("Editor exited with an error; comment unchanged".to_string()),
+        Err(e) => app.show_toast(format!("Failed to open editor: {}", e)),
+    }
+}
+
+/// Reload a task's comments into the open comments modal and the loaded task
+async fn refresh_comments(app: &mut App, client: &VikunjaClient, task_id: u64) {
+    match client.get_comments(task_id).await {
+        Ok(comments) => {
+            app.tasks.iter_mut().chain(app.all_tasks.iter_mut())
+                .filter(|t| t.id == task_id as i64)
+                .for_each(|t| t.comments = Some(comments.clone()));
+            if let Some(modal) = app.comments_modal.as_mut().filter(|m| m.task_id == task_id as i64) {
+                modal.set_comments(comments);
+            }
+            app.add_debug_message("Comments refreshed in modal".to_string());
+        }
+        Err(e) => {
+            app.add_debug_message(format!("Failed to refresh comments: {}", e));
+        }
+    }
+}
+
 /// Handle key events, return true if event was handled
 fn dispatch_key(app: &mut App, key: KeyEvent, terminal: &Terminal<CrosstermBackend<std::io::Stdout>>) -> bool {
     use crate::tui::modals::utils::try_show_modal;
@@ -823,7 +1020,7 @@ fn dispatch_key(app: &mut App, key: KeyEvent, terminal: &Terminal<CrosstermBacke
         }
         KeyCode::Down => {
             if app.show_advanced_features_modal {
-                let max_index = 2; // Number of advanced features - 1
+                let max_index = 5; // Number of advanced features - 1
                 if app.selected_advanced_feature_index < max_index {
                     app.selected_advanced_feature_index += 1;
                 }
@@ -855,11 +1052,18 @@ fn dispatch_key(app: &mut App, key: KeyEvent, terminal: &Terminal<CrosstermBacke
             true
         }
         Char('i') => { app.toggle_info_pane(); true }
-        Char('x') => { app.toggle_debug_pane(); true }
+        Char('x') => {
+            if app.show_advanced_features_modal {
+                try_show_modal(app, terminal, |app| { app.hide_advanced_features_modal(); app.show_checklist_modal(); });
+            } else {
+                app.toggle_debug_pane();
+            }
+            true
+        }
         // Navigation: move selection down/up
         Char('j') => { 
             if app.show_advanced_features_modal {
-                let max_index = 2; // Number of advanced features - 1
+                let max_index = 5; // Number of advanced features - 1
                 if app.selected_advanced_feature_index < max_index {
                     app.selected_advanced_feature_index += 1;
                 }
@@ -881,11 +1085,48 @@ fn dispatch_key(app: &mut App, key: KeyEvent, terminal: &Terminal<CrosstermBacke
             }
         }
         // Switch layouts backward/forward
-        Char('l') => { app.switch_to_next_layout(); true }
+        Char('l') => {
+            if app.show_advanced_features_modal {
+                try_show_modal(app, terminal, |app| { app.hide_advanced_features_modal(); app.show_label_manager_modal(); });
+            } else {
+                app.switch_to_next_layout();
+            }
+            true
+        }
+        Char('u') => {
+            if app.show_advanced_features_modal {
+                try_show_modal(app, terminal, |app| { app.hide_advanced_features_modal(); app.show_project_sharing_modal(); });
+                true
+            } else {
+                false
+            }
+        }
         // Cycle filters backward/forward
         Char('H') => { app.cycle_task_filter(); true }
         Char('L') => { app.cycle_task_filter(); true }
         Char('.') => { app.show_advanced_features_modal(); true }
+        Char('U') => {
+            try_show_modal(app, terminal, |app| app.show_undo_history_modal());
+            true
+        }
+        Char('T') => {
+            try_show_modal(app, terminal, |app| app.show_trash_modal());
+            true
+        }
+        Char('v') => { app.toggle_mark_selected_task(); true }
+        Char('V') => { app.clear_marks(); true }
+        Char('z') => {
+            try_show_modal(app, terminal, |app| app.show_snooze_modal());
+            true
+        }
+        Char('O') => {
+            try_show_modal(app, terminal, |app| app.show_reschedule_modal());
+            true
+        }
+        Char('N') => {
+            try_show_modal(app, terminal, |app| app.show_template_picker_modal());
+            true
+        }
         Char('S') => {
             // Create a new subtask under the current task
             if app.get_selected_task().is_some() {
@@ -997,6 +1238,18 @@ fn dispatch_key(app: &mut App, key: KeyEvent, terminal: &Terminal<CrosstermBacke
                         app.add_debug_message("Task relations feature requested".to_string());
                         app.show_toast("Task relations feature coming soon!".to_string());
                     }
+                    3 => { // Label Manager
+                        app.hide_advanced_features_modal();
+                        app.show_label_manager_modal();
+                    }
+                    4 => { // Project Sharing
+                        app.hide_advanced_features_modal();
+                        app.show_project_sharing_modal();
+                    }
+                    5 => { // Checklist
+                        app.hide_advanced_features_modal();
+                        app.show_checklist_modal();
+                    }
                     _ => {
                         app.hide_advanced_features_modal();
                     }
@@ -1025,7 +1278,7 @@ fn dispatch_key(app: &mut App, key: KeyEvent, terminal: &Terminal<CrosstermBacke
         }
         Down => {
             if app.show_advanced_features_modal {
-                let max_index = 2; // Number of advanced features - 1
+                let max_index = 5; // Number of advanced features - 1
                 if app.selected_advanced_feature_index < max_index {
                     app.selected_advanced_feature_index += 1;
                 }
@@ -1121,59 +1374,76 @@ async fn apply_quick_action_and_sync(
     action: crate::config::QuickAction,
     client_clone: &Arc<Mutex<VikunjaClient>>,
 ) {
+    let previous_task = app.get_selected_task().cloned();
     match app.apply_quick_action(&action) {
         Ok(_) => {
             app.add_debug_message(format!("Quick action applied: {} -> {}", action.key, action.target));
-            
-            // Update the task on the server - handle labels differently
+            let steps = action.steps();
             let selected_task = app.get_selected_task().cloned();
-            if let Some(task) = selected_task {
-                if action.action == "label" {
-                    // For label actions, use the specialized label API
-                    if let Some(label_id) = app.label_map.iter().find_map(|(id, name)| {
-                        if name == &action.target { Some(*id) } else { None }
-                    }) {
-                        app.add_debug_message(format!("Adding label {} (id={}) to task {}", action.target, label_id, task.id));
-                        match client_clone.lock().await.add_label_to_task(task.id as u64, label_id as u64).await {
-                            Ok(_) => {
-                                app.add_debug_message(format!("Label API update successful for task {}", task.id));
-                                app.show_toast(format!("Label added: {}", action.target));
-                            },
-                            Err(e) => {
-                                app.add_debug_message(format!("Label API update failed: {}", e));
-                                app.show_toast(format!("Label update failed: {}", e));
+            if let (Some(task), Some(previous_task)) = (selected_task, previous_task) {
+                let client = client_clone.lock().await;
+                // Fields, labels and assignees in one go, then bucket moves and comments,
+                // recorded as one undo entry so a macro takes one Ctrl+Z
+                let mut done = Vec::new();
+                let mut result = Ok(());
+                if steps.iter().any(|step| step.action != "comment" && step.action != "bucket") {
+                    result = crate::tui::app::undo::write_task_changes(&client, &previous_task, &task).await;
+                    match result {
+                        Ok(()) => done.push(UndoableAction::TaskEdit { task_id: task.id, previous_task: previous_task.clone() }),
+                        Err(_) => app.replace_loaded_task(previous_task.clone()),
+                    }
+                }
+                let mut bucket_id = previous_task.bucket_id.filter(|id| *id > 0);
+                for step in steps.iter().filter(|step| step.action == "bucket") {
+                    if result.is_err() {
+                        break;
+                    }
+                    result = match client.move_task_to_named_bucket(task.project_id, task.id, &step.target).await {
+                        Ok(to_bucket_id) => {
+                            // Without the bucket it came from there is nothing to undo to
+                            if let Some(from_bucket_id) = bucket_id {
+                                done.push(UndoableAction::BucketMoved { task_id: task.id, project_id: task.project_id, from_bucket_id, to_bucket_id });
+                            }
+                            bucket_id = Some(to_bucket_id);
+                            if let Some(mut moved) = app.get_selected_task().cloned() {
+                                moved.bucket_id = bucket_id;
+                                app.replace_loaded_task(moved);
                             }
+                            Ok(())
                         }
-                    } else {
-                        app.add_debug_message(format!("Label '{}' not found in label_map", action.target));
-                        app.show_toast(format!("Label '{}' not found", action.target));
-                    }
-                } else {
-                    // For non-label actions, use the general task update
-                    let api_task = crate::vikunja_client::VikunjaTask {
-                        id: Some(task.id as u64),
-                        title: task.title.clone(),
-                        description: task.description.clone(),
-                        done: Some(task.done),
-                        priority: task.priority.map(|p| p as u8),
-                        due_date: task.due_date,
-                        project_id: task.project_id as u64,
-                        labels: None, // Don't update labels via general task update
-                        assignees: None,
-                        is_favorite: Some(task.is_favorite),
-                        start_date: task.start_date,
+                        Err(e) => Err(e),
                     };
-                    match client_clone.lock().await.update_task(&api_task).await {
-                        Ok(_) => {
-                            app.show_toast(format!("Quick action applied: {} -> {}", action.key, action.target));
-                        },
-                        Err(e) => {
-                            app.add_debug_message(format!("API update failed: {}", e));
-                            app.show_toast(format!("Update failed: {}", e));
+                }
+                for step in steps.iter().filter(|step| step.action == "comment") {
+                    if result.is_err() {
+                        break;
+                    }
+                    result = match client.add_comment_to_task(task.id as u64, &step.target).await {
+                        Ok(comment) => {
+                            done.push(UndoableAction::CommentAdded {
+                                task_id: task.id,
+                                comment_id: comment.id,
+                                text: step.target.clone(),
+                            });
+                            Ok(())
                         }
+                        Err(e) => Err(e.to_string()),
+                    };
+                }
+                drop(client);
+                match done.len() {
+                    0 => {}
+                    1 => app.add_to_undo_stack(done.remove(0)),
+                    _ => app.add_to_undo_stack(UndoableAction::Batch { actions: done }),
+                }
+                match result {
+                    Ok(()) => app.show_toast(format!("Quick action applied: {}", action.get_description())),
+                    Err(e) => {
+                        app.add_debug_message(format!("API update failed: {}", e));
+                        app.show_toast(format!("Update failed: {}", e));
                     }
                 }
-                
+
                 // Add visual flash feedback
                 if let Some(task) = app.get_selected_task() {
                     app.flash_task_id = Some(task.id);
@@ -27,22 +27,51 @@ pub struct UrlWithContext {
     pub source: String, // "Description" or "Comment by {author}"
 }
 
-/// Extract URLs from task description and comments with context
+/// Extract URLs from a description or comment in reading order.
+/// HTML is parsed so link targets are found with their entities decoded.
+pub fn extract_urls_from_html(text: &str) -> Vec<String> {
+    fn walk(nodes: &[crate::html::HtmlNode], urls: &mut Vec<String>) {
+        for node in nodes {
+            match node {
+                crate::html::HtmlNode::Text(text) => urls.extend(extract_urls(text)),
+                crate::html::HtmlNode::Element { children, .. } => {
+                    if node.name() == Some("a") {
+                        if let Some(href) = node.attr("href").filter(|h| h.starts_with("http://") || h.starts_with("https://")) {
+                            urls.push(href.to_string());
+                        }
+                    }
+                    walk(children, urls);
+                }
+            }
+        }
+    }
+    if !crate::html::looks_like_html(text) {
+        return extract_urls(text);
+    }
+    let mut urls = Vec::new();
+    walk(&crate::html::parse_html(text), &mut urls);
+    urls
+}
+
+/// Extract URLs from task description and comments with context.
+/// URLs keep the order they appear in (description first) so they match the
+/// `[n]` link markers shown in the task details.
 pub fn extract_urls_from_task(task: &crate::vikunja::models::Task) -> Vec<UrlWithContext> {
-    use std::collections::HashMap;
-    let mut url_map: HashMap<String, UrlWithContext> = HashMap::new();
+    let mut unique_urls: Vec<UrlWithContext> = Vec::new();
     let mut total_found = 0;
+    let mut add = |urls: Vec<String>, source: &str| {
+        total_found += urls.len();
+        for url in urls {
+            // Only add if not already present (prefer description over comments)
+            if !unique_urls.iter().any(|u| u.url == url) {
+                unique_urls.push(UrlWithContext { url, source: source.to_string() });
+            }
+        }
+    };
     
     // Extract from task description
     if let Some(description) = &task.description {
-        let description_urls = extract_urls(description);
-        total_found += description_urls.len();
-        for url in description_urls {
-            url_map.insert(url.clone(), UrlWithContext {
-                url,
-                source: "Description".to_string(),
-            });
-        }
+        add(extract_urls_from_html(description), "Description");
     }
     
     // Extract from comments
@@ -50,28 +79,17 @@ pub fn extract_urls_from_task(task: &crate::vikunja::models::Task) -> Vec<UrlWit
         debug_log(&format!("Scanning {} comments for URLs", comments.len()));
         for comment in comments {
             let comment_text = comment.comment.as_deref().unwrap_or("");
-            let found_urls = extract_urls(comment_text);
+            let found_urls = extract_urls_from_html(comment_text);
+            let author = comment.author.as_ref()
+                .map(|a| a.username.as_str())
+                .unwrap_or("Unknown");
             if !found_urls.is_empty() {
-                let author = comment.author.as_ref()
-                    .map(|a| a.username.as_str())
-                    .unwrap_or("Unknown");
                 debug_log(&format!("Found {} URL(s) in comment by {}: {:?}", found_urls.len(), author, found_urls));
             }
-            total_found += found_urls.len();
-            for url in found_urls {
-                let author = comment.author.as_ref()
-                    .map(|a| a.username.as_str())
-                    .unwrap_or("Unknown");
-                // Only add if not already present (prefer description over comments)
-                url_map.entry(url.clone()).or_insert(UrlWithContext {
-                    url,
-                    source: format!("Comment by {}", author),
-                });
-            }
+            add(found_urls, &format!("Comment by {}", author));
         }
     }
     
-    let unique_urls: Vec<UrlWithContext> = url_map.into_values().collect();
     debug_log(&format!("URL deduplication: {} total found, {} unique URLs after deduplication", total_found, unique_urls.len()));
     
     unique_urls
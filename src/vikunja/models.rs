@@ -1,4 +1,4 @@
-use serde::{Serialize, Deserialize, Deserializer};
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
 use chrono::{DateTime, Utc, Datelike};
 
 fn deserialize_optional_datetime<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
@@ -25,7 +25,7 @@ where
     }
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[allow(dead_code)] // API response fields may not all be used
 pub struct Project {
     pub id: i64,
@@ -33,7 +33,7 @@ pub struct Project {
     pub hex_color: String,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[allow(dead_code)] // API response fields may not all be used
 pub struct Label {
     pub id: i64,
@@ -45,6 +45,30 @@ pub struct Label {
     pub created_by: Option<User>,
 }
 
+/// Vikunja stores progress as a fraction; tasks keep it as a percentage
+fn deserialize_percent<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let opt: Option<f64> = Option::deserialize(deserializer)?;
+    Ok(opt.map(percent_from_fraction))
+}
+
+fn serialize_percent<S>(percent: &Option<u8>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    percent.map(percent_to_fraction).serialize(serializer)
+}
+
+pub fn percent_from_fraction(fraction: f64) -> u8 {
+    (fraction * 100.0).round().clamp(0.0, 100.0) as u8
+}
+
+pub fn percent_to_fraction(percent: u8) -> f64 {
+    percent as f64 / 100.0
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[allow(dead_code)] // API response fields may not all be used
 pub struct User {
@@ -56,7 +80,7 @@ pub struct User {
     pub updated: Option<String>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[allow(dead_code)] // API response fields may not all be used
 pub struct Task {
     pub id: i64,
@@ -76,6 +100,7 @@ pub struct Task {
     pub created: Option<String>,
     pub updated: Option<String>,
     pub created_by: Option<User>,
+    #[serde(default, deserialize_with = "deserialize_percent", serialize_with = "serialize_percent")]
     pub percent_done: Option<u8>,
     pub is_favorite: bool,
     pub position: Option<i64>,
@@ -95,7 +120,7 @@ pub struct Task {
     pub subscription: Option<Subscription>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[allow(dead_code)] // API response fields may not all be used
 pub struct Attachment {
     pub id: i64,
@@ -105,7 +130,7 @@ pub struct Attachment {
     pub file: Option<FileAttachment>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[allow(dead_code)] // API response fields may not all be used
 pub struct FileAttachment {
     pub id: i64,
@@ -115,7 +140,7 @@ pub struct FileAttachment {
     pub created: Option<String>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[allow(dead_code)] // API response fields may not all be used
 pub struct Comment {
     pub id: i64,
@@ -126,7 +151,7 @@ pub struct Comment {
     pub reactions: Option<std::collections::HashMap<String, Vec<User>>>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[allow(dead_code)] // API response fields may not all be used
 pub struct Bucket {
     pub id: i64,
@@ -141,7 +166,7 @@ pub struct Bucket {
     pub tasks: Option<Vec<Task>>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[allow(dead_code)] // API response fields may not all be used
 pub struct Reminder {
     pub reminder: Option<String>,
@@ -149,7 +174,7 @@ pub struct Reminder {
     pub relative_period: Option<i64>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[allow(dead_code)] // API response fields may not all be used
 pub struct Subscription {
     pub id: i64,
@@ -158,6 +183,16 @@ pub struct Subscription {
     pub created: Option<String>,
 }
 
+impl User {
+    /// Display name if the user set one, otherwise the username
+    pub fn display_name(&self) -> &str {
+        match self.name {
+            Some(ref name) if !name.trim().is_empty() => name,
+            _ => &self.username,
+        }
+    }
+}
+
 impl Default for Task {
     fn default() -> Self {
         Task {
@@ -198,7 +233,8 @@ impl Default for Task {
 }
 
 impl Task {
-    #[allow(dead_code)]
+    /// Convert to an update payload that carries every field the server stores,
+    /// so a POST built from it only changes what the caller modified.
     pub fn to_vikunja_task(&self) -> crate::vikunja_client::tasks::VikunjaTask {
         crate::vikunja_client::tasks::VikunjaTask {
             id: Some(self.id as u64),
@@ -221,6 +257,15 @@ impl Task {
                 email: a.email.clone(),
             }).collect()),
             is_favorite: Some(self.is_favorite),
+            end_date: self.end_date.clone(),
+            percent_done: self.percent_done.map(percent_to_fraction),
+            hex_color: self.hex_color.clone(),
+            bucket_id: self.bucket_id,
+            position: self.position.map(|p| p as f64),
+            repeat_after: self.repeat_after,
+            repeat_mode: self.repeat_mode,
+            reminders: self.reminders.clone(),
+            cover_image_attachment_id: self.cover_image_attachment_id,
         }
     }
     pub fn from_vikunja_task(vikunja_task: crate::vikunja_client::tasks::VikunjaTask) -> Self {
@@ -251,26 +296,26 @@ impl Task {
             priority: vikunja_task.priority.map(|p| p as i32),
             due_date: vikunja_task.due_date,
             start_date: vikunja_task.start_date,
-            end_date: None,
+            end_date: vikunja_task.end_date,
             created: None,
             updated: None,
             created_by: None,
-            percent_done: None,
+            percent_done: vikunja_task.percent_done.map(percent_from_fraction),
             is_favorite: vikunja_task.is_favorite.unwrap_or(false),
-            position: None,
+            position: vikunja_task.position.map(|p| p as i64),
             index: None,
             identifier: None,
-            hex_color: None,
-            cover_image_attachment_id: None,
-            bucket_id: None,
+            hex_color: vikunja_task.hex_color,
+            cover_image_attachment_id: vikunja_task.cover_image_attachment_id,
+            bucket_id: vikunja_task.bucket_id,
             buckets: None,
             attachments: None,
             comments: None,
             reactions: None,
             related_tasks: None,
-            reminders: None,
-            repeat_after: None,
-            repeat_mode: None,
+            reminders: vikunja_task.reminders,
+            repeat_after: vikunja_task.repeat_after,
+            repeat_mode: vikunja_task.repeat_mode,
             subscription: None,
         }
     }
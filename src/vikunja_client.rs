@@ -1,4 +1,5 @@
 use reqwest::{Client, Result as ReqwestResult};
+use crate::dates::DateSettings;
 use crate::vikunja_parser::{QuickAddParser};
 use crate::debug::debug_log;
 
@@ -23,6 +24,12 @@ pub mod labels;
 pub mod attachments;
 pub use attachments::*;
 
+// --- Project sharing types and functions ---
+pub mod sharing;
+
+// --- Kanban bucket types and functions ---
+pub mod buckets;
+
 // --- Relation-related types and functions ---
 pub mod relations;
 // pub use relations::*; // Disabled: unused import
@@ -32,6 +39,7 @@ pub struct VikunjaClient {
     base_url: String,
     auth_token: String,
     parser: QuickAddParser,
+    date_settings: DateSettings,
     attachment_client: AttachmentClient,
 }
 
@@ -77,10 +85,17 @@ impl VikunjaClient {
             base_url,
             auth_token,
             parser: QuickAddParser::new(),
+            date_settings: DateSettings::default(),
             attachment_client,
         }
     }
 
+    /// Read dates in magic syntax and the form editor with `settings`
+    pub fn set_date_settings(&mut self, settings: DateSettings) {
+        self.parser = QuickAddParser::with_settings(settings);
+        self.date_settings = settings;
+    }
+
     #[allow(dead_code)]
     pub fn base_url(&self) -> &str {
         &self.base_url
@@ -0,0 +1,99 @@
+// Kanban bucket API functions for Vikunja
+// Buckets belong to a project's kanban view (Vikunja 0.24 and later).
+
+use reqwest::Result as ReqwestResult;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VikunjaBucket {
+    pub id: i64,
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectView {
+    id: i64,
+    /// "kanban", or 3 in some versions
+    #[serde(default)]
+    view_kind: serde_json::Value,
+}
+
+impl super::VikunjaClient {
+    /// Id of the project's kanban view, if it has one
+    pub async fn get_kanban_view_id(&self, project_id: i64) -> ReqwestResult<Option<i64>> {
+        let url = format!("{}/api/v1/projects/{}/views", self.base_url, project_id);
+        let views: Vec<ProjectView> = self.client.get(&url)
+            .bearer_auth(&self.auth_token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(views.iter()
+            .find(|v| v.view_kind == "kanban" || v.view_kind == 3)
+            .map(|v| v.id))
+    }
+
+    pub async fn get_buckets(&self, project_id: i64, view_id: i64) -> ReqwestResult<Vec<VikunjaBucket>> {
+        let url = format!("{}/api/v1/projects/{}/views/{}/buckets", self.base_url, project_id, view_id);
+        self.client.get(&url)
+            .bearer_auth(&self.auth_token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+
+    pub async fn create_bucket(&self, project_id: i64, view_id: i64, title: &str) -> ReqwestResult<VikunjaBucket> {
+        let url = format!("{}/api/v1/projects/{}/views/{}/buckets", self.base_url, project_id, view_id);
+        self.client.put(&url)
+            .bearer_auth(&self.auth_token)
+            .json(&serde_json::json!({ "title": title }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+
+    pub async fn move_task_to_bucket(&self, project_id: i64, view_id: i64, bucket_id: i64, task_id: i64) -> ReqwestResult<()> {
+        let url = format!("{}/api/v1/projects/{}/views/{}/buckets/{}/tasks", self.base_url, project_id, view_id, bucket_id);
+        self.client.post(&url)
+            .bearer_auth(&self.auth_token)
+            .json(&serde_json::json!({ "task_id": task_id, "bucket_id": bucket_id, "project_view_id": view_id }))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// The project's kanban view and the id of its bucket called `title` (ignoring
+    /// case), creating the bucket when there is none
+    pub async fn ensure_bucket(&self, project_id: i64, title: &str) -> Result<(i64, i64), String> {
+        let view_id = self.get_kanban_view_id(project_id).await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("project {} has no kanban view", project_id))?;
+        let buckets = self.get_buckets(project_id, view_id).await.map_err(|e| e.to_string())?;
+        if let Some(bucket) = buckets.iter().find(|b| b.title.eq_ignore_ascii_case(title)) {
+            return Ok((view_id, bucket.id));
+        }
+        let bucket = self.create_bucket(project_id, view_id, title).await.map_err(|e| e.to_string())?;
+        Ok((view_id, bucket.id))
+    }
+
+    /// Move a task to a bucket of its project's kanban view, given the bucket's title
+    /// (ignoring case) or id. Returns the bucket's id.
+    pub async fn move_task_to_named_bucket(&self, project_id: i64, task_id: i64, bucket: &str) -> Result<i64, String> {
+        let view_id = self.get_kanban_view_id(project_id).await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("project {} has no kanban view", project_id))?;
+        let buckets = self.get_buckets(project_id, view_id).await.map_err(|e| e.to_string())?;
+        let bucket_id = buckets.iter()
+            .find(|b| b.title.eq_ignore_ascii_case(bucket.trim()) || b.id.to_string() == bucket.trim())
+            .map(|b| b.id)
+            .ok_or_else(|| format!("no bucket '{}' in project {}", bucket.trim(), project_id))?;
+        self.move_task_to_bucket(project_id, view_id, bucket_id, task_id).await.map_err(|e| e.to_string())?;
+        Ok(bucket_id)
+    }
+}
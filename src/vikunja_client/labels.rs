@@ -1,5 +1,6 @@
 use reqwest::Result as ReqwestResult;
 use crate::vikunja_client::tasks::VikunjaLabel;
+use crate::debug::debug_log;
 
 impl super::VikunjaClient {
     pub async fn get_all_labels(&self) -> ReqwestResult<Vec<VikunjaLabel>> {
@@ -11,4 +12,109 @@ impl super::VikunjaClient {
             .await?;
         response.json().await
     }
+
+    /// Update a label's title and color
+    pub async fn update_label(
+        &self,
+        label_id: u64,
+        title: &str,
+        hex_color: Option<&str>,
+    ) -> Result<VikunjaLabel, Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/labels/{}", self.base_url, label_id);
+        let label = VikunjaLabel {
+            id: Some(label_id),
+            title: title.to_string(),
+            hex_color: hex_color.map(|c| c.trim_start_matches('#').to_string()),
+        };
+
+        let response = self.client
+            .post(&url)
+            .bearer_auth(&self.auth_token)
+            .json(&label)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let label: VikunjaLabel = response.json().await?;
+            Ok(label)
+        } else {
+            let error_text = response.text().await?;
+            Err(format!("Failed to update label: {}", error_text).into())
+        }
+    }
+
+    /// Delete a label
+    pub async fn delete_label(&self, label_id: u64) -> Result<(), Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/labels/{}", self.base_url, label_id);
+
+        let response = self.client
+            .delete(&url)
+            .bearer_auth(&self.auth_token)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let error_text = response.text().await?;
+            Err(format!("Failed to delete label: {}", error_text).into())
+        }
+    }
+
+    /// Every task carrying `label_id`, done or not, across all projects
+    pub async fn get_tasks_with_label(&self, label_id: u64) -> ReqwestResult<Vec<crate::vikunja::models::Task>> {
+        let per_page = 250;
+        let mut tasks = Vec::new();
+        for page in 1..=100 {
+            let url = format!(
+                "{}/api/v1/tasks/all?page={}&per_page={}&filter=labels+in+{}&filter_include_nulls=false",
+                self.base_url, page, per_page, label_id
+            );
+            let page_tasks: Vec<crate::vikunja::models::Task> = self.client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", self.auth_token))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            let count = page_tasks.len();
+            tasks.extend(page_tasks);
+            if count < per_page {
+                break;
+            }
+        }
+        // Older servers ignore the filter and return every task
+        tasks.retain(|t| t.labels.as_ref().is_some_and(|labels| labels.iter().any(|l| l.id as u64 == label_id)));
+        debug_log(&format!("Found {} tasks with label {}", tasks.len(), label_id));
+        Ok(tasks)
+    }
+
+    /// Merge one label into another: every task carrying `source_id` is re-labelled
+    /// with `target_id`, then the source label is deleted. Returns the tasks that
+    /// couldn't be moved, with why; the source label is only deleted when there are none.
+    pub async fn merge_labels(
+        &self,
+        source_id: u64,
+        target_id: u64,
+        task_ids: &[(u64, bool)], // (task_id, already has target label)
+    ) -> Result<Vec<(u64, String)>, Box<dyn std::error::Error>> {
+        let mut failures = Vec::new();
+        for (task_id, has_target) in task_ids {
+            if !has_target {
+                if let Err(e) = self.add_label_to_task(*task_id, target_id).await {
+                    failures.push((*task_id, e.to_string()));
+                    continue;
+                }
+            }
+            if let Err(e) = self.remove_label_from_task(*task_id, source_id).await {
+                failures.push((*task_id, e.to_string()));
+            }
+        }
+        debug_log(&format!("Re-labelled {} of {} tasks from label {} to {}", task_ids.len() - failures.len(), task_ids.len(), source_id, target_id));
+        if failures.is_empty() {
+            self.delete_label(source_id).await?;
+        }
+        Ok(failures)
+    }
 }
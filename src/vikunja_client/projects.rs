@@ -62,6 +62,31 @@ impl super::VikunjaClient {
             .await?;
         resp.json::<VikunjaProject>().await
     }
+
+    /// Create a project, under `parent_project_id` unless it is 0
+    pub async fn create_child_project(
+        &self,
+        title: &str,
+        description: Option<&str>,
+        hex_color: Option<&str>,
+        parent_project_id: i64,
+    ) -> reqwest::Result<VikunjaProject> {
+        let url = format!("{}/api/v1/projects", self.base_url);
+        let payload = serde_json::json!({
+            "title": title,
+            "description": description.unwrap_or_default(),
+            "hex_color": hex_color.unwrap_or_default(),
+            "parent_project_id": parent_project_id,
+        });
+        self.client.put(&url)
+            .header("Authorization", format!("Bearer {}", self.auth_token))
+            .json(&payload)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<VikunjaProject>()
+            .await
+    }
 }
 
 // --- Project-related API impls ---
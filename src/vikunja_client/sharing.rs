@@ -0,0 +1,280 @@
+// Project sharing API functions for Vikunja (users, teams and link shares)
+
+use crate::debug::debug_log;
+use serde::{Deserialize, Serialize};
+
+/// Permission level of a share. Vikunja encodes these as 0/1/2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SharePermission {
+    Read,
+    ReadWrite,
+    Admin,
+}
+
+impl SharePermission {
+    pub fn from_i64(value: i64) -> Self {
+        match value {
+            1 => SharePermission::ReadWrite,
+            2 => SharePermission::Admin,
+            _ => SharePermission::Read,
+        }
+    }
+
+    pub fn as_i64(self) -> i64 {
+        match self {
+            SharePermission::Read => 0,
+            SharePermission::ReadWrite => 1,
+            SharePermission::Admin => 2,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SharePermission::Read => "read",
+            SharePermission::ReadWrite => "read/write",
+            SharePermission::Admin => "admin",
+        }
+    }
+
+    /// Next higher permission, wrapping from admin back to read
+    pub fn next(self) -> Self {
+        match self {
+            SharePermission::Read => SharePermission::ReadWrite,
+            SharePermission::ReadWrite => SharePermission::Admin,
+            SharePermission::Admin => SharePermission::Read,
+        }
+    }
+
+    /// Next lower permission, wrapping from read to admin
+    pub fn previous(self) -> Self {
+        match self {
+            SharePermission::Read => SharePermission::Admin,
+            SharePermission::ReadWrite => SharePermission::Read,
+            SharePermission::Admin => SharePermission::ReadWrite,
+        }
+    }
+
+    /// One step higher, or None at admin
+    pub fn raised(self) -> Option<Self> {
+        match self {
+            SharePermission::Admin => None,
+            other => Some(other.next()),
+        }
+    }
+
+    /// One step lower, or None at read
+    pub fn lowered(self) -> Option<Self> {
+        match self {
+            SharePermission::Read => None,
+            other => Some(other.previous()),
+        }
+    }
+}
+
+/// A user a project is shared with.
+/// Older Vikunja versions call the permission field `right`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectUserShare {
+    pub id: i64,
+    pub username: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default, alias = "right")]
+    pub permission: i64,
+}
+
+/// A team a project is shared with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectTeamShare {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default, alias = "right")]
+    pub permission: i64,
+}
+
+/// A public link share of a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkShare {
+    pub id: i64,
+    pub hash: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default, alias = "right")]
+    pub permission: i64,
+    #[serde(default)]
+    pub sharing_type: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VikunjaTeam {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl super::VikunjaClient {
+    /// Web URL that opens a link share
+    pub fn link_share_url(&self, hash: &str) -> String {
+        format!("{}/share/{}/auth", self.base_url, hash)
+    }
+
+    /// GET a list endpoint, treating Vikunja's `null` as an empty list
+    async fn get_share_list<T: serde::de::DeserializeOwned>(&self, url: &str, query: &[(&str, &str)]) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+        let response = self.client
+            .get(url)
+            .bearer_auth(&self.auth_token)
+            .query(query)
+            .send()
+            .await?;
+        if response.status().is_success() {
+            let items: Option<Vec<T>> = response.json().await?;
+            Ok(items.unwrap_or_default())
+        } else {
+            let error_text = response.text().await?;
+            Err(format!("Failed to load {}: {}", url, error_text).into())
+        }
+    }
+
+    /// Send a JSON body to a sharing endpoint and check the status
+    async fn send_share_request(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        body: Option<serde_json::Value>,
+        what: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        debug_log(&format!("Sharing: {} {}", method, url));
+        let mut request = self.client
+            .request(method, url)
+            .bearer_auth(&self.auth_token);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request.send().await?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let error_text = response.text().await?;
+            Err(format!("Failed to {}: {}", what, error_text).into())
+        }
+    }
+
+    pub async fn get_project_users(&self, project_id: i64) -> Result<Vec<ProjectUserShare>, Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/projects/{}/users", self.base_url, project_id);
+        self.get_share_list(&url, &[]).await
+    }
+
+    pub async fn get_project_teams(&self, project_id: i64) -> Result<Vec<ProjectTeamShare>, Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/projects/{}/teams", self.base_url, project_id);
+        self.get_share_list(&url, &[]).await
+    }
+
+    pub async fn get_link_shares(&self, project_id: i64) -> Result<Vec<LinkShare>, Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/projects/{}/shares", self.base_url, project_id);
+        self.get_share_list(&url, &[]).await
+    }
+
+    /// Search the teams the current user can see (GET /teams?s=query)
+    pub async fn search_teams(&self, query: &str) -> Result<Vec<VikunjaTeam>, Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/teams", self.base_url);
+        self.get_share_list(&url, &[("s", query)]).await
+    }
+
+    /// Share a project with a user by username
+    pub async fn share_project_with_user(&self, project_id: i64, username: &str, permission: SharePermission) -> Result<(), Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/projects/{}/users", self.base_url, project_id);
+        // `user_id` and `right` are the field names used by older Vikunja versions
+        let body = serde_json::json!({
+            "username": username,
+            "user_id": username,
+            "permission": permission.as_i64(),
+            "right": permission.as_i64(),
+        });
+        self.send_share_request(reqwest::Method::PUT, &url, Some(body), "share project with user").await
+    }
+
+    pub async fn update_project_user_permission(&self, project_id: i64, username: &str, permission: SharePermission) -> Result<(), Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/projects/{}/users/{}", self.base_url, project_id, username);
+        let body = serde_json::json!({"permission": permission.as_i64(), "right": permission.as_i64()});
+        self.send_share_request(reqwest::Method::POST, &url, Some(body), "change user permission").await
+    }
+
+    pub async fn remove_project_user(&self, project_id: i64, username: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/projects/{}/users/{}", self.base_url, project_id, username);
+        self.send_share_request(reqwest::Method::DELETE, &url, None, "remove user from project").await
+    }
+
+    pub async fn share_project_with_team(&self, project_id: i64, team_id: i64, permission: SharePermission) -> Result<(), Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/projects/{}/teams", self.base_url, project_id);
+        let body = serde_json::json!({
+            "team_id": team_id,
+            "permission": permission.as_i64(),
+            "right": permission.as_i64(),
+        });
+        self.send_share_request(reqwest::Method::PUT, &url, Some(body), "share project with team").await
+    }
+
+    pub async fn update_project_team_permission(&self, project_id: i64, team_id: i64, permission: SharePermission) -> Result<(), Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/projects/{}/teams/{}", self.base_url, project_id, team_id);
+        let body = serde_json::json!({"permission": permission.as_i64(), "right": permission.as_i64()});
+        self.send_share_request(reqwest::Method::POST, &url, Some(body), "change team permission").await
+    }
+
+    pub async fn remove_project_team(&self, project_id: i64, team_id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/projects/{}/teams/{}", self.base_url, project_id, team_id);
+        self.send_share_request(reqwest::Method::DELETE, &url, None, "remove team from project").await
+    }
+
+    /// Create a link share without a password
+    pub async fn create_link_share(&self, project_id: i64, name: &str, permission: SharePermission) -> Result<LinkShare, Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/projects/{}/shares", self.base_url, project_id);
+        let body = serde_json::json!({
+            "name": name,
+            "permission": permission.as_i64(),
+            "right": permission.as_i64(),
+            "sharing_type": 1,
+        });
+        let response = self.client
+            .put(&url)
+            .bearer_auth(&self.auth_token)
+            .json(&body)
+            .send()
+            .await?;
+        if response.status().is_success() {
+            let share: LinkShare = response.json().await?;
+            Ok(share)
+        } else {
+            let error_text = response.text().await?;
+            Err(format!("Failed to create link share: {}", error_text).into())
+        }
+    }
+
+    pub async fn delete_link_share(&self, project_id: i64, share_id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/projects/{}/shares/{}", self.base_url, project_id, share_id);
+        self.send_share_request(reqwest::Method::DELETE, &url, None, "revoke link share").await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permission_round_trip() {
+        for p in [SharePermission::Read, SharePermission::ReadWrite, SharePermission::Admin] {
+            assert_eq!(SharePermission::from_i64(p.as_i64()), p);
+            assert_eq!(p.next().previous(), p);
+        }
+    }
+
+    #[test]
+    fn test_share_accepts_legacy_right_field() {
+        let user: ProjectUserShare = serde_json::from_str(r#"{"id":3,"username":"sam","name":"","right":2}"#).unwrap();
+        assert_eq!(SharePermission::from_i64(user.permission), SharePermission::Admin);
+        let team: ProjectTeamShare = serde_json::from_str(r#"{"id":5,"name":"Ops","permission":1}"#).unwrap();
+        assert_eq!(SharePermission::from_i64(team.permission), SharePermission::ReadWrite);
+    }
+}
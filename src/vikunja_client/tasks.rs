@@ -9,9 +9,16 @@ use chrono::{DateTime, Utc};
 use crate::debug::debug_log;
 
 use crate::vikunja_client::VikunjaUser;
+use crate::vikunja_client::relations::RelationKind;
+use crate::templates::{PlannedProject, PlannedTask};
+use crate::vikunja_parser::{OutlineEntry, TaskRef};
 use serde_json;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Task payload for create/update requests.
+///
+/// Vikunja's update replaces the whole task, so every field the server stores
+/// must be carried through here; a field left as `None` is reset on the server.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct VikunjaTask {
     pub id: Option<u64>,
     pub title: String,
@@ -25,9 +32,73 @@ pub struct VikunjaTask {
     pub labels: Option<Vec<VikunjaLabel>>,
     pub assignees: Option<Vec<VikunjaUser>>,
     pub is_favorite: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percent_done: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hex_color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bucket_id: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat_after: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat_mode: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reminders: Option<Vec<crate::vikunja::models::Reminder>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_image_attachment_id: Option<i64>,
+}
+
+/// The fields the form editor changed; `None` leaves a field as it is on the server
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TaskFormChanges {
+    pub title: Option<String>,
+    /// Markdown
+    pub description: Option<String>,
+    /// Dates as typed in the form; `Some(None)` clears the date
+    pub due_date: Option<Option<String>>,
+    pub start_date: Option<Option<String>>,
+    pub end_date: Option<Option<String>>,
+    pub priority: Option<Option<i32>>,
+    pub project_id: Option<i64>,
+    pub label_ids: Option<Vec<i64>>,
+    pub assignee_ids: Option<Vec<i64>>,
+    pub is_favorite: Option<bool>,
+    /// 0-100
+    pub percent_done: Option<u8>,
+    /// Six hex digits, or empty for no color
+    pub hex_color: Option<String>,
+    /// `repeat_after` in seconds and `repeat_mode`
+    pub repeat: Option<(i64, i64)>,
+    pub reminders: Option<Vec<crate::vikunja::models::Reminder>>,
+    /// A bucket of the project's kanban view, by title or id
+    pub bucket: Option<String>,
+    pub comment: Option<String>,
+}
+
+impl TaskFormChanges {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether anything sent with the task itself changed, as opposed to its labels,
+    /// assignees, bucket or comments
+    fn has_task_fields(&self) -> bool {
+        let task_only = Self {
+            label_ids: None,
+            assignee_ids: None,
+            bucket: None,
+            comment: None,
+            ..self.clone()
+        };
+        !task_only.is_empty()
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VikunjaLabel {
     pub id: Option<u64>,
     pub title: String,
@@ -82,10 +153,12 @@ impl super::VikunjaClient {
             priority: parsed.priority,
             due_date: parsed.due_date,
             start_date: parsed.start_date,
+            end_date: parsed.end_date.map(|d| d.to_rfc3339()),
             project_id: project_id.try_into().unwrap(),
             labels: None,
             assignees: None,
             is_favorite: Some(false),
+            ..Default::default()
         };
 
         debug_log(&format!("Creating task with project_id: {}, title: '{}'", project_id, task.title));
@@ -116,7 +189,15 @@ impl super::VikunjaClient {
             }
         }
 
-        // Step 5: Handle repeating tasks (if needed)
+        // Step 5: Add the parent and relations
+        if let Some(parent) = &parsed.parent {
+            self.relate_task(task_id, RelationKind::Parenttask, parent).await;
+        }
+        for (kind, target) in &parsed.relations {
+            self.relate_task(task_id, kind.clone(), target).await;
+        }
+
+        // Step 6: Handle repeating tasks (if needed)
         if let Some(_repeat) = &parsed.repeat_interval {
             // Implement repeat logic based on Vikunja's repeat API
             // This would involve setting repeat_after or repeat_mode fields
@@ -131,6 +212,166 @@ impl super::VikunjaClient {
         Ok(created_task)
     }
 
+    /// Create every line of an indented outline with `create_task_with_magic`, making each
+    /// a subtask of the line it is indented under. Subtasks go to their parent's project
+    /// unless they name one. Returns the created tasks in outline order.
+    pub async fn create_outline_with_magic(
+        &self,
+        outline: &[OutlineEntry],
+        default_project_id: i64,
+    ) -> ReqwestResult<Vec<VikunjaTask>> {
+        let mut created: Vec<VikunjaTask> = Vec::new();
+        for entry in outline {
+            let parent = entry.parent.and_then(|index| created.get(index));
+            let project_id = parent.map(|p| p.project_id as i64).unwrap_or(default_project_id);
+            let parent_id = parent.and_then(|p| p.id);
+            let task = self.create_task_with_magic(&entry.text, project_id).await?;
+            if let (Some(parent_id), Some(task_id)) = (parent_id, task.id) {
+                if let Err(e) = self.create_task_relation(parent_id, task_id, RelationKind::Subtask).await {
+                    debug_log(&format!("Failed to make task {} a subtask of {}: {}", task_id, parent_id, e));
+                }
+            }
+            created.push(task);
+        }
+        Ok(created)
+    }
+
+    /// Create the tasks of a filled-in template, each subtask related to its parent.
+    /// A task goes to its planned project (an id from `project_ids`), the project it
+    /// names, its parent's project, or `default_project_id`, in that order. Labels and
+    /// buckets are created when missing; dependencies become "blocked by" relations once
+    /// every task exists. Returns the created tasks in plan order.
+    pub async fn create_planned_tasks(
+        &self,
+        planned: &[PlannedTask],
+        default_project_id: i64,
+        project_ids: &[i64],
+    ) -> ReqwestResult<Vec<VikunjaTask>> {
+        let mut created: Vec<VikunjaTask> = Vec::new();
+        for entry in planned {
+            let parent = entry.parent.and_then(|index| created.get(index));
+            let inherited = parent.map(|p| p.project_id as i64).unwrap_or(default_project_id);
+            let project_id = match (entry.project_index.and_then(|i| project_ids.get(i)), &entry.project) {
+                (Some(id), _) => *id,
+                (None, Some(name)) => match self.find_or_get_project_id(name).await {
+                    Ok(Some(id)) => id,
+                    _ => {
+                        debug_log(&format!("Template project '{}' not found, using {}", name, inherited));
+                        inherited
+                    }
+                },
+                (None, None) => inherited,
+            };
+            let task = VikunjaTask {
+                title: entry.title.clone(),
+                description: entry.description.clone(),
+                done: Some(false),
+                priority: entry.priority.map(|p| p as u8),
+                due_date: entry.due_date,
+                start_date: entry.start_date,
+                project_id: project_id as u64,
+                is_favorite: Some(false),
+                ..Default::default()
+            };
+            let task = self.create_task(&task).await?;
+            let task_id = task.id.unwrap_or_default();
+            for label_name in &entry.labels {
+                match self.ensure_label_exists(label_name).await {
+                    Ok(label) => {
+                        if let Err(e) = self.add_label_to_task(task_id, label.id.unwrap_or_default()).await {
+                            debug_log(&format!("Failed to add label '{}' to task {}: {}", label_name, task_id, e));
+                        }
+                    }
+                    Err(e) => debug_log(&format!("Failed to ensure label '{}' exists: {}", label_name, e)),
+                }
+            }
+            if let Some(parent_id) = parent.and_then(|p| p.id) {
+                if let Err(e) = self.create_task_relation(parent_id, task_id, RelationKind::Subtask).await {
+                    debug_log(&format!("Failed to make task {} a subtask of {}: {}", task_id, parent_id, e));
+                }
+            }
+            if let Some(bucket) = &entry.bucket {
+                let moved = match self.ensure_bucket(project_id, bucket).await {
+                    Ok((view_id, bucket_id)) => self.move_task_to_bucket(project_id, view_id, bucket_id, task_id as i64).await.map_err(|e| e.to_string()),
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = moved {
+                    debug_log(&format!("Failed to put task {} in bucket '{}': {}", task_id, bucket, e));
+                }
+            }
+            created.push(task);
+        }
+
+        let ids: HashMap<&str, u64> = planned.iter().zip(&created)
+            .filter_map(|(entry, task)| Some((entry.id.as_deref()?, task.id?)))
+            .collect();
+        for (entry, task) in planned.iter().zip(&created) {
+            let task_id = task.id.unwrap_or_default();
+            for other_id in entry.depends_on.iter().filter_map(|key| ids.get(key.as_str())) {
+                if let Err(e) = self.create_task_relation(task_id, *other_id, RelationKind::Blocked).await {
+                    debug_log(&format!("Failed to make task {} blocked by {}: {}", task_id, other_id, e));
+                }
+            }
+        }
+        Ok(created)
+    }
+
+    /// Create the projects of a filled-in project template, sub-projects under their
+    /// parents and each with its buckets, then all their tasks. Returns the created
+    /// project ids in plan order and the tasks.
+    pub async fn create_planned_project(
+        &self,
+        projects: &[PlannedProject],
+        tasks: &[PlannedTask],
+        parent_project_id: i64,
+    ) -> ReqwestResult<(Vec<i64>, Vec<VikunjaTask>)> {
+        let mut project_ids: Vec<i64> = Vec::new();
+        for project in projects {
+            let parent_id = project.parent.and_then(|i| project_ids.get(i).copied()).unwrap_or(parent_project_id);
+            let created = self.create_child_project(&project.title, project.description.as_deref(), project.hex_color.as_deref(), parent_id).await?;
+            for bucket in &project.buckets {
+                if let Err(e) = self.ensure_bucket(created.id, bucket).await {
+                    debug_log(&format!("Failed to create bucket '{}' in project {}: {}", bucket, created.id, e));
+                }
+            }
+            project_ids.push(created.id);
+        }
+        let default_project_id = project_ids.first().copied().unwrap_or(parent_project_id);
+        let created = self.create_planned_tasks(tasks, default_project_id, &project_ids).await?;
+        Ok((project_ids, created))
+    }
+
+    /// Relate a task to one named in magic syntax, logging what can't be done
+    async fn relate_task(&self, task_id: u64, kind: RelationKind, target: &TaskRef) {
+        let other_id = match target {
+            TaskRef::Id(id) => Some(*id),
+            TaskRef::Title(title) => self.find_task_by_title(title).await.ok().flatten(),
+        };
+        let Some(other_id) = other_id else {
+            debug_log(&format!("No task {:?} to relate task {} to", target, task_id));
+            return;
+        };
+        match self.create_task_relation(task_id, other_id as u64, kind.clone()).await {
+            Ok(_) => debug_log(&format!("Added {} relation from task {} to {}", kind.to_string(), task_id, other_id)),
+            Err(e) => debug_log(&format!("Failed to relate task {} to {}: {}", task_id, other_id, e)),
+        }
+    }
+
+    /// Id of a task whose title is `title`, ignoring case. Open tasks are preferred.
+    pub async fn find_task_by_title(&self, title: &str) -> ReqwestResult<Option<i64>> {
+        let url = format!("{}/api/v1/tasks/all", self.base_url);
+        let response = self.client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.auth_token))
+            .query(&[("s", title)])
+            .send()
+            .await?;
+        let tasks: Vec<crate::vikunja::models::Task> = response.json().await?;
+        let mut matches: Vec<_> = tasks.into_iter().filter(|t| t.title.trim().eq_ignore_ascii_case(title.trim())).collect();
+        matches.sort_by_key(|t| t.done);
+        Ok(matches.first().map(|t| t.id))
+    }
+
     pub async fn create_task(&self, task: &VikunjaTask) -> ReqwestResult<VikunjaTask> {
         let url = format!("{}/api/v1/projects/{}/tasks", self.base_url, task.project_id);
         debug_log(&format!("Making PUT request to: {}", url));
@@ -246,18 +487,17 @@ impl super::VikunjaClient {
             debug_log(&format!("No project specified, keeping current: {}", current_task.project_id));
             current_task.project_id as i64
         };
+        // Start from the stored task so fields the edit line doesn't mention are kept
         let updated_task = VikunjaTask {
             id: Some(task_id as u64),
             title: parsed.title.clone(),
-            description: current_task.description,
-            done: current_task.done,
             priority: parsed.priority.or(current_task.priority),
             due_date: parsed.due_date.or(current_task.due_date),
-            start_date: current_task.start_date,
+            start_date: parsed.start_date.or(current_task.start_date),
+            end_date: parsed.end_date.map(|d| d.to_rfc3339()).or(current_task.end_date.clone()),
             project_id: project_id as u64,
             labels: None,
-            assignees: None,
-            is_favorite: current_task.is_favorite,
+            ..current_task.clone()
         };
         debug_log(&format!("Updating task with project_id: {}, title: '{}'", project_id, updated_task.title));
         let updated_task = self.update_task(&updated_task).await?;
@@ -289,6 +529,13 @@ impl super::VikunjaClient {
                 }
             }
         }
+        // Relations named in the edit line are added; existing ones are left alone
+        if let Some(parent) = &parsed.parent {
+            self.relate_task(task_id as u64, RelationKind::Parenttask, parent).await;
+        }
+        for (kind, target) in &parsed.relations {
+            self.relate_task(task_id as u64, kind.clone(), target).await;
+        }
         if let Some(_repeat) = &parsed.repeat_interval {
             // Implement repeat logic based on Vikunja's repeat API
         }
@@ -362,123 +609,116 @@ impl super::VikunjaClient {
         debug_log(&format!("Updating task {} with form data - title: '{}', project_id: {}, favorite: {}", 
                  task_id, title, project_id, is_favorite));
 
+        let current_task = self.get_task(task_id as u64).await?;
         let task = VikunjaTask {
             id: Some(task_id as u64),
             title: title.to_string(),
             description: description.map(|s| s.to_string()),
-            done: None, // Don't change done status in form edit
             priority: priority.map(|p| p as u8),
-            due_date: None, // TODO: Parse due_date from form
-            start_date: None,
             project_id: project_id as u64,
             labels: None, // TODO: Handle labels from form
-            assignees: None, // TODO: Handle assignees from form
             is_favorite: Some(is_favorite),
+            ..current_task
         };
 
         self.update_task(&task).await
     }
     
+    /// Send the fields the form editor changed, keeping everything else as stored on
+    /// the server, and add the form's comment
     pub async fn update_task_from_form(
         &self,
         task_id: i64,
-        title: &str,
-        description: &str,
-        due_date: Option<&str>,
-        start_date: Option<&str>,
-        priority: Option<i32>,
-        project_id: i64,
-        label_ids: &[i64],
-        assignee_ids: &[i64],
-        is_favorite: bool,
-        comment: Option<&str>,
+        changes: &TaskFormChanges,
     ) -> Result<crate::vikunja::models::Task, Box<dyn Error>> {
-        debug_log(&format!("Updating task {} from form - title: '{}', project_id: {}, favorite: {}", 
-                 task_id, title, project_id, is_favorite));
+        debug_log(&format!("Updating task {} from form: {:?}", task_id, changes));
 
-        // Parse dates
-        let due_date_parsed = if let Some(date_str) = due_date {
-            if !date_str.trim().is_empty() {
-                chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-                    .ok()
-                    .map(|d| d.and_hms_opt(23, 59, 59).unwrap().and_utc())
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        // Parse dates; date-only values get the configured default times
+        let settings = &self.date_settings;
+        let parse = |date: &Option<String>, default_time| date.as_deref().and_then(|text| settings.parse(text, default_time));
 
-        let start_date_parsed = if let Some(date_str) = start_date {
-            if !date_str.trim().is_empty() {
-                chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-                    .ok()
-                    .map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc())
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-
-        // Create task object for update
-        // Prepare HTML description body
-        let description_html = if description.trim().is_empty() {
-            None
-        } else {
-            Some(format!("<p>{}</p>", description.trim()))
-        };
-        let task = VikunjaTask {
+        let current_task = self.get_task(task_id as u64).await?;
+        let mut task = VikunjaTask {
             id: Some(task_id as u64),
-            title: title.to_string(),
-            description: description_html,
-            done: None, // Don't change done status in form edit
-            priority: priority.map(|p| p as u8),
-            due_date: due_date_parsed,
-            start_date: start_date_parsed,
-            project_id: project_id as u64,
             labels: None, // Will be set separately
-            assignees: None, // Will be set separately
-            is_favorite: Some(is_favorite),
+            ..current_task.clone()
         };
-
-        // Log JSON payload for debugging description
-        match serde_json::to_string(&task) {
-            Ok(json) => debug_log(&format!("update_task_from_form JSON: {}", json)),
-            Err(e) => debug_log(&format!("Failed to serialize task JSON: {}", e)),
+        if let Some(ref title) = changes.title {
+            task.title = title.clone();
         }
-        // Update the basic task
-        let _updated_task = self.update_task(&task).await?;
-        
-        // Handle labels - first remove all existing labels, then add new ones
-        if let Err(e) = self.clear_task_labels(task_id as u64).await {
-            debug_log(&format!("Warning: Failed to clear labels for task {}: {}", task_id, e));
+        // The form edits descriptions as Markdown; Vikunja stores HTML
+        if let Some(ref description) = changes.description {
+            task.description = Some(description_html(description));
         }
-        
-        for &label_id in label_ids {
-            if let Err(e) = self.add_label_to_task(task_id as u64, label_id as u64).await {
-                debug_log(&format!("Warning: Failed to add label {} to task {}: {}", label_id, task_id, e));
+        if let Some(ref due) = changes.due_date {
+            task.due_date = parse(due, settings.due_time);
+        }
+        if let Some(ref start) = changes.start_date {
+            task.start_date = parse(start, settings.start_time);
+        }
+        if let Some(ref end) = changes.end_date {
+            task.end_date = parse(end, settings.due_time).map(|d| d.to_rfc3339());
+        }
+        if let Some(priority) = changes.priority {
+            task.priority = priority.map(|p| p as u8);
+        }
+        if let Some(project_id) = changes.project_id {
+            task.project_id = project_id as u64;
+        }
+        if let Some(is_favorite) = changes.is_favorite {
+            task.is_favorite = Some(is_favorite);
+        }
+        if let Some(percent) = changes.percent_done {
+            task.percent_done = Some(crate::vikunja::models::percent_to_fraction(percent));
+        }
+        if let Some(ref hex_color) = changes.hex_color {
+            task.hex_color = Some(hex_color.clone());
+        }
+        if let Some((repeat_after, repeat_mode)) = changes.repeat {
+            task.repeat_after = Some(repeat_after);
+            task.repeat_mode = Some(repeat_mode);
+        }
+        if let Some(ref reminders) = changes.reminders {
+            task.reminders = Some(reminders.clone());
+        }
+
+        if changes.has_task_fields() {
+            match serde_json::to_string(&task) {
+                Ok(json) => debug_log(&format!("update_task_from_form JSON: {}", json)),
+                Err(e) => debug_log(&format!("Failed to serialize task JSON: {}", e)),
             }
+            self.update_task(&task).await?;
         }
 
-        // Handle assignees - similar approach
-        if let Err(e) = self.clear_task_assignees(task_id as u64).await {
-            debug_log(&format!("Warning: Failed to clear assignees for task {}: {}", task_id, e));
+        // Labels and assignees are replaced as a whole: clear them, then add the form's
+        if let Some(ref label_ids) = changes.label_ids {
+            if let Err(e) = self.clear_task_labels(task_id as u64).await {
+                debug_log(&format!("Warning: Failed to clear labels for task {}: {}", task_id, e));
+            }
+            for &label_id in label_ids {
+                if let Err(e) = self.add_label_to_task(task_id as u64, label_id as u64).await {
+                    debug_log(&format!("Warning: Failed to add label {} to task {}: {}", label_id, task_id, e));
+                }
+            }
         }
-        
-        for &assignee_id in assignee_ids {
-            if let Err(e) = self.add_assignee_to_task(task_id as u64, assignee_id as u64).await {
-                debug_log(&format!("Warning: Failed to add assignee {} to task {}: {}", assignee_id, task_id, e));
+        if let Some(ref assignee_ids) = changes.assignee_ids {
+            if let Err(e) = self.clear_task_assignees(task_id as u64).await {
+                debug_log(&format!("Warning: Failed to clear assignees for task {}: {}", task_id, e));
+            }
+            for &assignee_id in assignee_ids {
+                if let Err(e) = self.add_assignee_to_task(task_id as u64, assignee_id as u64).await {
+                    debug_log(&format!("Warning: Failed to add assignee {} to task {}: {}", assignee_id, task_id, e));
+                }
             }
         }
 
+        if let Some(ref bucket) = changes.bucket {
+            self.move_task_to_named_bucket(task.project_id as i64, task_id, bucket).await?;
+        }
 
-        // Handle comment
-        if let Some(comment_text) = comment {
-            if !comment_text.trim().is_empty() {
-                if let Err(e) = self.add_comment_to_task(task_id as u64, comment_text).await {
-                    debug_log(&format!("Warning: Failed to add comment to task {}: {}", task_id, e));
-                }
+        if let Some(ref comment_text) = changes.comment {
+            if let Err(e) = self.add_comment_to_task(task_id as u64, comment_text).await {
+                debug_log(&format!("Warning: Failed to add comment to task {}: {}", task_id, e));
             }
         }
 
@@ -835,54 +1075,58 @@ impl super::VikunjaClient {
     }
 
     pub async fn set_task_favorite(&self, task_id: u64, is_favorite: bool) -> ReqwestResult<()> {
-        // Update just the favorite status by making a task update with minimal data
-        let url = format!("{}/api/v1/tasks/{}", self.base_url, task_id);
-        
-        // Create a minimal task update that only changes the favorite status
-        let task_update = serde_json::json!({
-            "is_favorite": is_favorite
-        });
-        
+        // A POST replaces the whole task, so send the stored task with only the favorite flag changed
         debug_log(&format!("Setting task {} favorite status to: {}", task_id, is_favorite));
-        
-        let response = self.client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.auth_token))
-            .json(&task_update)
-            .send()
-            .await;
-            
-        match response {
-            Ok(resp) => {
-                let status = resp.status();
-                if status.is_success() {
-                    debug_log(&format!("Successfully updated favorite status for task {}", task_id));
-                    Ok(())
-                } else {
-                    let error_text = resp.text().await.unwrap_or_else(|_| "Failed to read error response".to_string());
-                    debug_log(&format!("API error updating favorite status ({}): {} characters", status, error_text.len()));
-                    // Create an error similar to how other methods do it
-                    let fake_response = self.client.get("http://invalid-url-that-will-fail").send().await;
-                    Err(fake_response.unwrap_err())
-                }
+        let mut task = self.get_task(task_id).await?;
+        task.is_favorite = Some(is_favorite);
+
+        match self.update_task(&task).await {
+            Ok(_) => {
+                debug_log(&format!("Successfully updated favorite status for task {}", task_id));
+                Ok(())
             }
             Err(e) => {
-                debug_log(&format!("Request error updating favorite status: {}", e));
+                debug_log(&format!("Error updating favorite status: {}", e));
                 Err(e)
             }
         }
     }
 
     // Add comment methods (from feature/advanced-modal)
-    pub async fn add_comment_to_task(&self, task_id: u64, comment: &str) -> ReqwestResult<()> {
+    pub async fn add_comment_to_task(&self, task_id: u64, comment: &str) -> ReqwestResult<crate::vikunja::models::Comment> {
         let url = format!("{}/api/v1/tasks/{}/comments", self.base_url, task_id);
         let comment_data = serde_json::json!({ "comment": comment });
-        self.client
+        let response = self.client
             .put(&url)
             .header("Authorization", format!("Bearer {}", self.auth_token))
             .json(&comment_data)
             .send()
-            .await?;
+            .await?
+            .error_for_status()?;
+        response.json().await
+    }
+
+    pub async fn update_comment(&self, task_id: u64, comment_id: i64, comment: &str) -> ReqwestResult<crate::vikunja::models::Comment> {
+        let url = format!("{}/api/v1/tasks/{}/comments/{}", self.base_url, task_id, comment_id);
+        let comment_data = serde_json::json!({ "comment": comment });
+        let response = self.client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.auth_token))
+            .json(&comment_data)
+            .send()
+            .await?
+            .error_for_status()?;
+        response.json().await
+    }
+
+    pub async fn delete_comment(&self, task_id: u64, comment_id: i64) -> ReqwestResult<()> {
+        let url = format!("{}/api/v1/tasks/{}/comments/{}", self.base_url, task_id, comment_id);
+        self.client
+            .delete(&url)
+            .header("Authorization", format!("Bearer {}", self.auth_token))
+            .send()
+            .await?
+            .error_for_status()?;
         Ok(())
     }
 
@@ -898,6 +1142,11 @@ impl super::VikunjaClient {
     }
 } // end impl super::VikunjaClient
 
+/// The HTML Vikunja stores for a description edited as Markdown; a blank one stays empty
+fn description_html(description: &str) -> String {
+    crate::markdown::markdown_to_html(description.trim())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -918,6 +1167,7 @@ mod tests {
             labels: None,
             assignees: None,
             is_favorite: None,
+            ..Default::default()
         };
         let json = serde_json::to_value(&task).unwrap();
         assert!(!json.as_object().unwrap().contains_key("description"));
@@ -939,6 +1189,7 @@ mod tests {
             labels: None,
             assignees: None,
             is_favorite: None,
+            ..Default::default()
         };
         let json = serde_json::to_value(&task).unwrap();
         let value = json.get("description").unwrap().as_str().unwrap();
@@ -946,17 +1197,63 @@ mod tests {
     }
 
     #[test]
-    fn test_description_html_wrapping_logic() {
-        // Replicate the HTML wrapping logic from update_task_from_form
-        fn wrap(desc: &str) -> Option<String> {
-            if desc.trim().is_empty() {
-                None
-            } else {
-                Some(format!("<p>{}</p>", desc.trim()))
-            }
+    fn test_to_vikunja_task_keeps_server_fields() {
+        let task = crate::vikunja::models::Task {
+            id: 7,
+            title: "Water plants".to_string(),
+            project_id: 3,
+            end_date: Some("2025-07-01T10:00:00Z".to_string()),
+            percent_done: Some(50),
+            hex_color: Some("e8a33d".to_string()),
+            bucket_id: Some(12),
+            position: Some(65536),
+            repeat_after: Some(86400),
+            repeat_mode: Some(0),
+            reminders: Some(vec![crate::vikunja::models::Reminder {
+                reminder: Some("2025-06-30T09:00:00Z".to_string()),
+                relative_to: None,
+                relative_period: None,
+            }]),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(task.to_vikunja_task()).unwrap();
+        assert_eq!(json["repeat_after"], 86400);
+        assert_eq!(json["hex_color"], "e8a33d");
+        assert_eq!(json["bucket_id"], 12);
+        assert_eq!(json["end_date"], "2025-07-01T10:00:00Z");
+        assert_eq!(json["reminders"][0]["reminder"], "2025-06-30T09:00:00Z");
+        // Vikunja keeps progress as a fraction
+        assert_eq!(json["percent_done"], 0.5);
+        let payload: VikunjaTask = serde_json::from_value(json).unwrap();
+        assert_eq!(crate::vikunja::models::Task::from_vikunja_task(payload).percent_done, Some(50));
+
+        // Task itself round-trips through JSON
+        let text = serde_json::to_string(&task).unwrap();
+        let back: crate::vikunja::models::Task = serde_json::from_str(&text).unwrap();
+        assert_eq!(back.repeat_after, Some(86400));
+        assert_eq!(back.position, Some(65536));
+        assert_eq!(back.percent_done, Some(50));
+    }
+
+    #[test]
+    fn test_server_progress_fraction_reads_as_percent() {
+        for (fraction, percent) in [(0.0, 0), (0.25, 25), (0.5, 50), (1.0, 100)] {
+            let json = serde_json::json!({
+                "id": 1, "title": "t", "done": false, "project_id": 1, "is_favorite": false,
+                "due_date": null, "start_date": null,
+                "percent_done": fraction,
+            });
+            let task: crate::vikunja::models::Task = serde_json::from_value(json).unwrap();
+            assert_eq!(task.percent_done, Some(percent));
         }
-        assert_eq!(wrap(""), None);
-        assert_eq!(wrap("foo"), Some("<p>foo</p>".to_string()));
-        assert_eq!(wrap(" foo  "), Some("<p>foo</p>".to_string()));
+    }
+
+    #[test]
+    fn test_description_html_wrapping_logic() {
+        // Clearing the description sends an empty one rather than leaving it unchanged
+        assert_eq!(description_html(""), "");
+        assert_eq!(description_html("  \n "), "");
+        assert_eq!(description_html("foo"), "<p>foo</p>");
+        assert_eq!(description_html(" foo  "), "<p>foo</p>");
     }
 }
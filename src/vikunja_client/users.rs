@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 
 // --- User-related types and functions ---
 // VikunjaUser, user-related impls and functions
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VikunjaUser {
     pub id: Option<u64>,
     pub username: String,
@@ -25,7 +25,31 @@ impl super::VikunjaClient {
         users.into_iter()
             .find(|u| u.username.eq_ignore_ascii_case(username))
     }
-    // Add other user-related methods here as needed
+
+    /// Search users by username or display name (GET /users?s=query)
+    pub async fn search_users(&self, query: &str) -> reqwest::Result<Vec<crate::vikunja::models::User>> {
+        let url = format!("{}/api/v1/users", self.base_url);
+        let response = self.client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.auth_token))
+            .query(&[("s", query)])
+            .send()
+            .await?;
+        // Vikunja returns `null` rather than `[]` when nothing matches
+        let users: Option<Vec<crate::vikunja::models::User>> = response.json().await?;
+        Ok(users.unwrap_or_default())
+    }
+
+    /// Fetch the user the API token belongs to (GET /user)
+    pub async fn get_current_user(&self) -> reqwest::Result<crate::vikunja::models::User> {
+        let url = format!("{}/api/v1/user", self.base_url);
+        let response = self.client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.auth_token))
+            .send()
+            .await?;
+        response.json().await
+    }
 }
 
 // --- User-related API impls ---
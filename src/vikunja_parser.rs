@@ -1,8 +1,10 @@
+use crate::config::DateDialect;
+use crate::dates::DateSettings;
 use crate::debug::debug_log;
+use crate::vikunja_client::relations::RelationKind;
 use regex::Regex;
-use chrono::{DateTime, Utc, NaiveDate, Local, Duration, Datelike, TimeZone};
-use chrono_english::{parse_date_string, Dialect};
-use aho_corasick::AhoCorasick;
+use chrono::{DateTime, Utc, NaiveDate, NaiveTime, Local, Duration, Datelike, Months, TimeZone, Weekday};
+use chrono_english::parse_date_string;
 
 #[derive(Debug, Clone)]
 pub struct ParsedTask {
@@ -13,9 +15,95 @@ pub struct ParsedTask {
     pub priority: Option<u8>,
     pub due_date: Option<DateTime<Utc>>,
     pub start_date: Option<DateTime<Utc>>,
+    /// Set by a range like "from mon to wed", together with `start_date`
+    pub end_date: Option<DateTime<Utc>>,
     pub repeat_interval: Option<RepeatInterval>,
+    /// `^#123` or `^"Parent title"`: the new task becomes a subtask of it
+    pub parent: Option<TaskRef>,
+    /// `blocks:#45`, `after:#46`, `related:#47`, as the relation from the new task
+    pub relations: Vec<(RelationKind, TaskRef)>,
 }
 
+/// Another task named in magic syntax
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskRef {
+    /// `#123`
+    Id(i64),
+    /// `"Task title"`, matched case-insensitively
+    Title(String),
+}
+
+/// One line of a pasted outline
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlineEntry {
+    /// The line without indentation and list markers, still in magic syntax
+    pub text: String,
+    /// Index of the entry it is indented under
+    pub parent: Option<usize>,
+}
+
+/// Split an indented outline into entries, nesting each line under the closest line above
+/// it that is indented less. List markers like "- ", "1. " and "[ ] " are dropped.
+pub fn parse_outline(text: &str) -> Vec<OutlineEntry> {
+    let mut entries: Vec<OutlineEntry> = Vec::new();
+    // (indent, index) of the entries the next line can be nested under
+    let mut open: Vec<(usize, usize)> = Vec::new();
+    for line in text.lines() {
+        let content = line.trim_start();
+        if content.trim().is_empty() {
+            continue;
+        }
+        let indent: usize = line[..line.len() - content.len()].chars()
+            .map(|c| if c == '\t' { 4 } else { 1 })
+            .sum();
+        while open.last().is_some_and(|&(open_indent, _)| open_indent >= indent) {
+            open.pop();
+        }
+        entries.push(OutlineEntry {
+            text: strip_list_marker(content).trim().to_string(),
+            parent: open.last().map(|&(_, index)| index),
+        });
+        open.push((indent, entries.len() - 1));
+    }
+    entries
+}
+
+/// "- ", "* ", "+ ", "• ", "1. " or "1) ", then an optional "[ ] " or "[x] "
+fn strip_list_marker(line: &str) -> &str {
+    let mut rest = line;
+    for bullet in ["- ", "* ", "+ ", "• "] {
+        if let Some(stripped) = rest.strip_prefix(bullet) {
+            rest = stripped.trim_start();
+            break;
+        }
+    }
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 {
+        let after = &rest[digits..];
+        if let Some(stripped) = after.strip_prefix(". ").or_else(|| after.strip_prefix(") ")) {
+            rest = stripped.trim_start();
+        }
+    }
+    for checkbox in ["[ ] ", "[x] ", "[X] "] {
+        if let Some(stripped) = rest.strip_prefix(checkbox) {
+            return stripped;
+        }
+    }
+    rest
+}
+
+/// `#123`, `"title"`, `'title'` or `[title]` in capture groups 1 to 4 of `cap`, from `offset`
+fn task_ref(cap: &regex::Captures, offset: usize) -> Option<TaskRef> {
+    if let Some(id) = cap.get(offset) {
+        return id.as_str().parse().ok().map(TaskRef::Id);
+    }
+    let title = cap.get(offset + 1).or(cap.get(offset + 2)).or(cap.get(offset + 3))?;
+    Some(TaskRef::Title(title.as_str().to_string()))
+}
+
+/// `#123`, `"title"`, `'title'` or `[title]`
+const TASK_REF: &str = r#"(?:#(\d+)|"([^"]+)"|'([^']+)'|\[([^\]]+)\])"#;
+
 #[derive(Debug, Clone)]
 pub struct RepeatInterval {
     #[allow(dead_code)]
@@ -24,6 +112,95 @@ pub struct RepeatInterval {
     pub interval_type: String, // "day", "week", "month", etc.
 }
 
+// Building blocks of the date expressions; longer spellings first
+const WEEKDAYS: &str = "monday|tuesday|wednesday|thursday|friday|saturday|sunday|tues|thurs|thur|mon|tue|wed|thu|fri|sat|sun";
+const MONTHS: &str = "january|february|march|april|may|june|july|august|september|october|november|december|sept|jan|feb|mar|apr|jun|jul|aug|sep|oct|nov|dec";
+const ORDINALS: &str = "first|second|third|fourth|fifth|last|1st|2nd|3rd|4th|5th";
+const UNITS: &str = "minute|hour|day|week|month|year";
+
+/// Any single date expression the parser understands, optionally with "at <time>".
+/// Used to find the two ends of a range.
+fn date_phrase_pattern() -> String {
+    let alternatives = [
+        format!(r"(?:{o})\s+(?:{w})\s+(?:of|in)\s+(?:(?:this|next)\s+month|{m})", o = ORDINALS, w = WEEKDAYS, m = MONTHS),
+        r"today|tomorrow|yesterday|eod|eow|eom|eoy".to_string(),
+        r"end\s+of\s+(?:the\s+)?(?:day|week|month|year)".to_string(),
+        r"(?:this|next)\s+(?:weekend|week|month|year)".to_string(),
+        format!(r"(?:(?:this|next)\s+)?(?:{w})", w = WEEKDAYS),
+        format!(r"(?:{m})\s+\d{{1,2}}(?:st|nd|rd|th)?", m = MONTHS),
+        r"\d{4}-\d{1,2}-\d{1,2}(?:t\d{1,2}:\d{2})?".to_string(),
+        r"\d{1,2}[./-]\d{1,2}[./-](?:\d{4}|\d{2})".to_string(),
+        r"\d{1,2}(?:st|nd|rd|th)".to_string(),
+        format!(r"in\s+(?:\d+|an?)\s+(?:{u})s?", u = UNITS),
+    ];
+    format!(r"(?:{})(?:\s+at\s+\d{{1,2}}(?::\d{{2}})?\s*(?:am|pm)?)?", alternatives.join("|"))
+}
+
+/// Date expressions removed from titles - order matters, longer patterns first
+fn date_text_patterns() -> Vec<String> {
+    vec![
+        format!(r"(?i)\b({o})\s+({w})\s+(of|in)\s+((this|next)\s+month|{m})\b", o = ORDINALS, w = WEEKDAYS, m = MONTHS),
+        r"(?i)\blater\s+(this|next)\s+week\b".to_string(),
+        r"(?i)\b(eod|eow|eom|eoy)\b".to_string(),
+        r"(?i)\bend\s+of\s+(the\s+)?(day|week|month|year)\b".to_string(),
+        format!(r"(?i)\bin\s+(\d+|an?)\s+({u})s?\b", u = UNITS),
+        format!(r"(?i)\b(this|next)\s+({w})\b", w = WEEKDAYS),
+        r"(?i)\b(this|next|last)\s+(week|month|year|weekend)\b".to_string(),
+        r"(?i)\b(today|tomorrow|yesterday)\b".to_string(),
+        format!(r"(?i)\b({w})\b", w = WEEKDAYS),
+        format!(r"(?i)\b({m})\s+\d{{1,2}}(?:st|nd|rd|th)?\b", m = MONTHS),
+        r"(?i)\b\d{4}-\d{1,2}-\d{1,2}(t\d{1,2}:\d{2})?\b".to_string(),
+        r"\b\d{1,2}[./-]\d{1,2}[./-](\d{4}|\d{2})\b".to_string(),
+        r"\b\d{1,2}(?:st|nd|rd|th)\b".to_string(),
+    ]
+}
+
+/// Days from `from` forward to the next `to`, 0 when they are the same day
+fn days_until(from: Weekday, to: Weekday) -> i64 {
+    ((to.num_days_from_monday() + 7 - from.num_days_from_monday()) % 7) as i64
+}
+
+fn last_day_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1)
+        .and_then(|first| first.checked_add_months(Months::new(1)))
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(date)
+}
+
+/// Weekday from a lowercase name or abbreviation matched by `WEEKDAYS`
+fn weekday_from_name(name: &str) -> Option<Weekday> {
+    match name.get(..3)? {
+        "mon" => Some(Weekday::Mon),
+        "tue" => Some(Weekday::Tue),
+        "wed" => Some(Weekday::Wed),
+        "thu" => Some(Weekday::Thu),
+        "fri" => Some(Weekday::Fri),
+        "sat" => Some(Weekday::Sat),
+        "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Month number from a lowercase name or abbreviation matched by `MONTHS`
+fn month_from_name(name: &str) -> Option<u32> {
+    let month = match name.get(..3)? {
+        "jan" => 1,
+        "feb" => 2,
+        "mar" => 3,
+        "apr" => 4,
+        "may" => 5,
+        "jun" => 6,
+        "jul" => 7,
+        "aug" => 8,
+        "sep" => 9,
+        "oct" => 10,
+        "nov" => 11,
+        "dec" => 12,
+        _ => return None,
+    };
+    Some(month)
+}
+
 #[derive(Debug, Clone)]
 pub struct QuickAddParser {
     label_regex: Regex,
@@ -33,31 +210,38 @@ pub struct QuickAddParser {
     repeat_regex: Regex,
     due_regex: Regex,
     start_regex: Regex,
+    parent_regex: Regex,
+    relation_regex: Regex,
     // Enhanced date parsing
     time_regex: Regex,
-    date_keywords: AhoCorasick,
-    weekday_keywords: AhoCorasick,
+    range_regex: Regex,
+    iso_date_regex: Regex,
+    numeric_date_regex: Regex,
+    end_of_regex: Regex,
+    weekday_of_month_regex: Regex,
+    relative_regex: Regex,
+    duration_regex: Regex,
+    weekday_regex: Regex,
+    month_day_regex: Regex,
+    ordinal_regex: Regex,
+    /// Date expressions stripped from the title, longer ones first
+    date_text_regexes: Vec<Regex>,
+    /// Dialect, default times and time zone of the dates
+    settings: DateSettings,
 }
 
 impl QuickAddParser {
     pub fn new() -> Self {
-        // Date and time keywords for more sophisticated parsing
-        let date_patterns = vec![
-            "today", "tomorrow", "yesterday",
-            "next week", "this week", "last week",
-            "next month", "this month", "last month",
-            "next year", "this year", "last year",
-            "this weekend", "next weekend",
-            "later this week", "later next week",
-            "end of month", "end of week", "end of year",
-        ];
-        
-        let weekdays = vec![
-            "monday", "tuesday", "wednesday", "thursday", 
-            "friday", "saturday", "sunday",
-            "mon", "tue", "wed", "thu", "fri", "sat", "sun"
-        ];
+        Self::with_settings(DateSettings::default())
+    }
+
+    /// A parser reading numeric dates like 03/04/2025 in `dialect`
+    pub fn with_dialect(dialect: DateDialect) -> Self {
+        Self::with_settings(DateSettings { dialect, ..DateSettings::default() })
+    }
 
+    pub fn with_settings(settings: DateSettings) -> Self {
+        let date_phrase = date_phrase_pattern();
         Self {
             // Match labels: *label, *"label with spaces", *'label with spaces', or *[label with spaces]
             label_regex: Regex::new(r#"\*(?:"([^"]+)"|'([^']+)'|\[([^\]]+)\]|(\S+))"#).unwrap(),
@@ -71,15 +255,49 @@ impl QuickAddParser {
             repeat_regex: Regex::new(r"every\s+(?:(\d+)\s+)?(\w+)").unwrap(),
             due_regex: Regex::new(r"(?i)\bdue\s+([^@+*!]+)").unwrap(),
             start_regex: Regex::new(r"(?i)\bstart[:\s]+([^@+*!\s]+)").unwrap(),
+            // Match parent task: ^#123, ^"Parent title", ^'Parent title' or ^[Parent title]
+            parent_regex: Regex::new(&format!(r"\^{}", TASK_REF)).unwrap(),
+            // Match relations: blocks:#45, after:"Other task", related:[Other task]
+            relation_regex: Regex::new(&format!(r"(?i)\b(blocks|after|related):{}", TASK_REF)).unwrap(),
             // Match time: "at 17:00" or "at 5pm" with capture groups for hour, minute, am/pm
             time_regex: Regex::new(r"(?i)\bat\s+(\d{1,2})(?::(\d{2}))?\s*(am|pm)?\b").unwrap(),
-            // Keyword matchers for faster date detection
-            date_keywords: AhoCorasick::new(date_patterns).unwrap(),
-            weekday_keywords: AhoCorasick::new(weekdays).unwrap(),
+            // "from mon to wed", "from 2025-03-01 until 2025-03-05"
+            range_regex: Regex::new(&format!(r"(?i)\bfrom\s+({p})\s+(?:to|until|till|through)\s+({p})\b", p = date_phrase)).unwrap(),
+            // 2025-03-04, optionally with a time: 2025-03-04T10:30
+            iso_date_regex: Regex::new(r"(?i)\b(\d{4})-(\d{1,2})-(\d{1,2})(?:t(\d{1,2}):(\d{2}))?\b").unwrap(),
+            // 03/04/2025, 3.4.25, 03-04-2025; the dialect decides which number is the month
+            numeric_date_regex: Regex::new(r"\b(\d{1,2})[./-](\d{1,2})[./-](\d{4}|\d{2})\b").unwrap(),
+            end_of_regex: Regex::new(r"(?i)\b(?:eo([dwmy])|end\s+of\s+(?:the\s+)?(day|week|month|year))\b").unwrap(),
+            // "first monday of next month", "last friday in march"
+            weekday_of_month_regex: Regex::new(&format!(
+                r"(?i)\b({o})\s+({w})\s+(?:of|in)\s+(?:(this|next)\s+month|({m}))\b",
+                o = ORDINALS, w = WEEKDAYS, m = MONTHS
+            )).unwrap(),
+            relative_regex: Regex::new(r"(?i)\b(today|tomorrow|yesterday|(?:this|next)\s+(?:weekend|week|month|year))\b").unwrap(),
+            // "in 3 days", "in a week"
+            duration_regex: Regex::new(&format!(r"(?i)\bin\s+(\d+|an?)\s+({u})s?\b", u = UNITS)).unwrap(),
+            weekday_regex: Regex::new(&format!(r"(?i)\b(?:(this|next)\s+)?({w})\b", w = WEEKDAYS)).unwrap(),
+            // "Feb 17", "february 17th"
+            month_day_regex: Regex::new(&format!(r"(?i)\b({m})\s+(\d{{1,2}})(?:st|nd|rd|th)?\b", m = MONTHS)).unwrap(),
+            ordinal_regex: Regex::new(r"\b(\d{1,2})(?:st|nd|rd|th)\b").unwrap(),
+            date_text_regexes: date_text_patterns().iter().map(|p| Regex::new(p).unwrap()).collect(),
+            settings,
         }
     }
 
     pub fn parse(&self, text: &str) -> ParsedTask {
+        self.parse_at(text, Local::now())
+    }
+
+    /// Read a date expression on its own ("next monday", "in 3 days", "2025-03-04").
+    /// Dates without a time of day get `default_time`.
+    pub fn parse_date(&self, text: &str, default_time: NaiveTime) -> Option<DateTime<Utc>> {
+        self.parse_date_at(text, Utc::now(), default_time)
+    }
+
+    /// Parse with relative dates ("tomorrow", "in 3 days") counted from `now`
+    pub fn parse_at<Tz: TimeZone>(&self, text: &str, now: DateTime<Tz>) -> ParsedTask {
+        let now = now.with_timezone(&Utc);
         let mut task = ParsedTask {
             title: text.to_string(),
             labels: Vec::new(),
@@ -88,9 +306,30 @@ impl QuickAddParser {
             priority: None,
             due_date: None,
             start_date: None,
+            end_date: None,
             repeat_interval: None,
+            parent: None,
+            relations: Vec::new(),
         };
 
+        // Extract parent and relations to other tasks
+        if let Some(cap) = self.parent_regex.captures(text) {
+            task.parent = task_ref(&cap, 1);
+        }
+        for cap in self.relation_regex.captures_iter(text) {
+            let kind = match cap[1].to_lowercase().as_str() {
+                "blocks" => RelationKind::Blocking,
+                "after" => RelationKind::Follows,
+                _ => RelationKind::Related,
+            };
+            if let Some(target) = task_ref(&cap, 2) {
+                task.relations.push((kind, target));
+            }
+        }
+        // The rest of the syntax is looked for outside them; a quoted title may contain any of it
+        let without_relations = self.relation_regex.replace_all(&self.parent_regex.replace_all(text, ""), "").to_string();
+        let text = without_relations.as_str();
+
         // Extract labels
         for cap in self.label_regex.captures_iter(text) {
             let label = cap.get(1).or(cap.get(2)).or(cap.get(3)).or(cap.get(4)).unwrap().as_str();
@@ -120,89 +359,43 @@ impl QuickAddParser {
             task.repeat_interval = Some(RepeatInterval { amount, interval_type });
         }
 
+        // A range sets the start and end dates; the rest of the text is searched for a due date
+        let mut date_text = text.to_string();
+        if let Some(cap) = self.range_regex.captures(text) {
+            let start = self.parse_date_at(&cap[1], now, self.settings.start_time);
+            let mut end = self.parse_date_at(&cap[2], now, self.settings.due_time);
+            if let (Some(start), Some(end_date)) = (start, end) {
+                // "from fri to mon" ends on the monday after that friday
+                if end_date < start && self.weekday_regex.is_match(&cap[2]) {
+                    end = Some(end_date + Duration::weeks(1));
+                }
+            }
+            task.start_date = start;
+            task.end_date = end;
+            date_text = self.range_regex.replace(text, " ").to_string();
+        }
+
         // Parse explicit start and due dates
         let mut last_start_cap = None;
         let mut start_matches = vec![];
         for cap in self.start_regex.captures_iter(text) {
             if let Some(m) = cap.get(1) {
-                println!("[DEBUG] start_regex match: {:?}", m.as_str());
                 start_matches.push(m.as_str().to_string());
             }
             last_start_cap = Some(cap);
         }
-        println!("[DEBUG] all start_regex matches: {:?}", start_matches);
+        if !start_matches.is_empty() {
+            debug_log(&format!("start_regex matches: {:?}", start_matches));
+        }
         if let Some(cap) = last_start_cap {
-            let start_text = cap.get(1).unwrap().as_str();
-            // Support start:eow, start:end of week, etc.
-            let start_text_lower = start_text.to_lowercase();
-            if start_text_lower == "eow" || start_text_lower == "end of week" {
-                // End of this week (Sunday)
-                let now = Local::now();
-                let current_weekday = now.weekday().num_days_from_sunday();
-                let days_until_sunday = if current_weekday == 0 { 0 } else { 7 - current_weekday };
-                let sunday = now + Duration::days(days_until_sunday as i64);
-                let naive = sunday.date_naive();
-                task.start_date = naive.and_hms_opt(23, 59, 59)
-                    .map(|dt| dt.and_utc())
-                    .or_else(|| naive.and_hms_opt(0, 0, 0).map(|dt| dt.and_utc()))
-                    .or_else(|| naive.and_hms_opt(12, 0, 0).map(|dt| dt.and_utc()));
-                if task.start_date.is_none() {
-                    println!("[DEBUG] now = {:?}", now);
-                    let current_weekday = now.weekday().num_days_from_sunday();
-                    println!("[DEBUG] current_weekday = {:?}", current_weekday);
-                    let days_until_sunday = if current_weekday == 0 { 0 } else { 7 - current_weekday };
-                    println!("[DEBUG] days_until_sunday = {:?}", days_until_sunday);
-                    let sunday = now + Duration::days(days_until_sunday as i64);
-                    println!("[DEBUG] sunday = {:?}", sunday);
-                    let naive = sunday.date_naive();
-                    println!("[DEBUG] sunday.date_naive() = {:?}", naive);
-                    let try_2359 = naive.and_hms_opt(23, 59, 59);
-                    println!("[DEBUG] naive.and_hms_opt(23,59,59) = {:?}", try_2359);
-                    let try_midnight = naive.and_hms_opt(0, 0, 0);
-                    println!("[DEBUG] naive.and_hms_opt(0,0,0) = {:?}", try_midnight);
-                    let try_noon = naive.and_hms_opt(12, 0, 0);
-                    println!("[DEBUG] naive.and_hms_opt(12,0,0) = {:?}", try_noon);
-                    let today = Local::now().date_naive();
-                    println!("[DEBUG] today = {:?}", today);
-                    let fallback = today.and_hms_opt(0, 0, 0).map(|dt| dt.and_utc());
-                    println!("[DEBUG] fallback = {:?}", fallback);
-                    task.start_date = fallback;
-                }
-            } else if start_text_lower == "eom" || start_text_lower == "end of month" {
-                let now = Local::now();
-                let mut last_day = now.date_naive();
-                last_day = last_day.with_day(1).unwrap();
-                last_day = last_day + Duration::days(32); // Move to next month
-                last_day = last_day.with_day(1).unwrap();
-                last_day = last_day - Duration::days(1); // Go back to last day of current month
-                task.start_date = last_day.and_hms_opt(23, 59, 59)
-                    .map(|dt| dt.and_utc())
-                    .or_else(|| last_day.and_hms_opt(0, 0, 0).map(|dt| dt.and_utc()))
-                    .or_else(|| last_day.and_hms_opt(12, 0, 0).map(|dt| dt.and_utc()));
-                if task.start_date.is_none() {
-                    // Debug output for start:eom fallback logic
-                    println!("[DEBUG] Failed to construct start:eom date for last_day {:?}", last_day);
-                    let try_2359 = last_day.and_hms_opt(23, 59, 59);
-                    println!("[DEBUG] try_2359 = {:?}", try_2359);
-                    let try_midnight = last_day.and_hms_opt(0, 0, 0);
-                    println!("[DEBUG] try_midnight = {:?}", try_midnight);
-                    let try_noon = last_day.and_hms_opt(12, 0, 0);
-                    println!("[DEBUG] try_noon = {:?}", try_noon);
-                    let today = Local::now().date_naive();
-                    println!("[DEBUG] today = {:?}", today);
-                    let fallback = today.and_hms_opt(0, 0, 0).map(|dt| dt.and_utc());
-                    println!("[DEBUG] fallback = {:?}", fallback);
-                    task.start_date = fallback;
-                }
-            } else {
-                task.start_date = self.parse_date(start_text);
-            }
+            // start:eow, start:eom and start:tomorrow all go through the date grammar
+            task.start_date = self.parse_date_at(cap.get(1).unwrap().as_str(), now, self.settings.start_time).or(task.start_date);
         }
-        if let Some(cap) = self.due_regex.captures(text) {
-            task.due_date = self.parse_date(cap.get(1).unwrap().as_str());
+        if let Some(cap) = self.due_regex.captures(&date_text) {
+            task.due_date = self.parse_date_at(cap.get(1).unwrap().as_str(), now, self.settings.due_time);
         } else {
             // fallback to any date mention
-            task.due_date = self.parse_date(text);
+            task.due_date = self.parse_date_at(&date_text, now, self.settings.due_time);
         }
 
         // Clean the title by removing all magic syntax
@@ -213,231 +406,190 @@ impl QuickAddParser {
         task
     }
 
-    fn parse_date(&self, text: &str) -> Option<DateTime<Utc>> {
-        let text_lower = text.to_lowercase();
-        let now = Local::now();
-
-        // First, try using chrono-english for natural language parsing
-        if let Ok(parsed_date) = parse_date_string(&text, now.into(), Dialect::Us) {
-            return Some(parsed_date);
+    /// Find a date expression in `text`. Dates without a time of day get `default_time`,
+    /// end-of phrases the end of the workday; both in the configured time zone.
+    fn parse_date_at(&self, text: &str, now: DateTime<Utc>, default_time: NaiveTime) -> Option<DateTime<Utc>> {
+        let text = text.to_lowercase();
+        let today = self.settings.local(now).date();
+        let time = self.extract_time(&text);
+        let at = |date: NaiveDate, fallback: NaiveTime| self.settings.at(date, time.unwrap_or(fallback));
+
+        // Numeric dates first, so the dialect decides what 03/04/2025 means
+        if let Some(cap) = self.iso_date_regex.captures(&text) {
+            let date = NaiveDate::from_ymd_opt(cap[1].parse().ok()?, cap[2].parse().ok()?, cap[3].parse().ok()?);
+            let iso_time = match (cap.get(4), cap.get(5)) {
+                (Some(hour), Some(minute)) => Some(NaiveTime::from_hms_opt(hour.as_str().parse().ok()?, minute.as_str().parse().ok()?, 0)?),
+                _ => None,
+            };
+            if let Some(date) = date {
+                return self.settings.at(date, iso_time.or(time).unwrap_or(default_time));
+            }
         }
-
-        // Enhanced date parsing with better keyword matching
-        if self.date_keywords.find(&text_lower).is_some() {
-            return self.parse_date_keywords(&text_lower, now);
+        if let Some(date) = self.parse_numeric_date(&text) {
+            return at(date, default_time);
         }
 
-        // Check for weekday mentions
-        if self.weekday_keywords.find(&text_lower).is_some() {
-            return self.parse_weekday(&text_lower, now);
+        // Longer phrases before the words they contain ("first monday of next month")
+        if let Some(date) = self.parse_end_of(&text, today) {
+            return at(date, self.settings.workday_end);
         }
-
-        // Check for "in X days/weeks/months" pattern
-        if let Some(duration_date) = self.parse_duration_date(&text_lower, now) {
-            return Some(duration_date);
+        let date = self.parse_weekday_of_month(&text, today)
+            .or_else(|| self.parse_relative(&text, today));
+        if let Some(date) = date {
+            return at(date, default_time);
+        }
+        if let Some(cap) = self.duration_regex.captures(&text) {
+            return self.parse_duration(&cap, now, time.unwrap_or(default_time));
+        }
+        let date = self.parse_weekday(&text, today)
+            .or_else(|| self.parse_month_day(&text, today))
+            .or_else(|| self.parse_ordinal_day(&text, today));
+        if let Some(date) = date {
+            return at(date, default_time);
         }
 
-        // Check for ordinal dates (17th, 23rd, etc.)
-        if let Some(ordinal_date) = self.parse_ordinal_date(&text_lower, now) {
-            return Some(ordinal_date);
+        // Anything else chrono-english understands. It slices words by byte and
+        // panics on other scripts, so it only gets ASCII text.
+        if !text.is_ascii() {
+            return None;
         }
+        parse_date_string(&text, self.settings.zoned(now), self.settings.dialect.into()).ok().map(|date| date.with_timezone(&Utc))
+    }
 
-        // Try specific date formats (DD/MM/YYYY, MM/DD/YYYY, etc.)
-        self.parse_specific_date(text)
+    /// 03/04/2025, 3.4.25 or 03-04-2025 in the configured dialect. When that order
+    /// can't be a date (15/03/2025 read month first) the other order is used.
+    fn parse_numeric_date(&self, text: &str) -> Option<NaiveDate> {
+        let cap = self.numeric_date_regex.captures(text)?;
+        let first: u32 = cap[1].parse().ok()?;
+        let second: u32 = cap[2].parse().ok()?;
+        let year: i32 = cap[3].parse().ok()?;
+        let year = if cap[3].len() == 2 { 2000 + year } else { year };
+        let (month, day) = match self.settings.dialect {
+            DateDialect::Us => (first, second),
+            DateDialect::Uk => (second, first),
+        };
+        NaiveDate::from_ymd_opt(year, month, day).or_else(|| NaiveDate::from_ymd_opt(year, day, month))
     }
 
-    fn parse_date_keywords(&self, text: &str, now: chrono::DateTime<Local>) -> Option<DateTime<Utc>> {
-        // Extract time if present
-        let target_time = self.extract_time(text).unwrap_or((23, 59));
-        
-        if text.contains("today") {
-            Some(now.date_naive().and_hms_opt(target_time.0, target_time.1, 59)?.and_utc())
-        } else if text.contains("tomorrow") {
-            Some((now + Duration::days(1)).date_naive().and_hms_opt(target_time.0, target_time.1, 59)?.and_utc())
-        } else if text.contains("yesterday") {
-            Some((now - Duration::days(1)).date_naive().and_hms_opt(target_time.0, target_time.1, 59)?.and_utc())
-        } else if text.contains("this weekend") {
-            // Next Saturday
-            let days_until_saturday = (6 - now.weekday().num_days_from_monday()) % 7;
-            let saturday = now + Duration::days(days_until_saturday as i64);
-            Some(saturday.date_naive().and_hms_opt(target_time.0, target_time.1, 59)?.and_utc())
-        } else if text.contains("next weekend") {
-            let days_until_next_saturday = 7 + (6 - now.weekday().num_days_from_monday()) % 7;
-            let next_saturday = now + Duration::days(days_until_next_saturday as i64);
-            Some(next_saturday.date_naive().and_hms_opt(target_time.0, target_time.1, 59)?.and_utc())
-        } else if text.contains("next week") {
-            Some((now + Duration::weeks(1)).date_naive().and_hms_opt(target_time.0, target_time.1, 59)?.and_utc())
-        } else if text.contains("this week") {
-            // End of this week (Sunday)
-            let days_until_sunday = (7 - now.weekday().num_days_from_monday()) % 7;
-            let sunday = now + Duration::days(days_until_sunday as i64);
-            Some(sunday.date_naive().and_hms_opt(target_time.0, target_time.1, 59)?.and_utc())
-        } else if text.contains("next month") {
-            // Approximate - add 30 days
-            Some((now + Duration::days(30)).date_naive().and_hms_opt(target_time.0, target_time.1, 59)?.and_utc())
-        } else if text.contains("end of month") {
-            // Last day of current month
-            let mut last_day = now.date_naive();
-            last_day = last_day.with_day(1).unwrap();
-            last_day = last_day + Duration::days(32); // Move to next month
-            last_day = last_day.with_day(1).unwrap();
-            last_day = last_day - Duration::days(1); // Go back to last day of current month
-            Some(last_day.and_hms_opt(target_time.0, target_time.1, 59)?.and_utc())
-        } else {
-            None
+    /// eod, eow (sunday), eom, eoy and their "end of ..." spellings
+    fn parse_end_of(&self, text: &str, today: NaiveDate) -> Option<NaiveDate> {
+        let cap = self.end_of_regex.captures(text)?;
+        let unit = cap.get(1).or(cap.get(2))?.as_str();
+        match unit.get(..1)? {
+            "d" => Some(today),
+            "w" => Some(today + Duration::days(days_until(today.weekday(), Weekday::Sun))),
+            "m" => Some(last_day_of_month(today)),
+            _ => NaiveDate::from_ymd_opt(today.year(), 12, 31),
         }
     }
 
-    fn parse_weekday(&self, text: &str, now: chrono::DateTime<Local>) -> Option<DateTime<Utc>> {
-        let target_time = self.extract_time(text).unwrap_or((23, 59));
-        
-        let weekdays = [
-            ("monday", 0), ("mon", 0),
-            ("tuesday", 1), ("tue", 1),
-            ("wednesday", 2), ("wed", 2),
-            ("thursday", 3), ("thu", 3),
-            ("friday", 4), ("fri", 4),
-            ("saturday", 5), ("sat", 5),
-            ("sunday", 6), ("sun", 6),
-        ];
-
-        for (day_name, target_weekday) in &weekdays {
-            if text.contains(day_name) {
-                let current_weekday = now.weekday().num_days_from_monday();
-                let days_ahead = if *target_weekday >= current_weekday {
-                    *target_weekday - current_weekday
-                } else {
-                    7 - current_weekday + *target_weekday
-                };
-                
-                let target_date = now + Duration::days(days_ahead as i64);
-                return Some(target_date.date_naive().and_hms_opt(target_time.0, target_time.1, 59)?.and_utc());
+    /// "first monday of next month", "last friday in march"
+    fn parse_weekday_of_month(&self, text: &str, today: NaiveDate) -> Option<NaiveDate> {
+        let cap = self.weekday_of_month_regex.captures(text)?;
+        let weekday = weekday_from_name(&cap[2])?;
+        let first_of_month = match (cap.get(3), cap.get(4)) {
+            (Some(which), _) => {
+                let first = today.with_day(1)?;
+                if which.as_str() == "next" { first.checked_add_months(Months::new(1))? } else { first }
             }
+            (None, Some(month)) => {
+                let month = month_from_name(month.as_str())?;
+                // A month that is over for this year means next year's
+                let year = if month < today.month() { today.year() + 1 } else { today.year() };
+                NaiveDate::from_ymd_opt(year, month, 1)?
+            }
+            (None, None) => return None,
+        };
+        if &cap[1] == "last" {
+            let last = last_day_of_month(first_of_month);
+            return Some(last - Duration::days(days_until(weekday, last.weekday())));
         }
-        
-        None
+        let nth = match &cap[1] {
+            "first" | "1st" => 0,
+            "second" | "2nd" => 1,
+            "third" | "3rd" => 2,
+            "fourth" | "4th" => 3,
+            _ => 4,
+        };
+        let date = first_of_month + Duration::days(days_until(first_of_month.weekday(), weekday) + 7 * nth);
+        (date.month() == first_of_month.month()).then_some(date)
     }
 
-    fn parse_duration_date(&self, text: &str, now: chrono::DateTime<Local>) -> Option<DateTime<Utc>> {
-        let duration_regex = Regex::new(r"in\s+(\d+)\s+(day|week|month|hour)s?").unwrap();
-        
-        if let Some(cap) = duration_regex.captures(text) {
-            let amount: i64 = cap[1].parse().ok()?;
-            let unit = &cap[2];
-            let target_time = self.extract_time(text).unwrap_or((23, 59));
-            
-            let target_date = match unit {
-                "hour" => now + Duration::hours(amount),
-                "day" => now + Duration::days(amount),
-                "week" => now + Duration::weeks(amount),
-                "month" => now + Duration::days(amount * 30), // Approximate
-                _ => return None,
-            };
-            
-            if unit == "hour" {
-                Some(target_date.with_timezone(&Utc))
-            } else {
-                Some(target_date.date_naive().and_hms_opt(target_time.0, target_time.1, 59)?.and_utc())
-            }
-        } else {
-            None
+    fn parse_relative(&self, text: &str, today: NaiveDate) -> Option<NaiveDate> {
+        let cap = self.relative_regex.captures(text)?;
+        let phrase = cap[1].split_whitespace().collect::<Vec<_>>().join(" ");
+        match phrase.as_str() {
+            "today" => Some(today),
+            "tomorrow" => Some(today + Duration::days(1)),
+            "yesterday" => Some(today - Duration::days(1)),
+            // The coming saturday
+            "this weekend" => Some(today + Duration::days(days_until(today.weekday(), Weekday::Sat))),
+            "next weekend" => Some(today + Duration::days(7 + days_until(today.weekday(), Weekday::Sat))),
+            // End of this week (Sunday)
+            "this week" => Some(today + Duration::days(days_until(today.weekday(), Weekday::Sun))),
+            "next week" => Some(today + Duration::weeks(1)),
+            "this month" => Some(last_day_of_month(today)),
+            "next month" => today.checked_add_months(Months::new(1)),
+            "this year" => NaiveDate::from_ymd_opt(today.year(), 12, 31),
+            "next year" => today.checked_add_months(Months::new(12)),
+            _ => None,
         }
     }
 
-    fn parse_ordinal_date(&self, text: &str, now: chrono::DateTime<Local>) -> Option<DateTime<Utc>> {
-        let ordinal_regex = Regex::new(r"(\d{1,2})(?:st|nd|rd|th)").unwrap();
-        
-        if let Some(cap) = ordinal_regex.captures(text) {
-            let day: u32 = cap[1].parse().ok()?;
-            let target_time = self.extract_time(text).unwrap_or((23, 59));
-            
-            // Use current month and year
-            let target_date = now.date_naive().with_day(day)?;
-            Some(target_date.and_hms_opt(target_time.0, target_time.1, 59)?.and_utc())
-        } else {
-            None
+    /// "in 3 days", "in a week". Minutes and hours count from now, the rest keep `time`.
+    fn parse_duration(&self, cap: &regex::Captures, now: DateTime<Utc>, time: NaiveTime) -> Option<DateTime<Utc>> {
+        let amount: u32 = match &cap[1] {
+            "a" | "an" => 1,
+            n => n.parse().ok()?,
+        };
+        let today = self.settings.local(now).date();
+        let date = match &cap[2] {
+            "minute" => return now.checked_add_signed(Duration::try_minutes(amount as i64)?),
+            "hour" => return now.checked_add_signed(Duration::try_hours(amount as i64)?),
+            "day" => today.checked_add_signed(Duration::try_days(amount as i64)?)?,
+            "week" => today.checked_add_signed(Duration::try_weeks(amount as i64)?)?,
+            "month" => today.checked_add_months(Months::new(amount))?,
+            _ => today.checked_add_months(Months::new(amount.checked_mul(12)?))?,
+        };
+        self.settings.at(date, time)
+    }
+
+    /// "friday" is the coming friday, today included; "next friday" the first one after today
+    fn parse_weekday(&self, text: &str, today: NaiveDate) -> Option<NaiveDate> {
+        let cap = self.weekday_regex.captures(text)?;
+        let weekday = weekday_from_name(&cap[2])?;
+        let mut days = days_until(today.weekday(), weekday);
+        if days == 0 && cap.get(1).is_some_and(|m| m.as_str() == "next") {
+            days = 7;
         }
+        Some(today + Duration::days(days))
+    }
+
+    /// "Feb 17", "february 17th" in the current year
+    fn parse_month_day(&self, text: &str, today: NaiveDate) -> Option<NaiveDate> {
+        let cap = self.month_day_regex.captures(text)?;
+        NaiveDate::from_ymd_opt(today.year(), month_from_name(&cap[1])?, cap[2].parse().ok()?)
     }
 
-    fn extract_time(&self, text: &str) -> Option<(u32, u32)> {
+    /// "15th" in the current month
+    fn parse_ordinal_day(&self, text: &str, today: NaiveDate) -> Option<NaiveDate> {
+        let cap = self.ordinal_regex.captures(text)?;
+        today.with_day(cap[1].parse().ok()?)
+    }
+
+    fn extract_time(&self, text: &str) -> Option<NaiveTime> {
         if let Some(cap) = self.time_regex.captures(text) {
             let hour: u32 = cap[1].parse().ok()?;
             let minute: u32 = cap.get(2).map(|m| m.as_str().parse().unwrap_or(0)).unwrap_or(0);
             let am_pm = cap.get(3).map(|m| m.as_str().to_lowercase());
-            
+
             let adjusted_hour = match am_pm.as_deref() {
                 Some("pm") if hour != 12 => hour + 12,
                 Some("am") if hour == 12 => 0,
                 _ => hour,
             };
-            
-            if adjusted_hour < 24 && minute < 60 {
-                Some((adjusted_hour, minute))
-            } else {
-                None
-            }
-        } else {
-            None
-        }
-    }
-
-    fn parse_specific_date(&self, text: &str) -> Option<DateTime<Utc>> {
-        // Enhanced date format parsing
-        let target_time = self.extract_time(text).unwrap_or((23, 59));
-
-        // Try DD/MM/YYYY format
-        if let Some(caps) = Regex::new(r"(\d{1,2})/(\d{1,2})/(\d{4})").unwrap().captures(text) {
-            let day: u32 = caps[1].parse().ok()?;
-            let month: u32 = caps[2].parse().ok()?;
-            let year: i32 = caps[3].parse().ok()?;
-            if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
-                return Some(date.and_hms_opt(target_time.0, target_time.1, 59)?.and_utc());
-            }
-        }
-
-        // Try YYYY-MM-DD (ISO format)
-        if let Some(caps) = Regex::new(r"(\d{4})-(\d{1,2})-(\d{1,2})").unwrap().captures(text) {
-            let year: i32 = caps[1].parse().ok()?;
-            let month: u32 = caps[2].parse().ok()?;
-            let day: u32 = caps[3].parse().ok()?;
-            if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
-                return Some(date.and_hms_opt(target_time.0, target_time.1, 59)?.and_utc());
-            }
-        }
-
-        // Try month name formats (Feb 17, February 17th, etc.)
-        self.parse_month_name_date(text)
-    }
-
-    fn parse_month_name_date(&self, text: &str) -> Option<DateTime<Utc>> {
-        let month_regex = Regex::new(
-            r"(?i)(jan|january|feb|february|mar|march|apr|april|may|jun|june|jul|july|aug|august|sep|september|oct|october|nov|november|dec|december)\s+(\d{1,2})(?:st|nd|rd|th)?"
-        ).unwrap();
-
-        if let Some(caps) = month_regex.captures(text) {
-            let month_str = caps[1].to_lowercase();
-            let day: u32 = caps[2].parse().ok()?;
-            let target_time = self.extract_time(text).unwrap_or((23, 59));
-
-            let month_num = match month_str.as_str() {
-                "jan" | "january" => 1,
-                "feb" | "february" => 2,
-                "mar" | "march" => 3,
-                "apr" | "april" => 4,
-                "may" => 5,
-                "jun" | "june" => 6,
-                "jul" | "july" => 7,
-                "aug" | "august" => 8,
-                "sep" | "september" => 9,
-                "oct" | "october" => 10,
-                "nov" | "november" => 11,
-                "dec" | "december" => 12,
-                _ => return None,
-            };
 
-            let current_year = Local::now().year();
-            let date = NaiveDate::from_ymd_opt(current_year, month_num, day)?;
-            Some(date.and_hms_opt(target_time.0, target_time.1, 59)?.and_utc())
+            NaiveTime::from_hms_opt(adjusted_hour, minute, 0)
         } else {
             None
         }
@@ -453,9 +605,10 @@ impl QuickAddParser {
         cleaned = self.project_regex.replace_all(&cleaned, "").to_string();
         cleaned = self.repeat_regex.replace_all(&cleaned, "").to_string();
 
-        // Remove explicit due and start date fragments
+        // Remove explicit due and start date fragments, and date ranges
         cleaned = self.due_regex.replace_all(&cleaned, "").to_string();
         cleaned = self.start_regex.replace_all(&cleaned, "").to_string();
+        cleaned = self.range_regex.replace_all(&cleaned, "").to_string();
 
         // Strip general date expressions (relative dates, weekdays, month names, ordinals, etc.)
         cleaned = self.remove_date_text(&cleaned);
@@ -469,33 +622,18 @@ impl QuickAddParser {
 
     fn remove_date_text(&self, text: &str) -> String {
         let mut cleaned = text.to_string();
-        
-        // Remove date keywords - order matters, longer patterns first
-        let date_patterns = [
-            r"(?i)\blater\s+(this|next)\s+week\b",
-            r"(?i)\bend\s+of\s+(week|month|year)\b",
-            r"(?i)\bin\s+\d+\s+(day|week|month|hour)s?\b",
-            r"(?i)\bnext\s+(monday|tuesday|wednesday|thursday|friday|saturday|sunday)\b",
-            r"(?i)\b(this|next|last)\s+(week|month|year|weekend)\b", 
-            r"(?i)\b(today|tomorrow|yesterday)\b",
-            r"(?i)\b(monday|tuesday|wednesday|thursday|friday|saturday|sunday)\b",
-            r"(?i)\b(mon|tue|wed|thu|fri|sat|sun)\b",
-            r"(?i)\b(jan|january|feb|february|mar|march|apr|april|may|jun|june|jul|july|aug|august|sep|september|oct|october|nov|november|dec|december)\s+\d{1,2}(?:st|nd|rd|th)?\b",
-            r"\b\d{1,2}/\d{1,2}/\d{4}\b",
-            r"\b\d{4}-\d{1,2}-\d{1,2}\b",
-            r"\b\d{1,2}(?:st|nd|rd|th)\b",
-        ];
-
-        for pattern in &date_patterns {
-            let regex = Regex::new(pattern).unwrap();
+        for regex in &self.date_text_regexes {
             cleaned = regex.replace_all(&cleaned, "").to_string();
         }
-
         cleaned
     }
 }
 
 #[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
     #[test]
     fn test_start_eow_and_tomorrow() {
         let parser = QuickAddParser::new();
@@ -509,8 +647,6 @@ impl QuickAddParser {
         println!("[TEST DEBUG] task_tomorrow = {:?}", task_tomorrow);
         assert!(task_tomorrow.start_date.is_some());
     }
-mod tests {
-    use super::*;
 
     #[test]
     fn test_parse_task_with_magic() {
@@ -612,4 +748,136 @@ mod tests {
         assert_eq!(task.labels, vec!["important"]);
         // Note: without a date, time extraction alone may not create a due_date
     }
+
+    /// Wednesday, 12 March 2025
+    fn wednesday() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 3, 12, 10, 0, 0).unwrap()
+    }
+
+    fn day(date: Option<DateTime<Utc>>) -> String {
+        date.map(|d| d.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_relative_and_weekday_phrases() {
+        let parser = QuickAddParser::new();
+        let now = wednesday();
+        let cases = [
+            ("Follow up in 3 days", "2025-03-15 23:59"),
+            ("Renew in 2 weeks", "2025-03-26 23:59"),
+            ("Check in a month", "2025-04-12 23:59"),
+            ("Call mom next friday at 5pm", "2025-03-14 17:00"),
+            ("Standup next wed", "2025-03-19 23:59"),
+            ("Standup wednesday", "2025-03-12 23:59"),
+            ("Ship it eod", "2025-03-12 23:59"),
+            ("Report eow", "2025-03-16 23:59"),
+            ("Invoice end of month", "2025-03-31 23:59"),
+            ("Team lunch first monday of next month", "2025-04-07 23:59"),
+            ("Retro last friday in march", "2025-03-28 23:59"),
+            ("Taxes second tuesday of april at 9am", "2025-04-08 09:00"),
+        ];
+        for (input, expected) in cases {
+            let task = parser.parse_at(input, now);
+            assert_eq!(day(task.due_date), expected, "{}", input);
+        }
+        let task = parser.parse_at("Team lunch first monday of next month *social", now);
+        assert_eq!(task.title, "Team lunch");
+        assert_eq!(parser.parse_at("Call mom next friday at 5pm", now).title, "Call mom");
+    }
+
+    #[test]
+    fn test_numeric_dates_follow_the_dialect() {
+        let us = QuickAddParser::new();
+        let uk = QuickAddParser::with_dialect(DateDialect::Uk);
+        let now = wednesday();
+        assert_eq!(day(us.parse_at("Dentist 03/04/2025", now).due_date), "2025-03-04 23:59");
+        assert_eq!(day(uk.parse_at("Dentist 03/04/2025", now).due_date), "2025-04-03 23:59");
+        assert_eq!(day(uk.parse_at("Dentist 3.4.25 at 14:30", now).due_date), "2025-04-03 14:30");
+        // Day first is the only reading of 15/03
+        assert_eq!(day(us.parse_at("Dentist 15/03/2025", now).due_date), "2025-03-15 23:59");
+        assert_eq!(day(uk.parse_at("Deploy 2025-04-01T09:30", now).due_date), "2025-04-01 09:30");
+        assert_eq!(uk.parse_at("Dentist 03-04-2025 !2", now).title, "Dentist");
+    }
+
+    #[test]
+    fn test_date_ranges_set_start_and_end() {
+        let parser = QuickAddParser::new();
+        let task = parser.parse_at("Conference from mon to wed *travel", wednesday());
+        assert_eq!(task.title, "Conference");
+        assert_eq!(task.labels, vec!["travel"]);
+        assert_eq!(day(task.start_date), "2025-03-17 00:00");
+        assert_eq!(day(task.end_date), "2025-03-19 23:59");
+        assert!(task.due_date.is_none());
+
+        // From a saturday, "fri to mon" runs over the following weekend
+        let saturday = Local.with_ymd_and_hms(2025, 3, 15, 10, 0, 0).unwrap();
+        let task = parser.parse_at("Trip from fri to mon due tomorrow", saturday);
+        assert_eq!(day(task.start_date), "2025-03-21 00:00");
+        assert_eq!(day(task.end_date), "2025-03-24 23:59");
+        assert_eq!(day(task.due_date), "2025-03-16 23:59");
+        assert_eq!(task.title, "Trip");
+
+        let task = parser.parse_at("Email from Bob about the offsite", wednesday());
+        assert_eq!(task.title, "Email from Bob about the offsite");
+        assert!(task.start_date.is_none() && task.end_date.is_none());
+    }
+
+    #[test]
+    fn test_default_times_and_time_zone() {
+        let settings = DateSettings {
+            due_time: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+            start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            workday_end: NaiveTime::from_hms_opt(18, 30, 0).unwrap(),
+            timezone: Some(chrono_tz::America::New_York),
+            ..DateSettings::default()
+        };
+        let parser = QuickAddParser::with_settings(settings);
+        // Late Wednesday evening in New York is already Thursday in UTC
+        let now = Utc.with_ymd_and_hms(2025, 3, 13, 2, 0, 0).unwrap();
+        let utc = |date: Option<DateTime<Utc>>| date.map(|d| d.to_rfc3339()).unwrap_or_default();
+
+        assert_eq!(utc(parser.parse_at("Report tomorrow", now).due_date), "2025-03-13T21:00:00+00:00");
+        assert_eq!(utc(parser.parse_at("Report tomorrow at 8am", now).due_date), "2025-03-13T12:00:00+00:00");
+        assert_eq!(utc(parser.parse_at("Report eod", now).due_date), "2025-03-12T22:30:00+00:00");
+        assert_eq!(utc(parser.parse_at("Report start:fri", now).start_date), "2025-03-14T13:00:00+00:00");
+        assert_eq!(utc(parser.parse_at("Report 2025-03-20", now).due_date), "2025-03-20T21:00:00+00:00");
+    }
+
+    #[test]
+    fn test_parent_and_relations() {
+        let parser = QuickAddParser::new();
+        let task = parser.parse(r#"Book venue ^"Plan +offsite *q3" blocks:#45 after:#46 related:[Budget review] *events"#);
+        assert_eq!(task.title, "Book venue");
+        assert_eq!(task.parent, Some(TaskRef::Title("Plan +offsite *q3".to_string())));
+        assert_eq!(task.relations, vec![
+            (RelationKind::Blocking, TaskRef::Id(45)),
+            (RelationKind::Follows, TaskRef::Id(46)),
+            (RelationKind::Related, TaskRef::Title("Budget review".to_string())),
+        ]);
+        assert_eq!(task.labels, vec!["events"]);
+        assert_eq!(task.project, None);
+
+        let task = parser.parse("Write tests ^#123 !2");
+        assert_eq!(task.parent, Some(TaskRef::Id(123)));
+        assert_eq!(task.title, "Write tests");
+        assert!(parser.parse("Compute 2^10 after lunch").parent.is_none());
+    }
+
+    #[test]
+    fn test_parse_outline() {
+        let outline = "Launch +website\n  - Write copy *writing\n  - Design\n\t1. Logo\n\t2) [ ] Colors\n    \n- Announce tomorrow\nRetro";
+        let entries = parse_outline(outline);
+        let summary: Vec<(&str, Option<usize>)> = entries.iter().map(|e| (e.text.as_str(), e.parent)).collect();
+        assert_eq!(summary, vec![
+            ("Launch +website", None),
+            ("Write copy *writing", Some(0)),
+            ("Design", Some(0)),
+            ("Logo", Some(2)),
+            ("Colors", Some(2)),
+            ("Announce tomorrow", None),
+            ("Retro", None),
+        ]);
+        // A "*" that isn't followed by a space is a label, not a bullet
+        assert_eq!(parse_outline("*home Fix sink")[0].text, "*home Fix sink");
+    }
 }
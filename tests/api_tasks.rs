@@ -28,6 +28,7 @@ async fn test_create_and_delete_task() {
         assignees: None,
         start_date: None,
         is_favorite: Some(false),
+        ..Default::default()
     };
     let created = client.create_task(&task).await.expect("create_task failed");
     assert_eq!(created.title, task.title);
@@ -5,7 +5,7 @@ use cria::config::CriaConfig;
 use cria::tui::app::state::App;
 use cria::tui::app::sort_order::SortOrder;
 use cria::tui::app::task_filter::TaskFilter;
-use cria::vikunja::models::{Task, Label};
+use cria::vikunja::models::{Task, Label, User};
 use chrono::{NaiveDate, TimeZone, Utc};
 use cria::tui::app::pending_action::PendingAction;
 
@@ -166,6 +166,37 @@ fn test_suggestions() {
     assert!(app.suggestions.is_empty());
 }
 
+#[test]
+fn test_tab_completion_cycles_and_learns_usage() {
+    let mut app = App::new_with_config(CriaConfig::default(), "Inbox".to_string());
+    app.label_map.insert(1, "Shopping".to_string());
+    app.label_map.insert(2, "Someday".to_string());
+    app.remember_users(vec![User { id: 7, username: "jonas".to_string(), name: None, email: None, created: None, updated: None }]);
+
+    app.update_suggestions("Buy milk *s", 11);
+    assert_eq!(app.suggestions, vec!["Shopping", "Someday"]);
+    let (input, cursor) = app.tab_complete("Buy milk *s", 11).unwrap();
+    assert_eq!((input.as_str(), cursor), ("Buy milk *Shopping ", 19));
+    let (input, cursor) = app.tab_complete(&input, cursor).unwrap();
+    assert_eq!((input.as_str(), cursor), ("Buy milk *Someday ", 18));
+    let (input, _) = app.tab_complete(&input, cursor).unwrap();
+    assert_eq!(input, "Buy milk *Shopping ");
+
+    // Labels used before are suggested first
+    app.record_suggestion_usage("Plan trip *someday", None);
+    app.update_suggestions("*s", 2);
+    assert_eq!(app.suggestions, vec!["Someday", "Shopping"]);
+
+    app.update_suggestions("Review @jo", 10);
+    assert_eq!(app.suggestions, vec!["jonas"]);
+    app.update_suggestions("Pay rent due tom", 16);
+    assert_eq!(app.suggestions, vec!["tomorrow"]);
+    assert!(app.should_autocomplete_on_enter());
+    app.update_suggestions("Water plants every ", 19);
+    assert_eq!(app.suggestions[0], "day");
+    assert!(!app.should_autocomplete_on_enter());
+}
+
 #[test]
 fn test_multi_word_suggestions() {
     let mut app = App::new_with_config(CriaConfig::default(), "Inbox".to_string());
@@ -381,11 +412,16 @@ fn test_config_loading_from_custom_path() {
     assert_eq!(config.api_url, "https://vikunja.example.com/api/v1");
     assert_eq!(config.api_key, Some("your-api-key-here".to_string()));
     assert_eq!(config.default_project, Some("Inbox".to_string()));
+
+    // The example template survives validation, subtasks and all
+    let templates = config.templates.as_ref().unwrap();
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0].task_count(), 4);
     
     // Test quick actions
     assert!(config.quick_actions.is_some());
     let quick_actions = config.quick_actions.unwrap();
-    assert_eq!(quick_actions.len(), 8); // There are 8 quick actions in config.example.yaml
+    assert_eq!(quick_actions.len(), 12); // There are 12 quick actions in config.example.yaml, all valid
     
     // Test first quick action (w -> Work project)
     assert_eq!(quick_actions[0].key, "w");
@@ -508,6 +544,32 @@ fn test_task_filter_header_display() {
     assert_eq!(app.get_filter_display_name(), "Active Tasks Only");
 }
 
+#[test]
+fn test_assignment_filters_with_current_user() {
+    use cria::vikunja::models::User;
+    let me = User { id: 7, username: "me".to_string(), name: None, email: None, created: None, updated: None };
+    let mut app = App::new_with_config(CriaConfig::default(), "Inbox".to_string());
+    app.set_current_user(me.clone());
+    let mut mine = sample_task(1, false);
+    mine.assignees = Some(vec![me]);
+    let unassigned = sample_task(2, false);
+    let mut mine_done = sample_task(3, true);
+    mine_done.assignees = mine.assignees.clone();
+    app.all_tasks = vec![mine, unassigned, mine_done];
+
+    app.cycle_task_filter(); // All
+    app.cycle_task_filter(); // CompletedOnly
+    app.cycle_task_filter();
+    assert_eq!(app.task_filter, TaskFilter::AssignedToMe);
+    assert_eq!(app.get_filter_display_name(), "Assigned to Me");
+    assert_eq!(app.tasks.iter().map(|t| t.id).collect::<Vec<_>>(), vec![1]);
+    app.cycle_task_filter();
+    assert_eq!(app.task_filter, TaskFilter::Unassigned);
+    assert_eq!(app.tasks.iter().map(|t| t.id).collect::<Vec<_>>(), vec![2]);
+    app.cycle_task_filter();
+    assert_eq!(app.task_filter, TaskFilter::ActiveOnly);
+}
+
 #[test]
 fn test_apply_label_quick_action() {
     use cria::vikunja::models::Task;
@@ -581,3 +643,27 @@ fn test_delete_task_after_confirmation() {
     assert!(app.tasks.is_empty());
     assert!(!app.show_confirmation_dialog);
 }
+
+#[test]
+fn test_partial_label_merge_keeps_failed_tasks_and_source_label() {
+    let mut app = App::new_with_config(CriaConfig::default(), "Inbox".to_string());
+    let label = |id: i64, title: &str| Label { id, title: title.to_string(), hex_color: None, description: None, created: None, updated: None, created_by: None };
+    app.label_map.insert(1, "bug".to_string());
+    app.label_map.insert(2, "Bug".to_string());
+    for id in 1..=2 {
+        let mut task = sample_task(id, false);
+        task.labels = Some(vec![label(1, "bug")]);
+        app.all_tasks.push(task);
+    }
+    app.tasks = app.all_tasks.clone();
+
+    // Task 2 failed to move on the server
+    app.apply_label_merge(1, 2, &[2]);
+    assert_eq!(app.tasks[0].labels.as_ref().unwrap()[0].id, 2);
+    assert_eq!(app.tasks[1].labels.as_ref().unwrap()[0].id, 1);
+    assert!(app.label_map.contains_key(&1));
+
+    app.apply_label_merge(1, 2, &[]);
+    assert_eq!(app.all_tasks[1].labels.as_ref().unwrap()[0].id, 2);
+    assert!(!app.label_map.contains_key(&1));
+}
@@ -148,6 +148,14 @@ fn test_config_with_default_filter() {
         active_layout: None,
         refresh_interval_seconds: Some(300),
         auto_refresh: Some(true),
+        date_dialect: None,
+        default_due_time: None,
+        default_start_time: None,
+        workday_end: None,
+        timezone: None,
+        trash_grace_seconds: None,
+        snooze_options: None,
+        templates: None,
     };
     
     assert_eq!(config.default_filter, Some("Daily Tasks".to_string()));
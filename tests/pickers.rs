@@ -1,4 +1,4 @@
-// Tests for picker event handling: project and filter pickers
+// Tests for picker event handling: project, filter and assignee pickers
 
 use cria::config::CriaConfig;
 use cria::tui::app::state::App;
@@ -235,3 +235,34 @@ fn test_project_picker_preserves_task_filter() {
     assert_eq!(app.tasks.len(), 2); // Should show all tasks from Work project (active + completed)
     assert!(app.tasks.iter().all(|t| t.project_id == 2)); // All tasks should be from Work project
 }
+
+#[test]
+fn test_assignee_picker_types_spaces_and_defers_search() {
+    use cria::tui::pickers::assignee::handle_assignee_picker;
+    use cria::vikunja::models::User;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    let mut app = App::new_with_config(CriaConfig::default(), "Inbox".to_string());
+    app.remember_users(vec![User {
+        id: 3,
+        username: "jdoe".to_string(),
+        name: Some("Jane Doe".to_string()),
+        email: None,
+        created: None,
+        updated: None,
+    }]);
+    app.show_assignee_picker = true;
+    for c in "jane d".chars() {
+        handle_assignee_picker(&mut app, &KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    assert_eq!(app.assignee_picker_input, "jane d");
+    assert_eq!(app.filtered_assignees.len(), 1);
+    // The server search waits for typing to pause
+    assert!(app.take_due_user_search().is_none());
+    assert_eq!(app.pending_user_search.as_ref().map(|(q, _)| q.as_str()), Some("jane d"));
+
+    handle_assignee_picker(&mut app, &KeyEvent::new(KeyCode::Char(' '), KeyModifiers::CONTROL));
+    assert_eq!(app.selected_assignee_ids, vec![3]);
+    handle_assignee_picker(&mut app, &KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+    assert!(app.selected_assignee_ids.is_empty());
+}
@@ -0,0 +1,120 @@
+// Property-based tests for natural-language dates in quick add
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc, Weekday};
+use cria::config::DateDialect;
+use cria::vikunja_parser::QuickAddParser;
+use lazy_static::lazy_static;
+use proptest::prelude::*;
+
+lazy_static! {
+    static ref US: QuickAddParser = QuickAddParser::new();
+    static ref UK: QuickAddParser = QuickAddParser::with_dialect(DateDialect::Uk);
+}
+
+/// Phrases that must always produce a due date
+const DATE_PHRASES: &[&str] = &[
+    "today", "tomorrow", "eod", "eow", "eom", "eoy", "end of week", "end of the month",
+    "this weekend", "next weekend", "next week", "next month", "in 3 days", "in 2 weeks",
+    "in a week", "in an hour", "in 6 months", "friday", "next friday at 5pm", "mon",
+    "first monday of next month", "last friday of this month", "third thursday in november",
+    "feb 17th", "15th", "2025-03-04", "2025-03-04t10:30", "03/04/2025", "4.3.25",
+];
+
+/// Title words that must survive date removal
+const TITLE_WORDS: &[&str] = &["Write", "report", "Call", "plumber", "Review", "budget", "Plan", "offsite"];
+
+/// Calendar day of a parsed date; parsing uses the system time zone by default
+fn local_day(date: Option<DateTime<Utc>>) -> NaiveDate {
+    date.unwrap().with_timezone(&Local).date_naive()
+}
+
+fn arb_now() -> impl Strategy<Value = DateTime<Local>> {
+    (2000i32..2100, 1u32..=12, 1u32..=28, 0u32..24).prop_map(|(year, month, day, hour)| {
+        Local.with_ymd_and_hms(year, month, day, hour, 0, 0).earliest()
+            .unwrap_or_else(|| Local.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap())
+    })
+}
+
+fn arb_date() -> impl Strategy<Value = NaiveDate> {
+    (2000i32..2100, 1u32..=12, 1u32..=31)
+        .prop_filter_map("valid date", |(year, month, day)| NaiveDate::from_ymd_opt(year, month, day))
+}
+
+fn arb_title() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::sample::select(TITLE_WORDS), 1..4).prop_map(|words| words.join(" "))
+}
+
+proptest! {
+    #[test]
+    fn test_parsing_never_panics(text in "\\PC{0,60}", now in arb_now()) {
+        let _ = US.parse_at(&text, now);
+        let _ = UK.parse_at(&text, now);
+    }
+
+    #[test]
+    fn test_corpus_phrases_give_a_date_and_leave_the_title(
+        title in arb_title(),
+        phrase in prop::sample::select(DATE_PHRASES),
+        now in arb_now(),
+    ) {
+        let task = US.parse_at(&format!("{} {}", title, phrase), now);
+        prop_assert!(task.due_date.is_some(), "no date for '{}'", phrase);
+        prop_assert_eq!(task.title, title);
+    }
+
+    #[test]
+    fn test_in_n_days(days in 0i64..3650, now in arb_now()) {
+        let task = US.parse_at(&format!("Water plants in {} days", days), now);
+        let due = local_day(task.due_date);
+        prop_assert_eq!(due, now.date_naive() + Duration::days(days));
+    }
+
+    #[test]
+    fn test_weekdays_are_within_a_week(
+        index in 0usize..7,
+        next in any::<bool>(),
+        now in arb_now(),
+    ) {
+        let names = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
+        let phrase = if next { format!("next {}", names[index]) } else { names[index].to_string() };
+        let due = local_day(US.parse_at(&format!("Gym {}", phrase), now).due_date);
+        let ahead = (due - now.date_naive()).num_days();
+        prop_assert_eq!(due.weekday(), Weekday::try_from(index as u8).unwrap());
+        if next {
+            prop_assert!((1..=7).contains(&ahead));
+        } else {
+            prop_assert!((0..7).contains(&ahead));
+        }
+    }
+
+    #[test]
+    fn test_numeric_dates_round_trip(
+        date in arb_date(),
+        separator in prop::sample::select(&["/", ".", "-"][..]),
+        now in arb_now(),
+    ) {
+        let (d, m, y) = (date.day(), date.month(), date.year());
+        let us_text = format!("Pay rent {m:02}{s}{d:02}{s}{y}", s = separator);
+        let uk_text = format!("Pay rent {d}{s}{m}{s}{y}", s = separator);
+        prop_assert_eq!(local_day(US.parse_at(&us_text, now).due_date), date);
+        prop_assert_eq!(local_day(UK.parse_at(&uk_text, now).due_date), date);
+        prop_assert_eq!(local_day(US.parse_at(&format!("Pay rent {}", date.format("%Y-%m-%d")), now).due_date), date);
+        prop_assert_eq!(US.parse_at(&us_text, now).title, "Pay rent");
+    }
+
+    #[test]
+    fn test_ranges_set_start_and_end(
+        start in arb_date(),
+        length in 0i64..60,
+        title in arb_title(),
+        now in arb_now(),
+    ) {
+        let end = start + Duration::days(length);
+        let input = format!("{} from {} to {} *trip", title, start.format("%Y-%m-%d"), end.format("%Y-%m-%d"));
+        let task = UK.parse_at(&input, now);
+        prop_assert_eq!(local_day(task.start_date), start);
+        prop_assert_eq!(local_day(task.end_date), end);
+        prop_assert!(task.due_date.is_none());
+        prop_assert_eq!(task.title, title);
+    }
+}
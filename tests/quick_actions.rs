@@ -2,7 +2,7 @@
 
 use cria::tui::app::state::App;
 use cria::config::{CriaConfig, QuickAction};
-use cria::vikunja::models::Task;
+use cria::vikunja::models::{Label, Task, User};
 
 #[tokio::test]
 async fn test_quick_action_by_shortcut_key() {
@@ -39,3 +39,86 @@ async fn test_quick_action_modal_enter() {
     // Similar to above, but simulate selection by index and Enter
     // ...
 }
+
+fn action(key: &str, action: &str, target: &str) -> QuickAction {
+    QuickAction { key: key.to_string(), action: action.to_string(), target: target.to_string() }
+}
+
+#[test]
+fn test_extended_quick_actions_and_macros() {
+    let mut app = App::new_with_config(CriaConfig::default(), "Inbox".to_string());
+    let due = app.date_settings.at(chrono::NaiveDate::from_ymd_opt(2025, 3, 4).unwrap(), app.date_settings.due_time);
+    app.all_tasks.push(Task {
+        id: 1,
+        title: "Write report".to_string(),
+        project_id: 1,
+        due_date: due,
+        labels: Some(vec![Label { id: 5, title: "today".to_string(), hex_color: None, description: None, created: None, updated: None, created_by: None }]),
+        ..Default::default()
+    });
+    app.tasks = app.all_tasks.clone();
+    app.project_map.insert(9, "Done".to_string());
+    app.label_map.insert(5, "today".to_string());
+    app.remember_users(vec![User { id: 3, username: "jane".to_string(), name: None, email: None, created: None, updated: None }]);
+
+    assert!(app.apply_quick_action(&action("d", "due", "+1d")).is_ok());
+    let moved = app.date_settings.at(chrono::NaiveDate::from_ymd_opt(2025, 3, 5).unwrap(), app.date_settings.due_time);
+    assert_eq!(app.tasks[0].due_date, moved);
+    assert!(app.apply_quick_action(&action("a", "assign", "Jane")).is_ok());
+    assert!(app.apply_quick_action(&action("p", "percent_done", "50%")).is_ok());
+    assert!(app.apply_quick_action(&action("f", "favorite", "")).is_ok());
+    assert_eq!(app.tasks[0].assignees.as_ref().unwrap()[0].id, 3);
+    assert_eq!(app.tasks[0].percent_done, Some(50));
+    assert!(app.tasks[0].is_favorite);
+
+    let wrap_up = action("x", "macro", "project: Done; remove_label: today; done: true; comment: Wrapped up");
+    assert_eq!(wrap_up.get_description(), "Move to project: Done, Remove label: today, Mark done, Add comment: Wrapped up");
+    assert!(app.apply_quick_action(&wrap_up).is_ok());
+    let task = &app.all_tasks[0];
+    assert_eq!(task.project_id, 9);
+    assert!(task.done);
+    assert!(task.labels.as_ref().unwrap().is_empty());
+
+    // A failing step leaves the task as it was
+    assert!(app.apply_quick_action(&action("y", "macro", "done: false; project: Nowhere")).is_err());
+    assert!(app.tasks[0].done);
+    assert!(app.apply_quick_action(&action("u", "unassign", "jane")).is_ok());
+    assert!(app.apply_quick_action(&action("c", "due", "clear")).is_ok());
+    assert!(app.tasks[0].assignees.as_ref().unwrap().is_empty());
+    assert_eq!(app.tasks[0].due_date, None);
+
+    // Bucket moves go through the kanban view on the server, by name or id
+    assert!(action("b", "bucket", "Doing").validate().is_ok());
+    assert!(app.apply_quick_action(&action("b", "bucket", "Doing")).is_ok());
+    assert_eq!(app.tasks[0].bucket_id, None);
+}
+
+#[test]
+fn test_invalid_quick_actions_are_dropped_at_load() {
+    let dir = std::env::temp_dir().join(format!("cria-quick-actions-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("config.yaml");
+    std::fs::write(&path, r#"
+api_url: "https://vikunja.example.com"
+quick_actions:
+  - { key: "w", action: "project", target: "Work" }
+  - { key: "n", action: "due", target: "next monday" }
+  - { key: "t", action: "due", target: "+1d" }
+  - { key: "m", action: "macro", target: "project: Done; remove_label: today; comment: Done" }
+  - { key: "z", action: "snooze", target: "1d" }
+  - { key: "p", action: "priority", target: "9" }
+  - { key: "b", action: "bucket", target: "" }
+  - { key: "q", action: "macro", target: "done; explode: now" }
+"#).unwrap();
+    let config = CriaConfig::load_from_path(path.to_str()).expect("config loads");
+    let keys: Vec<&str> = config.quick_actions.as_ref().unwrap().iter().map(|a| a.key.as_str()).collect();
+    assert_eq!(keys, vec!["w", "n", "t", "m"]);
+    std::fs::remove_dir_all(&dir).ok();
+
+    let mut config = CriaConfig {
+        quick_actions: Some(vec![action("?", "percent_done", "150")]),
+        ..CriaConfig::default()
+    };
+    let warnings = config.validate_quick_actions();
+    assert_eq!(warnings, vec!["Ignoring quick action '?': invalid percent done '150': must be 0-100".to_string()]);
+}
@@ -0,0 +1,74 @@
+//! Integration tests for snoozing and rescheduling overdue tasks
+
+use chrono::{Duration, NaiveDate, NaiveTime};
+use cria::config::CriaConfig;
+use cria::dates::DateSettings;
+use cria::tui::app::snooze::snoozed_task;
+use cria::tui::app::state::App;
+use cria::vikunja::models::Task;
+
+fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn test_snooze_moves_start_with_due_and_keeps_times() {
+    let settings = DateSettings { timezone: Some(chrono_tz::Europe::Berlin), ..DateSettings::default() };
+    let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+    let task = Task {
+        id: 1,
+        title: "Call the bank".to_string(),
+        // Across the switch to summer time: wall-clock times stay put
+        due_date: settings.at(day(2025, 3, 28), nine),
+        start_date: settings.at(day(2025, 3, 27), settings.start_time),
+        ..Default::default()
+    };
+
+    let snoozed = snoozed_task(&settings, &task, "+1w").unwrap();
+    assert_eq!(snoozed.due_date, settings.at(day(2025, 4, 4), nine));
+    assert_eq!(snoozed.start_date, settings.at(day(2025, 4, 3), settings.start_time));
+
+    // A day moves the due date to it and the start date by as many days
+    let snoozed = snoozed_task(&settings, &task, "2025-04-01").unwrap();
+    assert_eq!(snoozed.due_date, settings.at(day(2025, 4, 1), nine));
+    assert_eq!(snoozed.start_date, settings.at(day(2025, 3, 31), settings.start_time));
+
+    assert!(snoozed_task(&settings, &task, "someday").is_err());
+}
+
+#[test]
+fn test_snoozing_an_undated_task_gives_it_a_due_date() {
+    let settings = DateSettings::default();
+    let task = Task { id: 1, title: "Inbox zero".to_string(), ..Default::default() };
+    let snoozed = snoozed_task(&settings, &task, "+1d").unwrap();
+    assert_eq!(snoozed.due_date, settings.at(settings.today() + Duration::days(1), settings.due_time));
+    assert_eq!(snoozed.start_date, None);
+}
+
+#[test]
+fn test_marked_tasks_and_overdue_plan() {
+    let mut app = App::new_with_config(CriaConfig::default(), "Inbox".to_string());
+    let settings = app.date_settings;
+    let today = settings.today();
+    let ten = NaiveTime::from_hms_opt(10, 0, 0).unwrap();
+    app.all_tasks = vec![
+        Task { id: 1, title: "Overdue".to_string(), due_date: settings.at(today - Duration::days(3), ten), ..Default::default() },
+        Task { id: 2, title: "Done and overdue".to_string(), done: true, due_date: settings.at(today - Duration::days(3), ten), ..Default::default() },
+        Task { id: 3, title: "Due today".to_string(), due_date: settings.at(today, settings.due_time), ..Default::default() },
+        Task { id: 4, title: "Undated".to_string(), ..Default::default() },
+    ];
+    app.tasks = app.all_tasks.clone();
+
+    assert_eq!(app.marked_or_selected_tasks().iter().map(|t| t.id).collect::<Vec<_>>(), vec![1]);
+    app.toggle_mark_selected_task();
+    app.toggle_mark_selected_task();
+    app.toggle_mark_selected_task();
+    assert_eq!(app.selected_task_index, 3);
+    assert_eq!(app.marked_or_selected_tasks().iter().map(|t| t.id).collect::<Vec<_>>(), vec![1, 2, 3]);
+    app.clear_marks();
+
+    let plan = app.overdue_reschedule_plan();
+    assert_eq!(plan.len(), 1);
+    assert_eq!(plan[0].0.id, 1);
+    assert_eq!(plan[0].1.due_date, settings.at(today, ten));
+}
@@ -430,3 +430,183 @@ fn test_undo_redo_clears_opposite_stack() {
     assert_eq!(app.undo_stack.len(), 1);
     assert_eq!(app.redo_stack.len(), 0); // Should be cleared by add_to_undo_stack
 }
+
+#[test]
+fn test_undo_redo_relations_and_comments() {
+    use cria::vikunja_client::relations::RelationKind;
+    let mut app = App::new_with_config(CriaConfig::default(), "Inbox".to_string());
+    app.tasks.push(sample_task(1, false));
+
+    app.add_to_undo_stack(UndoableAction::RelationAdded { task_id: 1, other_task_id: 2, kind: RelationKind::Subtask });
+    assert_eq!(app.undo_last_action(), Some(1));
+    match &app.redo_stack[0] {
+        UndoableAction::RelationRemoved { task_id: 1, other_task_id: 2, kind } => assert_eq!(*kind, RelationKind::Subtask),
+        other => panic!("Expected RelationRemoved, got {:?}", other),
+    }
+
+    app.add_to_undo_stack(UndoableAction::CommentAdded { task_id: 1, comment_id: 9, text: "Looks good".to_string() });
+    assert!(app.redo_stack.is_empty());
+    app.undo_last_action();
+    match &app.redo_stack[0] {
+        UndoableAction::CommentDeleted { task_id: 1, text } => assert_eq!(text, "Looks good"),
+        other => panic!("Expected CommentDeleted, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_undo_comment_edit_restores_previous_text() {
+    let mut app = App::new_with_config(CriaConfig::default(), "Inbox".to_string());
+    let mut task = sample_task(1, false);
+    task.comments = Some(vec![cria::vikunja::models::Comment {
+        id: 9,
+        author: None,
+        comment: Some("Looks great".to_string()),
+        created: None,
+        updated: None,
+        reactions: None,
+    }]);
+    app.tasks.push(task);
+
+    app.add_to_undo_stack(UndoableAction::CommentEdited {
+        task_id: 1,
+        comment_id: 9,
+        previous_text: "Looks good".to_string(),
+        text: "Looks great".to_string(),
+    });
+    assert_eq!(app.undo_last_action(), Some(1));
+    assert_eq!(app.tasks[0].comments.as_ref().unwrap()[0].comment.as_deref(), Some("Looks good"));
+    match &app.redo_stack[0] {
+        UndoableAction::CommentEdited { comment_id: 9, previous_text, text, .. } => {
+            assert_eq!(previous_text, "Looks great");
+            assert_eq!(text, "Looks good");
+        }
+        other => panic!("Expected CommentEdited, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_undo_edit_updates_all_tasks_and_remaps_ids() {
+    let mut app = App::new_with_config(CriaConfig::default(), "Inbox".to_string());
+    let mut edited = sample_task(1, false);
+    edited.priority = Some(5);
+    app.tasks.push(edited.clone());
+    app.all_tasks.push(edited);
+    app.add_task_edit_to_undo_stack(1, sample_task(1, false));
+
+    app.undo_last_action();
+    assert_eq!(app.tasks[0].priority, Some(1));
+    assert_eq!(app.all_tasks[0].priority, Some(1));
+
+    // A recreated task gets a new ID; every action naming the old one follows it
+    let mut action = UndoableAction::TaskDeletion { task: sample_task(1, false), position: 0 };
+    action.remap_task_id(1, 42);
+    assert_eq!(action.task_id(), 42);
+    let mut relation = UndoableAction::RelationAdded {
+        task_id: 7,
+        other_task_id: 1,
+        kind: cria::vikunja_client::relations::RelationKind::Related,
+    };
+    relation.remap_task_id(1, 42);
+    match relation {
+        UndoableAction::RelationAdded { task_id: 7, other_task_id: 42, .. } => {}
+        other => panic!("Expected remapped relation, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_history_info_follows_undo_and_redo() {
+    let mut app = App::new_with_config(CriaConfig::default(), "Inbox".to_string());
+    app.tasks.push(sample_task(1, false));
+    app.tasks.push(sample_task(2, false));
+
+    app.selected_task_index = 1;
+    app.toggle_task_completion();
+    app.add_task_edit_to_undo_stack(1, sample_task(1, false));
+    assert_eq!(app.history_info(false, 0).unwrap().task_title, "Task 2");
+    assert_eq!(app.history_info(false, 1).unwrap().task_title, "Task 1");
+
+    // Moving between the stacks keeps when the action happened
+    let edited_at = chrono::Local::now() - chrono::Duration::hours(3);
+    app.undo_info[1].at = edited_at;
+    app.undo_last_action();
+    assert_eq!(app.undo_info.len(), 1);
+    assert_eq!(app.history_info(true, 0).unwrap().task_title, "Task 1");
+    assert_eq!(app.history_info(true, 0).unwrap().at, edited_at);
+    assert!(app.history_info(true, 0).unwrap().failed.is_none());
+    app.redo_last_action();
+    assert_eq!(app.history_info(false, 1).unwrap().at, edited_at);
+    app.undo_last_action();
+
+    // A new action starts a new branch of history
+    app.toggle_task_completion();
+    assert!(app.redo_info.is_empty());
+    assert_eq!(app.undo_info.len(), 2);
+}
+
+#[test]
+fn test_deleted_task_waits_in_trash() {
+    let mut app = App::new_with_config(CriaConfig::default(), "Inbox".to_string());
+    app.all_tasks = vec![sample_task(1, false), sample_task(2, false)];
+    app.apply_task_filter();
+
+    app.execute_delete_task(1);
+    assert!(app.is_trashed(1));
+    assert!(!app.has_expired_trash());
+    app.trash[0].deleted_at -= chrono::Duration::minutes(5);
+    assert!(app.has_expired_trash());
+    // After a failed server delete it waits before trying again
+    app.trash[0].retry_at = Some(chrono::Local::now() + chrono::Duration::seconds(30));
+    assert!(!app.has_expired_trash());
+    // A refresh brings the task back from the server, but it stays hidden
+    app.all_tasks = vec![sample_task(1, false), sample_task(2, false)];
+    app.apply_task_filter();
+    assert_eq!(app.tasks.iter().map(|t| t.id).collect::<Vec<_>>(), vec![2]);
+
+    // Undo takes it out of the trash, without needing the server
+    app.undo_last_action();
+    assert!(!app.is_trashed(1));
+    assert_eq!(app.tasks.iter().filter(|t| t.id == 1).count(), 1);
+
+    app.execute_delete_task(1);
+    let restored = app.restore_from_trash(1).unwrap();
+    assert_eq!(restored.id, 1);
+    app.apply_task_filter();
+    assert!(app.tasks.iter().any(|t| t.id == 1));
+}
+
+#[test]
+fn test_batch_undoes_as_one_entry() {
+    let mut app = App::new_with_config(CriaConfig::default(), "Inbox".to_string());
+    let mut task = sample_task(1, false);
+    task.bucket_id = Some(3);
+    app.tasks.push(task.clone());
+
+    // A macro that set the priority, moved the task to bucket 4 and commented
+    app.tasks[0].priority = Some(5);
+    app.tasks[0].bucket_id = Some(4);
+    app.add_to_undo_stack(UndoableAction::Batch {
+        actions: vec![
+            UndoableAction::TaskEdit { task_id: 1, previous_task: task },
+            UndoableAction::BucketMoved { task_id: 1, project_id: 1, from_bucket_id: 3, to_bucket_id: 4 },
+            UndoableAction::CommentAdded { task_id: 1, comment_id: 9, text: "Moved".to_string() },
+        ],
+    });
+    assert_eq!(app.undo_stack.len(), 1);
+
+    assert_eq!(app.undo_last_action(), Some(1));
+    assert_eq!(app.tasks[0].priority, Some(1));
+    assert_eq!(app.tasks[0].bucket_id, Some(3));
+    assert!(app.undo_stack.is_empty());
+    match &app.redo_stack[0] {
+        UndoableAction::Batch { actions } => {
+            assert!(matches!(actions[0], UndoableAction::CommentDeleted { .. }));
+            assert!(matches!(actions[1], UndoableAction::BucketMoved { from_bucket_id: 4, to_bucket_id: 3, .. }));
+            assert!(matches!(actions[2], UndoableAction::TaskEdit { .. }));
+        }
+        other => panic!("Expected Batch, got {:?}", other),
+    }
+
+    assert_eq!(app.redo_last_action(), Some(1));
+    assert_eq!(app.tasks[0].priority, Some(5));
+    assert_eq!(app.tasks[0].bucket_id, Some(4));
+}